@@ -31,6 +31,7 @@ import (
 	"github.com/kubilitics/kubilitics-backend/internal/config"
 	"github.com/kubilitics/kubilitics-backend/internal/models"
 	"github.com/kubilitics/kubilitics-backend/internal/k8s"
+	"github.com/kubilitics/kubilitics-backend/internal/pairing"
 	"github.com/kubilitics/kubilitics-backend/internal/metrics"
 	"github.com/kubilitics/kubilitics-backend/internal/pkg/logger"
 	"github.com/kubilitics/kubilitics-backend/internal/pkg/topologycache"
@@ -86,6 +87,15 @@ func main() {
 		log.Warn("Failed to load config, using defaults", "error", err)
 	}
 
+	// Per-context proxies from the desktop (SOCKS forwards through jump hosts)
+	if err := k8s.SetContextProxies(cfg.ContextProxies); err != nil {
+		log.Warn("Ignoring context proxies", "error", err)
+	}
+	// Per-context request timeouts and retries from the desktop
+	if err := k8s.SetContextPolicies(cfg.ContextPolicies); err != nil {
+		log.Warn("Ignoring context policies", "error", err)
+	}
+
 	// BE-OBS-001: Initialize OpenTelemetry tracing
 	var tracingCleanup func()
 	if cfg.TracingEnabled && cfg.TracingEndpoint != "" {
@@ -300,7 +310,11 @@ func main() {
 		lmc = lifecycle.NewLifecycleController(clusterService, repo, lmcHelmFactory, reg, log)
 		addonSvcImpl.SetLMC(lmc)
 		addonSvc = addonSvcImpl
-		reg.StartArtifactHubSync(ctx)
+		if cfg.AirGapped {
+			log.Info("Air-gapped mode: Artifact Hub catalog sync disabled")
+		} else {
+			reg.StartArtifactHubSync(ctx)
+		}
 		log.Info("Add-on service and LMC initialized")
 	}
 	router := mux.NewRouter()
@@ -329,6 +343,10 @@ func main() {
 	// Compliance handler (Phase 5: Compliance Reporting)
 	complianceHandler := rest.NewComplianceHandler(repo, cfg)
 
+	// Device pairing (desktop only: both paths are set by the desktop shell)
+	pairingStore := pairing.NewStore(cfg.PairingTokensPath, cfg.PairedDevicesPath)
+	pairingHandler := rest.NewPairingHandler(pairingStore)
+
 	// Deployment rollout routes on main router (full path) so they always match regardless of subrouter path handling
 	router.HandleFunc("/api/v1/clusters/{clusterId}/resources/deployments/{namespace}/{name}/rollout-history", handler.GetDeploymentRolloutHistory).Methods("GET")
 	router.HandleFunc("/api/v1/clusters/{clusterId}/resources/deployments/{namespace}/{name}/rollback", handler.PostDeploymentRollback).Methods("POST")
@@ -377,6 +395,7 @@ func main() {
 	groupsHandler.RegisterRoutes(apiRouter)
 	securityHandler.RegisterRoutes(apiRouter)
 	complianceHandler.RegisterRoutes(apiRouter)
+	pairingHandler.RegisterRoutes(apiRouter)
 	rest.SetupRoutes(apiRouter, handler)
 
 	// WebSocket routes
@@ -400,6 +419,7 @@ func main() {
 	router.Use(middleware.SecureHeaders(cfg))
 	router.Use(middleware.RequestID)
 	router.Use(middleware.RateLimit())
+	router.Use(middleware.PairedDevice(cfg, pairingStore)) // LAN clients of the desktop backend must be paired devices
 	router.Use(middleware.MetricsAuth(cfg, repo)) // Protect /metrics if enabled
 	router.Use(middleware.Auth(cfg, repo))
 	router.Use(middleware.StructuredLog)
@@ -452,9 +472,10 @@ func main() {
 		shutdownTimeout = time.Duration(cfg.ShutdownTimeoutSec) * time.Second
 	}
 
-	// Bind strictly to configured port (default 819) on all interfaces
+	// Bind strictly to configured port (default 819) on the configured address. The default ""
+	// listens on all interfaces in both families, so clients resolving localhost to ::1 connect too.
 	// Phase 2: Enforce Proper Port Strategy - No port hunting, no random ports.
-	addr := fmt.Sprintf("0.0.0.0:%d", cfg.Port)
+	addr := net.JoinHostPort(cfg.BindAddress, fmt.Sprint(cfg.Port))
 	listener, err := net.Listen("tcp", addr)
 	if err != nil {
 		log.Error("Failed to listen", "address", addr, "error", err)
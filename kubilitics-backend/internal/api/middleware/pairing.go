@@ -0,0 +1,60 @@
+package middleware
+
+import (
+	"net"
+	"net/http"
+
+	"github.com/kubilitics/kubilitics-backend/internal/config"
+	"github.com/kubilitics/kubilitics-backend/internal/pairing"
+)
+
+// DeviceCredentialHeader carries the credential a paired device received from the pairing exchange.
+const DeviceCredentialHeader = "X-Kubilitics-Device-Token"
+
+// deviceCredentialQueryParam is the fallback for WebSocket clients, which cannot set headers.
+const deviceCredentialQueryParam = "device_token"
+
+// PairingExchangePath is where a device redeems its QR pairing code; it is the one route a LAN
+// client may call without a credential.
+const PairingExchangePath = "/api/v1/pairing/exchange"
+
+// PairedDevice requires a paired-device credential from every non-loopback client when the desktop
+// backend listens beyond loopback. Devices get the credential by exchanging the short-lived code from
+// the desktop's QR code (PairingExchangePath); it stays valid until the device is revoked. Loopback
+// clients (the desktop WebView) are never asked. Without pairing configured (server deployments) or
+// on a loopback bind this is a no-op.
+func PairedDevice(cfg *config.Config, store *pairing.Store) func(http.Handler) http.Handler {
+	enforced := store.Enabled() && !isLoopbackBind(cfg.BindAddress)
+	return func(next http.Handler) http.Handler {
+		if !enforced {
+			return next
+		}
+		return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+			if pairing.IsLoopbackRemote(r.RemoteAddr) || (r.Method == http.MethodPost && r.URL.Path == PairingExchangePath) {
+				next.ServeHTTP(w, r)
+				return
+			}
+			credential := r.Header.Get(DeviceCredentialHeader)
+			if credential == "" {
+				credential = r.URL.Query().Get(deviceCredentialQueryParam)
+			}
+			if !store.ValidCredential(credential) {
+				w.Header().Set("Content-Type", "application/json")
+				w.WriteHeader(http.StatusUnauthorized)
+				w.Write([]byte(`{"error":"Paired device credential required"}`))
+				return
+			}
+			next.ServeHTTP(w, r)
+		})
+	}
+}
+
+// isLoopbackBind reports whether the listen address only accepts local connections.
+// "" listens on every interface.
+func isLoopbackBind(address string) bool {
+	if address == "localhost" {
+		return true
+	}
+	ip := net.ParseIP(address)
+	return ip != nil && ip.IsLoopback()
+}
@@ -0,0 +1,111 @@
+package middleware
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/kubilitics/kubilitics-backend/internal/config"
+	"github.com/kubilitics/kubilitics-backend/internal/pairing"
+)
+
+// newPairedStore writes a codes file holding one unexpired code and pairs a device with it.
+func newPairedStore(t *testing.T) (*pairing.Store, string) {
+	t.Helper()
+	dir := t.TempDir()
+	sum := sha256.Sum256([]byte("code"))
+	content, err := json.Marshal([]map[string]interface{}{
+		{"token_sha256": hex.EncodeToString(sum[:]), "expires_at": time.Now().Add(10 * time.Minute).Unix()},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	codesPath := filepath.Join(dir, "pairing_tokens.json")
+	if err := os.WriteFile(codesPath, content, 0o600); err != nil {
+		t.Fatal(err)
+	}
+	store := pairing.NewStore(codesPath, filepath.Join(dir, "paired_devices.json"))
+	_, credential, err := store.Exchange("code", "Phone", time.Now())
+	if err != nil {
+		t.Fatal(err)
+	}
+	return store, credential
+}
+
+func servePairing(cfg *config.Config, store *pairing.Store, method, remoteAddr, credential, target string) int {
+	handler := PairedDevice(cfg, store)(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusOK)
+	}))
+	req := httptest.NewRequest(method, target, nil)
+	req.RemoteAddr = remoteAddr
+	if credential != "" {
+		req.Header.Set(DeviceCredentialHeader, credential)
+	}
+	rec := httptest.NewRecorder()
+	handler.ServeHTTP(rec, req)
+	return rec.Code
+}
+
+func TestPairedDevice_LANClients(t *testing.T) {
+	store, credential := newPairedStore(t)
+	cfg := &config.Config{}
+
+	cases := []struct {
+		name       string
+		method     string
+		remoteAddr string
+		credential string
+		target     string
+		want       int
+	}{
+		{"loopback needs no credential", http.MethodGet, "127.0.0.1:5000", "", "/api/v1/clusters", http.StatusOK},
+		{"ipv6 loopback needs no credential", http.MethodGet, "[::1]:5000", "", "/api/v1/clusters", http.StatusOK},
+		{"LAN client without credential", http.MethodGet, "192.168.1.20:5000", "", "/api/v1/clusters", http.StatusUnauthorized},
+		{"LAN client with credential", http.MethodGet, "192.168.1.20:5000", credential, "/api/v1/clusters", http.StatusOK},
+		{"LAN client with credential in query", http.MethodGet, "192.168.1.20:5000", "", "/ws/resources?device_token=" + credential, http.StatusOK},
+		{"LAN client with pairing code as credential", http.MethodGet, "192.168.1.20:5000", "code", "/api/v1/clusters", http.StatusUnauthorized},
+		{"LAN client with unknown credential", http.MethodGet, "192.168.1.20:5000", "guess", "/api/v1/clusters", http.StatusUnauthorized},
+		{"LAN client may exchange a code", http.MethodPost, "192.168.1.20:5000", "", PairingExchangePath, http.StatusOK},
+		{"LAN client may not list devices", http.MethodGet, "192.168.1.20:5000", "", "/api/v1/pairing/devices", http.StatusUnauthorized},
+	}
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			if got := servePairing(cfg, store, tc.method, tc.remoteAddr, tc.credential, tc.target); got != tc.want {
+				t.Errorf("Expected status %d, got %d", tc.want, got)
+			}
+		})
+	}
+}
+
+func TestPairedDevice_RevokedCredentialRejected(t *testing.T) {
+	store, credential := newPairedStore(t)
+	devices, err := store.Devices()
+	if err != nil || len(devices) != 1 {
+		t.Fatalf("Devices = %v, %v", devices, err)
+	}
+	if _, err := store.Revoke(devices[0].ID); err != nil {
+		t.Fatal(err)
+	}
+	if got := servePairing(&config.Config{}, store, http.MethodGet, "192.168.1.20:5000", credential, "/api/v1/clusters"); got != http.StatusUnauthorized {
+		t.Errorf("Expected status 401, got %d", got)
+	}
+}
+
+func TestPairedDevice_NotEnforced(t *testing.T) {
+	store, _ := newPairedStore(t)
+
+	// Bound to loopback: nothing on the LAN can connect anyway
+	if got := servePairing(&config.Config{BindAddress: "127.0.0.1"}, store, http.MethodGet, "192.168.1.20:5000", "", "/api/v1/clusters"); got != http.StatusOK {
+		t.Errorf("Expected status 200 on a loopback bind, got %d", got)
+	}
+	// Pairing not configured: not the desktop backend
+	if got := servePairing(&config.Config{}, pairing.NewStore("", ""), http.MethodGet, "192.168.1.20:5000", "", "/api/v1/clusters"); got != http.StatusOK {
+		t.Errorf("Expected status 200 without pairing, got %d", got)
+	}
+}
@@ -0,0 +1,93 @@
+package rest
+
+import (
+	"encoding/json"
+	"errors"
+	"net/http"
+	"time"
+
+	"github.com/gorilla/mux"
+	"github.com/kubilitics/kubilitics-backend/internal/pairing"
+)
+
+// PairingHandler handles /api/v1/pairing/* endpoints
+type PairingHandler struct {
+	store *pairing.Store
+}
+
+// NewPairingHandler creates a new pairing handler
+func NewPairingHandler(store *pairing.Store) *PairingHandler {
+	return &PairingHandler{store: store}
+}
+
+// RegisterRoutes registers pairing routes. Nothing is registered unless the desktop configured pairing.
+func (h *PairingHandler) RegisterRoutes(router *mux.Router) {
+	if !h.store.Enabled() {
+		return
+	}
+	router.HandleFunc("/pairing/exchange", h.Exchange).Methods("POST")
+	router.HandleFunc("/pairing/devices", h.ListDevices).Methods("GET")
+	router.HandleFunc("/pairing/devices/{id}", h.RevokeDevice).Methods("DELETE")
+}
+
+type pairingExchangeRequest struct {
+	Code       string `json:"code"`
+	DeviceName string `json:"device_name"`
+}
+
+type pairingExchangeResponse struct {
+	DeviceID   string `json:"device_id"`
+	Credential string `json:"credential"`
+}
+
+// Exchange redeems a QR pairing code for a device credential. Called by the device over the LAN.
+func (h *PairingHandler) Exchange(w http.ResponseWriter, r *http.Request) {
+	var req pairingExchangeRequest
+	if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+		respondError(w, http.StatusBadRequest, "Invalid request body")
+		return
+	}
+	device, credential, err := h.store.Exchange(req.Code, req.DeviceName, time.Now())
+	if errors.Is(err, pairing.ErrInvalidCode) {
+		respondError(w, http.StatusUnauthorized, err.Error())
+		return
+	}
+	if err != nil {
+		respondError(w, http.StatusInternalServerError, "Failed to pair device: "+err.Error())
+		return
+	}
+	respondJSON(w, http.StatusOK, pairingExchangeResponse{DeviceID: device.ID, Credential: credential})
+}
+
+// ListDevices lists paired devices. Only the desktop (loopback) may call it.
+func (h *PairingHandler) ListDevices(w http.ResponseWriter, r *http.Request) {
+	if !pairing.IsLoopbackRemote(r.RemoteAddr) {
+		respondError(w, http.StatusForbidden, "Paired devices can only be managed from the desktop")
+		return
+	}
+	devices, err := h.store.Devices()
+	if err != nil {
+		respondError(w, http.StatusInternalServerError, "Failed to list paired devices: "+err.Error())
+		return
+	}
+	respondJSON(w, http.StatusOK, devices)
+}
+
+// RevokeDevice unpairs a device; its credential stops working immediately. Only the desktop
+// (loopback) may call it.
+func (h *PairingHandler) RevokeDevice(w http.ResponseWriter, r *http.Request) {
+	if !pairing.IsLoopbackRemote(r.RemoteAddr) {
+		respondError(w, http.StatusForbidden, "Paired devices can only be managed from the desktop")
+		return
+	}
+	revoked, err := h.store.Revoke(mux.Vars(r)["id"])
+	if err != nil {
+		respondError(w, http.StatusInternalServerError, "Failed to revoke device: "+err.Error())
+		return
+	}
+	if !revoked {
+		respondError(w, http.StatusNotFound, "Device not found")
+		return
+	}
+	w.WriteHeader(http.StatusNoContent)
+}
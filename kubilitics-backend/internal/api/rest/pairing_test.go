@@ -0,0 +1,96 @@
+package rest
+
+import (
+	"bytes"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/gorilla/mux"
+	"github.com/kubilitics/kubilitics-backend/internal/pairing"
+)
+
+func setupPairingRouter(t *testing.T, code string) (*mux.Router, *pairing.Store) {
+	t.Helper()
+	dir := t.TempDir()
+	sum := sha256.Sum256([]byte(code))
+	content, err := json.Marshal([]map[string]interface{}{
+		{"token_sha256": hex.EncodeToString(sum[:]), "expires_at": time.Now().Add(10 * time.Minute).Unix()},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	codesPath := filepath.Join(dir, "pairing_tokens.json")
+	if err := os.WriteFile(codesPath, content, 0o600); err != nil {
+		t.Fatal(err)
+	}
+	store := pairing.NewStore(codesPath, filepath.Join(dir, "paired_devices.json"))
+	router := mux.NewRouter()
+	NewPairingHandler(store).RegisterRoutes(router.PathPrefix("/api/v1").Subrouter())
+	return router, store
+}
+
+func pairingRequest(router *mux.Router, method, target, remoteAddr string, body interface{}) *httptest.ResponseRecorder {
+	var buf bytes.Buffer
+	if body != nil {
+		json.NewEncoder(&buf).Encode(body)
+	}
+	req := httptest.NewRequest(method, target, &buf)
+	req.RemoteAddr = remoteAddr
+	rec := httptest.NewRecorder()
+	router.ServeHTTP(rec, req)
+	return rec
+}
+
+func TestPairingHandler_ExchangeListRevoke(t *testing.T) {
+	router, store := setupPairingRouter(t, "code")
+	lan := "192.168.1.20:5000"
+	local := "127.0.0.1:5000"
+
+	rec := pairingRequest(router, "POST", "/api/v1/pairing/exchange", lan, pairingExchangeRequest{Code: "code", DeviceName: "Phone"})
+	if rec.Code != http.StatusOK {
+		t.Fatalf("Expected status 200, got %d: %s", rec.Code, rec.Body.String())
+	}
+	var exchanged pairingExchangeResponse
+	if err := json.NewDecoder(rec.Body).Decode(&exchanged); err != nil {
+		t.Fatal(err)
+	}
+	if !store.ValidCredential(exchanged.Credential) {
+		t.Error("Expected the returned credential to be valid")
+	}
+
+	// The code is single-use
+	rec = pairingRequest(router, "POST", "/api/v1/pairing/exchange", lan, pairingExchangeRequest{Code: "code", DeviceName: "Phone"})
+	if rec.Code != http.StatusUnauthorized {
+		t.Errorf("Expected status 401 for a reused code, got %d", rec.Code)
+	}
+
+	// Device management is loopback-only
+	if rec := pairingRequest(router, "GET", "/api/v1/pairing/devices", lan, nil); rec.Code != http.StatusForbidden {
+		t.Errorf("Expected status 403 from the LAN, got %d", rec.Code)
+	}
+	rec = pairingRequest(router, "GET", "/api/v1/pairing/devices", local, nil)
+	var devices []pairing.Device
+	if err := json.NewDecoder(rec.Body).Decode(&devices); err != nil {
+		t.Fatal(err)
+	}
+	if len(devices) != 1 || devices[0].ID != exchanged.DeviceID || devices[0].Name != "Phone" {
+		t.Fatalf("Unexpected devices %+v", devices)
+	}
+
+	if rec := pairingRequest(router, "DELETE", "/api/v1/pairing/devices/"+exchanged.DeviceID, local, nil); rec.Code != http.StatusNoContent {
+		t.Errorf("Expected status 204, got %d", rec.Code)
+	}
+	if store.ValidCredential(exchanged.Credential) {
+		t.Error("Expected the revoked credential to be rejected")
+	}
+	if rec := pairingRequest(router, "DELETE", "/api/v1/pairing/devices/"+exchanged.DeviceID, local, nil); rec.Code != http.StatusNotFound {
+		t.Errorf("Expected status 404, got %d", rec.Code)
+	}
+}
@@ -10,6 +10,12 @@ import (
 
 type Config struct {
 	Port                int      `mapstructure:"port"`
+	BindAddress         string   `mapstructure:"bind_address"`            // Listen address; "" = all interfaces, IPv4 and IPv6
+	PairingTokensPath   string   `mapstructure:"pairing_tokens_path"`     // Desktop pairing codes (hashed, written by the desktop); exchanged for device credentials
+	PairedDevicesPath   string   `mapstructure:"paired_devices_path"`     // Paired devices (hashed credentials, written by the backend); with pairing_tokens_path and a non-loopback bind, LAN clients need a credential
+	AirGapped           bool     `mapstructure:"air_gapped"`              // Desktop air-gapped mode: no internet calls (skips the Artifact Hub catalog sync)
+	ContextProxies      string   `mapstructure:"context_proxies"`         // JSON object of kubeconfig context → proxy URL (set by the desktop); overrides the kubeconfig's proxy-url
+	ContextPolicies     string   `mapstructure:"context_policies"`        // JSON object of kubeconfig context → {request_timeout_secs, retries, backoff_ms} (set by the desktop)
 	DatabasePath        string   `mapstructure:"database_path"`
 	LogLevel            string   `mapstructure:"log_level"`   // debug | info | warn | error
 	LogFormat           string   `mapstructure:"log_format"`  // json | text (BE-OBS-002)
@@ -106,6 +112,12 @@ func Load() (*Config, error) {
 
 	// Defaults
 	viper.SetDefault("port", 819)
+	viper.SetDefault("bind_address", "")
+	viper.SetDefault("pairing_tokens_path", "")
+	viper.SetDefault("paired_devices_path", "")
+	viper.SetDefault("air_gapped", false)
+	viper.SetDefault("context_proxies", "")
+	viper.SetDefault("context_policies", "")
 	viper.SetDefault("database_path", "./kubilitics.db")
 	viper.SetDefault("log_level", "info")
 	viper.SetDefault("log_format", "json") // BE-OBS-002: JSON structured logging by default
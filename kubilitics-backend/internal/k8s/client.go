@@ -50,6 +50,9 @@ type Client struct {
 	kubeconfigPath string
 	// Timeout for outbound K8s API calls; 0 means no timeout (use request context only).
 	Timeout time.Duration
+	// contextTimeout and retry come from the context's policy (KUBILITICS_CONTEXT_POLICIES); zero = not set.
+	contextTimeout time.Duration
+	retry          retryPolicy
 	// Limiter optionally rate-limits outbound API calls per cluster (C1.5). Nil = no limit.
 	limiter *rate.Limiter
 	// CircuitBreaker protects against cascading failures (BE-SCALE-001).
@@ -86,6 +89,7 @@ func NewClient(kubeconfigPath, context string) (*Client, error) {
 		if err != nil {
 			return nil, fmt.Errorf("failed to build config: %w", err)
 		}
+		applyContextProxy(config, context)
 	}
 
 	clientset, err := kubernetes.NewForConfig(config)
@@ -98,7 +102,7 @@ func NewClient(kubeconfigPath, context string) (*Client, error) {
 		return nil, fmt.Errorf("failed to create dynamic client: %w", err)
 	}
 
-	return &Client{
+	c := &Client{
 		Clientset:      clientset,
 		Dynamic:        dynamicClient,
 		Config:         config,
@@ -106,7 +110,9 @@ func NewClient(kubeconfigPath, context string) (*Client, error) {
 		kubeconfigPath: kubeconfigPath,
 		circuitBreaker: NewCircuitBreaker(""), // clusterID will be set via SetClusterID if available
 		lastSuccessTime: time.Now(),
-	}, nil
+	}
+	c.applyContextPolicy()
+	return c, nil
 }
 
 // SetTimeout sets the timeout for outbound K8s API calls. Call after NewClient when config is available.
@@ -141,10 +147,14 @@ func (c *Client) waitRateLimit(ctx context.Context) error {
 	return c.limiter.Wait(ctx)
 }
 
-// withTimeout returns ctx with timeout applied if c.Timeout > 0; otherwise returns ctx and a no-op cancel.
+// withTimeout returns ctx with the context policy's timeout, else c.Timeout, applied if > 0; otherwise returns ctx and a no-op cancel.
 func (c *Client) withTimeout(ctx context.Context) (context.Context, context.CancelFunc) {
-	if c.Timeout > 0 {
-		return context.WithTimeout(ctx, c.Timeout)
+	timeout := c.Timeout
+	if c.contextTimeout > 0 {
+		timeout = c.contextTimeout
+	}
+	if timeout > 0 {
+		return context.WithTimeout(ctx, timeout)
 	}
 	return ctx, func() {}
 }
@@ -190,6 +200,7 @@ func NewClientFromBytes(kubeconfigBytes []byte, context string) (*Client, error)
 	if err != nil {
 		return nil, fmt.Errorf("failed to build config for context %s: %w", contextToUse, err)
 	}
+	applyContextProxy(config, contextToUse)
 
 	clientset, err := kubernetes.NewForConfig(config)
 	if err != nil {
@@ -201,7 +212,7 @@ func NewClientFromBytes(kubeconfigBytes []byte, context string) (*Client, error)
 		return nil, fmt.Errorf("failed to create dynamic client: %w", err)
 	}
 
-	return &Client{
+	c := &Client{
 		Clientset:      clientset,
 		Dynamic:        dynamicClient,
 		Config:         config,
@@ -209,7 +220,9 @@ func NewClientFromBytes(kubeconfigBytes []byte, context string) (*Client, error)
 		kubeconfigPath: "", // Not stored when using bytes
 		circuitBreaker: NewCircuitBreaker(""), // clusterID will be set via SetClusterID if available
 		lastSuccessTime: time.Now(),
-	}, nil
+	}
+	c.applyContextPolicy()
+	return c, nil
 }
 
 // GetServerVersion returns Kubernetes server version
@@ -232,7 +245,7 @@ func (c *Client) TestConnection(ctx context.Context) error {
 	err := c.circuitBreaker.Execute(ctx, func() error {
 		ctx, cancel := c.withTimeout(ctx)
 		defer cancel()
-		return doWithRetry(ctx, defaultRetryAttempts, func() error {
+		return doWithRetry(ctx, c.retryPolicy(), func() error {
 			_, err := c.Clientset.CoreV1().Namespaces().List(ctx, metav1.ListOptions{Limit: 1})
 			return err
 		})
@@ -254,7 +267,7 @@ func (c *Client) GetClusterInfo(ctx context.Context) (map[string]interface{}, er
 		ctx, cancel := c.withTimeout(ctx)
 		defer cancel()
 		var fnErr error
-		result, fnErr = doWithRetryValue(ctx, defaultRetryAttempts, func() (map[string]interface{}, error) {
+		result, fnErr = doWithRetryValue(ctx, c.retryPolicy(), func() (map[string]interface{}, error) {
 			version, err := c.GetServerVersion(ctx)
 			if err != nil {
 				return nil, err
@@ -0,0 +1,71 @@
+package k8s
+
+import (
+	"encoding/json"
+	"fmt"
+	"sync"
+	"time"
+)
+
+// contextPolicyMaxBackoff caps the doubling backoff of a context policy; the desktop accepts an
+// initial backoff up to the same value.
+const contextPolicyMaxBackoff = 60 * time.Second
+
+// contextPolicy is a kubeconfig context's request budget, as the desktop passes it in
+// KUBILITICS_CONTEXT_POLICIES: a per-request timeout, retries after the first attempt, and the
+// delay before the first retry (doubled for each further one).
+type contextPolicy struct {
+	RequestTimeoutSecs int `json:"request_timeout_secs"`
+	Retries            int `json:"retries"`
+	BackoffMs          int `json:"backoff_ms"`
+}
+
+var (
+	contextPolicies   map[string]contextPolicy
+	contextPoliciesMu sync.RWMutex
+)
+
+// SetContextPolicies parses a JSON object of context name → policy and uses it for clients built
+// afterwards. An empty string clears the map.
+func SetContextPolicies(raw string) error {
+	parsed := map[string]contextPolicy{}
+	if raw != "" {
+		if err := json.Unmarshal([]byte(raw), &parsed); err != nil {
+			return fmt.Errorf("invalid context policies: %w", err)
+		}
+		for name, policy := range parsed {
+			if policy.RequestTimeoutSecs < 0 || policy.Retries < 0 || policy.BackoffMs < 0 {
+				return fmt.Errorf("invalid policy for context %s", name)
+			}
+		}
+	}
+	contextPoliciesMu.Lock()
+	contextPolicies = parsed
+	contextPoliciesMu.Unlock()
+	return nil
+}
+
+// applyContextPolicy gives c its context's timeout and retry policy, if the context has one.
+func (c *Client) applyContextPolicy() {
+	contextPoliciesMu.RLock()
+	policy, ok := contextPolicies[c.Context]
+	contextPoliciesMu.RUnlock()
+	if !ok {
+		return
+	}
+	c.contextTimeout = time.Duration(policy.RequestTimeoutSecs) * time.Second
+	c.retry = retryPolicy{
+		attempts:       policy.Retries + 1,
+		initialBackoff: time.Duration(policy.BackoffMs) * time.Millisecond,
+		factor:         2,
+		maxBackoff:     contextPolicyMaxBackoff,
+	}
+}
+
+// retryPolicy is the context's retry policy, or the default one.
+func (c *Client) retryPolicy() retryPolicy {
+	if c.retry.attempts > 0 {
+		return c.retry
+	}
+	return defaultRetryPolicy
+}
@@ -0,0 +1,50 @@
+package k8s
+
+import (
+	"testing"
+	"time"
+)
+
+func TestApplyContextPolicy(t *testing.T) {
+	t.Cleanup(func() { _ = SetContextPolicies("") })
+
+	if err := SetContextPolicies(`{"vpn":{"request_timeout_secs":90,"retries":4,"backoff_ms":500}}`); err != nil {
+		t.Fatalf("SetContextPolicies failed: %v", err)
+	}
+
+	c := &Client{Context: "vpn", Timeout: 30 * time.Second}
+	c.applyContextPolicy()
+	policy := c.retryPolicy()
+	if policy.attempts != 5 {
+		t.Errorf("Expected 5 attempts, got %d", policy.attempts)
+	}
+	for attempt, want := range []time.Duration{500 * time.Millisecond, time.Second, 2 * time.Second, 4 * time.Second} {
+		if got := policy.backoff(attempt); got != want {
+			t.Errorf("backoff(%d) = %v, want %v", attempt, got, want)
+		}
+	}
+	// The context's timeout wins over the global one set afterwards
+	c.SetTimeout(10 * time.Second)
+	ctx, cancel := c.withTimeout(t.Context())
+	defer cancel()
+	deadline, ok := ctx.Deadline()
+	if !ok || time.Until(deadline) < 80*time.Second {
+		t.Errorf("Expected a deadline about 90s away, got %v", time.Until(deadline))
+	}
+
+	other := &Client{Context: "kind"}
+	other.applyContextPolicy()
+	if other.retryPolicy() != defaultRetryPolicy {
+		t.Error("Expected the default policy for a context without one")
+	}
+}
+
+func TestSetContextPolicies_Invalid(t *testing.T) {
+	t.Cleanup(func() { _ = SetContextPolicies("") })
+
+	for _, raw := range []string{`not json`, `{"vpn":{"retries":-1}}`} {
+		if err := SetContextPolicies(raw); err == nil {
+			t.Errorf("Expected an error for %s", raw)
+		}
+	}
+}
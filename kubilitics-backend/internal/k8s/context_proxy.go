@@ -0,0 +1,57 @@
+package k8s
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/url"
+	"sync"
+
+	"k8s.io/client-go/rest"
+)
+
+// contextProxies maps kubeconfig context names to a proxy URL (typically socks5:// or socks5h://
+// for an `ssh -D` forward through a jump host). The desktop passes them in
+// KUBILITICS_CONTEXT_PROXIES; a context listed here overrides any proxy-url in the kubeconfig.
+var (
+	contextProxies   map[string]*url.URL
+	contextProxiesMu sync.RWMutex
+)
+
+// SetContextProxies parses a JSON object of context name → proxy URL and uses it for clients built
+// afterwards. An empty string clears the map.
+func SetContextProxies(raw string) error {
+	parsed := map[string]*url.URL{}
+	if raw != "" {
+		var proxies map[string]string
+		if err := json.Unmarshal([]byte(raw), &proxies); err != nil {
+			return fmt.Errorf("invalid context proxies: %w", err)
+		}
+		for name, proxy := range proxies {
+			u, err := url.Parse(proxy)
+			if err != nil || u.Host == "" {
+				return fmt.Errorf("invalid proxy for context %s", name)
+			}
+			switch u.Scheme {
+			case "socks5", "socks5h", "http", "https":
+			default:
+				return fmt.Errorf("unsupported proxy scheme %q for context %s", u.Scheme, name)
+			}
+			parsed[name] = u
+		}
+	}
+	contextProxiesMu.Lock()
+	contextProxies = parsed
+	contextProxiesMu.Unlock()
+	return nil
+}
+
+// applyContextProxy routes config through the context's proxy, if one is set.
+func applyContextProxy(config *rest.Config, context string) {
+	contextProxiesMu.RLock()
+	proxy, ok := contextProxies[context]
+	contextProxiesMu.RUnlock()
+	if ok {
+		config.Proxy = http.ProxyURL(proxy)
+	}
+}
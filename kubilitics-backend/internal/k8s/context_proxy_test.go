@@ -0,0 +1,48 @@
+package k8s
+
+import (
+	"net/http"
+	"testing"
+
+	"k8s.io/client-go/rest"
+)
+
+func TestSetContextProxies(t *testing.T) {
+	t.Cleanup(func() { _ = SetContextProxies("") })
+
+	if err := SetContextProxies(`{"prod":"socks5h://127.0.0.1:1080"}`); err != nil {
+		t.Fatalf("SetContextProxies failed: %v", err)
+	}
+
+	config := &rest.Config{Host: "https://10.0.0.1:6443"}
+	applyContextProxy(config, "prod")
+	if config.Proxy == nil {
+		t.Fatal("Expected a proxy for the prod context")
+	}
+	req, _ := http.NewRequest(http.MethodGet, config.Host, nil)
+	proxy, err := config.Proxy(req)
+	if err != nil || proxy.String() != "socks5h://127.0.0.1:1080" {
+		t.Errorf("Expected socks5h://127.0.0.1:1080, got %v (%v)", proxy, err)
+	}
+
+	other := &rest.Config{Host: "https://10.0.0.2:6443"}
+	applyContextProxy(other, "staging")
+	if other.Proxy != nil {
+		t.Error("Expected no proxy for a context without one")
+	}
+}
+
+func TestSetContextProxies_Invalid(t *testing.T) {
+	t.Cleanup(func() { _ = SetContextProxies("") })
+
+	cases := []string{
+		`not json`,
+		`{"prod":"ftp://jump:21"}`,
+		`{"prod":"socks5://"}`,
+	}
+	for _, raw := range cases {
+		if err := SetContextProxies(raw); err == nil {
+			t.Errorf("Expected an error for %s", raw)
+		}
+	}
+}
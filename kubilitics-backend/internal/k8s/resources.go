@@ -61,7 +61,7 @@ func (c *Client) ListResources(ctx context.Context, kind, namespace string, opts
 		ctx, cancel := c.withTimeout(ctx)
 		defer cancel()
 		var fnErr error
-		result, fnErr = doWithRetryValue(ctx, defaultRetryAttempts, func() (*unstructured.UnstructuredList, error) {
+		result, fnErr = doWithRetryValue(ctx, c.retryPolicy(), func() (*unstructured.UnstructuredList, error) {
 			if namespace != "" {
 				return c.Dynamic.Resource(gvr).Namespace(namespace).List(ctx, opts)
 			}
@@ -98,7 +98,7 @@ func (c *Client) GetResource(ctx context.Context, kind, namespace, name string)
 		ctx, cancel := c.withTimeout(ctx)
 		defer cancel()
 		var fnErr error
-		result, fnErr = doWithRetryValue(ctx, defaultRetryAttempts, func() (*unstructured.Unstructured, error) {
+		result, fnErr = doWithRetryValue(ctx, c.retryPolicy(), func() (*unstructured.Unstructured, error) {
 			if namespace != "" {
 				return c.Dynamic.Resource(gvr).Namespace(namespace).Get(ctx, name, metav1.GetOptions{})
 			}
@@ -127,7 +127,7 @@ func (c *Client) DeleteResource(ctx context.Context, kind, namespace, name strin
 	err = c.circuitBreaker.Execute(ctx, func() error {
 		ctx, cancel := c.withTimeout(ctx)
 		defer cancel()
-		return doWithRetry(ctx, defaultRetryAttempts, func() error {
+		return doWithRetry(ctx, c.retryPolicy(), func() error {
 			if namespace != "" {
 				return c.Dynamic.Resource(gvr).Namespace(namespace).Delete(ctx, name, opts)
 			}
@@ -157,7 +157,7 @@ func (c *Client) PatchResource(ctx context.Context, kind, namespace, name string
 		ctx, cancel := c.withTimeout(ctx)
 		defer cancel()
 		var fnErr error
-		result, fnErr = doWithRetryValue(ctx, defaultRetryAttempts, func() (*unstructured.Unstructured, error) {
+		result, fnErr = doWithRetryValue(ctx, c.retryPolicy(), func() (*unstructured.Unstructured, error) {
 			if namespace != "" {
 				return c.Dynamic.Resource(gvr).Namespace(namespace).Patch(ctx, name, types.MergePatchType, patch, metav1.PatchOptions{})
 			}
@@ -189,7 +189,7 @@ func (c *Client) CreateResource(ctx context.Context, obj *unstructured.Unstructu
 		namespace = "default"
 	}
 
-	return doWithRetryValue(ctx, defaultRetryAttempts, func() (*unstructured.Unstructured, error) {
+	return doWithRetryValue(ctx, c.retryPolicy(), func() (*unstructured.Unstructured, error) {
 		return c.Dynamic.Resource(gvr).Namespace(namespace).Create(ctx, obj, metav1.CreateOptions{})
 	})
 }
@@ -254,7 +254,7 @@ func (c *Client) ListCRDInstances(ctx context.Context, crdName, namespace string
 
 	gvr := schema.GroupVersionResource{Group: group, Version: version, Resource: plural}
 
-	return doWithRetryValue(ctx, defaultRetryAttempts, func() (*unstructured.UnstructuredList, error) {
+	return doWithRetryValue(ctx, c.retryPolicy(), func() (*unstructured.UnstructuredList, error) {
 		if namespace != "" {
 			return c.Dynamic.Resource(gvr).Namespace(namespace).List(ctx, opts)
 		}
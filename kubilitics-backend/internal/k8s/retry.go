@@ -32,20 +32,37 @@ func isRetryable(err error) bool {
 	return false
 }
 
+// retryPolicy is how many attempts a client makes and how long it waits between them.
+type retryPolicy struct {
+	attempts       int
+	initialBackoff time.Duration
+	factor         time.Duration
+	maxBackoff     time.Duration
+}
+
+// defaultRetryPolicy applies to contexts without a policy of their own.
+var defaultRetryPolicy = retryPolicy{
+	attempts:       defaultRetryAttempts,
+	initialBackoff: initialBackoff,
+	factor:         3,
+	maxBackoff:     maxBackoff,
+}
+
 // backoff returns delay for attempt (0-based); exponential with cap.
-func backoff(attempt int) time.Duration {
-	d := initialBackoff
-	for i := 0; i < attempt && d < maxBackoff; i++ {
-		d = d * 3
-		if d > maxBackoff {
-			d = maxBackoff
+func (p retryPolicy) backoff(attempt int) time.Duration {
+	d := p.initialBackoff
+	for i := 0; i < attempt && d < p.maxBackoff; i++ {
+		d = d * p.factor
+		if d > p.maxBackoff {
+			d = p.maxBackoff
 		}
 	}
 	return d
 }
 
-// doWithRetry runs fn up to maxAttempts times; retries on 5xx/429 with backoff. Non-retryable errors return immediately.
-func doWithRetry(ctx context.Context, maxAttempts int, fn func() error) error {
+// doWithRetry runs fn up to policy.attempts times; retries on 5xx/429 with backoff. Non-retryable errors return immediately.
+func doWithRetry(ctx context.Context, policy retryPolicy, fn func() error) error {
+	maxAttempts := policy.attempts
 	var lastErr error
 	for attempt := 0; attempt < maxAttempts; attempt++ {
 		lastErr = fn()
@@ -58,15 +75,16 @@ func doWithRetry(ctx context.Context, maxAttempts int, fn func() error) error {
 		select {
 		case <-ctx.Done():
 			return ctx.Err()
-		case <-time.After(backoff(attempt)):
+		case <-time.After(policy.backoff(attempt)):
 			// continue
 		}
 	}
 	return lastErr
 }
 
-// doWithRetryValue runs fn up to maxAttempts times and returns its value; retries on 5xx/429.
-func doWithRetryValue[T any](ctx context.Context, maxAttempts int, fn func() (T, error)) (T, error) {
+// doWithRetryValue runs fn up to policy.attempts times and returns its value; retries on 5xx/429.
+func doWithRetryValue[T any](ctx context.Context, policy retryPolicy, fn func() (T, error)) (T, error) {
+	maxAttempts := policy.attempts
 	var zero T
 	var lastErr error
 	for attempt := 0; attempt < maxAttempts; attempt++ {
@@ -81,7 +99,7 @@ func doWithRetryValue[T any](ctx context.Context, maxAttempts int, fn func() (T,
 		select {
 		case <-ctx.Done():
 			return zero, ctx.Err()
-		case <-time.After(backoff(attempt)):
+		case <-time.After(policy.backoff(attempt)):
 			// continue
 		}
 	}
@@ -0,0 +1,263 @@
+// Package pairing exchanges the desktop's short-lived QR pairing codes for long-lived, revocable
+// per-device credentials.
+//
+// The desktop writes issued codes (SHA-256 and expiry) to the codes file; the backend only reads it.
+// A device presents a code once to Exchange and receives a random credential. The backend records
+// the device in the devices file, which only the backend writes, with the credential stored as
+// SHA-256. The same file keeps the hashes of redeemed codes until they expire, so a code cannot be
+// exchanged twice. Revoking a device deletes its entry and its credential stops working at once.
+package pairing
+
+import (
+	"crypto/rand"
+	"crypto/sha256"
+	"crypto/subtle"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"net"
+	"os"
+	"path/filepath"
+	"sync"
+	"time"
+)
+
+// ErrInvalidCode is returned by Exchange for unknown, expired or already redeemed codes.
+var ErrInvalidCode = errors.New("invalid or expired pairing code")
+
+// issuedCode is one entry of the desktop's codes file, and of the redeemed-codes list.
+type issuedCode struct {
+	TokenSHA256 string `json:"token_sha256"`
+	ExpiresAt   int64  `json:"expires_at"`
+}
+
+// storedDevice is a paired device as persisted; the credential is kept only as its hash.
+type storedDevice struct {
+	ID               string `json:"id"`
+	Name             string `json:"name"`
+	CredentialSHA256 string `json:"credential_sha256"`
+	PairedAt         int64  `json:"paired_at"`
+}
+
+type devicesFile struct {
+	Devices   []storedDevice `json:"devices"`
+	UsedCodes []issuedCode   `json:"used_codes"`
+}
+
+// Device is a paired device as listed to the desktop.
+type Device struct {
+	ID       string `json:"id"`
+	Name     string `json:"name"`
+	PairedAt int64  `json:"paired_at"`
+}
+
+// Store reads the desktop's codes file and owns the devices file.
+type Store struct {
+	codesPath   string
+	devicesPath string
+	mu          sync.Mutex
+}
+
+// NewStore returns a store over the two files. Either path empty disables pairing.
+func NewStore(codesPath, devicesPath string) *Store {
+	return &Store{codesPath: codesPath, devicesPath: devicesPath}
+}
+
+// Enabled reports whether this backend was started by the desktop with pairing configured.
+func (s *Store) Enabled() bool {
+	return s.codesPath != "" && s.devicesPath != ""
+}
+
+func hashSecret(secret string) string {
+	sum := sha256.Sum256([]byte(secret))
+	return hex.EncodeToString(sum[:])
+}
+
+func randomHex(n int) (string, error) {
+	b := make([]byte, n)
+	if _, err := rand.Read(b); err != nil {
+		return "", err
+	}
+	return hex.EncodeToString(b), nil
+}
+
+func (s *Store) loadCodes() ([]issuedCode, error) {
+	content, err := os.ReadFile(s.codesPath)
+	if errors.Is(err, os.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	var codes []issuedCode
+	if err := json.Unmarshal(content, &codes); err != nil {
+		return nil, err
+	}
+	return codes, nil
+}
+
+// loadDevices reads the devices file. Callers must hold s.mu.
+func (s *Store) loadDevices() (devicesFile, error) {
+	var file devicesFile
+	content, err := os.ReadFile(s.devicesPath)
+	if errors.Is(err, os.ErrNotExist) {
+		return file, nil
+	}
+	if err != nil {
+		return file, err
+	}
+	err = json.Unmarshal(content, &file)
+	return file, err
+}
+
+// saveDevices replaces the devices file atomically (temp file + rename). Callers must hold s.mu.
+func (s *Store) saveDevices(file devicesFile) error {
+	content, err := json.MarshalIndent(file, "", "  ")
+	if err != nil {
+		return err
+	}
+	tmp, err := os.CreateTemp(filepath.Dir(s.devicesPath), ".paired_devices-*.tmp")
+	if err != nil {
+		return err
+	}
+	defer os.Remove(tmp.Name())
+	if _, err := tmp.Write(content); err != nil {
+		tmp.Close()
+		return err
+	}
+	if err := tmp.Chmod(0o600); err != nil {
+		tmp.Close()
+		return err
+	}
+	if err := tmp.Close(); err != nil {
+		return err
+	}
+	return os.Rename(tmp.Name(), s.devicesPath)
+}
+
+// Exchange redeems a pairing code for a new device credential. The credential is returned once and
+// only its hash is stored.
+func (s *Store) Exchange(code, name string, now time.Time) (Device, string, error) {
+	if !s.Enabled() || code == "" {
+		return Device{}, "", ErrInvalidCode
+	}
+	codes, err := s.loadCodes()
+	if err != nil {
+		return Device{}, "", err
+	}
+	hash := hashSecret(code)
+	var matched *issuedCode
+	for i := range codes {
+		if codes[i].ExpiresAt > now.Unix() && subtle.ConstantTimeCompare([]byte(codes[i].TokenSHA256), []byte(hash)) == 1 {
+			matched = &codes[i]
+		}
+	}
+	if matched == nil {
+		return Device{}, "", ErrInvalidCode
+	}
+
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	file, err := s.loadDevices()
+	if err != nil {
+		return Device{}, "", err
+	}
+	used := file.UsedCodes[:0]
+	for _, c := range file.UsedCodes {
+		if c.ExpiresAt > now.Unix() {
+			used = append(used, c)
+		}
+	}
+	for _, c := range used {
+		if c.TokenSHA256 == hash {
+			return Device{}, "", ErrInvalidCode
+		}
+	}
+
+	id, err := randomHex(8)
+	if err != nil {
+		return Device{}, "", err
+	}
+	credential, err := randomHex(32)
+	if err != nil {
+		return Device{}, "", err
+	}
+	if name == "" {
+		name = "Unnamed device"
+	}
+	device := storedDevice{ID: id, Name: name, CredentialSHA256: hashSecret(credential), PairedAt: now.Unix()}
+	file.Devices = append(file.Devices, device)
+	file.UsedCodes = append(used, *matched)
+	if err := s.saveDevices(file); err != nil {
+		return Device{}, "", err
+	}
+	return Device{ID: device.ID, Name: device.Name, PairedAt: device.PairedAt}, credential, nil
+}
+
+// Devices lists the paired devices, oldest first.
+func (s *Store) Devices() ([]Device, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	file, err := s.loadDevices()
+	if err != nil {
+		return nil, err
+	}
+	devices := make([]Device, 0, len(file.Devices))
+	for _, d := range file.Devices {
+		devices = append(devices, Device{ID: d.ID, Name: d.Name, PairedAt: d.PairedAt})
+	}
+	return devices, nil
+}
+
+// Revoke removes a paired device. It reports whether the device existed.
+func (s *Store) Revoke(id string) (bool, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	file, err := s.loadDevices()
+	if err != nil {
+		return false, err
+	}
+	kept := file.Devices[:0]
+	for _, d := range file.Devices {
+		if d.ID != id {
+			kept = append(kept, d)
+		}
+	}
+	if len(kept) == len(file.Devices) {
+		return false, nil
+	}
+	file.Devices = kept
+	return true, s.saveDevices(file)
+}
+
+// ValidCredential reports whether credential belongs to a paired device. The devices file is
+// re-read on every call so revocations apply immediately; it holds a handful of entries.
+func (s *Store) ValidCredential(credential string) bool {
+	if !s.Enabled() || credential == "" {
+		return false
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	file, err := s.loadDevices()
+	if err != nil {
+		return false
+	}
+	hash := hashSecret(credential)
+	valid := false
+	for _, d := range file.Devices {
+		if subtle.ConstantTimeCompare([]byte(d.CredentialSHA256), []byte(hash)) == 1 {
+			valid = true
+		}
+	}
+	return valid
+}
+
+// IsLoopbackRemote reports whether a request's RemoteAddr is on this machine.
+func IsLoopbackRemote(remoteAddr string) bool {
+	host, _, err := net.SplitHostPort(remoteAddr)
+	if err != nil {
+		host = remoteAddr
+	}
+	ip := net.ParseIP(host)
+	return ip != nil && ip.IsLoopback()
+}
@@ -0,0 +1,147 @@
+package pairing
+
+import (
+	"encoding/json"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func newTestStore(t *testing.T, codes map[string]time.Time) *Store {
+	t.Helper()
+	dir := t.TempDir()
+	entries := make([]issuedCode, 0, len(codes))
+	for code, expiresAt := range codes {
+		entries = append(entries, issuedCode{TokenSHA256: hashSecret(code), ExpiresAt: expiresAt.Unix()})
+	}
+	content, err := json.Marshal(entries)
+	if err != nil {
+		t.Fatal(err)
+	}
+	codesPath := filepath.Join(dir, "pairing_tokens.json")
+	if err := os.WriteFile(codesPath, content, 0o600); err != nil {
+		t.Fatal(err)
+	}
+	return NewStore(codesPath, filepath.Join(dir, "paired_devices.json"))
+}
+
+func TestExchange_IssuesWorkingCredential(t *testing.T) {
+	now := time.Now()
+	store := newTestStore(t, map[string]time.Time{"code": now.Add(10 * time.Minute)})
+
+	device, credential, err := store.Exchange("code", "Phone", now)
+	if err != nil {
+		t.Fatalf("Exchange failed: %v", err)
+	}
+	if device.Name != "Phone" || device.ID == "" {
+		t.Errorf("Unexpected device %+v", device)
+	}
+	if !store.ValidCredential(credential) {
+		t.Error("Expected the issued credential to be valid")
+	}
+	if store.ValidCredential("code") {
+		t.Error("Expected the pairing code not to work as a credential")
+	}
+	if store.ValidCredential("guess") {
+		t.Error("Expected an unknown credential to be rejected")
+	}
+}
+
+func TestExchange_CredentialOutlivesCode(t *testing.T) {
+	now := time.Now()
+	store := newTestStore(t, map[string]time.Time{"code": now.Add(time.Minute)})
+
+	_, credential, err := store.Exchange("code", "Phone", now)
+	if err != nil {
+		t.Fatalf("Exchange failed: %v", err)
+	}
+	// The desktop drops expired codes from its file; the device stays paired
+	if err := os.WriteFile(store.codesPath, []byte("[]"), 0o600); err != nil {
+		t.Fatal(err)
+	}
+	if !store.ValidCredential(credential) {
+		t.Error("Expected the credential to stay valid after the code expired")
+	}
+}
+
+func TestExchange_RejectsReusedExpiredAndUnknownCodes(t *testing.T) {
+	now := time.Now()
+	store := newTestStore(t, map[string]time.Time{
+		"code":    now.Add(10 * time.Minute),
+		"expired": now.Add(-time.Minute),
+	})
+
+	if _, _, err := store.Exchange("code", "Phone", now); err != nil {
+		t.Fatalf("Exchange failed: %v", err)
+	}
+	cases := []struct {
+		name string
+		code string
+	}{
+		{"reused code", "code"},
+		{"expired code", "expired"},
+		{"unknown code", "guess"},
+		{"empty code", ""},
+	}
+	for _, tc := range cases {
+		t.Run(tc.name, func(t *testing.T) {
+			if _, _, err := store.Exchange(tc.code, "Tablet", now); !errors.Is(err, ErrInvalidCode) {
+				t.Errorf("Expected ErrInvalidCode, got %v", err)
+			}
+		})
+	}
+}
+
+func TestRevoke_CutsOffDevice(t *testing.T) {
+	now := time.Now()
+	store := newTestStore(t, map[string]time.Time{
+		"first":  now.Add(10 * time.Minute),
+		"second": now.Add(10 * time.Minute),
+	})
+
+	phone, phoneCredential, err := store.Exchange("first", "Phone", now)
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, tabletCredential, err := store.Exchange("second", "Tablet", now)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	revoked, err := store.Revoke(phone.ID)
+	if err != nil || !revoked {
+		t.Fatalf("Revoke = %v, %v", revoked, err)
+	}
+	if store.ValidCredential(phoneCredential) {
+		t.Error("Expected the revoked credential to be rejected")
+	}
+	if !store.ValidCredential(tabletCredential) {
+		t.Error("Expected the other device to stay paired")
+	}
+	devices, err := store.Devices()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(devices) != 1 || devices[0].Name != "Tablet" {
+		t.Errorf("Unexpected devices %+v", devices)
+	}
+	if revoked, _ := store.Revoke(phone.ID); revoked {
+		t.Error("Expected revoking twice to report false")
+	}
+	// A revoked device cannot re-pair with its old code
+	if _, _, err := store.Exchange("first", "Phone", now); !errors.Is(err, ErrInvalidCode) {
+		t.Errorf("Expected ErrInvalidCode, got %v", err)
+	}
+}
+
+func TestStore_DisabledWithoutPaths(t *testing.T) {
+	store := NewStore("", "")
+	if store.Enabled() {
+		t.Error("Expected a store without paths to be disabled")
+	}
+	if _, _, err := store.Exchange("code", "Phone", time.Now()); !errors.Is(err, ErrInvalidCode) {
+		t.Errorf("Expected ErrInvalidCode, got %v", err)
+	}
+}
@@ -0,0 +1,144 @@
+// "Can I?" in bulk: many SelfSubjectAccessReviews at once, for the RBAC preflight before an action
+// and for greying out what the current user isn't allowed to do. Reviews run concurrently (at most
+// MAX_CONCURRENT_REVIEWS in flight) and the answers come back both per check and folded into a
+// resource × verb matrix.
+use std::collections::BTreeMap;
+
+use futures::StreamExt;
+use k8s_openapi::api::authorization::v1::{
+    ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+};
+use kube::api::PostParams;
+use kube::{Api, Client};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+const MAX_CONCURRENT_REVIEWS: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessCheck {
+    pub verb: String,
+    /// Plural resource name, e.g. `deployments`.
+    pub resource: String,
+    /// API group; core when omitted.
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub subresource: Option<String>,
+    /// Cluster-wide (all namespaces) when omitted.
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessCheckResult {
+    #[serde(flatten)]
+    pub check: AccessCheck,
+    pub allowed: bool,
+    /// Explicitly denied by an authorizer, not just missing a grant.
+    pub denied: bool,
+    pub reason: Option<String>,
+    /// The review itself failed; `allowed` is false.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessMatrixRow {
+    pub namespace: Option<String>,
+    /// kubectl notation: `deployments.apps`, `pods/log`.
+    pub resource: String,
+    /// Verb → allowed.
+    pub verbs: BTreeMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccessReviewBatch {
+    pub results: Vec<AccessCheckResult>,
+    pub matrix: Vec<AccessMatrixRow>,
+}
+
+fn resource_label(check: &AccessCheck) -> String {
+    let mut label = check.resource.clone();
+    if let Some(group) = check.group.as_deref().filter(|g| !g.is_empty()) {
+        label = format!("{}.{}", label, group);
+    }
+    if let Some(subresource) = check.subresource.as_deref().filter(|s| !s.is_empty()) {
+        label = format!("{}/{}", label, subresource);
+    }
+    label
+}
+
+async fn review(client: Client, check: AccessCheck) -> AccessCheckResult {
+    let request = SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                verb: Some(check.verb.clone()),
+                resource: Some(check.resource.clone()),
+                group: check.group.clone(),
+                subresource: check.subresource.clone(),
+                namespace: check.namespace.clone(),
+                name: check.name.clone(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let api: Api<SelfSubjectAccessReview> = Api::all(client);
+    match api.create(&PostParams::default(), &request).await {
+        Ok(response) => {
+            let status = response.status.unwrap_or_default();
+            AccessCheckResult {
+                check,
+                allowed: status.allowed,
+                denied: status.denied.unwrap_or(false),
+                reason: status.reason.filter(|r| !r.is_empty()),
+                error: status.evaluation_error.filter(|e| !e.is_empty()),
+            }
+        }
+        Err(e) => AccessCheckResult {
+            check,
+            allowed: false,
+            denied: false,
+            reason: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+/// Run every check as the context's user. Results are in the order of `checks`.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn can_i_batch(
+    context: String,
+    checks: Vec<AccessCheck>,
+) -> Result<AccessReviewBatch, String> {
+    let client = crate::k8s::client_for(&context).await?;
+    let results: Vec<AccessCheckResult> = futures::stream::iter(checks)
+        .map(|check| review(client.clone(), check))
+        .buffered(MAX_CONCURRENT_REVIEWS)
+        .collect()
+        .await;
+
+    let mut rows: BTreeMap<(Option<String>, String), BTreeMap<String, bool>> = BTreeMap::new();
+    for result in &results {
+        let key = (
+            result.check.namespace.clone(),
+            resource_label(&result.check),
+        );
+        rows.entry(key)
+            .or_default()
+            .insert(result.check.verb.clone(), result.allowed);
+    }
+    let matrix = rows
+        .into_iter()
+        .map(|((namespace, resource), verbs)| AccessMatrixRow {
+            namespace,
+            resource,
+            verbs,
+        })
+        .collect();
+    Ok(AccessReviewBatch { results, matrix })
+}
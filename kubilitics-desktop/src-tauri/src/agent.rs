@@ -0,0 +1,308 @@
+// The in-cluster companion agent: the Kubilitics backend image running in the cluster with a
+// read-only service account, for the features that need something next to the API server. The
+// manifests (namespace, RBAC, deployment, service) are rendered here and server-side applied, so
+// installing needs no kubectl and re-installing with another image is an update.
+//
+// After install the rollout is followed in the background and reported as `agent-rollout` events
+// until the deployment is available, fails or times out. Uninstall deletes the cluster-scoped RBAC
+// and the namespace, which takes everything else with it.
+use std::time::{Duration, Instant};
+
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::Namespace;
+use k8s_openapi::api::rbac::v1::{ClusterRole, ClusterRoleBinding};
+use kube::api::{DeleteParams, PatchParams};
+use kube::{Api, Client};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{command, AppHandle, Emitter};
+
+use crate::rollout::RolloutPhase;
+
+const AGENT_NAMESPACE: &str = "kubilitics-system";
+const AGENT_NAME: &str = "kubilitics-agent";
+const DEFAULT_AGENT_IMAGE: &str = "ghcr.io/kubilitics/kubilitics-backend:1.0.0";
+/// What clients connect to, matching the backend's usual port.
+const AGENT_SERVICE_PORT: u16 = 819;
+/// What the container listens on: unprivileged, since the agent runs as a non-root user without
+/// capabilities and couldn't bind a port below 1024.
+const AGENT_CONTAINER_PORT: u16 = 8080;
+const FIELD_MANAGER: &str = "kubilitics-agent-installer";
+const ROLLOUT_TIMEOUT: Duration = Duration::from_secs(300);
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentPhase {
+    NotInstalled,
+    Progressing,
+    Available,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStatus {
+    pub context: String,
+    pub phase: AgentPhase,
+    pub namespace: String,
+    pub image: Option<String>,
+    pub replicas: i32,
+    pub updated_replicas: i32,
+    pub ready_replicas: i32,
+    pub available_replicas: i32,
+    /// Why the rollout failed or what it is waiting for.
+    pub message: Option<String>,
+}
+
+fn labels() -> Value {
+    json!({
+        "app.kubernetes.io/name": AGENT_NAME,
+        "app.kubernetes.io/part-of": "kubilitics",
+        "app.kubernetes.io/managed-by": "kubilitics-desktop",
+    })
+}
+
+/// The agent's objects, in apply order.
+fn agent_manifests(image: &str) -> Vec<Value> {
+    let metadata = |namespaced: bool| {
+        let mut metadata = json!({ "name": AGENT_NAME, "labels": labels() });
+        if namespaced {
+            metadata["namespace"] = json!(AGENT_NAMESPACE);
+        }
+        metadata
+    };
+    let selector = json!({ "app.kubernetes.io/name": AGENT_NAME });
+    vec![
+        json!({
+            "apiVersion": "v1",
+            "kind": "Namespace",
+            "metadata": { "name": AGENT_NAMESPACE, "labels": labels() },
+        }),
+        json!({
+            "apiVersion": "v1",
+            "kind": "ServiceAccount",
+            "metadata": metadata(true),
+        }),
+        json!({
+            "apiVersion": "rbac.authorization.k8s.io/v1",
+            "kind": "ClusterRole",
+            "metadata": metadata(false),
+            "rules": [
+                { "apiGroups": ["*"], "resources": ["*"], "verbs": ["get", "list", "watch"] },
+                { "apiGroups": ["metrics.k8s.io"], "resources": ["nodes", "pods"], "verbs": ["get", "list"] },
+            ],
+        }),
+        json!({
+            "apiVersion": "rbac.authorization.k8s.io/v1",
+            "kind": "ClusterRoleBinding",
+            "metadata": metadata(false),
+            "roleRef": {
+                "apiGroup": "rbac.authorization.k8s.io",
+                "kind": "ClusterRole",
+                "name": AGENT_NAME,
+            },
+            "subjects": [
+                { "kind": "ServiceAccount", "name": AGENT_NAME, "namespace": AGENT_NAMESPACE },
+            ],
+        }),
+        json!({
+            "apiVersion": "apps/v1",
+            "kind": "Deployment",
+            "metadata": metadata(true),
+            "spec": {
+                "replicas": 1,
+                "selector": { "matchLabels": selector },
+                "template": {
+                    "metadata": { "labels": labels() },
+                    "spec": {
+                        "serviceAccountName": AGENT_NAME,
+                        "securityContext": { "runAsNonRoot": true, "runAsUser": 1000, "fsGroup": 1000 },
+                        "containers": [{
+                            "name": "agent",
+                            "image": image,
+                            "imagePullPolicy": "IfNotPresent",
+                            "ports": [{ "name": "http", "containerPort": AGENT_CONTAINER_PORT, "protocol": "TCP" }],
+                            "env": [
+                                { "name": "KUBILITICS_PORT", "value": AGENT_CONTAINER_PORT.to_string() },
+                                { "name": "KUBILITICS_DATABASE_PATH", "value": "/data/kubilitics.db" },
+                            ],
+                            "securityContext": {
+                                "allowPrivilegeEscalation": false,
+                                "capabilities": { "drop": ["ALL"] },
+                            },
+                            "livenessProbe": {
+                                "httpGet": { "path": "/health", "port": "http" },
+                                "initialDelaySeconds": 5,
+                                "periodSeconds": 10,
+                            },
+                            "readinessProbe": {
+                                "httpGet": { "path": "/health", "port": "http" },
+                                "initialDelaySeconds": 3,
+                                "periodSeconds": 5,
+                            },
+                            "resources": {
+                                "requests": { "cpu": "50m", "memory": "64Mi" },
+                                "limits": { "memory": "256Mi" },
+                            },
+                            "volumeMounts": [{ "name": "data", "mountPath": "/data" }],
+                        }],
+                        "volumes": [{ "name": "data", "emptyDir": {} }],
+                    },
+                },
+            },
+        }),
+        json!({
+            "apiVersion": "v1",
+            "kind": "Service",
+            "metadata": metadata(true),
+            "spec": {
+                "type": "ClusterIP",
+                "selector": selector,
+                "ports": [{ "name": "http", "port": AGENT_SERVICE_PORT, "targetPort": "http", "protocol": "TCP" }],
+            },
+        }),
+    ]
+}
+
+fn status_of(context: &str, deployment: Option<&Deployment>) -> AgentStatus {
+    let mut status = AgentStatus {
+        context: context.to_string(),
+        phase: AgentPhase::NotInstalled,
+        namespace: AGENT_NAMESPACE.to_string(),
+        image: None,
+        replicas: 0,
+        updated_replicas: 0,
+        ready_replicas: 0,
+        available_replicas: 0,
+        message: None,
+    };
+    let Some(deployment) = deployment else {
+        return status;
+    };
+    status.image = deployment
+        .spec
+        .as_ref()
+        .and_then(|s| s.template.spec.as_ref())
+        .and_then(|s| s.containers.first())
+        .and_then(|c| c.image.clone());
+
+    let progress = crate::rollout::deployment_progress(deployment);
+    status.phase = match progress.phase {
+        RolloutPhase::Progressing => AgentPhase::Progressing,
+        RolloutPhase::Complete => AgentPhase::Available,
+        RolloutPhase::Failed => AgentPhase::Failed,
+    };
+    status.replicas = progress.replicas;
+    status.updated_replicas = progress.updated_replicas;
+    status.ready_replicas = progress.ready_replicas;
+    status.available_replicas = progress.available_replicas;
+    status.message = progress.message;
+    status
+}
+
+async fn fetch_status(client: &Client, context: &str) -> Result<AgentStatus, String> {
+    let deployments: Api<Deployment> = Api::namespaced(client.clone(), AGENT_NAMESPACE);
+    let deployment = deployments
+        .get_opt(AGENT_NAME)
+        .await
+        .map_err(|e| format!("Failed to read the agent deployment: {}", e))?;
+    Ok(status_of(context, deployment.as_ref()))
+}
+
+/// Report rollout progress as `agent-rollout` events until it settles or ROLLOUT_TIMEOUT passes.
+async fn follow_rollout(app: AppHandle, client: Client, context: String) {
+    let deadline = Instant::now() + ROLLOUT_TIMEOUT;
+    loop {
+        let mut status = match fetch_status(&client, &context).await {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::warn!(error = %e, "Agent rollout check failed");
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+        if status.phase == AgentPhase::Progressing && Instant::now() >= deadline {
+            status.phase = AgentPhase::Failed;
+            status.message =
+                Some("Timed out waiting for the agent to become available".to_string());
+        }
+        let _ = app.emit("agent-rollout", &status);
+        if status.phase != AgentPhase::Progressing {
+            return;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[command]
+pub async fn get_agent_status(context: String) -> Result<AgentStatus, String> {
+    let client = crate::k8s::client_for(&context).await?;
+    fetch_status(&client, &context).await
+}
+
+/// Install (or update) the agent in a context. Returns once the objects are applied; rollout
+/// progress follows as `agent-rollout` events.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn install_agent(
+    app_handle: AppHandle,
+    context: String,
+    image: Option<String>,
+) -> Result<AgentStatus, String> {
+    let image = image
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty())
+        .unwrap_or_else(|| DEFAULT_AGENT_IMAGE.to_string());
+    let client = crate::k8s::client_for(&context).await?;
+
+    // The installer owns these objects outright
+    let params = PatchParams::apply(FIELD_MANAGER).force();
+    for manifest in agent_manifests(&image) {
+        let kind = manifest["kind"].as_str().unwrap_or_default().to_string();
+        crate::apply::apply_document(&client, manifest, &params)
+            .await
+            .map_err(|e| format!("Failed to apply the agent {}: {}", kind, e))?;
+    }
+
+    let status = fetch_status(&client, &context).await?;
+    tauri::async_runtime::spawn(follow_rollout(app_handle, client, context));
+    Ok(status)
+}
+
+/// Remove the agent from a context. Namespace deletion finishes in the background.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn uninstall_agent(context: String) -> Result<(), String> {
+    let client = crate::k8s::client_for(&context).await?;
+    let params = DeleteParams::background();
+    let not_found = |e: &kube::Error| matches!(e, kube::Error::Api(r) if r.code == 404);
+
+    if let Err(e) = Api::<ClusterRoleBinding>::all(client.clone())
+        .delete(AGENT_NAME, &params)
+        .await
+    {
+        if !not_found(&e) {
+            return Err(format!("Failed to delete the agent role binding: {}", e));
+        }
+    }
+    if let Err(e) = Api::<ClusterRole>::all(client.clone())
+        .delete(AGENT_NAME, &params)
+        .await
+    {
+        if !not_found(&e) {
+            return Err(format!("Failed to delete the agent role: {}", e));
+        }
+    }
+    if let Err(e) = Api::<Namespace>::all(client)
+        .delete(AGENT_NAMESPACE, &params)
+        .await
+    {
+        if !not_found(&e) {
+            return Err(format!(
+                "Failed to delete the {} namespace: {}",
+                AGENT_NAMESPACE, e
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,204 @@
+// Air-gapped mode: a global "no external network" switch. The shell then only talks to loopback
+// (the sidecars), the API servers in the kubeconfig and the per-context SOCKS proxies.
+//
+// Enforced in two layers. Features that exist only to reach the internet (update checks, release
+// notes, connectivity endpoints, export uploads, analytics) check `ensure_external_allowed` and
+// fail with a clear message. Underneath, `proxy::client_builder` routes every other destination to
+// a dead proxy, so a call site that forgets the check still can't leave the machine. Connections
+// that don't use reqwest (stream WebSockets) check `ensure_host_allowed` instead. Proxies are
+// bypassed while air-gapped; allowed destinations are reached directly.
+//
+// The sidecars: the backend gets KUBILITICS_AIR_GAPPED and skips its Artifact Hub catalog sync. The
+// AI backend exists to call external LLM providers, so it is not started (and is stopped when the
+// mode is switched on).
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager, Url};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::commands::{get_app_data_dir, KubeconfigStamp};
+use crate::sidecar::BackendManager;
+
+/// Nothing listens on port 0, so requests routed here fail at connect.
+const BLOCKED_PROXY: &str = "http://127.0.0.1:0";
+const LOOPBACK_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "::1"];
+
+/// Read synchronously by the client factory and background loops.
+static AIR_GAPPED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AirGapSettings {
+    pub enabled: bool,
+}
+
+async fn get_air_gap_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("airgap_settings.json"))
+}
+
+async fn load_air_gap_settings() -> Result<AirGapSettings, String> {
+    let path = get_air_gap_settings_path().await?;
+
+    if !fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(AirGapSettings::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| "Failed to read air-gap settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse air-gap settings".to_string())
+}
+
+async fn save_air_gap_settings(settings: &AirGapSettings) -> Result<(), String> {
+    let path = get_air_gap_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize air-gap settings".to_string())?;
+
+    fs::write(&path, content)
+        .await
+        .map_err(|_| "Failed to write air-gap settings".to_string())
+}
+
+/// Load the persisted switch. Runs before any background task starts.
+pub async fn init() {
+    let enabled = load_air_gap_settings().await.unwrap_or_default().enabled;
+    AIR_GAPPED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_air_gapped() -> bool {
+    AIR_GAPPED.load(Ordering::Relaxed)
+}
+
+/// Gate for features whose whole purpose is an external call. `feature` reads as a sentence subject,
+/// e.g. "Update checks".
+pub fn ensure_external_allowed(feature: &str) -> Result<(), String> {
+    if is_air_gapped() {
+        Err(format!("{} are disabled in air-gapped mode", feature))
+    } else {
+        Ok(())
+    }
+}
+
+fn host_of(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(|h| h.trim_start_matches('[').trim_end_matches(']').to_lowercase())
+}
+
+/// What the allow-list was built from: the kubeconfig (path and stamp) and the per-context proxies.
+type AllowListKey = (PathBuf, KubeconfigStamp, Vec<String>);
+
+/// The last allow-list built, reused while its inputs are unchanged: building it parses the
+/// kubeconfig, and every air-gapped client needs it.
+static ALLOW_LIST: Mutex<Option<(AllowListKey, Arc<HashSet<String>>)>> = Mutex::const_new(None);
+
+/// Loopback plus every API server in the kubeconfig and every per-context SOCKS proxy.
+pub async fn allowed_hosts() -> Arc<HashSet<String>> {
+    let proxies: Vec<String> = crate::socks::load_context_proxies()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.proxy_url)
+        .collect();
+    let kubeconfig = crate::commands::get_kubeconfig_path(None).await.ok();
+    let stamp = match &kubeconfig {
+        Some(path) => crate::commands::kubeconfig_stamp(path).await.ok().flatten(),
+        None => None,
+    };
+    let key = kubeconfig.clone().zip(stamp).map(|(path, stamp)| (path, stamp, proxies.clone()));
+
+    // Held while building, so concurrent callers wait for one kubeconfig parse
+    let mut cached = ALLOW_LIST.lock().await;
+    if let (Some(key), Some((cached_key, hosts))) = (&key, cached.as_ref()) {
+        if key == cached_key {
+            return hosts.clone();
+        }
+    }
+
+    let mut hosts: HashSet<String> = LOOPBACK_HOSTS.iter().map(|h| h.to_string()).collect();
+    if let Some(path) = &kubeconfig {
+        for server in crate::commands::kubeconfig_server_urls(path).await.unwrap_or_default() {
+            hosts.extend(host_of(&server));
+        }
+    }
+    for proxy in &proxies {
+        hosts.extend(host_of(proxy));
+    }
+    let hosts = Arc::new(hosts);
+    // Without a stamp there is nothing to validate an entry against
+    *cached = key.map(|key| (key, hosts.clone()));
+    hosts
+}
+
+/// Gate for connections made without `proxy::client_builder`: refuses any host outside the allowed
+/// set while air-gapped.
+pub async fn ensure_host_allowed(url: &Url) -> Result<(), String> {
+    if !is_air_gapped() {
+        return Ok(());
+    }
+    let host = host_of(url.as_str()).unwrap_or_default();
+    if allowed_hosts().await.contains(&host) {
+        Ok(())
+    } else {
+        Err(format!("{} is not reachable in air-gapped mode", host))
+    }
+}
+
+/// Apply the air-gap restriction to a client builder; unchanged when air-gapped mode is off.
+pub async fn restrict(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    if !is_air_gapped() {
+        return builder;
+    }
+    restrict_to(builder, allowed_hosts().await)
+}
+
+/// Restrict a client builder to `allowed` (from `allowed_hosts`).
+pub fn restrict_to(
+    builder: reqwest::ClientBuilder,
+    allowed: Arc<HashSet<String>>,
+) -> reqwest::ClientBuilder {
+    let guard = reqwest::Proxy::custom(move |url| {
+        let host = url
+            .host_str()
+            .map(|h| h.trim_start_matches('[').trim_end_matches(']').to_lowercase())
+            .unwrap_or_default();
+        (!allowed.contains(&host)).then_some(BLOCKED_PROXY)
+    });
+    builder.no_proxy().proxy(guard)
+}
+
+/// Environment for the backend sidecar, which then skips its Artifact Hub catalog sync.
+pub fn sidecar_env() -> Vec<(String, String)> {
+    if !is_air_gapped() {
+        return Vec::new();
+    }
+    vec![("KUBILITICS_AIR_GAPPED".to_string(), "true".to_string())]
+}
+
+#[command]
+pub async fn get_air_gap_mode() -> Result<bool, String> {
+    Ok(is_air_gapped())
+}
+
+/// Switch air-gapped mode. The shell and the AI backend follow immediately; the backend on its next
+/// start (`restart_sidecar`), which the frontend offers on `air-gap-changed`.
+#[command]
+pub async fn set_air_gap_mode(app_handle: AppHandle, enabled: bool) -> Result<(), String> {
+    save_air_gap_settings(&AirGapSettings { enabled }).await?;
+    AIR_GAPPED.store(enabled, Ordering::Relaxed);
+    if let Some(manager) = app_handle.try_state::<Arc<BackendManager>>() {
+        manager.apply_air_gap_mode().await;
+    }
+    let _ = app_handle.emit("air-gap-changed", enabled);
+    Ok(())
+}
@@ -0,0 +1,454 @@
+// Local analytics event queue. The frontend hands events to the shell instead of posting them
+// itself: they are batched, kept on disk until the collector accepts them (so a laptop that spends
+// the day offline loses nothing), and uploaded in the background with backoff.
+//
+// Consent is checked twice: events are dropped at the door without it, and withdrawing consent
+// empties the queue. Nothing is collected in air-gapped mode.
+//
+// Property values are anonymized when queued, not when sent, so `preview_pending_analytics` shows
+// exactly the bytes that will leave the machine. Besides anything shaped like a path, URL, host or
+// address, the kubeconfig's context, cluster and user names are redacted — they often name the
+// company, team or customer.
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{command, AppHandle};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+use crate::commands::{get_app_data_dir, KubeconfigStamp};
+
+const ANALYTICS_ENDPOINT: &str = "https://telemetry.kubilitics.dev/v1/events";
+const UPLOAD_INTERVAL_SECS: u64 = 5 * 60;
+const UPLOAD_REQUEST_TIMEOUT_SECS: u64 = 30;
+const MAX_BATCH_SIZE: usize = 100;
+/// Oldest events are dropped past this, so a machine that never reaches the collector doesn't grow
+/// the file without bound.
+const MAX_QUEUED_EVENTS: usize = 5_000;
+const MAX_BACKOFF_SECS: u64 = 6 * 60 * 60;
+const MAX_EVENT_NAME_LEN: usize = 128;
+const REDACTED: &str = "[redacted]";
+/// Known names shorter than this are only redacted as the whole value, not inside longer strings
+/// ("dev" would otherwise take out "device").
+const MIN_EMBEDDED_NAME_LEN: usize = 4;
+
+/// In-memory copy of the queue file, loaded on first use. Never held across a network call.
+static QUEUE: Mutex<Option<QueueState>> = Mutex::const_new(None);
+
+/// Serializes uploads, so two never send the same events.
+static UPLOAD_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Kubeconfig names to redact, reused while the kubeconfig's stamp is unchanged.
+static KNOWN_NAMES: Mutex<Option<(PathBuf, KubeconfigStamp, Arc<HashSet<String>>)>> = Mutex::const_new(None);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsEvent {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub properties: Value,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct QueueState {
+    events: Vec<AnalyticsEvent>,
+    last_upload_at: Option<u64>,
+    last_error: Option<String>,
+    consecutive_failures: u32,
+    dropped: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsQueueStatus {
+    pub enabled: bool,
+    pub queued: usize,
+    pub dropped: u64,
+    pub last_upload_at: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UploadBatch<'a> {
+    app_version: &'static str,
+    platform: &'static str,
+    arch: &'static str,
+    events: &'a [AnalyticsEvent],
+}
+
+impl<'a> UploadBatch<'a> {
+    fn new(events: &'a [AnalyticsEvent]) -> Self {
+        Self {
+            app_version: env!("CARGO_PKG_VERSION"),
+            platform: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            events,
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_analytics_queue_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("analytics_queue.json"))
+}
+
+async fn load_queue_state() -> Result<QueueState, String> {
+    let path = get_analytics_queue_path().await?;
+
+    if !path.exists() {
+        return Ok(QueueState::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read analytics queue".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse analytics queue".to_string())
+}
+
+async fn save_queue_state(state: &QueueState) -> Result<(), String> {
+    let path = get_analytics_queue_path().await?;
+
+    let content = serde_json::to_string(state)
+        .map_err(|_| "Failed to serialize analytics queue".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write analytics queue".to_string())
+}
+
+/// The loaded queue, reading the file on first use. A corrupt file starts an empty queue rather
+/// than blocking collection forever.
+async fn queue_state(slot: &mut Option<QueueState>) -> &mut QueueState {
+    if slot.is_none() {
+        *slot = Some(load_queue_state().await.unwrap_or_default());
+    }
+    slot.as_mut().unwrap()
+}
+
+/// Context, cluster and user names from the current kubeconfig. Empty when there is none.
+async fn known_names() -> Arc<HashSet<String>> {
+    let Ok(path) = crate::commands::get_kubeconfig_path(None).await else {
+        return Arc::default();
+    };
+    let Ok(Some(stamp)) = crate::commands::kubeconfig_stamp(&path).await else {
+        return Arc::default();
+    };
+
+    let mut cached = KNOWN_NAMES.lock().await;
+    if let Some((cached_path, cached_stamp, names)) = cached.as_ref() {
+        if *cached_path == path && *cached_stamp == stamp {
+            return names.clone();
+        }
+    }
+    let names: HashSet<String> = crate::commands::kubeconfig_info(Some(path.to_string_lossy().to_string()))
+        .await
+        .map(|info| {
+            info.contexts
+                .into_iter()
+                .flat_map(|c| [c.name, c.cluster, c.user])
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let names = Arc::new(names);
+    *cached = Some((path, stamp, names.clone()));
+    names
+}
+
+/// Strings that could identify the user or their infrastructure: paths (home directories carry
+/// user names), URLs and hostnames with a domain (cluster endpoints), e-mail addresses, IPs, and
+/// anything holding one of the kubeconfig's `known_names`.
+fn is_identifying(value: &str, known_names: &HashSet<String>) -> bool {
+    let value = value.trim();
+    value.starts_with('/')
+        || value.starts_with('~')
+        || value.get(1..3) == Some(":\\")
+        || value.contains("://")
+        || value.contains('@')
+        || value.parse::<std::net::IpAddr>().is_ok()
+        || (value.contains('.')
+            && !value.contains(' ')
+            && value.rsplit('.').next().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic())))
+        || known_names
+            .iter()
+            .any(|name| value == name || (name.len() >= MIN_EMBEDDED_NAME_LEN && value.contains(name.as_str())))
+}
+
+/// Replace identifying strings anywhere in the properties with a placeholder.
+fn anonymize(value: Value, known_names: &HashSet<String>) -> Value {
+    match value {
+        Value::String(s) if is_identifying(&s, known_names) => Value::String(REDACTED.to_string()),
+        Value::Array(items) => Value::Array(items.into_iter().map(|v| anonymize(v, known_names)).collect()),
+        Value::Object(map) => Value::Object(map.into_iter().map(|(k, v)| (k, anonymize(v, known_names))).collect()),
+        other => other,
+    }
+}
+
+async fn collection_enabled() -> bool {
+    !crate::airgap::is_air_gapped() && crate::commands::get_analytics_consent().await.unwrap_or(false)
+}
+
+/// Empty the queue. Called when consent is withdrawn.
+pub async fn clear_queue() -> Result<(), String> {
+    let mut slot = QUEUE.lock().await;
+    let state = queue_state(&mut slot).await;
+    state.events.clear();
+    state.consecutive_failures = 0;
+    state.last_error = None;
+    save_queue_state(state).await
+}
+
+/// Seconds to wait after `failures` consecutive failed uploads.
+fn backoff_secs(failures: u32) -> u64 {
+    if failures == 0 {
+        return 0;
+    }
+    (UPLOAD_INTERVAL_SECS << (failures - 1).min(10)).min(MAX_BACKOFF_SECS)
+}
+
+async fn post_batch(app: &AppHandle, events: &[AnalyticsEvent]) -> Result<(), String> {
+    crate::airgap::ensure_external_allowed("Analytics uploads")?;
+
+    let client = crate::http_client::shared(app).await?;
+    let response = client
+        .post(ANALYTICS_ENDPOINT)
+        .timeout(Duration::from_secs(UPLOAD_REQUEST_TIMEOUT_SECS))
+        .json(&UploadBatch::new(events))
+        .send()
+        .await
+        .map_err(|e| format!("Analytics upload failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Analytics upload failed: HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Upload queued events in batches until the queue is empty or a batch fails. Events leave the
+/// queue only once the collector has accepted them. Each batch is a snapshot: the queue stays open
+/// to new events while it is in flight, and the sent events are removed by id afterwards.
+async fn upload_pending(app: &AppHandle) -> Result<usize, String> {
+    let _upload = UPLOAD_LOCK.lock().await;
+
+    if !collection_enabled().await {
+        return Ok(0);
+    }
+
+    let mut uploaded = 0;
+    loop {
+        let batch: Vec<AnalyticsEvent> = {
+            let mut slot = QUEUE.lock().await;
+            let state = queue_state(&mut slot).await;
+            state.events.iter().take(MAX_BATCH_SIZE).cloned().collect()
+        };
+        if batch.is_empty() {
+            return Ok(uploaded);
+        }
+
+        let result = post_batch(app, &batch).await;
+
+        let mut slot = QUEUE.lock().await;
+        let state = queue_state(&mut slot).await;
+        match result {
+            Ok(()) => {
+                // Trimming or a purge may have removed some of them meanwhile
+                let sent: HashSet<&str> = batch.iter().map(|e| e.id.as_str()).collect();
+                state.events.retain(|e| !sent.contains(e.id.as_str()));
+                uploaded += batch.len();
+                state.last_upload_at = Some(now_secs());
+                state.last_error = None;
+                state.consecutive_failures = 0;
+                save_queue_state(state).await?;
+            }
+            Err(e) => {
+                state.last_error = Some(e.clone());
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                let _ = save_queue_state(state).await;
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Background uploader. Runs on the regular interval (stretched in low-bandwidth mode), backing off
+/// exponentially while the collector is unreachable.
+pub fn start_analytics_uploader(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let failures = {
+                let mut slot = QUEUE.lock().await;
+                queue_state(&mut slot).await.consecutive_failures
+            };
+            let base = Duration::from_secs(UPLOAD_INTERVAL_SECS.max(backoff_secs(failures)));
+            sleep(crate::network::polling_interval(base)).await;
+
+            if let Err(e) = upload_pending(&app).await {
+                tracing::warn!(error = %e, "Analytics upload will be retried");
+            }
+        }
+    });
+}
+
+/// Queue an event. Without consent (or in air-gapped mode) the event is dropped and this still
+/// succeeds, so the frontend never has to check first.
+#[command]
+pub async fn track_analytics_event(name: String, properties: Option<Value>) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > MAX_EVENT_NAME_LEN {
+        return Err(format!("Event name must be 1 to {} characters", MAX_EVENT_NAME_LEN));
+    }
+    if !collection_enabled().await {
+        return Ok(());
+    }
+    let known_names = known_names().await;
+
+    let mut slot = QUEUE.lock().await;
+    let state = queue_state(&mut slot).await;
+    state.events.push(AnalyticsEvent {
+        id: format!("{:016x}", rand::random::<u64>()),
+        name: name.to_string(),
+        properties: anonymize(properties.unwrap_or(Value::Null), &known_names),
+        timestamp: now_secs(),
+    });
+    if state.events.len() > MAX_QUEUED_EVENTS {
+        let excess = state.events.len() - MAX_QUEUED_EVENTS;
+        state.events.drain(..excess);
+        state.dropped += excess as u64;
+    }
+    save_queue_state(state).await
+}
+
+#[command]
+pub async fn get_analytics_queue_status() -> Result<AnalyticsQueueStatus, String> {
+    let enabled = collection_enabled().await;
+    let mut slot = QUEUE.lock().await;
+    let state = queue_state(&mut slot).await;
+    Ok(AnalyticsQueueStatus {
+        enabled,
+        queued: state.events.len(),
+        dropped: state.dropped,
+        last_upload_at: state.last_upload_at,
+        last_error: state.last_error.clone(),
+    })
+}
+
+/// Upload now instead of waiting for the next interval. Returns the number of events sent.
+#[command]
+pub async fn flush_analytics_queue(app_handle: AppHandle) -> Result<usize, String> {
+    upload_pending(&app_handle).await
+}
+
+/// The request bodies the next upload would send, batch by batch, exactly as serialized.
+#[command]
+pub async fn preview_pending_analytics() -> Result<Vec<Value>, String> {
+    let mut slot = QUEUE.lock().await;
+    let state = queue_state(&mut slot).await;
+    state
+        .events
+        .chunks(MAX_BATCH_SIZE)
+        .map(|events| {
+            serde_json::to_value(UploadBatch::new(events))
+                .map_err(|_| "Failed to serialize analytics batch".to_string())
+        })
+        .collect()
+}
+
+/// Delete every queued event without sending it. Consent is left as it is.
+#[command]
+pub async fn purge_analytics_data() -> Result<(), String> {
+    let mut slot = QUEUE.lock().await;
+    *slot = Some(QueueState::default());
+    let path = get_analytics_queue_path().await?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete analytics queue: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Delete everything telemetry-related the app has stored: queued events, the analytics and
+/// crash-reporting consent records (with their timestamps) and stored crash reports. Collection
+/// stops until consent is given again.
+#[command]
+pub async fn delete_all_telemetry() -> Result<(), String> {
+    purge_analytics_data().await?;
+    crate::commands::delete_analytics_settings().await?;
+    crate::crash::delete_all_crash_data().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn names(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn is_identifying_flags_infrastructure_details() {
+        let known = names(&["acme-prod-eu", "gke_acme_europe-west1_prod", "dev"]);
+        let cases = [
+            // Paths, URLs, hosts and addresses
+            ("/Users/jane/.kube/config", true),
+            ("~/projects", true),
+            ("C:\\Users\\jane", true),
+            ("https://10.0.0.1:6443", true),
+            ("jane@example.com", true),
+            ("10.0.0.1", true),
+            ("fd00::1", true),
+            ("api.acme.internal", true),
+            // Kubeconfig names, whole or embedded
+            ("acme-prod-eu", true),
+            ("  acme-prod-eu  ", true),
+            ("switched to acme-prod-eu", true),
+            ("gke_acme_europe-west1_prod", true),
+            ("dev", true),
+            // Short names only match whole values
+            ("device", false),
+            // Ordinary values
+            ("topology", false),
+            ("Pods", false),
+            ("v1.2.3", false),
+            ("3 clusters connected", false),
+            ("", false),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(is_identifying(value, &known), expected, "value: {:?}", value);
+        }
+    }
+
+    #[test]
+    fn anonymize_redacts_nested_values() {
+        let known = names(&["acme-prod-eu"]);
+        let properties = json!({
+            "view": "topology",
+            "context": "acme-prod-eu",
+            "count": 3,
+            "enabled": true,
+            "paths": ["/home/jane/export.json", "summary"],
+            "target": { "server": "https://api.acme.example:6443" },
+        });
+        let expected = json!({
+            "view": "topology",
+            "context": REDACTED,
+            "count": 3,
+            "enabled": true,
+            "paths": [REDACTED, "summary"],
+            "target": { "server": REDACTED },
+        });
+        assert_eq!(anonymize(properties, &known), expected);
+    }
+}
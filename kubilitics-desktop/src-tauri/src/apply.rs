@@ -0,0 +1,246 @@
+// "Paste YAML and apply": server-side apply of a multi-document manifest through kube-rs. Every
+// document is applied on its own and gets its own result, so one bad object doesn't hide what
+// happened to the rest; `dry_run` sends the same requests with dryRun=All for a preview.
+//
+// Each result carries a unified diff between the live object and what the server returned
+// (server-populated metadata and status left out), which is the preview the UI shows before the
+// user applies for real.
+use kube::api::{DynamicObject, GroupVersionKind, Patch, PatchParams};
+use kube::discovery::{self, Scope};
+use kube::{Api, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::TextDiff;
+use tauri::command;
+
+pub(crate) const DEFAULT_FIELD_MANAGER: &str = "kubilitics";
+/// Set by the server on every write; they would make every diff non-empty.
+const VOLATILE_METADATA: [&str; 6] = [
+    "managedFields",
+    "resourceVersion",
+    "generation",
+    "uid",
+    "creationTimestamp",
+    "selfLink",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyAction {
+    Created,
+    Configured,
+    Unchanged,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplyDocumentResult {
+    /// Position in the manifest, counting from 0 (items of a List count individually).
+    pub index: usize,
+    pub api_version: Option<String>,
+    pub kind: Option<String>,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub action: ApplyAction,
+    /// Unified diff, live → applied. Empty when unchanged, absent on failure.
+    pub diff: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplyResult {
+    pub dry_run: bool,
+    pub field_manager: String,
+    pub documents: Vec<ApplyDocumentResult>,
+    pub failed: usize,
+}
+
+/// Documents of a manifest as JSON, with `kind: List` expanded and empty documents skipped.
+pub(crate) fn parse_documents(yaml: &str) -> Result<Vec<Value>, String> {
+    let mut documents = Vec::new();
+    for (i, document) in serde_yaml::Deserializer::from_str(yaml).enumerate() {
+        let value = Value::deserialize(document)
+            .map_err(|e| format!("Document {}: invalid YAML: {}", i + 1, e))?;
+        match value {
+            Value::Null => {}
+            Value::Object(ref map) if map.get("kind").and_then(Value::as_str) == Some("List") => {
+                if let Some(Value::Array(items)) = map.get("items") {
+                    documents.extend(items.iter().cloned());
+                }
+            }
+            value => documents.push(value),
+        }
+    }
+    if documents.is_empty() {
+        return Err("The manifest contains no objects".to_string());
+    }
+    Ok(documents)
+}
+
+fn gvk_of(api_version: &str, kind: &str) -> GroupVersionKind {
+    match api_version.split_once('/') {
+        Some((group, version)) => GroupVersionKind::gvk(group, version, kind),
+        None => GroupVersionKind::gvk("", api_version, kind),
+    }
+}
+
+/// An object as the diff compares it: no status, no server-maintained metadata.
+pub(crate) fn normalized_value(object: &DynamicObject) -> Value {
+    let mut value = serde_json::to_value(object).unwrap_or(Value::Null);
+    if let Some(map) = value.as_object_mut() {
+        map.remove("status");
+        if let Some(Value::Object(metadata)) = map.get_mut("metadata") {
+            for field in VOLATILE_METADATA {
+                metadata.remove(field);
+            }
+            if let Some(Value::Object(annotations)) = metadata.get_mut("annotations") {
+                annotations.remove("deployment.kubernetes.io/revision");
+                if annotations.is_empty() {
+                    metadata.remove("annotations");
+                }
+            }
+        }
+    }
+    value
+}
+
+/// YAML of an object as the diff compares it.
+pub(crate) fn normalized_yaml(object: &DynamicObject) -> String {
+    serde_yaml::to_string(&normalized_value(object)).unwrap_or_default()
+}
+
+/// API handle for any kind, found through discovery. Namespaced kinds use `namespace`, or the
+/// context's default namespace.
+pub(crate) async fn dynamic_api(
+    client: &Client,
+    api_version: &str,
+    kind: &str,
+    namespace: Option<&str>,
+) -> Result<Api<DynamicObject>, String> {
+    let gvk = gvk_of(api_version, kind);
+    let (resource, capabilities) = discovery::pinned_kind(client, &gvk)
+        .await
+        .map_err(|e| format!("Unknown kind {} {}: {}", api_version, kind, e))?;
+    Ok(match capabilities.scope {
+        Scope::Namespaced => Api::namespaced_with(
+            client.clone(),
+            namespace.unwrap_or(client.default_namespace()),
+            &resource,
+        ),
+        Scope::Cluster => Api::all_with(client.clone(), &resource),
+    })
+}
+
+/// Unified diff between two YAML texts, empty when they're the same.
+pub(crate) fn yaml_diff(before: &str, after: &str, before_name: &str, after_name: &str) -> String {
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(3)
+        .header(before_name, after_name)
+        .to_string()
+}
+
+/// Server-side apply one object: what happened to it and the live → applied diff.
+pub(crate) async fn apply_document(
+    client: &Client,
+    document: Value,
+    params: &PatchParams,
+) -> Result<(ApplyAction, String), String> {
+    let object: DynamicObject =
+        serde_json::from_value(document).map_err(|e| format!("Not a Kubernetes object: {}", e))?;
+    let types = object.types.as_ref().ok_or("Missing apiVersion or kind")?;
+    let name = object
+        .metadata
+        .name
+        .as_deref()
+        .ok_or("Missing metadata.name")?;
+
+    let api = dynamic_api(
+        client,
+        &types.api_version,
+        &types.kind,
+        object.metadata.namespace.as_deref(),
+    )
+    .await?;
+
+    let live = api
+        .get_opt(name)
+        .await
+        .map_err(|e| format!("Failed to read the live object: {}", e))?;
+    let applied = api
+        .patch(name, params, &Patch::Apply(&object))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let before = live.as_ref().map(normalized_yaml).unwrap_or_default();
+    let after = normalized_yaml(&applied);
+    let diff = yaml_diff(&before, &after, "live", "applied");
+    let action = match (&live, diff.is_empty()) {
+        (None, _) => ApplyAction::Created,
+        (Some(_), true) => ApplyAction::Unchanged,
+        (Some(_), false) => ApplyAction::Configured,
+    };
+    Ok((action, diff))
+}
+
+/// Server-side apply every object in `yaml` to a context. Field conflicts with other managers are
+/// reported per object, not forced.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn apply_manifest(
+    context: String,
+    yaml: String,
+    dry_run: bool,
+    field_manager: Option<String>,
+) -> Result<ApplyResult, String> {
+    let documents = parse_documents(&yaml)?;
+    let field_manager = field_manager
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| DEFAULT_FIELD_MANAGER.to_string());
+    let client = crate::k8s::client_for(&context).await?;
+
+    let mut params = PatchParams::apply(&field_manager);
+    if dry_run {
+        params = params.dry_run();
+    }
+
+    let mut results = Vec::with_capacity(documents.len());
+    for (index, document) in documents.into_iter().enumerate() {
+        let field = |path: &str| {
+            document
+                .pointer(path)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let mut result = ApplyDocumentResult {
+            index,
+            api_version: field("/apiVersion"),
+            kind: field("/kind"),
+            namespace: field("/metadata/namespace"),
+            name: field("/metadata/name"),
+            action: ApplyAction::Failed,
+            diff: None,
+            error: None,
+        };
+        match apply_document(&client, document, &params).await {
+            Ok((action, diff)) => {
+                result.action = action;
+                result.diff = Some(diff);
+            }
+            Err(e) => result.error = Some(e),
+        }
+        results.push(result);
+    }
+
+    let failed = results
+        .iter()
+        .filter(|r| r.action == ApplyAction::Failed)
+        .count();
+    Ok(ApplyResult {
+        dry_run,
+        field_manager,
+        documents: results,
+        failed,
+    })
+}
@@ -0,0 +1,136 @@
+// Breadcrumbs: the last 200 significant events, in memory, for reconstructing intermittent
+// problems ("the backend restarted twice and then the cluster list went empty") from a support
+// report instead of asking the user to reproduce with debug logging on.
+//
+// Warnings and errors are picked up from `tracing` by `BreadcrumbLayer`, which covers failed
+// instrumented commands (their `err` events) and health-check failures. Sidecar restarts are
+// recorded explicitly since they log at info.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::command;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+const CAPACITY: usize = 200;
+
+static BREADCRUMBS: Mutex<VecDeque<Breadcrumb>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BreadcrumbKind {
+    Error,
+    Warning,
+    Restart,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Breadcrumb {
+    /// Unix milliseconds.
+    pub timestamp: u64,
+    pub kind: BreadcrumbKind,
+    /// Innermost span (usually the command or sidecar step), or the module for bare events.
+    pub source: String,
+    pub message: String,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn push(breadcrumb: Breadcrumb) {
+    let Ok(mut breadcrumbs) = BREADCRUMBS.lock() else { return };
+    if breadcrumbs.len() == CAPACITY {
+        breadcrumbs.pop_front();
+    }
+    breadcrumbs.push_back(breadcrumb);
+}
+
+pub fn record(kind: BreadcrumbKind, source: &str, message: impl Into<String>) {
+    push(Breadcrumb {
+        timestamp: now_millis(),
+        kind,
+        source: source.to_string(),
+        message: message.into(),
+    });
+}
+
+/// Oldest first.
+pub fn snapshot() -> Vec<Breadcrumb> {
+    BREADCRUMBS
+        .lock()
+        .map(|b| b.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Message plus `error` (from `#[instrument(err)]` and `error = %e` fields).
+#[derive(Default)]
+struct MessageCollector {
+    message: String,
+    error: Option<String>,
+}
+
+impl Visit for MessageCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            "error" => self.error = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "error" => self.error = Some(format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
+
+/// Turns warning and error events into breadcrumbs. Installed by `logging::init`, unfiltered, so
+/// breadcrumbs are kept even when the log level is error-only.
+pub struct BreadcrumbLayer;
+
+impl<S> Layer<S> for BreadcrumbLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let kind = match *metadata.level() {
+            Level::ERROR => BreadcrumbKind::Error,
+            Level::WARN => BreadcrumbKind::Warning,
+            _ => return,
+        };
+        let mut fields = MessageCollector::default();
+        event.record(&mut fields);
+        let message = match (fields.message.is_empty(), fields.error) {
+            (_, None) => fields.message,
+            (true, Some(error)) => error,
+            (false, Some(error)) => format!("{}: {}", fields.message, error),
+        };
+        let source = ctx
+            .event_span(event)
+            .map(|span| span.name().to_string())
+            .unwrap_or_else(|| metadata.target().to_string());
+        push(Breadcrumb {
+            timestamp: now_millis(),
+            kind,
+            source,
+            message,
+        });
+    }
+}
+
+#[command]
+pub async fn get_recent_errors() -> Result<Vec<Breadcrumb>, String> {
+    Ok(snapshot())
+}
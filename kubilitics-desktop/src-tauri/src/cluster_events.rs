@@ -0,0 +1,297 @@
+// Desktop alerts for Warning events. While a context is connected, its Events are watched (Warning
+// only, filtered server-side); events matching the user's rules become alerts: kept in a short list
+// shown in the app and the tray's Alerts submenu, sent to the frontend as `event-alert`, and, for
+// rules that ask for it, raised as a native notification.
+//
+// Events that existed before the watch started are not alerts, and the same event recurring (its
+// count going up) alerts again only after ALERT_REPEAT_INTERVAL.
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::Event as CoreEvent;
+use kube::runtime::watcher::{self, Event};
+use kube::runtime::WatchStreamExt;
+use kube::Api;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+
+const MAX_ALERTS: usize = 100;
+const ALERT_REPEAT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+static WATCHERS: Mutex<BTreeMap<String, tauri::async_runtime::JoinHandle<()>>> =
+    Mutex::const_new(BTreeMap::new());
+static ALERTS: Mutex<VecDeque<EventAlert>> = Mutex::const_new(VecDeque::new());
+
+/// Matches an event when every non-empty list contains the event's value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventRule {
+    pub reasons: Vec<String>,
+    pub namespaces: Vec<String>,
+    /// Kind of the involved object (Pod, Node, ...).
+    pub kinds: Vec<String>,
+    /// Also raise a native notification, not just a tray/app alert.
+    pub notify: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventNotificationSettings {
+    pub enabled: bool,
+    /// An event alerts when any rule matches it.
+    pub rules: Vec<EventRule>,
+}
+
+impl Default for EventNotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rules: vec![
+                EventRule {
+                    reasons: [
+                        "BackOff",
+                        "Failed",
+                        "FailedScheduling",
+                        "FailedMount",
+                        "OOMKilling",
+                        "Evicted",
+                        "NodeNotReady",
+                    ]
+                    .iter()
+                    .map(|r| r.to_string())
+                    .collect(),
+                    notify: true,
+                    ..Default::default()
+                },
+                // Every other Warning, in the app and tray only
+                EventRule::default(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventAlert {
+    pub id: String,
+    pub context: String,
+    pub namespace: Option<String>,
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub reason: String,
+    pub message: String,
+    pub count: i32,
+    pub at: u64,
+}
+
+impl EventRule {
+    fn matches(&self, reason: &str, namespace: &str, kind: &str) -> bool {
+        let allows =
+            |list: &[String], value: &str| list.is_empty() || list.iter().any(|v| v == value);
+        allows(&self.reasons, reason)
+            && allows(&self.namespaces, namespace)
+            && allows(&self.kinds, kind)
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_event_notification_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("event_notification_settings.json"))
+}
+
+async fn load_event_notification_settings() -> Result<EventNotificationSettings, String> {
+    let path = get_event_notification_settings_path().await?;
+
+    if !path.exists() {
+        return Ok(EventNotificationSettings::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read event notification settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse event notification settings".to_string())
+}
+
+#[command]
+pub async fn get_event_notification_settings() -> Result<EventNotificationSettings, String> {
+    load_event_notification_settings().await
+}
+
+/// Rules apply to running watchers from the next event on.
+#[command]
+pub async fn set_event_notification_settings(
+    settings: EventNotificationSettings,
+) -> Result<(), String> {
+    let path = get_event_notification_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|_| "Failed to serialize event notification settings".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write event notification settings".to_string())
+}
+
+async fn refresh_tray(app: &AppHandle) {
+    let alerts: Vec<crate::tray::TrayAlert> = ALERTS
+        .lock()
+        .await
+        .iter()
+        .map(|a| crate::tray::TrayAlert {
+            id: a.id.clone(),
+            label: format!(
+                "{}: {}/{}",
+                a.reason,
+                a.kind.as_deref().unwrap_or("?"),
+                a.name.as_deref().unwrap_or("?")
+            ),
+        })
+        .collect();
+    crate::tray::set_alerts(app, alerts);
+}
+
+async fn raise(app: &AppHandle, alert: EventAlert, notify: bool) {
+    use tauri_plugin_notification::NotificationExt;
+
+    if notify {
+        let _ = app
+            .notification()
+            .builder()
+            .title(format!(
+                "{} — {}/{}",
+                alert.reason,
+                alert.kind.as_deref().unwrap_or("object"),
+                alert.name.as_deref().unwrap_or("?")
+            ))
+            .body(&alert.message)
+            .show();
+    }
+    let _ = app.emit("event-alert", &alert);
+    {
+        let mut alerts = ALERTS.lock().await;
+        alerts.push_front(alert);
+        alerts.truncate(MAX_ALERTS);
+    }
+    refresh_tray(app).await;
+}
+
+async fn watch_events(app: AppHandle, context: String, client: kube::Client) {
+    let api: Api<CoreEvent> = Api::all(client);
+    let config = watcher::Config::default().fields("type=Warning");
+    let mut stream = watcher::watcher(api, config).default_backoff().boxed();
+    let mut synced = false;
+    // (namespace, involved uid or name, reason) → last alert
+    let mut last_alert: HashMap<(String, String, String), Instant> = HashMap::new();
+
+    while let Some(event) = stream.next().await {
+        let event = match event {
+            Ok(Event::InitDone) => {
+                synced = true;
+                continue;
+            }
+            // Relists after a reconnect replay existing events; only changes after that alert
+            Ok(Event::Init) | Ok(Event::InitApply(_)) | Ok(Event::Delete(_)) => continue,
+            Ok(Event::Apply(event)) if synced => event,
+            Ok(Event::Apply(_)) => continue,
+            Err(e) => {
+                tracing::debug!(context = %context, error = %e, "Event watch interrupted");
+                continue;
+            }
+        };
+
+        let settings = load_event_notification_settings().await.unwrap_or_default();
+        if !settings.enabled {
+            continue;
+        }
+        let involved = &event.involved_object;
+        let reason = event.reason.clone().unwrap_or_default();
+        let namespace = event.metadata.namespace.clone().unwrap_or_default();
+        let kind = involved.kind.clone().unwrap_or_default();
+        let Some(rule) = settings
+            .rules
+            .iter()
+            .find(|r| r.matches(&reason, &namespace, &kind))
+        else {
+            continue;
+        };
+
+        let key = (
+            namespace.clone(),
+            involved
+                .uid
+                .clone()
+                .or_else(|| involved.name.clone())
+                .unwrap_or_default(),
+            reason.clone(),
+        );
+        if last_alert
+            .get(&key)
+            .is_some_and(|at| at.elapsed() < ALERT_REPEAT_INTERVAL)
+        {
+            continue;
+        }
+        last_alert.retain(|_, at| at.elapsed() < ALERT_REPEAT_INTERVAL);
+        last_alert.insert(key, Instant::now());
+
+        let alert = EventAlert {
+            id: format!("{:016x}", rand::random::<u64>()),
+            context: context.clone(),
+            namespace: (!namespace.is_empty()).then_some(namespace),
+            kind: involved.kind.clone(),
+            name: involved.name.clone(),
+            reason,
+            message: event.message.clone().unwrap_or_default(),
+            count: event.count.unwrap_or(1),
+            at: now_secs(),
+        };
+        raise(&app, alert, rule.notify).await;
+    }
+}
+
+/// Start alerting on a context's Warning events (call when the context connects).
+#[command]
+pub async fn start_event_notifications(
+    app_handle: AppHandle,
+    context: String,
+) -> Result<(), String> {
+    let mut watchers = WATCHERS.lock().await;
+    if watchers.contains_key(&context) {
+        return Ok(());
+    }
+    let client = crate::k8s::streaming_client_for(&context).await?;
+    let task = tauri::async_runtime::spawn(watch_events(app_handle, context.clone(), client));
+    watchers.insert(context, task);
+    Ok(())
+}
+
+#[command]
+pub async fn stop_event_notifications(context: String) -> Result<(), String> {
+    if let Some(task) = WATCHERS.lock().await.remove(&context) {
+        task.abort();
+    }
+    Ok(())
+}
+
+/// Newest first.
+#[command]
+pub async fn get_event_alerts() -> Result<Vec<EventAlert>, String> {
+    Ok(ALERTS.lock().await.iter().cloned().collect())
+}
+
+#[command]
+pub async fn clear_event_alerts(app_handle: AppHandle) -> Result<(), String> {
+    ALERTS.lock().await.clear();
+    refresh_tray(&app_handle).await;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// Per-context request budgets. A kind cluster on localhost answers in milliseconds; an API server
+// behind a VPN and a jump host needs longer timeouts and a couple of retries before an error is
+// worth showing. Contexts without a policy use the defaults, which match the backend's own.
+//
+// The shell's own clients (`k8s::client_for`) apply the policy directly. The backend gets every
+// policy in KUBILITICS_CONTEXT_POLICIES (context name → policy, JSON) when it starts, and its cluster
+// clients use the context's timeout and retry its failed requests with the same doubling backoff.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::commands::get_app_data_dir;
+
+/// Same as the backend's k8s_timeout_sec default.
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_RETRIES: u32 = 2;
+const DEFAULT_BACKOFF_MS: u64 = 500;
+const MAX_REQUEST_TIMEOUT_SECS: u64 = 600;
+const MAX_RETRIES: u32 = 10;
+const MAX_BACKOFF_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestPolicy {
+    pub request_timeout_secs: u64,
+    /// Retries after the first attempt, for idempotent requests only.
+    pub retries: u32,
+    /// Delay before the first retry; doubled for each further one.
+    pub backoff_ms: u64,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            retries: DEFAULT_RETRIES,
+            backoff_ms: DEFAULT_BACKOFF_MS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPolicy {
+    pub context: String,
+    #[serde(flatten)]
+    pub policy: RequestPolicy,
+}
+
+async fn get_context_policies_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("context_policies.json"))
+}
+
+async fn load_context_policies() -> Result<Vec<ContextPolicy>, String> {
+    let path = get_context_policies_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read context policies".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse context policies".to_string())
+}
+
+async fn save_context_policies(policies: &[ContextPolicy]) -> Result<(), String> {
+    let path = get_context_policies_path().await?;
+
+    let content = serde_json::to_string_pretty(policies)
+        .map_err(|_| "Failed to serialize context policies".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write context policies".to_string())
+}
+
+fn validate(policy: &RequestPolicy) -> Result<(), String> {
+    if !(1..=MAX_REQUEST_TIMEOUT_SECS).contains(&policy.request_timeout_secs) {
+        return Err(format!("Request timeout must be between 1 and {} seconds", MAX_REQUEST_TIMEOUT_SECS));
+    }
+    if policy.retries > MAX_RETRIES {
+        return Err(format!("Retries must be at most {}", MAX_RETRIES));
+    }
+    if policy.backoff_ms > MAX_BACKOFF_MS {
+        return Err(format!("Backoff must be at most {} ms", MAX_BACKOFF_MS));
+    }
+    Ok(())
+}
+
+/// The policy in effect for a context: its own, or the defaults.
+pub async fn policy_for(context: &str) -> RequestPolicy {
+    load_context_policies()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .find(|p| p.context == context)
+        .map(|p| p.policy)
+        .unwrap_or_default()
+}
+
+/// Environment for the backend sidecar. Unset when every context uses the defaults.
+pub async fn sidecar_env() -> Vec<(String, String)> {
+    let policies = load_context_policies().await.unwrap_or_default();
+    if policies.is_empty() {
+        return Vec::new();
+    }
+    let map: BTreeMap<String, RequestPolicy> = policies
+        .into_iter()
+        .map(|p| (p.context, p.policy))
+        .collect();
+    match serde_json::to_string(&map) {
+        Ok(json) => vec![("KUBILITICS_CONTEXT_POLICIES".to_string(), json)],
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Contexts with their own policy.
+#[command]
+pub async fn get_context_policies() -> Result<Vec<ContextPolicy>, String> {
+    load_context_policies().await
+}
+
+#[command]
+pub async fn get_context_policy(context: String) -> Result<RequestPolicy, String> {
+    Ok(policy_for(&context).await)
+}
+
+/// Set (or with `policy: None`, reset to the defaults) a context's request policy. The backend picks
+/// it up on its next start (`restart_sidecar`).
+#[command]
+pub async fn set_context_policy(context: String, policy: Option<RequestPolicy>) -> Result<(), String> {
+    if let Some(policy) = &policy {
+        validate(policy)?;
+    }
+
+    let mut policies = load_context_policies().await?;
+    policies.retain(|p| p.context != context);
+    if let Some(policy) = policy {
+        policies.push(ContextPolicy { context, policy });
+    }
+    save_context_policies(&policies).await
+}
@@ -6,9 +6,10 @@ use tauri_plugin_shell::ShellExt;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::process::Command;
-use std::fs;
+use tokio::fs;
 
 use crate::backend_ports::{BACKEND_PORT, AI_BACKEND_PORT};
+use crate::exports::{write_export, write_export_via_dialog};
 
 use aes_gcm::{
     aead::{Aead, AeadCore, KeyInit, OsRng},
@@ -16,7 +17,7 @@ use aes_gcm::{
 };
 use base64::{engine::general_purpose, Engine as _};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KubeconfigContext {
     pub name: String,
     pub cluster: String,
@@ -24,13 +25,30 @@ pub struct KubeconfigContext {
     pub namespace: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KubeconfigInfo {
     pub path: String,
     pub current_context: Option<String>,
     pub contexts: Vec<KubeconfigContext>,
 }
 
+/// Modification time and size of a kubeconfig when it was parsed.
+pub(crate) type KubeconfigStamp = (std::time::SystemTime, u64);
+
+/// `get_kubeconfig_info` results by kubeconfig path (managed state), reused while the file's
+/// modification time and size are unchanged so frequent UI refreshes don't re-parse large configs.
+#[derive(Default)]
+pub struct KubeconfigInfoCache {
+    entries: tokio::sync::Mutex<std::collections::HashMap<PathBuf, (KubeconfigStamp, KubeconfigInfo)>>,
+}
+
+impl KubeconfigInfoCache {
+    /// Drop every entry; for writes that could land within the filesystem's timestamp granularity.
+    pub async fn invalidate(&self) {
+        self.entries.lock().await.clear();
+    }
+}
+
 // C4.1: Never include path or content in error messages (no secrets in logs).
 fn kubeconfig_read_error() -> String {
     "Failed to read kubeconfig at configured path".to_string()
@@ -42,19 +60,92 @@ fn kubeconfig_write_error() -> String {
     "Failed to write kubeconfig".to_string()
 }
 
+/// Read and parse a kubeconfig.
+async fn load_kubeconfig(kubeconfig_path: &std::path::Path) -> Result<Value, String> {
+    let content = fs::read_to_string(kubeconfig_path)
+        .await
+        .map_err(|_| kubeconfig_read_error())?;
+    parse_kubeconfig_yaml(content).await
+}
+
+/// Parse kubeconfig YAML on the blocking pool: multi-thousand-line configs take long enough to
+/// stall the async runtime.
+async fn parse_kubeconfig_yaml(content: String) -> Result<Value, String> {
+    tokio::task::spawn_blocking(move || serde_yaml::from_str::<Value>(&content))
+        .await
+        .map_err(|_| kubeconfig_parse_error())?
+        .map_err(|_| kubeconfig_parse_error())
+}
+
 #[command]
+#[tracing::instrument(skip_all, err)]
 pub async fn read_kubeconfig(path: Option<String>) -> Result<String, String> {
     let kubeconfig_path = get_kubeconfig_path(path).await?;
 
-    std::fs::read_to_string(kubeconfig_path).map_err(|_| kubeconfig_read_error())
+    fs::read_to_string(kubeconfig_path)
+        .await
+        .map_err(|_| kubeconfig_read_error())
 }
 
 #[command]
-pub async fn get_kubeconfig_info(path: Option<String>) -> Result<KubeconfigInfo, String> {
-    let kubeconfig_path = get_kubeconfig_path(path.clone()).await?;
-    let content = std::fs::read_to_string(&kubeconfig_path).map_err(|_| kubeconfig_read_error())?;
-    
-    let config: Value = serde_yaml::from_str(&content).map_err(|_| kubeconfig_parse_error())?;
+#[tracing::instrument(skip_all, err)]
+pub async fn get_kubeconfig_info(
+    cache: tauri::State<'_, KubeconfigInfoCache>,
+    path: Option<String>,
+) -> Result<KubeconfigInfo, String> {
+    let kubeconfig_path = get_kubeconfig_path(path).await?;
+    let stamp = kubeconfig_stamp(&kubeconfig_path).await?;
+
+    // Held while parsing, so concurrent refreshes wait for one parse instead of each doing it
+    let mut entries = cache.entries.lock().await;
+    if let (Some(stamp), Some((cached_stamp, info))) = (stamp, entries.get(&kubeconfig_path)) {
+        if stamp == *cached_stamp {
+            return Ok(info.clone());
+        }
+    }
+    let info = parse_kubeconfig_info(&kubeconfig_path).await?;
+    match stamp {
+        Some(stamp) => {
+            entries.insert(kubeconfig_path, (stamp, info.clone()));
+        }
+        // No modification times on this filesystem: nothing to validate an entry against
+        None => {
+            entries.remove(&kubeconfig_path);
+        }
+    }
+    Ok(info)
+}
+
+/// The kubeconfig's current stamp; `None` when the filesystem has no modification times.
+pub(crate) async fn kubeconfig_stamp(kubeconfig_path: &std::path::Path) -> Result<Option<KubeconfigStamp>, String> {
+    let metadata = fs::metadata(kubeconfig_path)
+        .await
+        .map_err(|_| kubeconfig_read_error())?;
+    Ok(metadata.modified().ok().map(|modified| (modified, metadata.len())))
+}
+
+/// Every API server URL in the kubeconfig (`clusters[].cluster.server`), from a single parse.
+pub(crate) async fn kubeconfig_server_urls(kubeconfig_path: &std::path::Path) -> Result<Vec<String>, String> {
+    let config = load_kubeconfig(kubeconfig_path).await?;
+    Ok(config
+        .get("clusters")
+        .and_then(|v| v.as_array())
+        .map(|clusters| {
+            clusters
+                .iter()
+                .filter_map(|c| c.get("cluster")?.get("server")?.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Kubeconfig info read fresh from disk, for callers outside the command layer.
+pub(crate) async fn kubeconfig_info(path: Option<String>) -> Result<KubeconfigInfo, String> {
+    parse_kubeconfig_info(&get_kubeconfig_path(path).await?).await
+}
+
+async fn parse_kubeconfig_info(kubeconfig_path: &std::path::Path) -> Result<KubeconfigInfo, String> {
+    let config = load_kubeconfig(kubeconfig_path).await?;
     
     let current_context = config.get("current-context")
         .and_then(|v| v.as_str())
@@ -70,11 +161,12 @@ pub async fn get_kubeconfig_info(path: Option<String>) -> Result<KubeconfigInfo,
 }
 
 #[command]
-pub async fn switch_context(context_name: String) -> Result<(), String> {
+pub async fn switch_context(
+    cache: tauri::State<'_, KubeconfigInfoCache>,
+    context_name: String,
+) -> Result<(), String> {
     let kubeconfig_path = get_kubeconfig_path(None).await?;
-    let content = std::fs::read_to_string(&kubeconfig_path).map_err(|_| kubeconfig_read_error())?;
-    
-    let mut config: Value = serde_yaml::from_str(&content).map_err(|_| kubeconfig_parse_error())?;
+    let mut config = load_kubeconfig(&kubeconfig_path).await?;
     
     // Validate context exists
     let contexts = parse_contexts(&config)?;
@@ -90,25 +182,20 @@ pub async fn switch_context(context_name: String) -> Result<(), String> {
     // Write back
     let yaml = serde_yaml::to_string(&config).map_err(|_| kubeconfig_parse_error())?;
     
-    std::fs::write(&kubeconfig_path, yaml).map_err(|_| kubeconfig_write_error())?;
+    fs::write(&kubeconfig_path, yaml)
+        .await
+        .map_err(|_| kubeconfig_write_error())?;
+    cache.invalidate().await;
     
     Ok(())
 }
 
 #[command]
+#[tracing::instrument(skip_all, err)]
 pub async fn validate_kubeconfig(path: Option<String>) -> Result<bool, String> {
     let kubeconfig_path = get_kubeconfig_path(path).await?;
     
-    if !kubeconfig_path.exists() {
-        return Ok(false);
-    }
-    
-    let content = match std::fs::read_to_string(&kubeconfig_path) {
-        Ok(c) => c,
-        Err(_) => return Ok(false),
-    };
-    
-    match serde_yaml::from_str::<Value>(&content) {
+    match load_kubeconfig(&kubeconfig_path).await {
         Ok(config) => {
             // Check required fields
             let has_clusters = config.get("clusters").is_some();
@@ -128,7 +215,7 @@ pub async fn auto_detect_kubeconfig() -> Result<Vec<String>, String> {
     // Check default location
     if let Some(home) = dirs::home_dir() {
         let default_path = home.join(".kube").join("config");
-        if default_path.exists() {
+        if fs::try_exists(&default_path).await.unwrap_or(false) {
             paths.push(default_path.to_string_lossy().to_string());
         }
     }
@@ -141,7 +228,7 @@ pub async fn auto_detect_kubeconfig() -> Result<Vec<String>, String> {
         let separator = ':';
         for path in kubeconfig_env.split(separator) {
             let p = PathBuf::from(path);
-            if p.exists() && !paths.contains(&p.to_string_lossy().to_string()) {
+            if fs::try_exists(&p).await.unwrap_or(false) && !paths.contains(&p.to_string_lossy().to_string()) {
                 paths.push(p.to_string_lossy().to_string());
             }
         }
@@ -157,32 +244,31 @@ pub async fn browse_for_kubeconfig() -> Result<Option<String>, String> {
     Ok(None)
 }
 
+/// Save an export into app-data/exports, or — with `show_save_dialog` — wherever the user picks
+/// in a native Save As dialog. Returns the written path, or `None` if the dialog was cancelled.
 #[command]
 pub async fn save_topology_export(
+    app_handle: tauri::AppHandle,
     data: Vec<u8>,
     filename: String,
-    _format: String,
-) -> Result<String, String> {
-    let app_data_dir = get_app_data_dir().await?;
-    let exports_dir = PathBuf::from(app_data_dir).join("exports");
-    
-    if !exports_dir.exists() {
-        std::fs::create_dir_all(&exports_dir)
-            .map_err(|e| format!("Failed to create exports directory: {}", e))?;
-    }
-    
-    let file_path = exports_dir.join(filename);
-    std::fs::write(&file_path, data)
-        .map_err(|e| format!("Failed to write export file: {}", e))?;
+    format: String,
+    cluster: Option<String>,
+    show_save_dialog: Option<bool>,
+) -> Result<Option<String>, String> {
+    let file_path = if show_save_dialog.unwrap_or(false) {
+        write_export_via_dialog(&app_handle, &filename, &data, &format, cluster).await?
+    } else {
+        Some(write_export(&filename, &data, &format, cluster).await?)
+    };
     
-    Ok(file_path.to_string_lossy().to_string())
+    Ok(file_path.map(|p| p.to_string_lossy().to_string()))
 }
 
 #[command]
 pub async fn open_in_system_editor(file_path: String) -> Result<(), String> {
     let path = PathBuf::from(&file_path);
     
-    if !path.exists() {
+    if !fs::try_exists(&path).await.unwrap_or(false) {
         return Err(format!("File not found: {}", file_path));
     }
     
@@ -217,7 +303,7 @@ pub async fn open_in_system_editor(file_path: String) -> Result<(), String> {
 pub async fn reveal_in_file_manager(file_path: String) -> Result<(), String> {
     let path = PathBuf::from(&file_path);
     
-    if !path.exists() {
+    if !fs::try_exists(&path).await.unwrap_or(false) {
         return Err(format!("File not found: {}", file_path));
     }
     
@@ -257,46 +343,13 @@ pub async fn get_app_data_dir() -> Result<String, String> {
     
     let kubilitics_dir = data_dir.join("kubilitics");
     
-    if !kubilitics_dir.exists() {
-        std::fs::create_dir_all(&kubilitics_dir)
-            .map_err(|e| format!("Failed to create data directory: {}", e))?;
-    }
+    fs::create_dir_all(&kubilitics_dir)
+        .await
+        .map_err(|e| format!("Failed to create data directory: {}", e))?;
     
     Ok(kubilitics_dir.to_string_lossy().to_string())
 }
 
-#[command]
-pub async fn get_recent_exports() -> Result<Vec<String>, String> {
-    let app_data_dir = get_app_data_dir().await?;
-    let exports_dir = PathBuf::from(app_data_dir).join("exports");
-    
-    if !exports_dir.exists() {
-        return Ok(Vec::new());
-    }
-    
-    let mut exports = Vec::new();
-    
-    for entry in std::fs::read_dir(exports_dir)
-        .map_err(|e| format!("Failed to read exports directory: {}", e))? {
-        if let Ok(entry) = entry {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_file() {
-                    exports.push(entry.path().to_string_lossy().to_string());
-                }
-            }
-        }
-    }
-    
-    // Sort by modification time (most recent first)
-    exports.sort_by(|a, b| {
-        let a_time = std::fs::metadata(a).and_then(|m| m.modified()).ok();
-        let b_time = std::fs::metadata(b).and_then(|m| m.modified()).ok();
-        b_time.cmp(&a_time)
-    });
-    
-    Ok(exports.into_iter().take(10).collect())
-}
-
 #[command]
 pub async fn select_kubeconfig_file(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
     use tauri_plugin_dialog::DialogExt;
@@ -351,7 +404,7 @@ async fn get_security_settings_path() -> Result<PathBuf, String> {
 async fn load_security_settings() -> Result<KubeconfigSecuritySettings, String> {
     let settings_path = get_security_settings_path().await?;
     
-    if !settings_path.exists() {
+    if !fs::try_exists(&settings_path).await.unwrap_or(false) {
         return Ok(KubeconfigSecuritySettings {
             selected_contexts: Vec::new(),
             kubeconfig_path: None,
@@ -361,6 +414,7 @@ async fn load_security_settings() -> Result<KubeconfigSecuritySettings, String>
     }
     
     let content = fs::read_to_string(&settings_path)
+        .await
         .map_err(|_| "Failed to read security settings".to_string())?;
     
     serde_json::from_str(&content)
@@ -379,6 +433,7 @@ async fn save_security_settings(settings: &KubeconfigSecuritySettings) -> Result
     // Ensure parent directory exists
     if let Some(parent) = settings_path.parent() {
         fs::create_dir_all(parent)
+            .await
             .map_err(|_| "Failed to create settings directory".to_string())?;
     }
     
@@ -386,6 +441,7 @@ async fn save_security_settings(settings: &KubeconfigSecuritySettings) -> Result
         .map_err(|_| "Failed to serialize settings".to_string())?;
     
     fs::write(&settings_path, content)
+        .await
         .map_err(|_| "Failed to write security settings".to_string())?;
     
     Ok(())
@@ -431,21 +487,22 @@ pub async fn get_custom_kubeconfig_path() -> Result<Option<String>, String> {
 /// written to `<app-data>/kubilitics/encryption.key`; subsequent runs load that
 /// same file.  The key file is created with mode 0600 on Unix so only the
 /// current user can read it.
-fn get_encryption_key() -> Result<Vec<u8>, String> {
+async fn get_encryption_key() -> Result<Vec<u8>, String> {
     let key_path = dirs::data_local_dir()
         .ok_or("Could not find data directory")?
         .join("kubilitics")
         .join("encryption.key");
 
-    if key_path.exists() {
+    if fs::try_exists(&key_path).await.unwrap_or(false) {
         // Load the persisted key
         let key_bytes = fs::read(&key_path)
+            .await
             .map_err(|e| format!("Failed to read encryption key: {}", e))?;
         if key_bytes.len() == 32 {
             return Ok(key_bytes);
         }
         // Key file is corrupt — regenerate below
-        eprintln!("Encryption key file is malformed (len={}), regenerating", key_bytes.len());
+        tracing::warn!("Encryption key file is malformed (len={}), regenerating", key_bytes.len());
     }
 
     // Generate a new random 32-byte key
@@ -455,27 +512,29 @@ fn get_encryption_key() -> Result<Vec<u8>, String> {
 
     // Ensure the directory exists
     if let Some(parent) = key_path.parent() {
-        let _ = fs::create_dir_all(parent);
+        let _ = fs::create_dir_all(parent).await;
     }
 
     // Write with restricted permissions on Unix
     #[cfg(unix)]
     {
-        use std::os::unix::fs::OpenOptionsExt;
+        use tokio::io::AsyncWriteExt;
         let mut file = fs::OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
             .mode(0o600) // owner read+write only
             .open(&key_path)
+            .await
             .map_err(|e| format!("Failed to create encryption key file: {}", e))?;
-        use std::io::Write;
         file.write_all(&key_bytes)
+            .await
             .map_err(|e| format!("Failed to write encryption key: {}", e))?;
     }
     #[cfg(not(unix))]
     {
         fs::write(&key_path, &key_bytes)
+            .await
             .map_err(|e| format!("Failed to write encryption key: {}", e))?;
     }
 
@@ -484,7 +543,7 @@ fn get_encryption_key() -> Result<Vec<u8>, String> {
 
 #[command]
 pub async fn encrypt_kubeconfig(kubeconfig_content: String) -> Result<String, String> {
-    let key_bytes = get_encryption_key()?;
+    let key_bytes = get_encryption_key().await?;
     let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes);
     let cipher = Aes256Gcm::new(key);
     
@@ -503,7 +562,7 @@ pub async fn encrypt_kubeconfig(kubeconfig_content: String) -> Result<String, St
 
 #[command]
 pub async fn decrypt_kubeconfig(encrypted_content: String) -> Result<String, String> {
-    let key_bytes = get_encryption_key()?;
+    let key_bytes = get_encryption_key().await?;
     let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&key_bytes);
     let cipher = Aes256Gcm::new(key);
     
@@ -549,21 +608,6 @@ pub async fn load_encrypted_kubeconfig() -> Result<Option<String>, String> {
     }
 }
 
-#[command]
-pub async fn check_for_updates(_app_handle: tauri::AppHandle) -> Result<bool, String> {
-    // Tauri v2 updater plugin uses the app handle directly
-    // For now, return false (no update) - updater functionality can be implemented later
-    // The updater plugin handles updates automatically via the config
-    Ok(false)
-}
-
-#[command]
-pub async fn install_update(_app_handle: tauri::AppHandle) -> Result<(), String> {
-    // Tauri v2 updater plugin handles updates automatically via the config
-    // Manual install is not needed - the plugin handles it
-    Err("Updates are handled automatically by the updater plugin".to_string())
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DesktopInfo {
     pub app_version: String,
@@ -572,6 +616,7 @@ pub struct DesktopInfo {
     pub backend_uptime_seconds: Option<u64>,
     pub kubeconfig_path: String,
     pub app_data_dir: String,
+    pub update_channel: crate::updater::UpdateChannel,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -580,92 +625,204 @@ pub struct ConnectivityStatus {
     pub backend_reachable: bool,
     pub ai_backend_reachable: bool,
     pub last_check: u64, // Unix timestamp
+    /// Round trip of the probe that succeeded; none when it failed or (internet) when no external
+    /// endpoint was contacted.
+    pub internet_latency_ms: Option<f64>,
+    pub backend_latency_ms: Option<f64>,
+    pub ai_backend_latency_ms: Option<f64>,
+}
+
+/// Budget for the whole internet probe; the external endpoints are tried in parallel within it.
+const INTERNET_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+const HEALTH_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Probe targets for `check_connectivity`. Unset URLs fall back to the bundled sidecars on their
+/// default ports; a remote backend (configured in the frontend) is set here so the probe follows it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConnectivitySettings {
+    pub backend_url: Option<String>,
+    pub ai_backend_url: Option<String>,
+    /// Extra URLs that must answer for the app to count as online, on top of the OS network
+    /// status (e.g. to catch captive portals). Empty by default: nothing is contacted outside the
+    /// local machine, which privacy-restricted networks require.
+    pub external_endpoints: Vec<String>,
+    /// Address the bundled backend listens on (KUBILITICS_BIND_ADDRESS). Unset listens on every
+    /// interface, IPv4 and IPv6; "127.0.0.1" or "::1" keeps it off the LAN, which disables mobile
+    /// pairing.
+    pub backend_bind_address: Option<String>,
+}
+
+impl ConnectivitySettings {
+    async fn backend_url(&self) -> String {
+        match &self.backend_url {
+            Some(url) => url.clone(),
+            None => crate::loopback::base_url(BACKEND_PORT).await,
+        }
+    }
+
+    async fn ai_backend_url(&self) -> String {
+        match &self.ai_backend_url {
+            Some(url) => url.clone(),
+            None => crate::loopback::base_url(AI_BACKEND_PORT).await,
+        }
+    }
+}
+
+async fn get_connectivity_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("connectivity_settings.json"))
+}
+
+pub(crate) async fn load_connectivity_settings() -> Result<ConnectivitySettings, String> {
+    let settings_path = get_connectivity_settings_path().await?;
+
+    if !fs::try_exists(&settings_path).await.unwrap_or(false) {
+        return Ok(ConnectivitySettings::default());
+    }
+
+    let content = fs::read_to_string(&settings_path)
+        .await
+        .map_err(|_| "Failed to read connectivity settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse connectivity settings".to_string())
+}
+
+async fn save_connectivity_settings(settings: &ConnectivitySettings) -> Result<(), String> {
+    let settings_path = get_connectivity_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize connectivity settings".to_string())?;
+
+    fs::write(&settings_path, content)
+        .await
+        .map_err(|_| "Failed to write connectivity settings".to_string())
 }
 
 #[command]
-pub async fn check_connectivity() -> Result<ConnectivityStatus, String> {
+#[tracing::instrument(skip_all, err)]
+pub async fn check_connectivity(app_handle: tauri::AppHandle) -> Result<ConnectivityStatus, String> {
     use std::time::{SystemTime, UNIX_EPOCH};
     
     let now = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
         .as_secs();
-    
-    // Check basic internet connectivity
-    let is_online = check_internet_connectivity().await;
-    
-    // Check backend connectivity
-    let backend_reachable = check_backend_connectivity().await;
-    
-    // Check AI backend connectivity
-    let ai_backend_reachable = check_ai_backend_connectivity().await;
-    
+
+    let settings = load_connectivity_settings().await.unwrap_or_default();
+    let client = crate::http_client::shared(&app_handle).await?;
+
+    // All three probes run at once, each within its own budget (which covers finding the loopback
+    // address), so a check takes no longer than the slowest budget
+    let backend = tokio::time::timeout(HEALTH_PROBE_TIMEOUT, async {
+        check_health_endpoint(&client, &settings.backend_url().await).await
+    });
+    let ai_backend = tokio::time::timeout(HEALTH_PROBE_TIMEOUT, async {
+        check_health_endpoint(&client, &settings.ai_backend_url().await).await
+    });
+    let ((is_online, internet_latency_ms), backend, ai_backend) = tokio::join!(
+        check_internet_connectivity(&client, &settings.external_endpoints),
+        backend,
+        ai_backend,
+    );
+    let backend_latency_ms = backend.ok().flatten();
+    let ai_backend_latency_ms = ai_backend.ok().flatten();
+
     Ok(ConnectivityStatus {
         is_online,
-        backend_reachable,
-        ai_backend_reachable,
+        backend_reachable: backend_latency_ms.is_some(),
+        ai_backend_reachable: ai_backend_latency_ms.is_some(),
         last_check: now,
+        internet_latency_ms,
+        backend_latency_ms,
+        ai_backend_latency_ms,
     })
 }
 
-async fn check_internet_connectivity() -> bool {
-    // Try to connect to a reliable external service
-    let client = match reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
-    {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-    
-    // Try multiple endpoints for reliability
-    let endpoints = vec![
-        "https://www.google.com",
-        "https://1.1.1.1", // Cloudflare DNS
-        "https://8.8.8.8", // Google DNS
-    ];
-    
-    for endpoint in endpoints {
-        if client.get(endpoint).send().await.is_ok() {
-            return true;
+/// From the OS network monitor, confirmed by the configured external endpoints (if any): online,
+/// and the latency of the first endpoint that answered.
+async fn check_internet_connectivity(
+    client: &reqwest::Client,
+    external_endpoints: &[String],
+) -> (bool, Option<f64>) {
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    if !crate::network::current_network_status().await.online {
+        return (false, None);
+    }
+    // Air-gapped mode never contacts the external endpoints
+    if external_endpoints.is_empty() || crate::airgap::is_air_gapped() {
+        return (true, None);
+    }
+
+    let start = std::time::Instant::now();
+    let mut probes: FuturesUnordered<_> = external_endpoints
+        .iter()
+        .map(|endpoint| client.get(endpoint).timeout(INTERNET_PROBE_TIMEOUT).send())
+        .collect();
+    let first_answer = async {
+        while let Some(result) = probes.next().await {
+            if result.is_ok() {
+                return Some(start.elapsed().as_secs_f64() * 1000.0);
+            }
         }
+        None
+    };
+    match tokio::time::timeout(INTERNET_PROBE_TIMEOUT, first_answer).await {
+        Ok(Some(latency)) => (true, Some(latency)),
+        _ => (false, None),
     }
-    
-    false
 }
 
-async fn check_backend_connectivity() -> bool {
-    let client = match reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-    {
-        Ok(c) => c,
-        Err(_) => return false,
-    };
-    
-    let url = format!("http://localhost:{}/health", BACKEND_PORT);
-    client.get(&url)
-        .send()
-        .await
-        .map(|r| r.status().is_success())
-        .unwrap_or(false)
+/// Latency of a successful `/health` call; none when the service didn't answer.
+async fn check_health_endpoint(client: &reqwest::Client, base_url: &str) -> Option<f64> {
+    let url = format!("{}/health", base_url.trim_end_matches('/'));
+    let start = std::time::Instant::now();
+    let response = client.get(&url).send().await.ok()?;
+    response
+        .status()
+        .is_success()
+        .then(|| start.elapsed().as_secs_f64() * 1000.0)
 }
 
-async fn check_ai_backend_connectivity() -> bool {
-    let client = match reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-    {
-        Ok(c) => c,
-        Err(_) => return false,
+#[command]
+pub async fn get_connectivity_settings() -> Result<ConnectivitySettings, String> {
+    load_connectivity_settings().await
+}
+
+#[command]
+pub async fn set_connectivity_settings(mut settings: ConnectivitySettings) -> Result<(), String> {
+    let normalize = |url: Option<String>| {
+        url.map(|u| u.trim().trim_end_matches('/').to_string())
+            .filter(|u| !u.is_empty())
     };
-    
-    let url = format!("http://localhost:{}/health", AI_BACKEND_PORT);
-    client.get(&url)
-        .send()
-        .await
-        .map(|r| r.status().is_success())
-        .unwrap_or(false)
+    settings.backend_url = normalize(settings.backend_url);
+    settings.ai_backend_url = normalize(settings.ai_backend_url);
+    settings.external_endpoints.retain(|e| !e.trim().is_empty());
+    settings.backend_bind_address = settings
+        .backend_bind_address
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    if let Some(address) = &settings.backend_bind_address {
+        address
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| format!("Invalid bind address {}: must be an IP address", address))?;
+    }
+
+    let urls = settings
+        .backend_url
+        .iter()
+        .chain(settings.ai_backend_url.iter())
+        .chain(settings.external_endpoints.iter());
+    for url in urls {
+        let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!("Invalid URL {}: must be http or https", url));
+        }
+    }
+
+    save_connectivity_settings(&settings).await
 }
 
 async fn get_analytics_settings_path() -> Result<PathBuf, String> {
@@ -677,7 +834,7 @@ async fn get_analytics_settings_path() -> Result<PathBuf, String> {
 async fn load_analytics_settings() -> Result<AnalyticsSettings, String> {
     let settings_path = get_analytics_settings_path().await?;
     
-    if !settings_path.exists() {
+    if !fs::try_exists(&settings_path).await.unwrap_or(false) {
         return Ok(AnalyticsSettings {
             consent_given: false,
             consent_timestamp: None,
@@ -686,6 +843,7 @@ async fn load_analytics_settings() -> Result<AnalyticsSettings, String> {
     }
     
     let content = fs::read_to_string(&settings_path)
+        .await
         .map_err(|_| "Failed to read analytics settings".to_string())?;
     
     serde_json::from_str(&content)
@@ -697,6 +855,7 @@ async fn save_analytics_settings(settings: &AnalyticsSettings) -> Result<(), Str
     
     if let Some(parent) = settings_path.parent() {
         fs::create_dir_all(parent)
+            .await
             .map_err(|_| "Failed to create settings directory".to_string())?;
     }
     
@@ -704,11 +863,23 @@ async fn save_analytics_settings(settings: &AnalyticsSettings) -> Result<(), Str
         .map_err(|_| "Failed to serialize analytics settings".to_string())?;
     
     fs::write(&settings_path, content)
+        .await
         .map_err(|_| "Failed to write analytics settings".to_string())?;
     
     Ok(())
 }
 
+/// Forget the consent decision and its timestamp; the next launch asks again.
+pub(crate) async fn delete_analytics_settings() -> Result<(), String> {
+    let settings_path = get_analytics_settings_path().await?;
+    if fs::try_exists(&settings_path).await.unwrap_or(false) {
+        fs::remove_file(&settings_path)
+            .await
+            .map_err(|_| "Failed to delete analytics settings".to_string())?;
+    }
+    Ok(())
+}
+
 #[command]
 pub async fn get_analytics_consent() -> Result<bool, String> {
     let settings = load_analytics_settings().await?;
@@ -732,7 +903,13 @@ pub async fn set_analytics_consent(consent: bool) -> Result<(), String> {
         );
     }
     
-    save_analytics_settings(&settings).await
+    save_analytics_settings(&settings).await?;
+
+    // Withdrawn consent also discards anything collected but not yet sent
+    if !consent {
+        crate::analytics::clear_queue().await?;
+    }
+    Ok(())
 }
 
 #[command]
@@ -756,6 +933,7 @@ pub async fn get_desktop_info() -> Result<DesktopInfo, String> {
     let backend_port = BACKEND_PORT;
     let backend_version = None; // Would need to call /api/v1/version endpoint
     let backend_uptime_seconds = None; // Would need to call /api/v1/health and parse uptime
+    let update_channel = crate::updater::load_update_settings().await.unwrap_or_default().channel;
     
     Ok(DesktopInfo {
         app_version,
@@ -764,10 +942,12 @@ pub async fn get_desktop_info() -> Result<DesktopInfo, String> {
         backend_uptime_seconds,
         kubeconfig_path,
         app_data_dir,
+        update_channel,
     })
 }
 
 #[command]
+#[tracing::instrument(skip_all, err)]
 pub async fn restart_sidecar(app_handle: tauri::AppHandle) -> Result<(), String> {
     use crate::sidecar::BackendManager;
 
@@ -788,7 +968,7 @@ pub fn is_kcli_sidecar_available(app_handle: tauri::AppHandle) -> Result<bool, S
 
 // Helper functions
 
-async fn get_kubeconfig_path(path: Option<String>) -> Result<PathBuf, String> {
+pub(crate) async fn get_kubeconfig_path(path: Option<String>) -> Result<PathBuf, String> {
     // First check if custom path is set
     if path.is_none() {
         if let Ok(settings) = load_security_settings().await {
@@ -808,6 +988,50 @@ async fn get_kubeconfig_path(path: Option<String>) -> Result<PathBuf, String> {
     }
 }
 
+/// A context of the kubeconfig in use, by name.
+pub(crate) async fn get_context(context_name: &str) -> Result<KubeconfigContext, String> {
+    let kubeconfig_path = get_kubeconfig_path(None).await?;
+    let config = load_kubeconfig(&kubeconfig_path).await?;
+
+    parse_contexts(&config)?
+        .into_iter()
+        .find(|c| c.name == context_name)
+        .ok_or_else(|| format!("Context '{}' not found", context_name))
+}
+
+/// The `cluster` entry of a kubeconfig context's cluster (server, certificate-authority-data, …).
+pub(crate) async fn get_context_cluster(context_name: &str) -> Result<Value, String> {
+    let kubeconfig_path = get_kubeconfig_path(None).await?;
+    let config = load_kubeconfig(&kubeconfig_path).await?;
+
+    let cluster = parse_contexts(&config)?
+        .into_iter()
+        .find(|c| c.name == context_name)
+        .map(|c| c.cluster)
+        .ok_or_else(|| format!("Context '{}' not found", context_name))?;
+
+    config.get("clusters")
+        .and_then(|v| v.as_array())
+        .and_then(|clusters| {
+            clusters
+                .iter()
+                .find(|c| c.get("name").and_then(|n| n.as_str()) == Some(cluster.as_str()))
+        })
+        .and_then(|c| c.get("cluster"))
+        .cloned()
+        .ok_or_else(|| format!("Cluster '{}' not found in kubeconfig", cluster))
+}
+
+/// API server URL of a kubeconfig context (`clusters[].cluster.server` of the context's cluster).
+pub(crate) async fn get_context_server_url(context_name: &str) -> Result<String, String> {
+    get_context_cluster(context_name)
+        .await?
+        .get("server")
+        .and_then(|s| s.as_str())
+        .map(String::from)
+        .ok_or_else(|| format!("Context '{}' has no server in kubeconfig", context_name))
+}
+
 fn parse_contexts(config: &Value) -> Result<Vec<KubeconfigContext>, String> {
     let contexts = config.get("contexts")
         .and_then(|v| v.as_array())
@@ -848,3 +1072,64 @@ fn parse_contexts(config: &Value) -> Result<Vec<KubeconfigContext>, String> {
     
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A kubeconfig the size of a large fleet's: `count` contexts, each with its own cluster (and
+    /// CA bundle) and user.
+    fn large_kubeconfig(count: usize) -> String {
+        let ca = "A".repeat(1500);
+        let mut clusters = String::new();
+        let mut contexts = String::new();
+        let mut users = String::new();
+        for i in 0..count {
+            clusters.push_str(&format!(
+                "- name: cluster-{i}\n  cluster:\n    server: https://10.{}.{}.1:6443\n    certificate-authority-data: {ca}\n",
+                i / 256 % 256,
+                i % 256
+            ));
+            contexts.push_str(&format!(
+                "- name: context-{i}\n  context:\n    cluster: cluster-{i}\n    user: user-{i}\n    namespace: ns-{i}\n"
+            ));
+            users.push_str(&format!("- name: user-{i}\n  user:\n    token: token-{i}\n"));
+        }
+        format!(
+            "apiVersion: v1\nkind: Config\ncurrent-context: context-0\nclusters:\n{clusters}contexts:\n{contexts}users:\n{users}"
+        )
+    }
+
+    /// Parsing a multi-megabyte kubeconfig must not stall the runtime, so it has to run on the
+    /// blocking pool. With the pool's only thread held busy, a parse there can't finish until the
+    /// thread is released, while a parse on the runtime thread would complete on the first poll.
+    #[test]
+    fn large_kubeconfig_parses_on_the_blocking_pool() {
+        const CONTEXTS: usize = 5000;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .max_blocking_threads(1)
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let (release, held) = std::sync::mpsc::channel::<()>();
+            let occupant = tokio::task::spawn_blocking(move || {
+                let _ = held.recv();
+            });
+
+            let mut parse = std::pin::pin!(parse_kubeconfig_yaml(large_kubeconfig(CONTEXTS)));
+            assert!(
+                futures::poll!(parse.as_mut()).is_pending(),
+                "kubeconfig was parsed on the runtime thread"
+            );
+            release.send(()).unwrap();
+            occupant.await.unwrap();
+
+            let config = parse.await.unwrap();
+            let contexts = parse_contexts(&config).unwrap();
+            assert_eq!(contexts.len(), CONTEXTS);
+            assert_eq!(config.get("current-context").and_then(|v| v.as_str()), Some("context-0"));
+            assert_eq!(contexts[CONTEXTS - 1].namespace.as_deref(), Some("ns-4999"));
+        });
+    }
+}
@@ -0,0 +1,91 @@
+// "Open in terminal" for a context: the user's own terminal with kubectl already pointed at the
+// context and namespace, without touching their kubeconfig or its current-context.
+//
+// The context is pinned by a small overlay kubeconfig (terminal_kubeconfigs/<context>.yaml) that
+// holds only that context, with the chosen namespace, and makes it current. KUBECONFIG lists the
+// overlay first and the real kubeconfig after it; kubectl merges them with the first file winning,
+// so the cluster and user entries (and their credentials) still come from the real file.
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::command;
+use tokio::fs;
+
+use crate::commands::get_app_data_dir;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextTerminal {
+    pub context: String,
+    pub namespace: Option<String>,
+    /// The KUBECONFIG the terminal was started with.
+    pub kubeconfig: String,
+}
+
+/// File name for a context's overlay; context names often hold `/` and `:` (EKS ARNs).
+fn overlay_file_name(context: &str) -> String {
+    let safe: String = context
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.yaml", safe)
+}
+
+async fn write_overlay(context: &str, namespace: Option<&str>) -> Result<PathBuf, String> {
+    let entry = crate::commands::get_context(context).await?;
+    let mut context_spec = json!({ "cluster": entry.cluster, "user": entry.user });
+    if let Some(namespace) = namespace {
+        context_spec["namespace"] = json!(namespace);
+    }
+    let overlay = json!({
+        "apiVersion": "v1",
+        "kind": "Config",
+        "current-context": context,
+        "contexts": [{ "name": context, "context": context_spec }],
+    });
+    let content = serde_yaml::to_string(&overlay)
+        .map_err(|e| format!("Failed to render kubeconfig overlay: {}", e))?;
+
+    let dir = PathBuf::from(get_app_data_dir().await?).join("terminal_kubeconfigs");
+    fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(overlay_file_name(context));
+    fs::write(&path, content)
+        .await
+        .map_err(|e| format!("Failed to write kubeconfig overlay: {}", e))?;
+    Ok(path)
+}
+
+/// Open the user's terminal with KUBECONFIG set so kubectl (and helm, k9s, …) use `context` and
+/// `namespace`. The namespace defaults to the context's own.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn open_terminal_for_context(
+    context: String,
+    namespace: Option<String>,
+) -> Result<ContextTerminal, String> {
+    let namespace = match namespace.filter(|n| !n.trim().is_empty()) {
+        Some(namespace) => Some(namespace),
+        None => crate::commands::get_context(&context).await?.namespace,
+    };
+    let overlay = write_overlay(&context, namespace.as_deref()).await?;
+    let kubeconfig_path = crate::commands::get_kubeconfig_path(None).await?;
+    let kubeconfig = std::env::join_paths([overlay, kubeconfig_path])
+        .map_err(|e| format!("Invalid kubeconfig path: {}", e))?
+        .to_string_lossy()
+        .to_string();
+
+    crate::terminal::open_shell_in_terminal(&[("KUBECONFIG".to_string(), kubeconfig.clone())])?;
+    Ok(ContextTerminal {
+        context,
+        namespace,
+        kubeconfig,
+    })
+}
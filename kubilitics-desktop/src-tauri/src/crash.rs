@@ -0,0 +1,262 @@
+// Crash reporting for the Rust layer. A panic in a command or background task used to vanish into
+// stderr, which nobody sees in a GUI app. The panic hook writes a report (message, location,
+// backtrace, versions) to crash_reports/ under app data before the default hook runs.
+//
+// Reports always stay on the machine; sending one is a separate, explicit step that requires
+// crash-reporting consent (off by default) and is refused in air-gapped mode. Native crashes that
+// never reach a panic (aborts, segfaults in C dependencies) aren't captured — that would need an
+// out-of-process minidump handler.
+use std::backtrace::Backtrace;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::get_app_data_dir;
+
+const CRASH_REPORT_ENDPOINT: &str = "https://telemetry.kubilitics.dev/v1/crashes";
+const SUBMIT_REQUEST_TIMEOUT_SECS: u64 = 30;
+const CRASH_REPORTS_DIR: &str = "crash_reports";
+/// Oldest reports are deleted past this; a crash loop shouldn't fill the disk.
+const MAX_STORED_REPORTS: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: Option<String>,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    #[serde(default)]
+    pub submitted_at: Option<u64>,
+}
+
+/// List entry; the backtrace is only loaded for submission.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportSummary {
+    pub id: String,
+    pub timestamp: u64,
+    pub app_version: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub submitted_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrashReportingSettings {
+    pub consent_given: bool,
+    pub consent_timestamp: Option<u64>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Same location as `get_app_data_dir`, resolved synchronously: the panic hook can't await.
+fn crash_reports_dir_sync() -> Option<PathBuf> {
+    let dir = dirs::data_local_dir()?.join("kubilitics").join(CRASH_REPORTS_DIR);
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+async fn get_crash_reports_dir() -> Result<PathBuf, String> {
+    let dir = PathBuf::from(get_app_data_dir().await?).join(CRASH_REPORTS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create crash report directory: {}", e))?;
+    Ok(dir)
+}
+
+async fn get_crash_reporting_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("crash_reporting_settings.json"))
+}
+
+async fn load_crash_reporting_settings() -> Result<CrashReportingSettings, String> {
+    let path = get_crash_reporting_settings_path().await?;
+
+    if !path.exists() {
+        return Ok(CrashReportingSettings::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read crash reporting settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse crash reporting settings".to_string())
+}
+
+async fn save_crash_reporting_settings(settings: &CrashReportingSettings) -> Result<(), String> {
+    let path = get_crash_reporting_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize crash reporting settings".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write crash reporting settings".to_string())
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+/// Reports are named `<timestamp>-<id>.json`, so a name sort is chronological.
+fn prune_old_reports(dir: &std::path::Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut names: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    if names.len() <= MAX_STORED_REPORTS {
+        return;
+    }
+    names.sort();
+    for path in &names[..names.len() - MAX_STORED_REPORTS] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn write_report(info: &PanicHookInfo<'_>) -> Option<PathBuf> {
+    let dir = crash_reports_dir_sync()?;
+    let report = CrashReport {
+        id: format!("{:016x}", rand::random::<u64>()),
+        timestamp: now_secs(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread: std::thread::current().name().map(str::to_string),
+        message: panic_message(info),
+        location: info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: Backtrace::force_capture().to_string(),
+        submitted_at: None,
+    };
+    let path = dir.join(format!("{}-{}.json", report.timestamp, report.id));
+    std::fs::write(&path, serde_json::to_string_pretty(&report).ok()?).ok()?;
+    prune_old_reports(&dir);
+    Some(path)
+}
+
+/// Install the panic hook. First thing in `main`, so panics during setup are captured too.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Never panic inside the hook; a failed write just falls through to the default output
+        if let Some(path) = write_report(info) {
+            eprintln!("Crash report written to {}", path.display());
+        }
+        default_hook(info);
+    }));
+}
+
+async fn find_report(id: &str) -> Result<(PathBuf, CrashReport), String> {
+    let dir = get_crash_reports_dir().await?;
+    let entries = std::fs::read_dir(&dir).map_err(|e| format!("Failed to read crash reports: {}", e))?;
+    let suffix = format!("-{}.json", id);
+    let path = entries
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.ends_with(&suffix)))
+        .ok_or_else(|| format!("Crash report not found: {}", id))?;
+    let content = std::fs::read_to_string(&path).map_err(|_| "Failed to read crash report".to_string())?;
+    let report = serde_json::from_str(&content).map_err(|_| "Failed to parse crash report".to_string())?;
+    Ok((path, report))
+}
+
+/// Remove every stored report and the consent record.
+pub(crate) async fn delete_all_crash_data() -> Result<(), String> {
+    let dir = PathBuf::from(get_app_data_dir().await?).join(CRASH_REPORTS_DIR);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete crash reports: {}", e))?;
+    }
+    let path = get_crash_reporting_settings_path().await?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|_| "Failed to delete crash reporting settings".to_string())?;
+    }
+    Ok(())
+}
+
+/// Stored crash reports, newest first.
+#[command]
+pub async fn list_crash_reports() -> Result<Vec<CrashReportSummary>, String> {
+    let dir = get_crash_reports_dir().await?;
+    let entries = std::fs::read_dir(&dir).map_err(|e| format!("Failed to read crash reports: {}", e))?;
+    let mut reports: Vec<CrashReportSummary> = entries
+        .flatten()
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .filter_map(|content| serde_json::from_str::<CrashReport>(&content).ok())
+        .map(|r| CrashReportSummary {
+            id: r.id,
+            timestamp: r.timestamp,
+            app_version: r.app_version,
+            message: r.message,
+            location: r.location,
+            submitted_at: r.submitted_at,
+        })
+        .collect();
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(reports)
+}
+
+/// Send one report. Requires crash-reporting consent; the report is kept (marked as submitted) so
+/// the user can still see what was sent.
+#[command]
+pub async fn submit_crash_report(app_handle: AppHandle, id: String) -> Result<(), String> {
+    if !load_crash_reporting_settings().await?.consent_given {
+        return Err("Crash reporting is not enabled".to_string());
+    }
+    crate::airgap::ensure_external_allowed("Crash report submissions")?;
+
+    let (path, mut report) = find_report(&id).await?;
+    let client = crate::http_client::shared(&app_handle).await?;
+    let response = client
+        .post(CRASH_REPORT_ENDPOINT)
+        .timeout(Duration::from_secs(SUBMIT_REQUEST_TIMEOUT_SECS))
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to submit crash report: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to submit crash report: HTTP {}", response.status()));
+    }
+
+    report.submitted_at = Some(now_secs());
+    let content = serde_json::to_string_pretty(&report)
+        .map_err(|_| "Failed to serialize crash report".to_string())?;
+    std::fs::write(&path, content).map_err(|_| "Failed to write crash report".to_string())
+}
+
+#[command]
+pub async fn delete_crash_report(id: String) -> Result<(), String> {
+    let (path, _) = find_report(&id).await?;
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to delete crash report: {}", e))
+}
+
+#[command]
+pub async fn get_crash_reporting_consent() -> Result<bool, String> {
+    Ok(load_crash_reporting_settings().await?.consent_given)
+}
+
+#[command]
+pub async fn set_crash_reporting_consent(consent: bool) -> Result<(), String> {
+    save_crash_reporting_settings(&CrashReportingSettings {
+        consent_given: consent,
+        consent_timestamp: consent.then(now_secs),
+    })
+    .await
+}
@@ -0,0 +1,177 @@
+// Custom resources without backend support: the CRDs a context has, and their instances through the
+// dynamic API. A CRD is addressed by its name (<plural>.<group>); the version defaults to the
+// storage version, which every served CRD has.
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use kube::api::{ApiResource, DynamicObject, ListParams};
+use kube::{Api, Client};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+
+const DEFAULT_PAGE_SIZE: u32 = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrdVersion {
+    pub name: String,
+    pub served: bool,
+    pub storage: bool,
+    pub deprecated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrdSummary {
+    pub name: String,
+    pub group: String,
+    pub kind: String,
+    pub plural: String,
+    pub namespaced: bool,
+    pub versions: Vec<CrdVersion>,
+    pub short_names: Vec<String>,
+    pub categories: Vec<String>,
+    /// The Established condition is True; instances can be created and listed.
+    pub established: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomResourceList {
+    pub items: Vec<Value>,
+    /// Pass back as `continue_token` for the next page; absent on the last page.
+    pub continue_token: Option<String>,
+}
+
+fn summarize(crd: &CustomResourceDefinition) -> CrdSummary {
+    let spec = &crd.spec;
+    CrdSummary {
+        name: crd.metadata.name.clone().unwrap_or_default(),
+        group: spec.group.clone(),
+        kind: spec.names.kind.clone(),
+        plural: spec.names.plural.clone(),
+        namespaced: spec.scope == "Namespaced",
+        versions: spec
+            .versions
+            .iter()
+            .map(|v| CrdVersion {
+                name: v.name.clone(),
+                served: v.served,
+                storage: v.storage,
+                deprecated: v.deprecated.unwrap_or(false),
+            })
+            .collect(),
+        short_names: spec.names.short_names.clone().unwrap_or_default(),
+        categories: spec.names.categories.clone().unwrap_or_default(),
+        established: crd
+            .status
+            .as_ref()
+            .and_then(|s| s.conditions.as_ref())
+            .is_some_and(|conditions| {
+                conditions
+                    .iter()
+                    .any(|c| c.type_ == "Established" && c.status == "True")
+            }),
+    }
+}
+
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn list_crds(context: String) -> Result<Vec<CrdSummary>, String> {
+    let client = crate::k8s::client_for(&context).await?;
+    let crds: Api<CustomResourceDefinition> = Api::all(client);
+    let list = crds
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to list CRDs: {}", e))?;
+    let mut summaries: Vec<CrdSummary> = list.items.iter().map(summarize).collect();
+    summaries.sort_by(|a, b| a.group.cmp(&b.group).then(a.kind.cmp(&b.kind)));
+    Ok(summaries)
+}
+
+/// API for a CRD's instances at `version` (storage version when `None`).
+async fn custom_resource_api(
+    context: &str,
+    crd_name: &str,
+    version: Option<&str>,
+    namespace: Option<&str>,
+) -> Result<Api<DynamicObject>, String> {
+    let client: Client = crate::k8s::client_for(context).await?;
+    let crd = Api::<CustomResourceDefinition>::all(client.clone())
+        .get(crd_name)
+        .await
+        .map_err(|e| format!("Failed to get CRD {}: {}", crd_name, e))?;
+    let spec = &crd.spec;
+    let version = match version {
+        Some(version) => spec
+            .versions
+            .iter()
+            .find(|v| v.name == version && v.served)
+            .ok_or_else(|| format!("{} does not serve version {}", crd_name, version))?,
+        None => spec
+            .versions
+            .iter()
+            .find(|v| v.storage)
+            .or_else(|| spec.versions.iter().find(|v| v.served))
+            .ok_or_else(|| format!("{} serves no versions", crd_name))?,
+    };
+
+    let resource = ApiResource {
+        group: spec.group.clone(),
+        version: version.name.clone(),
+        api_version: format!("{}/{}", spec.group, version.name),
+        kind: spec.names.kind.clone(),
+        plural: spec.names.plural.clone(),
+    };
+    Ok(match (spec.scope.as_str(), namespace) {
+        ("Namespaced", Some(namespace)) => Api::namespaced_with(client, namespace, &resource),
+        _ => Api::all_with(client, &resource),
+    })
+}
+
+/// Instances of a CRD, one page at a time. Namespaced CRDs are listed across all namespaces when
+/// `namespace` is `None`.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn list_custom_resources(
+    context: String,
+    crd: String,
+    version: Option<String>,
+    namespace: Option<String>,
+    label_selector: Option<String>,
+    limit: Option<u32>,
+    continue_token: Option<String>,
+) -> Result<CustomResourceList, String> {
+    let api = custom_resource_api(&context, &crd, version.as_deref(), namespace.as_deref()).await?;
+    let mut params = ListParams::default().limit(limit.unwrap_or(DEFAULT_PAGE_SIZE));
+    if let Some(selector) = label_selector.as_deref().filter(|s| !s.is_empty()) {
+        params = params.labels(selector);
+    }
+    if let Some(token) = continue_token {
+        params = params.continue_token(&token);
+    }
+    let list = api
+        .list(&params)
+        .await
+        .map_err(|e| format!("Failed to list {}: {}", crd, e))?;
+    Ok(CustomResourceList {
+        items: list
+            .items
+            .iter()
+            .filter_map(|item| serde_json::to_value(item).ok())
+            .collect(),
+        continue_token: list.metadata.continue_.filter(|t| !t.is_empty()),
+    })
+}
+
+#[command]
+pub async fn get_custom_resource(
+    context: String,
+    crd: String,
+    version: Option<String>,
+    namespace: Option<String>,
+    name: String,
+) -> Result<Value, String> {
+    let api = custom_resource_api(&context, &crd, version.as_deref(), namespace.as_deref()).await?;
+    let object = api
+        .get(&name)
+        .await
+        .map_err(|e| format!("Failed to get {} {}: {}", crd, name, e))?;
+    serde_json::to_value(&object).map_err(|e| e.to_string())
+}
@@ -0,0 +1,130 @@
+// Ephemeral debug containers (`kubectl debug -it <pod> --image=... --target=...`), for images with
+// no shell to exec into. The container is added through the pod's ephemeralcontainers
+// subresource; with a target container it shares that container's process namespace, so its
+// processes and /proc/<pid>/root are visible. Once running, its TTY is attached and handed to the
+// exec terminal bridge, so the session behaves like any other terminal tab.
+//
+// Ephemeral containers can't be removed; the container stays (terminated) in the pod spec after the
+// session ends, as with kubectl.
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{AttachParams, Patch, PatchParams};
+use kube::Api;
+use serde_json::json;
+use tauri::{command, AppHandle};
+
+use crate::exec::ExecSessionInfo;
+
+const DEFAULT_DEBUG_IMAGE: &str = "busybox:1.36";
+const START_TIMEOUT: Duration = Duration::from_secs(120);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Wait until the ephemeral container runs; fails early when it can't start (bad image).
+async fn wait_running(pods: &Api<Pod>, pod: &str, container: &str) -> Result<(), String> {
+    let deadline = Instant::now() + START_TIMEOUT;
+    loop {
+        let current = pods
+            .get(pod)
+            .await
+            .map_err(|e| format!("Failed to get pod {}: {}", pod, e))?;
+        let state = current
+            .status
+            .and_then(|s| s.ephemeral_container_statuses)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|s| s.name == container)
+            .and_then(|s| s.state);
+        if let Some(state) = state {
+            if state.running.is_some() {
+                return Ok(());
+            }
+            if let Some(terminated) = state.terminated {
+                return Err(format!(
+                    "Debug container exited: {}",
+                    terminated.message.or(terminated.reason).unwrap_or_default()
+                ));
+            }
+            if let Some(reason) = state.waiting.and_then(|w| w.reason) {
+                if matches!(
+                    reason.as_str(),
+                    "ErrImagePull" | "ImagePullBackOff" | "InvalidImageName"
+                ) {
+                    return Err(format!("Debug image can't be pulled ({})", reason));
+                }
+            }
+        }
+        if Instant::now() >= deadline {
+            return Err("Timed out waiting for the debug container to start".to_string());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Add a debug container to a pod and open a terminal session on it. `target_container` shares
+/// that container's process namespace; `image` defaults to busybox.
+#[command]
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, err)]
+pub async fn debug_pod(
+    app_handle: AppHandle,
+    context: String,
+    namespace: String,
+    pod: String,
+    image: Option<String>,
+    target_container: Option<String>,
+    cols: u16,
+    rows: u16,
+) -> Result<ExecSessionInfo, String> {
+    let image = image
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty())
+        .unwrap_or_else(|| DEFAULT_DEBUG_IMAGE.to_string());
+    let name = format!("debugger-{:05x}", rand::random::<u32>() & 0xfffff);
+
+    let client = crate::k8s::streaming_client_for(&context).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+
+    let mut container = json!({
+        "name": name,
+        "image": image,
+        "stdin": true,
+        "tty": true,
+        "terminationMessagePolicy": "File",
+    });
+    if let Some(target) = &target_container {
+        container["targetContainerName"] = json!(target);
+    }
+    let patch = json!({ "spec": { "ephemeralContainers": [container] } });
+    pods.patch_ephemeral_containers(&pod, &PatchParams::default(), &Patch::Strategic(&patch))
+        .await
+        .map_err(|e| format!("Failed to add a debug container to {}: {}", pod, e))?;
+
+    wait_running(&pods, &pod, &name).await?;
+
+    let attached = pods
+        .attach(
+            &pod,
+            &AttachParams::interactive_tty().container(name.clone()),
+        )
+        .await
+        .map_err(|e| format!("Failed to attach to the debug container: {}", e))?;
+    let info = ExecSessionInfo {
+        id: format!("{:016x}", rand::random::<u64>()),
+        context,
+        namespace,
+        pod,
+        container: Some(name),
+        // Runs the image's own entrypoint
+        command: Vec::new(),
+        started_at: now_secs(),
+    };
+    crate::exec::bridge(app_handle, attached, info, cols, rows).await
+}
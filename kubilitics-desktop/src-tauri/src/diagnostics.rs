@@ -0,0 +1,379 @@
+// One-shot network diagnostics for support requests: are the sidecar ports answering, does each
+// context's API server complete a TLS handshake, how long does it take to reach, and does a
+// full-size packet make it there unfragmented (VPNs with a lower MTU silently drop those, which
+// shows up as TLS hangs after the connection is made).
+//
+// The report holds no credentials — server URLs and error text only — so it can be attached as is.
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::Serialize;
+use tauri::{command, AppHandle, Url};
+
+use crate::backend_ports::{AI_BACKEND_PORT, BACKEND_PORT};
+
+const PORT_CHECK_TIMEOUT_SECS: u64 = 3;
+const TLS_CHECK_TIMEOUT_SECS: u64 = 10;
+/// ICMP payload that fills a 1500-byte Ethernet frame (20 IP + 8 ICMP header bytes).
+const FULL_MTU_PAYLOAD: u32 = 1472;
+const SMALL_PAYLOAD: u32 = 56;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortCheck {
+    pub name: String,
+    pub port: u16,
+    /// Loopback address checked (127.0.0.1 or ::1, whichever answers).
+    pub address: String,
+    /// Something accepts TCP connections on the port.
+    pub listening: bool,
+    /// `/health` answered with a success status.
+    pub healthy: bool,
+    pub duration_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TlsCheck {
+    pub ok: bool,
+    /// HTTP status of `/version`; any status means the handshake succeeded.
+    pub http_status: Option<u16>,
+    pub duration_ms: Option<f64>,
+    /// True when the kubeconfig skips certificate verification.
+    pub insecure_skip_verify: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MtuCheck {
+    /// False when even small pings get no answer (ICMP filtered), so nothing can be concluded.
+    pub conclusive: bool,
+    pub small_packet_ok: bool,
+    pub full_size_packet_ok: bool,
+    pub payload_bytes: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClusterCheck {
+    pub context: String,
+    pub server_url: Option<String>,
+    pub via_socks_proxy: bool,
+    /// TCP connect time to the API server (tunnel setup time through a SOCKS proxy).
+    pub connect_ms: Option<f64>,
+    pub tls: Option<TlsCheck>,
+    /// Skipped when the API server is only reached through a SOCKS proxy.
+    pub mtu: Option<MtuCheck>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkDiagnosticsReport {
+    pub generated_at: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub network: crate::network::NetworkStatus,
+    pub proxy_mode: crate::proxy::ProxyMode,
+    pub low_bandwidth: bool,
+    pub ports: Vec<PortCheck>,
+    pub clusters: Vec<ClusterCheck>,
+    /// Plain-language problems found, most important first.
+    pub findings: Vec<String>,
+    /// Warnings, errors and restarts leading up to the report, oldest first.
+    pub recent_errors: Vec<crate::breadcrumbs::Breadcrumb>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// reqwest's top-level message ("error sending request") hides the TLS cause; append the chain.
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+async fn check_port(name: &str, port: u16) -> PortCheck {
+    let timeout = Duration::from_secs(PORT_CHECK_TIMEOUT_SECS);
+    let address = crate::loopback::socket_addr(port).await;
+    let start = Instant::now();
+    let connected = tokio::time::timeout(timeout, tokio::net::TcpStream::connect(address)).await;
+    let (listening, duration_ms, error) = match connected {
+        Ok(Ok(_)) => (true, Some(elapsed_ms(start)), None),
+        Ok(Err(e)) => (false, None, Some(e.to_string())),
+        Err(_) => (false, None, Some("Timed out".to_string())),
+    };
+
+    let healthy = listening
+        && match reqwest::Client::builder().no_proxy().timeout(timeout).build() {
+            Ok(client) => client
+                .get(format!("http://{}/health", address))
+                .send()
+                .await
+                .is_ok_and(|r| r.status().is_success()),
+            Err(_) => false,
+        };
+
+    PortCheck {
+        name: name.to_string(),
+        port,
+        address: address.ip().to_string(),
+        listening,
+        healthy,
+        duration_ms,
+        error,
+    }
+}
+
+/// TLS handshake against the API server, verifying with the kubeconfig's CA like kubectl does.
+async fn check_tls(
+    app: &AppHandle,
+    server_url: &str,
+    cluster: &serde_json::Value,
+    socks_proxy: Option<&Url>,
+) -> TlsCheck {
+    let insecure_skip_verify = cluster
+        .get("insecure-skip-tls-verify")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let fail = |error: String| TlsCheck {
+        ok: false,
+        http_status: None,
+        duration_ms: None,
+        insecure_skip_verify,
+        error: Some(error),
+    };
+
+    let ca_pem = if insecure_skip_verify {
+        None
+    } else if let Some(data) = cluster.get("certificate-authority-data").and_then(|v| v.as_str()) {
+        match general_purpose::STANDARD.decode(data.trim()) {
+            Ok(pem) => Some(pem),
+            Err(_) => return fail("certificate-authority-data is not valid base64".to_string()),
+        }
+    } else if let Some(path) = cluster.get("certificate-authority").and_then(|v| v.as_str()) {
+        match tokio::fs::read(path).await {
+            Ok(pem) => Some(pem),
+            Err(e) => return fail(format!("Failed to read certificate-authority file: {}", e)),
+        }
+    } else {
+        None
+    };
+
+    // A publicly trusted server reached directly needs nothing of its own: use the shared client
+    let client = if socks_proxy.is_none() && !insecure_skip_verify && ca_pem.is_none() {
+        crate::http_client::shared(app).await
+    } else {
+        let mut builder = match socks_proxy {
+            Some(proxy) => match reqwest::Proxy::all(proxy.as_str()) {
+                Ok(proxy) => reqwest::Client::builder().proxy(proxy),
+                Err(e) => return fail(format!("Invalid SOCKS proxy: {}", e)),
+            },
+            None => crate::proxy::client_builder().await,
+        };
+        if insecure_skip_verify {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        if let Some(pem) = ca_pem {
+            match reqwest::Certificate::from_pem(&pem) {
+                Ok(cert) => builder = builder.add_root_certificate(cert),
+                Err(e) => return fail(format!("Invalid cluster CA certificate: {}", e)),
+            }
+        }
+        builder.build().map_err(|e| format!("Failed to create HTTP client: {}", e))
+    };
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => return fail(e),
+    };
+    let url = format!("{}/version", server_url.trim_end_matches('/'));
+    let start = Instant::now();
+    match client
+        .get(&url)
+        .timeout(Duration::from_secs(TLS_CHECK_TIMEOUT_SECS))
+        .send()
+        .await
+    {
+        Ok(response) => TlsCheck {
+            ok: true,
+            http_status: Some(response.status().as_u16()),
+            duration_ms: Some(elapsed_ms(start)),
+            insecure_skip_verify,
+            error: None,
+        },
+        Err(e) => fail(error_chain(&e)),
+    }
+}
+
+/// One ping with the don't-fragment bit set. `false` on any failure, including a missing ping.
+async fn ping_unfragmented(host: &str, payload: u32) -> bool {
+    let payload = payload.to_string();
+    let mut cmd = tokio::process::Command::new("ping");
+    #[cfg(target_os = "linux")]
+    cmd.args(["-c", "1", "-W", "2", "-M", "do", "-s", &payload, host]);
+    #[cfg(target_os = "macos")]
+    cmd.args(["-c", "1", "-t", "2", "-D", "-s", &payload, host]);
+    #[cfg(target_os = "windows")]
+    {
+        // Don't flash a console window
+        cmd.creation_flags(0x0800_0000);
+        cmd.args(["-n", "1", "-w", "2000", "-f", "-l", &payload, host]);
+    }
+    cmd.kill_on_drop(true);
+    matches!(
+        tokio::time::timeout(Duration::from_secs(5), cmd.output()).await,
+        Ok(Ok(output)) if output.status.success()
+    )
+}
+
+async fn check_mtu(host: &str) -> MtuCheck {
+    let (small_packet_ok, full_size_packet_ok) = tokio::join!(
+        ping_unfragmented(host, SMALL_PAYLOAD),
+        ping_unfragmented(host, FULL_MTU_PAYLOAD)
+    );
+    MtuCheck {
+        conclusive: small_packet_ok,
+        small_packet_ok,
+        full_size_packet_ok,
+        payload_bytes: FULL_MTU_PAYLOAD,
+    }
+}
+
+async fn check_cluster(app: &AppHandle, context: String) -> ClusterCheck {
+    let mut check = ClusterCheck {
+        context,
+        server_url: None,
+        via_socks_proxy: false,
+        connect_ms: None,
+        tls: None,
+        mtu: None,
+        error: None,
+    };
+
+    let cluster = match crate::commands::get_context_cluster(&check.context).await {
+        Ok(cluster) => cluster,
+        Err(e) => {
+            check.error = Some(e);
+            return check;
+        }
+    };
+    let Some(server_url) = cluster.get("server").and_then(|s| s.as_str()).map(String::from) else {
+        check.error = Some("Cluster has no server in kubeconfig".to_string());
+        return check;
+    };
+    let host = Url::parse(&server_url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_start_matches('[').trim_end_matches(']').to_string()));
+
+    let socks_proxy = crate::socks::context_proxy(&check.context).await;
+    check.via_socks_proxy = socks_proxy.is_some();
+
+    let (connect_ms, tls, mtu) = tokio::join!(
+        crate::latency::probe_api_server(&check.context, &server_url),
+        check_tls(app, &server_url, &cluster, socks_proxy.as_ref()),
+        async {
+            match (&host, &socks_proxy) {
+                (Some(host), None) => Some(check_mtu(host).await),
+                _ => None,
+            }
+        }
+    );
+    check.connect_ms = connect_ms;
+    check.tls = Some(tls);
+    check.mtu = mtu;
+    check.server_url = Some(server_url);
+    check
+}
+
+fn findings(ports: &[PortCheck], clusters: &[ClusterCheck], network: &crate::network::NetworkStatus) -> Vec<String> {
+    let mut findings = Vec::new();
+    if !network.online {
+        findings.push("No routable network address is configured; the machine looks offline.".to_string());
+    }
+    for port in ports {
+        if !port.listening {
+            findings.push(format!("Nothing is listening on port {} ({}).", port.port, port.name));
+        } else if !port.healthy {
+            findings.push(format!(
+                "Port {} accepts connections but /health doesn't answer; another program may be using the {} port.",
+                port.port, port.name
+            ));
+        }
+    }
+    for cluster in clusters {
+        if let Some(error) = &cluster.error {
+            findings.push(format!("{}: {}", cluster.context, error));
+            continue;
+        }
+        if cluster.connect_ms.is_none() {
+            findings.push(format!("{}: the API server is unreachable.", cluster.context));
+        } else if let Some(tls) = cluster.tls.as_ref().filter(|t| !t.ok) {
+            findings.push(format!(
+                "{}: the API server accepts connections but the TLS handshake failed: {}",
+                cluster.context,
+                tls.error.as_deref().unwrap_or("unknown error")
+            ));
+        }
+        if let Some(mtu) = cluster.mtu.as_ref().filter(|m| m.conclusive && !m.full_size_packet_ok) {
+            findings.push(format!(
+                "{}: full-size packets ({} bytes) don't reach the API server unfragmented. A VPN with a lower MTU can make large responses hang.",
+                cluster.context, mtu.payload_bytes
+            ));
+        }
+    }
+    findings
+}
+
+/// Run the network checks — sidecar ports, and TLS, latency and MTU for each context (`contexts`,
+/// or every kubeconfig context) — and return a report to attach to support requests.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn run_network_diagnostics(
+    app_handle: AppHandle,
+    contexts: Option<Vec<String>>,
+) -> Result<NetworkDiagnosticsReport, String> {
+    let contexts = match contexts {
+        Some(contexts) => contexts,
+        None => crate::commands::kubeconfig_info(None)
+            .await
+            .map(|info| info.contexts.into_iter().map(|c| c.name).collect())
+            .unwrap_or_default(),
+    };
+
+    let (backend, ai, clusters) = tokio::join!(
+        check_port("backend", BACKEND_PORT),
+        check_port("AI backend", AI_BACKEND_PORT),
+        futures::future::join_all(contexts.into_iter().map(|context| check_cluster(&app_handle, context)))
+    );
+    let ports = vec![backend, ai];
+
+    let network = crate::network::current_network_status().await;
+    let proxy_mode = crate::proxy::load_proxy_settings().await.unwrap_or_default().mode;
+    let findings = findings(&ports, &clusters, &network);
+
+    Ok(NetworkDiagnosticsReport {
+        generated_at: now_secs(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        network,
+        proxy_mode,
+        low_bandwidth: crate::network::is_low_bandwidth(),
+        ports,
+        clusters,
+        findings,
+        recent_errors: crate::breadcrumbs::snapshot(),
+    })
+}
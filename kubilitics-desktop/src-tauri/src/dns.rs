@@ -0,0 +1,256 @@
+// DNS diagnostics for a context's API server — the usual cause of "works in kubectl, fails in the
+// app": the name only resolves through a VPN's scoped/split DNS, which a resolver that reads
+// resolv.conf directly (such as Go's pure-Go resolver) never asks.
+//
+// The hostname is resolved through the system resolver (getaddrinfo, what kubectl uses on macOS
+// and Windows) and through each nameserver individually, and the answers are compared.
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use hickory_resolver::TokioAsyncResolver;
+use serde::Serialize;
+use tauri::{command, Url};
+
+const LOOKUP_TIMEOUT_SECS: u64 = 3;
+/// Public baseline to tell an internal-only name from a broken resolver.
+const PUBLIC_RESOLVER: &str = "1.1.1.1";
+const SLOW_LOOKUP_MS: f64 = 1000.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DnsLookup {
+    /// "system", or the nameserver address.
+    pub resolver: String,
+    pub addresses: Vec<String>,
+    pub duration_ms: f64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DnsDiagnostics {
+    pub context: String,
+    pub server_url: String,
+    pub hostname: String,
+    pub lookups: Vec<DnsLookup>,
+    /// macOS per-domain resolvers (/etc/resolver/<domain>) that cover the hostname.
+    pub scoped_resolvers: Vec<String>,
+    /// Plain-language conclusions, most important first.
+    pub findings: Vec<String>,
+}
+
+fn is_private(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(a) => {
+            // 100.64.0.0/10 (CGNAT) is common for VPN-internal ranges, e.g. Tailscale
+            let cgnat = a.octets()[0] == 100 && (a.octets()[1] & 0xc0) == 64;
+            a.is_private() || a.is_loopback() || a.is_link_local() || cgnat
+        }
+        // Unique local (fc00::/7) and link-local
+        IpAddr::V6(a) => {
+            a.is_loopback() || (a.segments()[0] & 0xfe00) == 0xfc00 || (a.segments()[0] & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+async fn system_lookup(hostname: &str, port: u16) -> DnsLookup {
+    let start = Instant::now();
+    let result = tokio::time::timeout(
+        Duration::from_secs(LOOKUP_TIMEOUT_SECS),
+        tokio::net::lookup_host((hostname.to_string(), port)),
+    )
+    .await;
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let (addresses, error) = match result {
+        Ok(Ok(addrs)) => {
+            let mut ips: Vec<String> = addrs.map(|a: SocketAddr| a.ip().to_string()).collect();
+            ips.sort();
+            ips.dedup();
+            (ips, None)
+        }
+        Ok(Err(e)) => (Vec::new(), Some(e.to_string())),
+        Err(_) => (Vec::new(), Some("Timed out".to_string())),
+    };
+    DnsLookup {
+        resolver: "system".to_string(),
+        addresses,
+        duration_ms,
+        error,
+    }
+}
+
+async fn nameserver_lookup(hostname: &str, nameserver: IpAddr) -> DnsLookup {
+    let config = ResolverConfig::from_parts(
+        None,
+        Vec::new(),
+        NameServerConfigGroup::from_ips_clear(&[nameserver], 53, true),
+    );
+    let mut opts = ResolverOpts::default();
+    opts.timeout = Duration::from_secs(LOOKUP_TIMEOUT_SECS);
+    opts.attempts = 1;
+    opts.cache_size = 0;
+    let resolver = TokioAsyncResolver::tokio(config, opts);
+
+    let start = Instant::now();
+    let result = resolver.lookup_ip(hostname).await;
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let (addresses, error) = match result {
+        Ok(lookup) => {
+            let mut ips: Vec<String> = lookup.iter().map(|ip| ip.to_string()).collect();
+            ips.sort();
+            ips.dedup();
+            (ips, None)
+        }
+        Err(e) => (Vec::new(), Some(e.to_string())),
+    };
+    DnsLookup {
+        resolver: nameserver.to_string(),
+        addresses,
+        duration_ms,
+        error,
+    }
+}
+
+/// Nameservers from the system resolver configuration (resolv.conf / the registry).
+fn system_nameservers() -> Vec<IpAddr> {
+    let mut servers: Vec<IpAddr> = hickory_resolver::system_conf::read_system_conf()
+        .map(|(config, _)| config.name_servers().iter().map(|ns| ns.socket_addr.ip()).collect())
+        .unwrap_or_default();
+    servers.sort();
+    servers.dedup();
+    servers
+}
+
+/// macOS VPN clients register split DNS as /etc/resolver/<domain> files. getaddrinfo honors them;
+/// anything reading resolv.conf directly does not.
+fn scoped_resolvers_for(hostname: &str) -> Vec<String> {
+    if !cfg!(target_os = "macos") {
+        return Vec::new();
+    }
+    let Ok(entries) = std::fs::read_dir("/etc/resolver") else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|domain| hostname == domain || hostname.ends_with(&format!(".{}", domain)))
+        .collect()
+}
+
+fn findings(hostname: &str, lookups: &[DnsLookup], public: Option<&DnsLookup>, scoped: &[String]) -> Vec<String> {
+    let mut findings = Vec::new();
+    let Some(system) = lookups.iter().find(|l| l.resolver == "system") else {
+        return findings;
+    };
+    let nameservers: Vec<&DnsLookup> = lookups.iter().filter(|l| l.resolver != "system").collect();
+    let any_nameserver_ok = nameservers.iter().any(|l| l.error.is_none());
+
+    if system.error.is_some() {
+        if any_nameserver_ok {
+            findings.push(format!(
+                "The system resolver can't resolve {}, but a nameserver queried directly can. Check the DNS settings your VPN or network pushes.",
+                hostname
+            ));
+        } else {
+            findings.push(format!(
+                "{} doesn't resolve anywhere. Check the server address in the kubeconfig, or connect to the network/VPN that serves it.",
+                hostname
+            ));
+        }
+    } else if !nameservers.is_empty() && !any_nameserver_ok {
+        findings.push(format!(
+            "Only the system resolver can resolve {} (split DNS). Components that bypass it, such as Go binaries using the pure-Go resolver, can fail while kubectl works.",
+            hostname
+        ));
+    }
+
+    if let Some(public) = public.filter(|p| p.error.is_none()) {
+        let system_private = system.addresses.iter().filter_map(|a| a.parse().ok()).any(|a| is_private(&a));
+        if system.error.is_none() && system.addresses != public.addresses && system_private {
+            findings.push(format!(
+                "{} resolves to an internal address here but to {} publicly (split-horizon DNS). Without the internal DNS, clients reach the public address instead.",
+                hostname,
+                public.addresses.join(", ")
+            ));
+        }
+    }
+
+    for lookup in lookups.iter().filter(|l| l.error.is_none() && l.duration_ms > SLOW_LOOKUP_MS) {
+        findings.push(format!(
+            "Resolving through {} took {:.0} ms; slow DNS delays every new connection.",
+            lookup.resolver, lookup.duration_ms
+        ));
+    }
+
+    if !scoped.is_empty() {
+        findings.push(format!(
+            "Scoped resolvers in /etc/resolver cover this name ({}); they only apply to lookups through the system resolver.",
+            scoped.join(", ")
+        ));
+    }
+    findings
+}
+
+/// Resolve a context's API server hostname through the system resolver and each nameserver
+/// (`resolvers`, or the system's configured ones plus a public baseline) and explain differences.
+#[command]
+pub async fn diagnose_cluster_dns(context: String, resolvers: Option<Vec<String>>) -> Result<DnsDiagnostics, String> {
+    let server_url = crate::commands::get_context_server_url(&context).await?;
+    let url = Url::parse(&server_url).map_err(|e| format!("Invalid server URL: {}", e))?;
+    let hostname = url
+        .host_str()
+        .ok_or_else(|| "Server URL has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+
+    if hostname.parse::<IpAddr>().is_ok() {
+        return Ok(DnsDiagnostics {
+            context,
+            server_url,
+            hostname,
+            lookups: Vec::new(),
+            scoped_resolvers: Vec::new(),
+            findings: vec!["The API server is addressed by IP, so DNS isn't involved.".to_string()],
+        });
+    }
+
+    let public: IpAddr = PUBLIC_RESOLVER.parse().map_err(|_| "Invalid public resolver".to_string())?;
+    let nameservers: Vec<IpAddr> = match resolvers {
+        Some(resolvers) => resolvers
+            .iter()
+            .map(|r| r.trim().parse().map_err(|_| format!("Invalid resolver address: {}", r)))
+            .collect::<Result<_, _>>()?,
+        None => {
+            let mut servers = tokio::task::spawn_blocking(system_nameservers)
+                .await
+                .unwrap_or_default();
+            // The public baseline is an external lookup
+            if !servers.contains(&public) && !crate::airgap::is_air_gapped() {
+                servers.push(public);
+            }
+            servers
+        }
+    };
+
+    let port = url.port_or_known_default().unwrap_or(443);
+    let (system, mut lookups) = tokio::join!(
+        system_lookup(&hostname, port),
+        futures::future::join_all(nameservers.iter().map(|ns| nameserver_lookup(&hostname, *ns)))
+    );
+    lookups.insert(0, system);
+
+    let scoped_resolvers = scoped_resolvers_for(&hostname);
+    let public_lookup = lookups.iter().find(|l| l.resolver == PUBLIC_RESOLVER);
+    let findings = findings(&hostname, &lookups, public_lookup, &scoped_resolvers);
+
+    Ok(DnsDiagnostics {
+        context,
+        server_url,
+        hostname,
+        lookups,
+        scoped_resolvers,
+        findings,
+    })
+}
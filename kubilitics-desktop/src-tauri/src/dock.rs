@@ -0,0 +1,283 @@
+// macOS dock menu (right-click on the app icon): recent clusters plus the tray quick actions.
+// Many macOS users hide the menu bar tray, so the dock is the only always-visible entry point.
+//
+// The recent-cluster list itself is platform independent — the frontend records every cluster
+// switch through `record_recent_cluster` and the list is persisted in app data.
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+
+use crate::commands::get_app_data_dir;
+
+const MAX_RECENT_CLUSTERS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentCluster {
+    pub id: String,
+    pub name: String,
+}
+
+async fn get_recent_clusters_path() -> Result<PathBuf, String> {
+    let app_data_dir = PathBuf::from(get_app_data_dir().await?);
+    Ok(app_data_dir.join("recent_clusters.json"))
+}
+
+async fn load_recent_clusters() -> Result<Vec<RecentCluster>, String> {
+    let path = get_recent_clusters_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read recent clusters".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse recent clusters".to_string())
+}
+
+async fn save_recent_clusters(clusters: &[RecentCluster]) -> Result<(), String> {
+    let path = get_recent_clusters_path().await?;
+
+    let content = serde_json::to_string_pretty(clusters)
+        .map_err(|_| "Failed to serialize recent clusters".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write recent clusters".to_string())
+}
+
+#[command]
+pub async fn get_recent_clusters() -> Result<Vec<RecentCluster>, String> {
+    load_recent_clusters().await
+}
+
+/// Move a cluster to the front of the recent list. Called by the frontend on every cluster switch;
+/// the dock menu is rebuilt immediately so it never shows a stale list.
+#[command]
+pub async fn record_recent_cluster(app_handle: AppHandle, id: String, name: String) -> Result<(), String> {
+    let mut clusters = load_recent_clusters().await?;
+    clusters.retain(|c| c.id != id);
+    clusters.insert(0, RecentCluster { id, name });
+    clusters.truncate(MAX_RECENT_CLUSTERS);
+
+    save_recent_clusters(&clusters).await?;
+    refresh_dock_menu(&app_handle, clusters);
+
+    Ok(())
+}
+
+/// Install the dock menu and populate it from the persisted recent clusters.
+/// Must be called from the main thread (i.e. from the tauri `setup` hook).
+#[cfg(target_os = "macos")]
+pub fn setup_dock_menu(app: &AppHandle) {
+    macos::install(app);
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let clusters = load_recent_clusters().await.unwrap_or_default();
+        refresh_dock_menu(&handle, clusters);
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn refresh_dock_menu(app: &AppHandle, clusters: Vec<RecentCluster>) {
+    // AppKit objects may only be touched on the main thread.
+    let _ = app.run_on_main_thread(move || macos::rebuild(&clusters));
+}
+
+#[cfg(not(target_os = "macos"))]
+fn refresh_dock_menu(_app: &AppHandle, _clusters: Vec<RecentCluster>) {}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use std::cell::RefCell;
+    use std::sync::{Arc, OnceLock};
+
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, AnyObject, Imp, NSObject, NSObjectProtocol, Sel};
+    use objc2::{define_class, msg_send, sel, MainThreadMarker, MainThreadOnly};
+    use objc2_app_kit::{NSApplication, NSMenu, NSMenuItem};
+    use objc2_foundation::NSString;
+    use tauri::{AppHandle, Emitter, Manager};
+
+    use super::RecentCluster;
+    use crate::sidecar::BackendManager;
+
+    #[derive(Debug, Clone)]
+    enum DockAction {
+        Open,
+        ShowStatus,
+        OpenCluster(String),
+        RestartEngine,
+    }
+
+    static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+    thread_local! {
+        static DOCK_MENU: RefCell<Option<Retained<NSMenu>>> = const { RefCell::new(None) };
+        static DOCK_TARGET: RefCell<Option<Retained<DockMenuTarget>>> = const { RefCell::new(None) };
+        // Indexed by NSMenuItem tag.
+        static DOCK_ACTIONS: RefCell<Vec<DockAction>> = const { RefCell::new(Vec::new()) };
+    }
+
+    define_class!(
+        // SAFETY: NSObject has no subclassing requirements and DockMenuTarget does not implement Drop.
+        #[unsafe(super(NSObject))]
+        #[thread_kind = MainThreadOnly]
+        #[name = "KubiliticsDockMenuTarget"]
+        struct DockMenuTarget;
+
+        impl DockMenuTarget {
+            #[unsafe(method(dockItemClicked:))]
+            fn dock_item_clicked(&self, sender: &NSMenuItem) {
+                let action = DOCK_ACTIONS.with(|actions| {
+                    usize::try_from(sender.tag())
+                        .ok()
+                        .and_then(|i| actions.borrow().get(i).cloned())
+                });
+                if let (Some(action), Some(app)) = (action, APP_HANDLE.get()) {
+                    handle_dock_action(app, action);
+                }
+            }
+        }
+
+        unsafe impl NSObjectProtocol for DockMenuTarget {}
+    );
+
+    impl DockMenuTarget {
+        fn new(mtm: MainThreadMarker) -> Retained<Self> {
+            let this = Self::alloc(mtm).set_ivars(());
+            unsafe { msg_send![super(this), init] }
+        }
+    }
+
+    /// `-[NSApplicationDelegate applicationDockMenu:]`. Tauri has no dock menu API, so this is
+    /// added to tao's application delegate class at runtime.
+    unsafe extern "C-unwind" fn application_dock_menu(
+        _this: &AnyObject,
+        _cmd: Sel,
+        _sender: &AnyObject,
+    ) -> *mut NSMenu {
+        DOCK_MENU.with(|menu| {
+            menu.borrow()
+                .as_ref()
+                .map_or(std::ptr::null_mut(), |m| Retained::as_ptr(m) as *mut NSMenu)
+        })
+    }
+
+    pub fn install(app: &AppHandle) {
+        let Some(mtm) = MainThreadMarker::new() else {
+            tracing::error!("Dock menu must be installed from the main thread");
+            return;
+        };
+        let _ = APP_HANDLE.set(app.clone());
+
+        let ns_app = NSApplication::sharedApplication(mtm);
+        let Some(delegate) = ns_app.delegate() else {
+            tracing::warn!("No application delegate, dock menu unavailable");
+            return;
+        };
+
+        unsafe {
+            let class: &AnyClass = (*Retained::as_ptr(&delegate).cast::<AnyObject>()).class();
+            let imp: Imp = std::mem::transmute(
+                application_dock_menu
+                    as unsafe extern "C-unwind" fn(&AnyObject, Sel, &AnyObject) -> *mut NSMenu,
+            );
+            // Returns NO if the delegate already implements the method — nothing to do then.
+            objc2::ffi::class_addMethod(
+                class as *const AnyClass as *mut AnyClass,
+                sel!(applicationDockMenu:),
+                imp,
+                c"@@:@".as_ptr(),
+            );
+        }
+    }
+
+    pub fn rebuild(clusters: &[RecentCluster]) {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+
+        let target = DOCK_TARGET.with(|t| {
+            t.borrow_mut()
+                .get_or_insert_with(|| DockMenuTarget::new(mtm))
+                .clone()
+        });
+        let target_obj: &AnyObject = &target;
+
+        let menu = NSMenu::new(mtm);
+        let mut actions = Vec::new();
+        let mut add_item = |title: &str, action: Option<DockAction>| {
+            let selector = action.as_ref().map(|_| sel!(dockItemClicked:));
+            let item = unsafe {
+                NSMenuItem::initWithTitle_action_keyEquivalent(
+                    NSMenuItem::alloc(mtm),
+                    &NSString::from_str(title),
+                    selector,
+                    &NSString::from_str(""),
+                )
+            };
+            // Items without an action (section headers) are auto-disabled by NSMenu.
+            if let Some(action) = action {
+                unsafe { item.setTarget(Some(target_obj)) };
+                item.setTag(actions.len() as isize);
+                actions.push(action);
+            }
+            menu.addItem(&item);
+        };
+
+        if !clusters.is_empty() {
+            add_item("Recent Clusters", None);
+            for cluster in clusters {
+                add_item(&cluster.name, Some(DockAction::OpenCluster(cluster.id.clone())));
+            }
+            menu.addItem(&NSMenuItem::separatorItem(mtm));
+        }
+        add_item("Show Cluster Status", Some(DockAction::ShowStatus));
+        add_item("Restart Engine", Some(DockAction::RestartEngine));
+        menu.addItem(&NSMenuItem::separatorItem(mtm));
+        add_item("Open Kubilitics", Some(DockAction::Open));
+
+        DOCK_ACTIONS.with(|a| *a.borrow_mut() = actions);
+        DOCK_MENU.with(|m| *m.borrow_mut() = Some(menu));
+    }
+
+    fn show_main_window(app: &AppHandle) {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+
+    fn handle_dock_action(app: &AppHandle, action: DockAction) {
+        match action {
+            DockAction::Open => show_main_window(app),
+            DockAction::ShowStatus => {
+                // Same event as the tray's "Show Cluster Status" so the frontend handles both.
+                show_main_window(app);
+                let _ = app.emit("tray-show-status", ());
+            }
+            DockAction::OpenCluster(id) => {
+                show_main_window(app);
+                let _ = app.emit("dock-open-cluster", id);
+            }
+            DockAction::RestartEngine => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let Some(mgr) = app.try_state::<Arc<BackendManager>>() else {
+                        return;
+                    };
+                    if let Err(e) = mgr.restart().await {
+                        tracing::error!("Failed to restart backend from dock menu: {:#}", e);
+                        let _ = app.emit("backend-status", serde_json::json!({
+                            "status": "error",
+                            "message": format!("Backend engine failed to restart: {:#}", e)
+                        }));
+                    }
+                });
+            }
+        }
+    }
+}
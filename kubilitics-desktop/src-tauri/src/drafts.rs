@@ -0,0 +1,255 @@
+// Local drafts of resource YAML. Opening a resource for editing saves a copy under drafts/ (one JSON
+// file per draft, holding the YAML as fetched and as edited), so edits survive a restart or a lost
+// connection and can be reviewed as a diff against the live object before anything is changed.
+// Applying goes through server-side apply like `apply_manifest`.
+//
+// The copy leaves out status and server-managed metadata. The live object's resourceVersion at
+// fetch time is kept, so the diff can say when someone else changed the resource in the meantime.
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use kube::api::DynamicObject;
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::apply::{dynamic_api, normalized_yaml, yaml_diff, ApplyResult};
+use crate::commands::get_app_data_dir;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftInfo {
+    pub id: String,
+    pub context: String,
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    /// resourceVersion of the live object when the draft was taken.
+    pub resource_version: Option<String>,
+    /// Edited since it was taken.
+    pub dirty: bool,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Draft {
+    #[serde(flatten)]
+    pub info: DraftInfo,
+    /// YAML as fetched.
+    pub original: String,
+    /// YAML as edited.
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftDiff {
+    /// Unified diff, live → draft. Empty when the draft matches the live object.
+    pub diff: String,
+    /// The live object changed since the draft was taken.
+    pub live_changed: bool,
+    /// The live object no longer exists; applying recreates it.
+    pub live_deleted: bool,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_drafts_dir() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    let dir = PathBuf::from(app_data_dir).join("drafts");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create drafts directory: {}", e))?;
+    Ok(dir)
+}
+
+async fn draft_path(id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid draft id: {}", id));
+    }
+    Ok(get_drafts_dir().await?.join(format!("{}.json", id)))
+}
+
+async fn load_draft(id: &str) -> Result<Draft, String> {
+    let path = draft_path(id).await?;
+    let content = std::fs::read_to_string(&path).map_err(|_| format!("Draft not found: {}", id))?;
+    serde_json::from_str(&content).map_err(|_| "Failed to parse draft".to_string())
+}
+
+async fn save_draft_file(draft: &Draft) -> Result<(), String> {
+    let path = draft_path(&draft.info.id).await?;
+    let content =
+        serde_json::to_string_pretty(draft).map_err(|_| "Failed to serialize draft".to_string())?;
+    std::fs::write(&path, content).map_err(|_| "Failed to write draft".to_string())
+}
+
+async fn load_drafts() -> Result<Vec<Draft>, String> {
+    let dir = get_drafts_dir().await?;
+    let entries = std::fs::read_dir(&dir).map_err(|e| format!("Failed to read drafts: {}", e))?;
+    let mut drafts: Vec<Draft> = entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .filter_map(|c| serde_json::from_str(&c).ok())
+        .collect();
+    drafts.sort_by(|a, b| b.info.updated_at.cmp(&a.info.updated_at));
+    Ok(drafts)
+}
+
+async fn fetch_live(
+    context: &str,
+    api_version: &str,
+    kind: &str,
+    namespace: Option<&str>,
+    name: &str,
+) -> Result<Option<DynamicObject>, String> {
+    let client = crate::k8s::client_for(context).await?;
+    let api = dynamic_api(&client, api_version, kind, namespace).await?;
+    api.get_opt(name)
+        .await
+        .map_err(|e| format!("Failed to get {} {}: {}", kind, name, e))
+}
+
+/// Start editing a resource: returns its existing draft, or fetches it and saves a new one.
+/// `refresh` replaces an existing draft (and its edits) with the live object.
+#[command]
+pub async fn get_resource_yaml(
+    context: String,
+    api_version: String,
+    kind: String,
+    namespace: Option<String>,
+    name: String,
+    refresh: Option<bool>,
+) -> Result<Draft, String> {
+    let existing = load_drafts().await?.into_iter().find(|d| {
+        d.info.context == context
+            && d.info.api_version == api_version
+            && d.info.kind == kind
+            && d.info.namespace == namespace
+            && d.info.name == name
+    });
+    if let Some(draft) = &existing {
+        if !refresh.unwrap_or(false) {
+            return Ok(draft.clone());
+        }
+    }
+
+    let live = fetch_live(&context, &api_version, &kind, namespace.as_deref(), &name)
+        .await?
+        .ok_or_else(|| format!("{} {} not found", kind, name))?;
+    let yaml = normalized_yaml(&live);
+    let now = now_secs();
+    let draft = Draft {
+        info: DraftInfo {
+            id: existing
+                .map(|d| d.info.id)
+                .unwrap_or_else(|| format!("{:016x}", rand::random::<u64>())),
+            context,
+            api_version,
+            kind,
+            namespace: live.metadata.namespace.clone(),
+            name,
+            resource_version: live.metadata.resource_version.clone(),
+            dirty: false,
+            created_at: now,
+            updated_at: now,
+        },
+        original: yaml.clone(),
+        content: yaml,
+    };
+    save_draft_file(&draft).await?;
+    Ok(draft)
+}
+
+#[command]
+pub async fn list_drafts() -> Result<Vec<DraftInfo>, String> {
+    Ok(load_drafts().await?.into_iter().map(|d| d.info).collect())
+}
+
+#[command]
+pub async fn get_draft(id: String) -> Result<Draft, String> {
+    load_draft(&id).await
+}
+
+/// Save edited YAML. It must stay a single object of the same kind and name.
+#[command]
+pub async fn save_draft(id: String, content: String) -> Result<DraftInfo, String> {
+    let mut draft = load_draft(&id).await?;
+    let object: serde_json::Value =
+        serde_yaml::from_str(&content).map_err(|e| format!("Invalid YAML: {}", e))?;
+    let text = |path: &str| object.pointer(path).and_then(serde_json::Value::as_str);
+    if text("/kind") != Some(draft.info.kind.as_str())
+        || text("/metadata/name") != Some(draft.info.name.as_str())
+    {
+        return Err(format!(
+            "The draft must stay {} {}; use apply_manifest for other objects",
+            draft.info.kind, draft.info.name
+        ));
+    }
+
+    draft.info.dirty = content != draft.original;
+    draft.info.updated_at = now_secs();
+    draft.content = content;
+    save_draft_file(&draft).await?;
+    Ok(draft.info)
+}
+
+/// Diff of the draft against the live object as it is now.
+#[command]
+pub async fn diff_draft(id: String) -> Result<DraftDiff, String> {
+    let draft = load_draft(&id).await?;
+    let info = &draft.info;
+    let live = fetch_live(
+        &info.context,
+        &info.api_version,
+        &info.kind,
+        info.namespace.as_deref(),
+        &info.name,
+    )
+    .await?;
+
+    let edited: DynamicObject =
+        serde_yaml::from_str(&draft.content).map_err(|e| format!("Invalid draft YAML: {}", e))?;
+    let before = live.as_ref().map(normalized_yaml).unwrap_or_default();
+    Ok(DraftDiff {
+        diff: yaml_diff(&before, &normalized_yaml(&edited), "live", "draft"),
+        live_changed: live
+            .as_ref()
+            .is_some_and(|l| l.metadata.resource_version != info.resource_version),
+        live_deleted: live.is_none(),
+    })
+}
+
+/// Apply the draft with server-side apply. A real apply retires the draft; a dry run keeps it.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn apply_draft(
+    id: String,
+    dry_run: bool,
+    field_manager: Option<String>,
+) -> Result<ApplyResult, String> {
+    let draft = load_draft(&id).await?;
+    let result = crate::apply::apply_manifest(
+        draft.info.context.clone(),
+        draft.content.clone(),
+        dry_run,
+        field_manager,
+    )
+    .await?;
+    if !dry_run && result.failed == 0 {
+        discard_draft(id).await?;
+    }
+    Ok(result)
+}
+
+#[command]
+pub async fn discard_draft(id: String) -> Result<(), String> {
+    let path = draft_path(&id).await?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to delete draft: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,231 @@
+// Interactive shells into containers, for the embedded terminal. The shell holds the exec stream
+// (TTY, so line editing and full-screen programs work) and bridges it over Tauri: output goes out as
+// `exec-output` ({ id, data } with data base64, since a TTY emits arbitrary bytes), keystrokes come
+// back through `write_exec_stdin`, window size through `resize_exec`. `exec-exit` reports the end.
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose, Engine as _};
+use futures::channel::mpsc::Sender;
+use futures::SinkExt;
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{AttachParams, AttachedProcess, TerminalSize};
+use kube::Api;
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::{mpsc, Mutex};
+
+const READ_BUFFER_BYTES: usize = 16 * 1024;
+/// bash when the image has it, sh otherwise.
+const DEFAULT_SHELL: [&str; 3] = ["/bin/sh", "-c", "command -v bash >/dev/null 2>&1 && exec bash || exec sh"];
+
+struct ExecSession {
+    info: ExecSessionInfo,
+    stdin: mpsc::UnboundedSender<Vec<u8>>,
+    resize: Sender<TerminalSize>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static SESSIONS: Mutex<BTreeMap<String, ExecSession>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecSessionInfo {
+    pub id: String,
+    pub context: String,
+    pub namespace: String,
+    pub pod: String,
+    pub container: Option<String>,
+    pub command: Vec<String>,
+    pub started_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExecOutput<'a> {
+    id: &'a str,
+    data: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExecExit {
+    id: String,
+    /// Exit code when the container reported one.
+    exit_code: Option<i32>,
+    message: Option<String>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Exit code from the exec status: Success is 0, NonZeroExitCode carries it in details.causes.
+fn exit_code(status: &k8s_openapi::apimachinery::pkg::apis::meta::v1::Status) -> Option<i32> {
+    if status.status.as_deref() == Some("Success") {
+        return Some(0);
+    }
+    status
+        .details
+        .as_ref()?
+        .causes
+        .as_ref()?
+        .iter()
+        .find(|c| c.reason.as_deref() == Some("ExitCode"))
+        .and_then(|c| c.message.as_deref())
+        .and_then(|m| m.parse().ok())
+}
+
+/// Open a shell (or `command`) in a container with a TTY of `cols`×`rows`.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn open_exec_session(
+    app_handle: AppHandle,
+    context: String,
+    namespace: String,
+    pod: String,
+    container: Option<String>,
+    command: Option<Vec<String>>,
+    cols: u16,
+    rows: u16,
+) -> Result<ExecSessionInfo, String> {
+    let command = command
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| DEFAULT_SHELL.iter().map(|s| s.to_string()).collect());
+
+    let client = crate::k8s::streaming_client_for(&context).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+    let mut params = AttachParams::interactive_tty();
+    if let Some(container) = &container {
+        params = params.container(container.clone());
+    }
+    let attached = pods
+        .exec(&pod, command.clone(), &params)
+        .await
+        .map_err(|e| format!("Failed to exec into {}: {}", pod, e))?;
+
+    let info = ExecSessionInfo {
+        id: format!("{:016x}", rand::random::<u64>()),
+        context,
+        namespace,
+        pod,
+        container,
+        command,
+        started_at: now_secs(),
+    };
+    bridge(app_handle, attached, info, cols, rows).await
+}
+
+/// Run a TTY session over the Tauri bridge: `exec-output` out, `write_exec_stdin`/`resize_exec`
+/// in, `exec-exit` at the end. Also used for attaching to debug containers.
+pub(crate) async fn bridge(
+    app_handle: AppHandle,
+    mut attached: AttachedProcess,
+    info: ExecSessionInfo,
+    cols: u16,
+    rows: u16,
+) -> Result<ExecSessionInfo, String> {
+    let mut stdout = attached.stdout().ok_or("Exec stream has no output")?;
+    let mut remote_stdin = attached.stdin().ok_or("Exec stream has no input")?;
+    let mut resize = attached.terminal_size().ok_or("Exec stream has no terminal")?;
+    let _ = resize.send(TerminalSize { width: cols, height: rows }).await;
+
+    let id = info.id.clone();
+    let (stdin, mut stdin_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+
+    let app = app_handle.clone();
+    let session_id = id.clone();
+    let mut sessions = SESSIONS.lock().await;
+    let task = tauri::async_runtime::spawn(async move {
+        let mut buffer = vec![0u8; READ_BUFFER_BYTES];
+        let mut message = None;
+        loop {
+            tokio::select! {
+                read = stdout.read(&mut buffer) => match read {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        let data = general_purpose::STANDARD.encode(&buffer[..n]);
+                        let _ = app.emit("exec-output", ExecOutput { id: &session_id, data });
+                    }
+                    Err(e) => {
+                        message = Some(format!("Exec stream failed: {}", e));
+                        break;
+                    }
+                },
+                input = stdin_rx.recv() => match input {
+                    Some(bytes) => {
+                        if let Err(e) = remote_stdin.write_all(&bytes).await {
+                            message = Some(format!("Failed to write to the container: {}", e));
+                            break;
+                        }
+                    }
+                    // Session closed from our side
+                    None => break,
+                },
+            }
+        }
+        drop(remote_stdin);
+
+        let status = match attached.take_status() {
+            Some(status) => status.await,
+            None => None,
+        };
+        let exit = ExecExit {
+            id: session_id.clone(),
+            exit_code: status.as_ref().and_then(exit_code),
+            message: message.or_else(|| status.and_then(|s| s.message).filter(|m| !m.is_empty())),
+        };
+        SESSIONS.lock().await.remove(&session_id);
+        let _ = app.emit("exec-exit", exit);
+    });
+    sessions.insert(id, ExecSession {
+        info: info.clone(),
+        stdin,
+        resize,
+        task,
+    });
+    Ok(info)
+}
+
+/// Keystrokes from the terminal, as typed (UTF-8).
+#[command]
+pub async fn write_exec_stdin(id: String, data: String) -> Result<(), String> {
+    let sessions = SESSIONS.lock().await;
+    let session = sessions.get(&id).ok_or_else(|| format!("Exec session not found: {}", id))?;
+    session
+        .stdin
+        .send(data.into_bytes())
+        .map_err(|_| "Exec session has ended".to_string())
+}
+
+#[command]
+pub async fn resize_exec(id: String, cols: u16, rows: u16) -> Result<(), String> {
+    let mut sessions = SESSIONS.lock().await;
+    let session = sessions.get_mut(&id).ok_or_else(|| format!("Exec session not found: {}", id))?;
+    session
+        .resize
+        .send(TerminalSize { width: cols, height: rows })
+        .await
+        .map_err(|_| "Exec session has ended".to_string())
+}
+
+/// Close a session. The remote process gets a hangup when its input closes.
+#[command]
+pub async fn close_exec_session(id: String) -> Result<(), String> {
+    let Some(session) = SESSIONS.lock().await.remove(&id) else {
+        return Ok(());
+    };
+    session.task.abort();
+    Ok(())
+}
+
+#[command]
+pub async fn list_exec_sessions() -> Result<Vec<ExecSessionInfo>, String> {
+    Ok(SESSIONS
+        .lock()
+        .await
+        .values()
+        .map(|s| s.info.clone())
+        .collect())
+}
@@ -0,0 +1,557 @@
+// Export subsystem: everything that manages the app-data exports directory written by
+// save_topology_export and the native renderers (history index, search, retention and cleanup).
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+use tokio::fs;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+use crate::commands::get_app_data_dir;
+
+pub mod bundle;
+pub mod clipboard;
+pub mod diagram;
+pub mod diff;
+pub mod drawio;
+pub mod inventory;
+pub mod pdf;
+pub mod png;
+pub mod schedule;
+pub mod stream;
+mod svg;
+pub mod templates;
+mod topology;
+pub mod upload;
+
+const EXPORT_CLEANUP_INTERVAL_SECS: u64 = 60 * 60;
+const RECENT_EXPORTS_LIMIT: usize = 10;
+const DEFAULT_SEARCH_PAGE_SIZE: usize = 25;
+
+/// Serializes read-modify-write cycles on the export index file.
+static EXPORT_INDEX_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// One entry in the export history index (`exports_index.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub filename: String,
+    pub path: String,
+    pub format: String,
+    pub cluster: Option<String>,
+    pub size_bytes: u64,
+    pub created_at: u64, // Unix timestamp
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExportSearchFilters {
+    pub format: Option<String>,
+    pub cluster: Option<String>,
+    pub created_after: Option<u64>,
+    pub created_before: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportSearchResult {
+    pub items: Vec<ExportRecord>,
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+/// Retention limits for the exports directory. Each limit is optional; `None` disables it.
+/// The newest exports are always kept first — a file is removed as soon as it falls outside any limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRetentionPolicy {
+    pub enabled: bool,
+    pub max_count: Option<usize>,
+    pub max_age_days: Option<u64>,
+    pub max_total_size_mb: Option<u64>,
+}
+
+impl Default for ExportRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_count: Some(200),
+            max_age_days: Some(90),
+            max_total_size_mb: Some(2048),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportCleanupReport {
+    pub removed_files: Vec<String>,
+    pub freed_bytes: u64,
+    pub remaining_files: usize,
+}
+
+pub async fn get_exports_dir() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("exports"))
+}
+
+async fn get_export_index_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("exports_index.json"))
+}
+
+fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Build index records from the files already on disk. Used once, when upgrading from
+/// versions that predate the index; cluster is unknown for those files.
+async fn scan_exports_dir(exports_dir: &Path) -> Vec<ExportRecord> {
+    let Ok(mut entries) = fs::read_dir(exports_dir).await else {
+        return Vec::new();
+    };
+
+    let mut records = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let format = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        records.push(ExportRecord {
+            filename: entry.file_name().to_string_lossy().to_string(),
+            path: path.to_string_lossy().to_string(),
+            format,
+            cluster: None,
+            size_bytes: metadata.len(),
+            created_at: unix_timestamp(metadata.modified().unwrap_or(UNIX_EPOCH)),
+        });
+    }
+    records
+}
+
+/// Load the index, dropping entries whose file no longer exists (deleted by retention or the user).
+/// Callers must hold EXPORT_INDEX_LOCK.
+async fn load_export_index() -> Result<Vec<ExportRecord>, String> {
+    let index_path = get_export_index_path().await?;
+
+    let records: Vec<ExportRecord> = if fs::try_exists(&index_path).await.unwrap_or(false) {
+        let content = fs::read_to_string(&index_path)
+            .await
+            .map_err(|_| "Failed to read export index".to_string())?;
+        serde_json::from_str(&content)
+            .map_err(|_| "Failed to parse export index".to_string())?
+    } else {
+        scan_exports_dir(&get_exports_dir().await?).await
+    };
+
+    let mut existing = Vec::with_capacity(records.len());
+    for record in records {
+        if fs::try_exists(&record.path).await.unwrap_or(false) {
+            existing.push(record);
+        }
+    }
+    Ok(existing)
+}
+
+async fn save_export_index(records: &[ExportRecord]) -> Result<(), String> {
+    let index_path = get_export_index_path().await?;
+
+    let content = serde_json::to_string_pretty(records)
+        .map_err(|_| "Failed to serialize export index".to_string())?;
+
+    fs::write(&index_path, content)
+        .await
+        .map_err(|_| "Failed to write export index".to_string())
+}
+
+/// Resolve `filename` inside the exports dir, creating the dir if needed.
+pub async fn export_file_path(filename: &str) -> Result<PathBuf, String> {
+    // Only a bare file name is accepted — never let the frontend write outside the exports dir
+    let file_name = Path::new(filename)
+        .file_name()
+        .filter(|name| *name == std::ffi::OsStr::new(filename))
+        .ok_or_else(|| "Invalid export filename".to_string())?;
+
+    let exports_dir = get_exports_dir().await?;
+    fs::create_dir_all(&exports_dir)
+        .await
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+
+    Ok(exports_dir.join(file_name))
+}
+
+/// Write an export into the exports directory and record it in the history index.
+/// Every export format goes through here so the index and retention see all of them.
+pub async fn write_export(
+    filename: &str,
+    data: &[u8],
+    format: &str,
+    cluster: Option<String>,
+) -> Result<PathBuf, String> {
+    let file_path = export_file_path(filename).await?;
+    fs::write(&file_path, data)
+        .await
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    record_export(&file_path, format, cluster).await?;
+    upload::spawn_auto_upload(file_path.clone());
+
+    Ok(file_path)
+}
+
+async fn get_dialog_dirs_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("export_dialog_dirs.json"))
+}
+
+/// Last directory chosen in the Save As dialog, keyed by export format.
+async fn load_dialog_dirs() -> HashMap<String, String> {
+    let Ok(path) = get_dialog_dirs_path().await else {
+        return HashMap::new();
+    };
+    fs::read_to_string(path)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+async fn remember_dialog_dir(format: &str, dir: &Path) -> Result<(), String> {
+    let mut dirs = load_dialog_dirs().await;
+    dirs.insert(format.to_lowercase(), dir.to_string_lossy().to_string());
+
+    let content = serde_json::to_string_pretty(&dirs)
+        .map_err(|_| "Failed to serialize export directories".to_string())?;
+    fs::write(get_dialog_dirs_path().await?, content)
+        .await
+        .map_err(|_| "Failed to write export directories".to_string())
+}
+
+/// Like `write_export`, but lets the user pick the destination in a native Save As dialog.
+/// The dialog opens in the directory last used for the same format (the exports dir the first time).
+/// Returns `None` when the user cancels.
+pub async fn write_export_via_dialog(
+    app_handle: &AppHandle,
+    filename: &str,
+    data: &[u8],
+    format: &str,
+    cluster: Option<String>,
+) -> Result<Option<PathBuf>, String> {
+    use tauri_plugin_dialog::DialogExt;
+    use tokio::sync::oneshot;
+
+    let default_dir = match load_dialog_dirs().await.remove(&format.to_lowercase()) {
+        Some(dir) if fs::metadata(&dir).await.is_ok_and(|m| m.is_dir()) => PathBuf::from(dir),
+        _ => get_exports_dir().await?,
+    };
+
+    // Same oneshot pattern as select_kubeconfig_file — never block the executor on the dialog.
+    let (tx, rx) = oneshot::channel::<Option<PathBuf>>();
+    let mut dialog = app_handle
+        .dialog()
+        .file()
+        .set_title("Save Export")
+        .set_directory(&default_dir)
+        .set_file_name(filename);
+    if !format.is_empty() {
+        dialog = dialog.add_filter(format.to_uppercase(), &[format]);
+    }
+    dialog.save_file(move |file_path| {
+        let _ = tx.send(file_path.and_then(|p| p.into_path().ok()));
+    });
+
+    let Some(file_path) = rx
+        .await
+        .map_err(|_| "Save dialog closed without a selection".to_string())?
+    else {
+        return Ok(None);
+    };
+
+    fs::write(&file_path, data)
+        .await
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    if let Some(parent) = file_path.parent() {
+        if let Err(e) = remember_dialog_dir(format, parent).await {
+            tracing::warn!(error = %e, "Failed to remember the export directory");
+        }
+    }
+    record_export(&file_path, format, cluster).await?;
+    upload::spawn_auto_upload(file_path.clone());
+
+    Ok(Some(file_path))
+}
+
+/// Add (or replace, when a file is overwritten) the index entry for a freshly written export.
+pub async fn record_export(path: &Path, format: &str, cluster: Option<String>) -> Result<ExportRecord, String> {
+    let _guard = EXPORT_INDEX_LOCK.lock().await;
+
+    let size_bytes = fs::metadata(path)
+        .await
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read export file metadata: {}", e))?;
+    let record = ExportRecord {
+        filename: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_string_lossy().to_string(),
+        format: format.to_lowercase(),
+        cluster,
+        size_bytes,
+        created_at: unix_timestamp(SystemTime::now()),
+    };
+
+    let mut records = load_export_index().await?;
+    records.retain(|r| r.path != record.path);
+    records.push(record.clone());
+    save_export_index(&records).await?;
+
+    Ok(record)
+}
+
+fn matches_search(record: &ExportRecord, query: &str, filters: &ExportSearchFilters) -> bool {
+    if !query.is_empty() {
+        let in_filename = record.filename.to_lowercase().contains(query);
+        let in_cluster = record
+            .cluster
+            .as_ref()
+            .is_some_and(|c| c.to_lowercase().contains(query));
+        if !in_filename && !in_cluster {
+            return false;
+        }
+    }
+    if let Some(format) = &filters.format {
+        if !record.format.eq_ignore_ascii_case(format) {
+            return false;
+        }
+    }
+    if let Some(cluster) = &filters.cluster {
+        if record.cluster.as_deref() != Some(cluster.as_str()) {
+            return false;
+        }
+    }
+    if filters.created_after.is_some_and(|t| record.created_at < t) {
+        return false;
+    }
+    if filters.created_before.is_some_and(|t| record.created_at > t) {
+        return false;
+    }
+    true
+}
+
+async fn get_retention_policy_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("export_retention.json"))
+}
+
+async fn load_retention_policy() -> Result<ExportRetentionPolicy, String> {
+    let path = get_retention_policy_path().await?;
+
+    if !fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(ExportRetentionPolicy::default());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| "Failed to read export retention policy".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse export retention policy".to_string())
+}
+
+async fn save_retention_policy(policy: &ExportRetentionPolicy) -> Result<(), String> {
+    let path = get_retention_policy_path().await?;
+
+    let content = serde_json::to_string_pretty(policy)
+        .map_err(|_| "Failed to serialize export retention policy".to_string())?;
+
+    fs::write(&path, content)
+        .await
+        .map_err(|_| "Failed to write export retention policy".to_string())
+}
+
+/// Apply the retention policy to the exports directory, newest files first.
+async fn enforce_retention(policy: &ExportRetentionPolicy) -> Result<ExportCleanupReport, String> {
+    let exports_dir = get_exports_dir().await?;
+    let mut report = ExportCleanupReport {
+        removed_files: Vec::new(),
+        freed_bytes: 0,
+        remaining_files: 0,
+    };
+
+    if !fs::try_exists(&exports_dir).await.unwrap_or(false) {
+        return Ok(report);
+    }
+
+    let mut entries = fs::read_dir(&exports_dir)
+        .await
+        .map_err(|e| format!("Failed to read exports directory: {}", e))?;
+    let mut files: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        files.push((entry.path(), modified, metadata.len()));
+    }
+
+    // Most recent first
+    files.sort_by_key(|f| std::cmp::Reverse(f.1));
+
+    let now = SystemTime::now();
+    let max_age = policy
+        .max_age_days
+        .map(|days| Duration::from_secs(days.saturating_mul(24 * 60 * 60)));
+    let max_total_bytes = policy
+        .max_total_size_mb
+        .map(|mb| mb.saturating_mul(1024 * 1024));
+    let mut kept_bytes: u64 = 0;
+
+    for (path, modified, size) in files {
+        let too_many = policy
+            .max_count
+            .is_some_and(|max| report.remaining_files >= max);
+        // A modification time in the future (clock skew, copied files) counts as brand new
+        let too_old = max_age.is_some_and(|max| {
+            now.duration_since(modified).unwrap_or(Duration::ZERO) > max
+        });
+        let too_large = max_total_bytes.is_some_and(|max| kept_bytes.saturating_add(size) > max);
+
+        if too_many || too_old || too_large {
+            match fs::remove_file(&path).await {
+                Ok(()) => {
+                    report.freed_bytes = report.freed_bytes.saturating_add(size);
+                    report.removed_files.push(path.to_string_lossy().to_string());
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, path = %path.display(), "Failed to remove expired export");
+                    report.remaining_files += 1;
+                    kept_bytes = kept_bytes.saturating_add(size);
+                }
+            }
+        } else {
+            report.remaining_files += 1;
+            kept_bytes = kept_bytes.saturating_add(size);
+        }
+    }
+
+    // Keep the index in step with what was removed
+    if !report.removed_files.is_empty() {
+        let _guard = EXPORT_INDEX_LOCK.lock().await;
+        let records = load_export_index().await?;
+        save_export_index(&records).await?;
+    }
+
+    Ok(report)
+}
+
+/// Periodically enforce the retention policy so the exports directory never grows unbounded.
+/// The policy is re-read on every tick so changes apply without a restart.
+pub fn start_export_cleanup_task() {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match load_retention_policy().await {
+                Ok(policy) if policy.enabled => {
+                    match enforce_retention(&policy).await {
+                        Ok(report) if !report.removed_files.is_empty() => {
+                            tracing::info!(
+                                removed = report.removed_files.len(),
+                                freed_bytes = report.freed_bytes,
+                                "Export cleanup removed expired exports"
+                            );
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::error!(error = %e, "Export cleanup failed"),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "Export cleanup skipped"),
+            }
+
+            sleep(Duration::from_secs(EXPORT_CLEANUP_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+#[command]
+pub async fn get_export_retention_policy() -> Result<ExportRetentionPolicy, String> {
+    load_retention_policy().await
+}
+
+#[command]
+pub async fn set_export_retention_policy(policy: ExportRetentionPolicy) -> Result<(), String> {
+    save_retention_policy(&policy).await
+}
+
+/// Enforce the current retention policy immediately, regardless of `enabled`.
+#[command]
+pub async fn cleanup_exports_now() -> Result<ExportCleanupReport, String> {
+    let policy = load_retention_policy().await?;
+    enforce_retention(&policy).await
+}
+
+/// Most recent export paths, newest first. Kept for the existing "recent exports" UI;
+/// the Exports page uses `search_exports`.
+#[command]
+pub async fn get_recent_exports() -> Result<Vec<String>, String> {
+    let _guard = EXPORT_INDEX_LOCK.lock().await;
+    let mut records = load_export_index().await?;
+
+    records.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+
+    Ok(records
+        .into_iter()
+        .take(RECENT_EXPORTS_LIMIT)
+        .map(|r| r.path)
+        .collect())
+}
+
+/// Search the export history. `query` matches filename or cluster (case-insensitive);
+/// `page` is zero-based. Results are newest first.
+#[command]
+pub async fn search_exports(
+    query: Option<String>,
+    filters: Option<ExportSearchFilters>,
+    page: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<ExportSearchResult, String> {
+    let query = query.unwrap_or_default().trim().to_lowercase();
+    let filters = filters.unwrap_or_default();
+    let page = page.unwrap_or(0);
+    let page_size = page_size.unwrap_or(DEFAULT_SEARCH_PAGE_SIZE).max(1);
+
+    let records = {
+        let _guard = EXPORT_INDEX_LOCK.lock().await;
+        load_export_index().await?
+    };
+
+    let mut matches: Vec<ExportRecord> = records
+        .into_iter()
+        .filter(|r| matches_search(r, &query, &filters))
+        .collect();
+    matches.sort_by_key(|r| std::cmp::Reverse(r.created_at));
+
+    let total = matches.len();
+    let items = matches
+        .into_iter()
+        .skip(page.saturating_mul(page_size))
+        .take(page_size)
+        .collect();
+
+    Ok(ExportSearchResult {
+        items,
+        total,
+        page,
+        page_size,
+    })
+}
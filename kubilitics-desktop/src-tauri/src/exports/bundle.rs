@@ -0,0 +1,180 @@
+// Bundle export: everything needed to reproduce a topology view offline — rendered image, raw
+// topology JSON, the manifests of every referenced resource and a manifest.json — in one
+// `.kubilitics.zip` archive.
+use std::io::{Cursor, Write};
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{command, AppHandle};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::topology::{TopologyGraph, TopologyNode};
+use super::write_export;
+use crate::backend_ports::BACKEND_PORT;
+
+const BUNDLE_EXTENSION: &str = ".kubilitics.zip";
+const MANIFEST_FETCH_CONCURRENCY: usize = 8;
+const BACKEND_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Serialize)]
+struct BundleManifest {
+    format_version: u32,
+    app_version: String,
+    backend_version: Option<String>,
+    created_at: String,
+    cluster_id: String,
+    image: Option<String>,
+    resource_count: usize,
+    manifests: Vec<String>,
+    /// Resources present in the topology whose manifest could not be fetched (deleted, forbidden).
+    missing_resources: Vec<String>,
+}
+
+async fn fetch_backend_version(client: &reqwest::Client) -> Option<String> {
+    let url = format!("{}/health", crate::loopback::base_url(BACKEND_PORT).await);
+    let body: Value = client
+        .get(&url)
+        .timeout(Duration::from_secs(BACKEND_REQUEST_TIMEOUT_SECS))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    body.get("version").and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Fetch one resource from the backend and render it as YAML, without the managedFields noise.
+async fn fetch_manifest(client: &reqwest::Client, cluster_id: &str, node: &TopologyNode) -> Result<String, String> {
+    // Backend convention: "-" addresses cluster-scoped resources
+    let namespace = if node.namespace.is_empty() { "-" } else { &node.namespace };
+    let url = format!(
+        "{}/api/v1/clusters/{}/resources/{}/{}/{}",
+        crate::loopback::base_url(BACKEND_PORT).await, cluster_id, node.kind, namespace, node.name
+    );
+    let response = client
+        .get(&url)
+        .timeout(Duration::from_secs(BACKEND_REQUEST_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("backend returned {}", response.status()));
+    }
+    let mut object: Value = response.json().await.map_err(|e| e.to_string())?;
+    if let Some(metadata) = object.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        metadata.remove("managedFields");
+    }
+    serde_yaml::to_string(&object).map_err(|e| e.to_string())
+}
+
+fn manifest_path(node: &TopologyNode) -> String {
+    let namespace = if node.namespace.is_empty() { "_cluster" } else { &node.namespace };
+    format!(
+        "manifests/{}/{}-{}.yaml",
+        namespace,
+        node.kind.to_lowercase(),
+        node.name
+    )
+}
+
+fn add_zip_file(zip: &mut ZipWriter<Cursor<Vec<u8>>>, name: &str, data: &[u8]) -> Result<(), String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+    zip.write_all(data)
+        .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))
+}
+
+/// Assemble a `.kubilitics.zip` bundle from the current topology and save it through the export
+/// pipeline. `image` is the rendered view as exported by the frontend (`image_format`: png | svg).
+#[command]
+pub async fn export_bundle(
+    app_handle: AppHandle,
+    filename: String,
+    cluster_id: String,
+    topology: Value,
+    image: Option<Vec<u8>>,
+    image_format: Option<String>,
+) -> Result<String, String> {
+    let graph = TopologyGraph::from_value(&topology)?;
+
+    let client = crate::http_client::shared(&app_handle).await?;
+
+    let backend_version = fetch_backend_version(&client).await;
+
+    // Fetch manifests with bounded concurrency — large topologies reference hundreds of resources
+    let fetched: Vec<(TopologyNode, Result<String, String>)> = stream::iter(graph.nodes.clone())
+        .map(|node| {
+            let client = client.clone();
+            let cluster_id = cluster_id.clone();
+            async move {
+                let result = fetch_manifest(&client, &cluster_id, &node).await;
+                (node, result)
+            }
+        })
+        .buffer_unordered(MANIFEST_FETCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut manifests = Vec::new();
+    let mut missing_resources = Vec::new();
+
+    for (node, result) in fetched {
+        match result {
+            Ok(yaml) => {
+                let path = manifest_path(&node);
+                add_zip_file(&mut zip, &path, yaml.as_bytes())?;
+                manifests.push(path);
+            }
+            Err(e) => missing_resources.push(format!("{}/{}/{}: {}", node.kind, node.namespace, node.name, e)),
+        }
+    }
+    manifests.sort();
+
+    let topology_json = serde_json::to_vec_pretty(&topology)
+        .map_err(|e| format!("Failed to serialize topology: {}", e))?;
+    add_zip_file(&mut zip, "topology.json", &topology_json)?;
+
+    let image_name = match image {
+        Some(bytes) => {
+            let extension = image_format.as_deref().unwrap_or("png").to_lowercase();
+            let name = format!("topology.{}", extension);
+            add_zip_file(&mut zip, &name, &bytes)?;
+            Some(name)
+        }
+        None => None,
+    };
+
+    let manifest = BundleManifest {
+        format_version: 1,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        backend_version,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        cluster_id: cluster_id.clone(),
+        image: image_name,
+        resource_count: graph.nodes.len(),
+        manifests,
+        missing_resources,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize bundle manifest: {}", e))?;
+    add_zip_file(&mut zip, "manifest.json", &manifest_json)?;
+
+    let data = zip
+        .finish()
+        .map_err(|e| format!("Failed to finalize bundle: {}", e))?
+        .into_inner();
+
+    let filename = if filename.ends_with(BUNDLE_EXTENSION) {
+        filename
+    } else {
+        format!("{}{}", filename.trim_end_matches(".zip"), BUNDLE_EXTENSION)
+    };
+    let path = write_export(&filename, &data, "bundle", Some(cluster_id)).await?;
+    Ok(path.to_string_lossy().to_string())
+}
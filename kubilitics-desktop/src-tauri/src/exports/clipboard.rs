@@ -0,0 +1,86 @@
+// OS clipboard access from Rust (arboard) — the webview clipboard API needs focus and a user
+// gesture, and can't carry images on every platform. Lets users paste a topology screenshot or a
+// manifest into chat without saving a file first.
+use std::borrow::Cow;
+
+use arboard::{Clipboard, ImageData};
+use resvg::tiny_skia::Pixmap;
+use serde_json::Value;
+use tauri::command;
+use tauri::ipc::{InvokeBody, Request};
+
+use super::png::{render_png, PngRenderOptions};
+
+/// arboard blocks (and on Linux talks to the X server), so every clipboard write runs on the
+/// blocking pool.
+async fn with_clipboard<F>(f: F) -> Result<(), String>
+where
+    F: FnOnce(&mut Clipboard) -> Result<(), arboard::Error> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        Clipboard::new()
+            .and_then(|mut clipboard| f(&mut clipboard))
+            .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Clipboard task failed: {}", e))?
+}
+
+/// Put text on the clipboard.
+pub async fn copy_text(text: String) -> Result<(), String> {
+    with_clipboard(move |clipboard| clipboard.set_text(text)).await
+}
+
+/// Put a PNG on the clipboard. The OS clipboard takes straight (non-premultiplied) RGBA pixels.
+pub async fn copy_png(png: &[u8]) -> Result<(), String> {
+    let pixmap = Pixmap::decode_png(png).map_err(|e| format!("Invalid PNG: {}", e))?;
+    let width = pixmap.width() as usize;
+    let height = pixmap.height() as usize;
+    let rgba: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+
+    with_clipboard(move |clipboard| {
+        clipboard.set_image(ImageData {
+            width,
+            height,
+            bytes: Cow::Owned(rgba),
+        })
+    })
+    .await
+}
+
+/// Copy a rendered PNG to the clipboard. The PNG is the raw request body (Uint8Array/ArrayBuffer).
+#[command]
+pub async fn copy_png_to_clipboard(request: Request<'_>) -> Result<(), String> {
+    let InvokeBody::Raw(png) = request.body() else {
+        return Err("PNG data must be sent as raw binary".to_string());
+    };
+    copy_png(png).await
+}
+
+/// Rasterize the topology SVG and copy the image to the clipboard in one step.
+#[command]
+pub async fn copy_svg_as_png_to_clipboard(svg: String, options: Option<PngRenderOptions>) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    let png = tokio::task::spawn_blocking(move || render_png(&svg, &options))
+        .await
+        .map_err(|e| format!("PNG rendering task failed: {}", e))??;
+    copy_png(&png).await
+}
+
+/// Copy a resource as a YAML manifest, without the server-side managedFields noise.
+#[command]
+pub async fn copy_manifest_to_clipboard(mut resource: Value) -> Result<(), String> {
+    if let Some(metadata) = resource.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        metadata.remove("managedFields");
+    }
+    let yaml = serde_yaml::to_string(&resource)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    copy_text(yaml).await
+}
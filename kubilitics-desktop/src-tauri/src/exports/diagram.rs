@@ -0,0 +1,170 @@
+// Text diagram exporters — Mermaid flowcharts and Graphviz DOT — for embedding topology in docs
+// and READMEs. Both can be saved through the export pipeline or copied straight to the clipboard.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::command;
+
+use super::clipboard::copy_text;
+use super::drawio::kind_colors;
+use super::topology::{TopologyGraph, TopologyNode};
+use super::write_export;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagramFormat {
+    Mermaid,
+    Dot,
+}
+
+impl DiagramFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            DiagramFormat::Mermaid => "mmd",
+            DiagramFormat::Dot => "dot",
+        }
+    }
+}
+
+fn node_title(node: &TopologyNode) -> String {
+    if node.kind.is_empty() {
+        node.name.clone()
+    } else {
+        format!("{}: {}", node.kind, node.name)
+    }
+}
+
+/// Mermaid labels can't contain raw quotes; the entity form renders correctly.
+fn mermaid_label(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+fn dot_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Node ids in both formats are positional (`n0`, `n1`, ...) — topology ids contain `/` and `:`
+/// which neither syntax accepts unquoted.
+fn node_ids(graph: &TopologyGraph) -> HashMap<&str, String> {
+    graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.id.as_str(), format!("n{}", i)))
+        .collect()
+}
+
+pub fn render_mermaid(graph: &TopologyGraph) -> String {
+    let ids = node_ids(graph);
+    let mut out = String::from("flowchart LR\n");
+    let mut classes: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+
+    for node in &graph.nodes {
+        let id = &ids[node.id.as_str()];
+        let _ = writeln!(out, "  {}[\"{}\"]", id, mermaid_label(&node_title(node)));
+        if !node.kind.is_empty() {
+            classes.entry(node.kind.clone()).or_default().push(id);
+        }
+    }
+    for edge in &graph.edges {
+        let (Some(source), Some(target)) = (ids.get(edge.source.as_str()), ids.get(edge.target.as_str())) else {
+            continue;
+        };
+        if edge.label.is_empty() {
+            let _ = writeln!(out, "  {} --> {}", source, target);
+        } else {
+            let _ = writeln!(out, "  {} -->|\"{}\"| {}", source, mermaid_label(&edge.label), target);
+        }
+    }
+    for (kind, members) in &classes {
+        let (fill, stroke) = kind_colors(kind);
+        let _ = writeln!(out, "  classDef {} fill:{},stroke:{}", kind, fill, stroke);
+        let _ = writeln!(out, "  class {} {}", members.join(","), kind);
+    }
+    out
+}
+
+pub fn render_dot(graph: &TopologyGraph) -> String {
+    let ids = node_ids(graph);
+    let mut out = String::from(
+        "digraph topology {\n  rankdir=LR;\n  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\", fontsize=10];\n  edge [fontname=\"Helvetica\", fontsize=8, color=\"#94a3b8\"];\n",
+    );
+
+    // One cluster box per namespace; cluster-scoped resources stay at the top level
+    let mut by_namespace: BTreeMap<&str, Vec<&TopologyNode>> = BTreeMap::new();
+    for node in &graph.nodes {
+        by_namespace.entry(node.namespace.as_str()).or_default().push(node);
+    }
+    for (namespace, nodes) in &by_namespace {
+        let indent = if namespace.is_empty() { "  " } else { "    " };
+        if !namespace.is_empty() {
+            let _ = writeln!(out, "  subgraph \"cluster_{}\" {{", dot_string(namespace));
+            let _ = writeln!(out, "    label=\"{}\";", dot_string(namespace));
+            let _ = writeln!(out, "    style=dashed; color=\"#cbd5e1\";");
+        }
+        for node in nodes {
+            let (fill, stroke) = kind_colors(&node.kind);
+            let _ = writeln!(
+                out,
+                "{}{} [label=\"{}\", fillcolor=\"{}\", color=\"{}\"];",
+                indent,
+                ids[node.id.as_str()],
+                dot_string(&node_title(node)),
+                fill,
+                stroke
+            );
+        }
+        if !namespace.is_empty() {
+            out.push_str("  }\n");
+        }
+    }
+    for edge in &graph.edges {
+        let (Some(source), Some(target)) = (ids.get(edge.source.as_str()), ids.get(edge.target.as_str())) else {
+            continue;
+        };
+        if edge.label.is_empty() {
+            let _ = writeln!(out, "  {} -> {};", source, target);
+        } else {
+            let _ = writeln!(out, "  {} -> {} [label=\"{}\"];", source, target, dot_string(&edge.label));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render(topology: &Value, format: DiagramFormat) -> Result<String, String> {
+    let graph = TopologyGraph::from_value(topology)?;
+    Ok(match format {
+        DiagramFormat::Mermaid => render_mermaid(&graph),
+        DiagramFormat::Dot => render_dot(&graph),
+    })
+}
+
+/// Save the topology as a Mermaid (`.mmd`) or Graphviz (`.dot`) file in the exports folder.
+#[command]
+pub async fn export_topology_diagram(
+    topology: Value,
+    format: DiagramFormat,
+    filename: String,
+    cluster: Option<String>,
+) -> Result<String, String> {
+    let text = render(&topology, format)?;
+
+    let extension = format!(".{}", format.extension());
+    let filename = if filename.ends_with(&extension) {
+        filename
+    } else {
+        format!("{}{}", filename, extension)
+    };
+    let path = write_export(&filename, text.as_bytes(), format.extension(), cluster).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Copy the Mermaid/DOT source to the clipboard, ready to paste into a README or wiki page.
+#[command]
+pub async fn copy_topology_diagram(topology: Value, format: DiagramFormat) -> Result<(), String> {
+    let text = render(&topology, format)?;
+    copy_text(text).await
+}
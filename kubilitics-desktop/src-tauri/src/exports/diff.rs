@@ -0,0 +1,187 @@
+// Structural diff between two exported snapshots — "what changed since last week". Accepts
+// topology JSON exports and `.kubilitics.zip` bundles; container image changes can only be
+// detected for bundles, since only they carry the resource manifests.
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+
+use super::topology::{TopologyGraph, TopologyNode};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ResourceRef {
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceChange {
+    pub resource: ResourceRef,
+    /// replicas | image | status | health
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportDiff {
+    pub added: Vec<ResourceRef>,
+    pub removed: Vec<ResourceRef>,
+    pub changed: Vec<ResourceChange>,
+    /// False when either side is a plain topology export, i.e. image changes were not compared.
+    pub images_compared: bool,
+}
+
+struct Snapshot {
+    nodes: BTreeMap<ResourceRef, TopologyNode>,
+    images: Option<BTreeMap<ResourceRef, Vec<String>>>,
+}
+
+fn resource_ref(kind: &str, namespace: &str, name: &str) -> ResourceRef {
+    ResourceRef {
+        kind: kind.to_string(),
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+    }
+}
+
+/// Container images of a workload manifest (pod spec, pod template or cronjob template).
+fn manifest_images(manifest: &Value) -> Vec<String> {
+    const POD_SPECS: [&str; 3] = [
+        "/spec",
+        "/spec/template/spec",
+        "/spec/jobTemplate/spec/template/spec",
+    ];
+    let mut images: Vec<String> = POD_SPECS
+        .iter()
+        .filter_map(|p| manifest.pointer(p))
+        .flat_map(|spec| {
+            ["initContainers", "containers"]
+                .iter()
+                .filter_map(|c| spec.get(*c).and_then(|v| v.as_array()))
+                .flatten()
+                .filter_map(|c| c.get("image").and_then(|i| i.as_str()).map(String::from))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    images.sort();
+    images.dedup();
+    images
+}
+
+fn snapshot_from_graph(graph: TopologyGraph) -> BTreeMap<ResourceRef, TopologyNode> {
+    graph
+        .nodes
+        .into_iter()
+        .map(|n| (resource_ref(&n.kind, &n.namespace, &n.name), n))
+        .collect()
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, name: &str) -> Result<String, String> {
+    let mut content = String::new();
+    archive
+        .by_name(name)
+        .map_err(|e| format!("Failed to read {} from bundle: {}", name, e))?
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read {} from bundle: {}", name, e))?;
+    Ok(content)
+}
+
+fn read_bundle(data: Vec<u8>) -> Result<Snapshot, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| format!("Invalid bundle: {}", e))?;
+
+    let topology: Value = serde_json::from_str(&read_entry(&mut archive, "topology.json")?)
+        .map_err(|e| format!("Invalid topology.json in bundle: {}", e))?;
+    let nodes = snapshot_from_graph(TopologyGraph::from_value(&topology)?);
+
+    let manifest_names: Vec<String> = archive
+        .file_names()
+        .filter(|n| n.starts_with("manifests/") && n.ends_with(".yaml"))
+        .map(String::from)
+        .collect();
+    let mut images = BTreeMap::new();
+    for name in manifest_names {
+        let content = read_entry(&mut archive, &name)?;
+        let Ok(manifest) = serde_yaml::from_str::<Value>(&content) else {
+            continue;
+        };
+        let field = |p: &str| manifest.pointer(p).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let key = resource_ref(&field("/kind"), &field("/metadata/namespace"), &field("/metadata/name"));
+        let container_images = manifest_images(&manifest);
+        if !container_images.is_empty() {
+            images.insert(key, container_images);
+        }
+    }
+
+    Ok(Snapshot { nodes, images: Some(images) })
+}
+
+async fn load_snapshot(path: &str) -> Result<Snapshot, String> {
+    let data = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    if Path::new(path).extension().is_some_and(|e| e == "zip") {
+        return read_bundle(data);
+    }
+
+    let topology: Value = serde_json::from_slice(&data)
+        .map_err(|e| format!("{} is not a topology JSON export: {}", path, e))?;
+    Ok(Snapshot {
+        nodes: snapshot_from_graph(TopologyGraph::from_value(&topology)?),
+        images: None,
+    })
+}
+
+fn diff_snapshots(a: Snapshot, b: Snapshot) -> ExportDiff {
+    let before: BTreeSet<&ResourceRef> = a.nodes.keys().collect();
+    let after: BTreeSet<&ResourceRef> = b.nodes.keys().collect();
+
+    let added = after.difference(&before).map(|r| (*r).clone()).collect();
+    let removed = before.difference(&after).map(|r| (*r).clone()).collect();
+
+    let mut changed = Vec::new();
+    for key in before.intersection(&after) {
+        let (old, new) = (&a.nodes[*key], &b.nodes[*key]);
+        let mut push = |field: &str, before: String, after: String| {
+            if before != after {
+                changed.push(ResourceChange {
+                    resource: (*key).clone(),
+                    field: field.to_string(),
+                    before,
+                    after,
+                });
+            }
+        };
+
+        let desired = |n: &TopologyNode| n.computed.replicas.map(|r| r.desired.to_string()).unwrap_or_default();
+        push("replicas", desired(old), desired(new));
+        push("status", old.status.clone(), new.status.clone());
+        push("health", old.computed.health.clone(), new.computed.health.clone());
+
+        if let (Some(old_images), Some(new_images)) = (&a.images, &b.images) {
+            let images = |m: &BTreeMap<ResourceRef, Vec<String>>| m.get(*key).map(|i| i.join(", ")).unwrap_or_default();
+            push("image", images(old_images), images(new_images));
+        }
+    }
+
+    ExportDiff {
+        added,
+        removed,
+        changed,
+        images_compared: a.images.is_some() && b.images.is_some(),
+    }
+}
+
+/// Compare two exported snapshots (`a` = older, `b` = newer) and list resources added, removed
+/// and changed (replicas, status, health and — for bundles — container images).
+#[command]
+pub async fn diff_exports(a: String, b: String) -> Result<ExportDiff, String> {
+    let (old, new) = tokio::try_join!(load_snapshot(&a), load_snapshot(&b))?;
+    Ok(diff_snapshots(old, new))
+}
@@ -0,0 +1,141 @@
+// draw.io (diagrams.net) exporter: the topology graph becomes an mxGraph document with the
+// on-screen node positions, colored by kind, so the diagram can be edited further in diagrams.net.
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde_json::Value;
+use tauri::command;
+
+use super::topology::{Position, TopologyGraph, TopologyNode};
+use super::write_export;
+
+const NODE_WIDTH: f64 = 160.0;
+const NODE_HEIGHT: f64 = 48.0;
+
+pub(super) fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// (fill, stroke) per resource family, roughly matching the in-app topology palette.
+pub(super) fn kind_colors(kind: &str) -> (&'static str, &'static str) {
+    match kind {
+        "Deployment" | "StatefulSet" | "DaemonSet" | "ReplicaSet" | "Job" | "CronJob" => ("#dbeafe", "#2563eb"),
+        "Pod" => ("#dcfce7", "#16a34a"),
+        "Service" | "Ingress" | "Endpoints" | "EndpointSlice" | "NetworkPolicy" => ("#ede9fe", "#7c3aed"),
+        "ConfigMap" | "Secret" => ("#fef3c7", "#d97706"),
+        "PersistentVolumeClaim" | "PersistentVolume" | "StorageClass" => ("#ccfbf1", "#0d9488"),
+        "Node" | "Namespace" => ("#e2e8f0", "#475569"),
+        _ => ("#f1f5f9", "#64748b"),
+    }
+}
+
+fn node_style(node: &TopologyNode) -> String {
+    let (fill, mut stroke) = kind_colors(&node.kind);
+    // Unhealthy resources keep their family fill but get a red outline
+    if !matches!(node.computed.health.as_str(), "" | "healthy" | "unknown") {
+        stroke = "#dc2626";
+    }
+    format!(
+        "rounded=1;whiteSpace=wrap;html=1;fillColor={};strokeColor={};fontSize=11;",
+        fill, stroke
+    )
+}
+
+fn node_label(node: &TopologyNode) -> String {
+    let mut label = if node.kind.is_empty() {
+        node.name.clone()
+    } else {
+        format!("{}: {}", node.kind, node.name)
+    };
+    if !node.namespace.is_empty() {
+        label.push_str(&format!("\n({})", node.namespace));
+    }
+    label
+}
+
+/// Render the graph as a draw.io `mxfile` document.
+pub fn render_drawio(mut graph: TopologyGraph) -> String {
+    graph.apply_grid_layout();
+
+    let mut cells = String::new();
+    let mut cell_ids: HashMap<&str, String> = HashMap::new();
+    let mut next_id = 2;
+
+    for node in &graph.nodes {
+        let position = node.position.unwrap_or(Position { x: 0.0, y: 0.0 });
+        let cell_id = format!("n{}", next_id);
+        next_id += 1;
+        let _ = writeln!(
+            cells,
+            r#"        <mxCell id="{}" value="{}" style="{}" vertex="1" parent="1">
+          <mxGeometry x="{}" y="{}" width="{}" height="{}" as="geometry"/>
+        </mxCell>"#,
+            cell_id,
+            escape_xml(&node_label(node)),
+            node_style(node),
+            position.x,
+            position.y,
+            NODE_WIDTH,
+            NODE_HEIGHT,
+        );
+        cell_ids.insert(node.id.as_str(), cell_id);
+    }
+
+    for edge in &graph.edges {
+        let (Some(source), Some(target)) = (cell_ids.get(edge.source.as_str()), cell_ids.get(edge.target.as_str())) else {
+            continue;
+        };
+        let label = if edge.label.is_empty() { &edge.relationship_type } else { &edge.label };
+        let _ = writeln!(
+            cells,
+            r#"        <mxCell id="e{}" value="{}" style="edgeStyle=orthogonalEdgeStyle;rounded=1;endArrow=classic;html=1;strokeColor=#94a3b8;fontSize=9;" edge="1" parent="1" source="{}" target="{}">
+          <mxGeometry relative="1" as="geometry"/>
+        </mxCell>"#,
+            next_id,
+            escape_xml(label),
+            source,
+            target,
+        );
+        next_id += 1;
+    }
+
+    format!(
+        r#"<mxfile host="Kubilitics" modified="{}" agent="Kubilitics Desktop {}" type="device">
+  <diagram id="topology" name="Topology">
+    <mxGraphModel dx="1200" dy="800" grid="1" gridSize="10" guides="1" arrows="1" connect="1" page="0">
+      <root>
+        <mxCell id="0"/>
+        <mxCell id="1" parent="0"/>
+{}      </root>
+    </mxGraphModel>
+  </diagram>
+</mxfile>
+"#,
+        chrono::Utc::now().to_rfc3339(),
+        env!("CARGO_PKG_VERSION"),
+        cells
+    )
+}
+
+/// Convert a topology graph (as returned by the backend) to a `.drawio` file in the exports folder.
+#[command]
+pub async fn export_topology_drawio(
+    topology: Value,
+    filename: String,
+    cluster: Option<String>,
+) -> Result<String, String> {
+    let graph = TopologyGraph::from_value(&topology)?;
+    let xml = render_drawio(graph);
+
+    let filename = if filename.ends_with(".drawio") {
+        filename
+    } else {
+        format!("{}.drawio", filename)
+    };
+    let path = write_export(&filename, xml.as_bytes(), "drawio", cluster).await?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,254 @@
+// Resource inventories (pods, deployments, nodes, ...) as CSV or XLSX for audit and reporting.
+// The frontend passes the resource list it already holds; this module flattens each object into
+// the key columns for its kind.
+use serde_json::Value;
+use tauri::command;
+
+use super::write_export;
+
+type ColumnFn = fn(&Value) -> String;
+
+fn str_at(resource: &Value, pointer: &str) -> String {
+    match resource.pointer(pointer) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn name(r: &Value) -> String {
+    str_at(r, "/metadata/name")
+}
+
+fn namespace(r: &Value) -> String {
+    str_at(r, "/metadata/namespace")
+}
+
+fn created(r: &Value) -> String {
+    str_at(r, "/metadata/creationTimestamp")
+}
+
+fn container_images(r: &Value, containers_pointer: &str) -> String {
+    r.pointer(containers_pointer)
+        .and_then(|c| c.as_array())
+        .map(|containers| {
+            containers
+                .iter()
+                .filter_map(|c| c.get("image").and_then(|i| i.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+fn pod_phase(r: &Value) -> String {
+    str_at(r, "/status/phase")
+}
+
+fn pod_node(r: &Value) -> String {
+    str_at(r, "/spec/nodeName")
+}
+
+fn pod_ip(r: &Value) -> String {
+    str_at(r, "/status/podIP")
+}
+
+fn pod_restarts(r: &Value) -> String {
+    r.pointer("/status/containerStatuses")
+        .and_then(|c| c.as_array())
+        .map(|statuses| {
+            statuses
+                .iter()
+                .filter_map(|s| s.get("restartCount").and_then(|n| n.as_u64()))
+                .sum::<u64>()
+        })
+        .unwrap_or(0)
+        .to_string()
+}
+
+fn pod_images(r: &Value) -> String {
+    container_images(r, "/spec/containers")
+}
+
+fn workload_desired(r: &Value) -> String {
+    str_at(r, "/spec/replicas")
+}
+
+fn workload_ready(r: &Value) -> String {
+    str_at(r, "/status/readyReplicas")
+}
+
+fn workload_available(r: &Value) -> String {
+    str_at(r, "/status/availableReplicas")
+}
+
+fn workload_images(r: &Value) -> String {
+    container_images(r, "/spec/template/spec/containers")
+}
+
+fn node_ready(r: &Value) -> String {
+    r.pointer("/status/conditions")
+        .and_then(|c| c.as_array())
+        .and_then(|conditions| {
+            conditions
+                .iter()
+                .find(|c| c.get("type").and_then(|t| t.as_str()) == Some("Ready"))
+        })
+        .and_then(|c| c.get("status").and_then(|s| s.as_str()))
+        .map(|s| if s == "True" { "Ready" } else { "NotReady" })
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+fn node_roles(r: &Value) -> String {
+    r.pointer("/metadata/labels")
+        .and_then(|l| l.as_object())
+        .map(|labels| {
+            labels
+                .keys()
+                .filter_map(|k| k.strip_prefix("node-role.kubernetes.io/"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+fn node_kubelet(r: &Value) -> String {
+    str_at(r, "/status/nodeInfo/kubeletVersion")
+}
+
+fn node_cpu(r: &Value) -> String {
+    str_at(r, "/status/capacity/cpu")
+}
+
+fn node_memory(r: &Value) -> String {
+    str_at(r, "/status/capacity/memory")
+}
+
+fn columns_for_kind(kind: &str) -> Vec<(&'static str, ColumnFn)> {
+    match kind.to_lowercase().trim_end_matches('s') {
+        "pod" => vec![
+            ("Name", name),
+            ("Namespace", namespace),
+            ("Phase", pod_phase),
+            ("Node", pod_node),
+            ("Pod IP", pod_ip),
+            ("Restarts", pod_restarts),
+            ("Images", pod_images),
+            ("Created", created),
+        ],
+        "deployment" | "statefulset" | "replicaset" => vec![
+            ("Name", name),
+            ("Namespace", namespace),
+            ("Desired", workload_desired),
+            ("Ready", workload_ready),
+            ("Available", workload_available),
+            ("Images", workload_images),
+            ("Created", created),
+        ],
+        "node" => vec![
+            ("Name", name),
+            ("Status", node_ready),
+            ("Roles", node_roles),
+            ("Kubelet Version", node_kubelet),
+            ("CPU", node_cpu),
+            ("Memory", node_memory),
+            ("Created", created),
+        ],
+        _ => vec![("Name", name), ("Namespace", namespace), ("Created", created)],
+    }
+}
+
+fn build_rows(kind: &str, resources: &[Value]) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let columns = columns_for_kind(kind);
+    let headers = columns.iter().map(|(h, _)| *h).collect();
+    let rows = resources
+        .iter()
+        .map(|r| columns.iter().map(|(_, f)| f(r)).collect())
+        .collect();
+    (headers, rows)
+}
+
+fn render_csv(headers: &[&str], rows: &[Vec<String>]) -> Result<Vec<u8>, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(headers)
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    for row in rows {
+        writer
+            .write_record(row)
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+fn render_xlsx(kind: &str, headers: &[&str], rows: &[Vec<String>]) -> Result<Vec<u8>, String> {
+    use rust_xlsxwriter::{Format, Workbook};
+
+    let mut workbook = Workbook::new();
+    let header_format = Format::new().set_bold();
+    let sheet = workbook.add_worksheet();
+    // Sheet names are limited to 31 characters
+    let sheet_name: String = kind.chars().take(31).collect();
+    if !sheet_name.is_empty() {
+        sheet
+            .set_name(sheet_name)
+            .map_err(|e| format!("Failed to write XLSX: {}", e))?;
+    }
+
+    for (col, header) in headers.iter().enumerate() {
+        sheet
+            .write_string_with_format(0, col as u16, *header, &header_format)
+            .map_err(|e| format!("Failed to write XLSX: {}", e))?;
+    }
+    for (row_index, row) in rows.iter().enumerate() {
+        let row_number = row_index as u32 + 1;
+        for (col, value) in row.iter().enumerate() {
+            // Keep numeric columns numeric so spreadsheet sorting and sums work
+            let result = match value.parse::<f64>() {
+                Ok(n) => sheet.write_number(row_number, col as u16, n),
+                Err(_) => sheet.write_string(row_number, col as u16, value),
+            };
+            result.map_err(|e| format!("Failed to write XLSX: {}", e))?;
+        }
+    }
+    sheet
+        .set_freeze_panes(1, 0)
+        .map_err(|e| format!("Failed to write XLSX: {}", e))?;
+    sheet.autofit();
+
+    workbook
+        .save_to_buffer()
+        .map_err(|e| format!("Failed to write XLSX: {}", e))
+}
+
+/// Export a resource list as a CSV or XLSX inventory. `kind` picks the columns (pods, deployments,
+/// nodes, ...; unknown kinds get name/namespace/created). Returns the saved file path.
+#[command]
+pub async fn export_resource_inventory(
+    kind: String,
+    resources: Vec<Value>,
+    format: String,
+    filename: String,
+    cluster: Option<String>,
+) -> Result<String, String> {
+    let format = format.to_lowercase();
+    let (headers, rows) = build_rows(&kind, &resources);
+
+    let data = match format.as_str() {
+        "csv" => render_csv(&headers, &rows)?,
+        "xlsx" => render_xlsx(&kind, &headers, &rows)?,
+        other => return Err(format!("Unsupported inventory format: {}", other)),
+    };
+
+    let extension = format!(".{}", format);
+    let filename = if filename.to_lowercase().ends_with(&extension) {
+        filename
+    } else {
+        format!("{}{}", filename, extension)
+    };
+    let path = write_export(&filename, &data, &format, cluster).await?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,191 @@
+// Native PDF export: the topology SVG is embedded as vector graphics on a fixed-size page,
+// instead of relying on the webview's print-to-PDF (blurry output, unpredictable page breaks).
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use super::svg::parse_svg;
+use super::templates::{apply_template, get_template, resolve_template};
+use super::write_export;
+
+const MARGIN_PT: f32 = 36.0;
+const TITLE_FONT_SIZE: f32 = 18.0;
+const SUBTITLE_FONT_SIZE: f32 = 10.0;
+const HEADER_GAP_PT: f32 = 12.0;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PdfPageSize {
+    #[default]
+    A4,
+    A3,
+    Letter,
+    /// Page sized to the SVG itself (plus margins) — no scaling at all.
+    Fit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfExportOptions {
+    pub page_size: PdfPageSize,
+    pub landscape: bool,
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+}
+
+impl Default for PdfExportOptions {
+    fn default() -> Self {
+        Self {
+            page_size: PdfPageSize::A4,
+            // Topology graphs are usually wider than they are tall
+            landscape: true,
+            title: None,
+            subtitle: None,
+        }
+    }
+}
+
+/// The built-in Helvetica font only covers WinAnsi; replace anything else rather than emit garbage.
+fn pdf_text(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| if c.is_ascii() && !c.is_ascii_control() { c as u8 } else { b'?' })
+        .collect()
+}
+
+fn header_height(options: &PdfExportOptions) -> f32 {
+    let mut height = 0.0;
+    if options.title.is_some() {
+        height += TITLE_FONT_SIZE + 4.0;
+    }
+    if options.subtitle.is_some() {
+        height += SUBTITLE_FONT_SIZE + 4.0;
+    }
+    if height > 0.0 {
+        height += HEADER_GAP_PT;
+    }
+    height
+}
+
+/// Render SVG markup into a single-page PDF, scaled to fit the page while keeping its aspect ratio.
+pub fn render_pdf(svg: &str, options: &PdfExportOptions) -> Result<Vec<u8>, String> {
+    let tree = parse_svg(svg)?;
+    let svg_width = tree.size.width();
+    let svg_height = tree.size.height();
+    let header = header_height(options);
+
+    let (page_width, page_height) = match options.page_size {
+        PdfPageSize::Fit => (
+            svg_width + 2.0 * MARGIN_PT,
+            svg_height + 2.0 * MARGIN_PT + header,
+        ),
+        size => {
+            let (w, h) = match size {
+                PdfPageSize::A3 => (842.0, 1191.0),
+                PdfPageSize::Letter => (612.0, 792.0),
+                _ => (595.0, 842.0),
+            };
+            if options.landscape { (h, w) } else { (w, h) }
+        }
+    };
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let page_id = Ref::new(3);
+    let font_id = Ref::new(4);
+    let content_id = Ref::new(5);
+    let svg_id = Ref::new(6);
+    let font_name = Name(b"F1");
+    let svg_name = Name(b"S1");
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id).kids([page_id]).count(1);
+
+    let mut page = pdf.page(page_id);
+    page.media_box(Rect::new(0.0, 0.0, page_width, page_height));
+    page.parent(page_tree_id);
+    page.contents(content_id);
+    let mut resources = page.resources();
+    resources.x_objects().pair(svg_name, svg_id);
+    resources.fonts().pair(font_name, font_id);
+    resources.finish();
+    page.finish();
+
+    pdf.type1_font(font_id).base_font(Name(b"Helvetica"));
+
+    // svg2pdf allocates consecutive ids from svg_id and returns the next free one
+    let info_id = svg2pdf::convert_tree_into(&tree, svg2pdf::Options::default(), &mut pdf, svg_id);
+    let mut info = pdf.document_info(info_id);
+    info.producer(TextStr("Kubilitics"));
+    if let Some(title) = &options.title {
+        info.title(TextStr(title));
+    }
+    info.finish();
+
+    let mut content = Content::new();
+    let mut cursor_y = page_height - MARGIN_PT;
+    if let Some(title) = &options.title {
+        cursor_y -= TITLE_FONT_SIZE;
+        content
+            .begin_text()
+            .set_font(font_name, TITLE_FONT_SIZE)
+            .next_line(MARGIN_PT, cursor_y)
+            .show(Str(&pdf_text(title)))
+            .end_text();
+        cursor_y -= 4.0;
+    }
+    if let Some(subtitle) = &options.subtitle {
+        cursor_y -= SUBTITLE_FONT_SIZE;
+        content
+            .begin_text()
+            .set_font(font_name, SUBTITLE_FONT_SIZE)
+            .next_line(MARGIN_PT, cursor_y)
+            .show(Str(&pdf_text(subtitle)))
+            .end_text();
+    }
+
+    // Fit the graphic into the area below the header, centered
+    let available_width = page_width - 2.0 * MARGIN_PT;
+    let available_height = page_height - 2.0 * MARGIN_PT - header;
+    let scale = (available_width / svg_width).min(available_height / svg_height);
+    let draw_width = svg_width * scale;
+    let draw_height = svg_height * scale;
+    let x = MARGIN_PT + (available_width - draw_width) / 2.0;
+    let y = MARGIN_PT + (available_height - draw_height) / 2.0;
+
+    // The XObject is one point square, so the transform carries the final size
+    content
+        .save_state()
+        .transform([draw_width, 0.0, 0.0, draw_height, x, y])
+        .x_object(svg_name)
+        .restore_state();
+
+    pdf.stream(content_id, &content.finish());
+    Ok(pdf.finish())
+}
+
+#[command]
+pub async fn export_topology_pdf(
+    svg: String,
+    filename: String,
+    options: Option<PdfExportOptions>,
+    cluster: Option<String>,
+    template_id: Option<String>,
+) -> Result<String, String> {
+    let options = options.unwrap_or_default();
+    let template = match template_id {
+        Some(id) => Some(resolve_template(&get_template(&id).await?, cluster.as_deref()).await?),
+        None => None,
+    };
+
+    // Text layout and vector conversion are CPU-bound; keep them off the async executor
+    let pdf = tokio::task::spawn_blocking(move || match template {
+        Some(template) => render_pdf(&apply_template(&svg, &template)?, &options),
+        None => render_pdf(&svg, &options),
+    })
+        .await
+        .map_err(|e| format!("PDF rendering task failed: {}", e))??;
+
+    let path = write_export(&filename, &pdf, "pdf", cluster).await?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,106 @@
+// Native SVG -> PNG rasterization. High-DPI exports of large topologies exceed the webview's
+// canvas size limits (crash or silent blur); resvg has no such ceiling short of memory.
+use resvg::tiny_skia::{Color, Pixmap, Transform};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+use tauri::ipc::Response;
+
+use super::svg::parse_svg;
+use super::templates::{apply_template, get_template, resolve_template};
+
+/// Hard cap per side; 16k x 16k RGBA is already 1 GiB of pixels.
+const MAX_PNG_DIMENSION: u32 = 16_384;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PngRenderOptions {
+    /// Target width in pixels. With only one of width/height set, the other follows the aspect ratio.
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Multiplier on the SVG's own size, used when neither width nor height is given (e.g. 2.0 for retina).
+    pub scale: Option<f32>,
+    /// Background as `#rrggbb`; transparent when unset.
+    pub background: Option<String>,
+}
+
+fn parse_hex_color(hex: &str) -> Result<Color, String> {
+    let digits = hex.trim_start_matches('#');
+    let channel = |i: usize| {
+        digits
+            .get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .ok_or_else(|| format!("Invalid background color: {}", hex))
+    };
+    if digits.len() != 6 {
+        return Err(format!("Invalid background color: {}", hex));
+    }
+    Ok(Color::from_rgba8(channel(0)?, channel(2)?, channel(4)?, 255))
+}
+
+/// Render SVG markup to PNG bytes at the requested resolution.
+pub fn render_png(svg: &str, options: &PngRenderOptions) -> Result<Vec<u8>, String> {
+    let tree = parse_svg(svg)?;
+    let svg_width = tree.size.width();
+    let svg_height = tree.size.height();
+
+    let (width, height) = match (options.width, options.height) {
+        (Some(w), Some(h)) => (w as f32, h as f32),
+        (Some(w), None) => (w as f32, w as f32 * svg_height / svg_width),
+        (None, Some(h)) => (h as f32 * svg_width / svg_height, h as f32),
+        (None, None) => {
+            let scale = options.scale.unwrap_or(1.0);
+            (svg_width * scale, svg_height * scale)
+        }
+    };
+    let width = width.round() as u32;
+    let height = height.round() as u32;
+
+    if width == 0 || height == 0 {
+        return Err("PNG dimensions must be greater than zero".to_string());
+    }
+    if width > MAX_PNG_DIMENSION || height > MAX_PNG_DIMENSION {
+        return Err(format!(
+            "PNG dimensions {}x{} exceed the maximum of {} pixels per side",
+            width, height, MAX_PNG_DIMENSION
+        ));
+    }
+
+    let mut pixmap = Pixmap::new(width, height)
+        .ok_or_else(|| "Failed to allocate PNG canvas".to_string())?;
+    if let Some(background) = &options.background {
+        pixmap.fill(parse_hex_color(background)?);
+    }
+
+    let transform = Transform::from_scale(width as f32 / svg_width, height as f32 / svg_height);
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map_err(|e| format!("Failed to encode PNG: {}", e))
+}
+
+/// Rasterize SVG to PNG. The bytes are returned as a raw IPC response (an ArrayBuffer on the JS
+/// side) rather than a JSON number array, which would be several times larger than the image.
+/// With `template_id`, the export template's header, logo and legend are added around the graph.
+#[command]
+pub async fn rasterize_svg_to_png(
+    svg: String,
+    options: Option<PngRenderOptions>,
+    template_id: Option<String>,
+    cluster: Option<String>,
+) -> Result<Response, String> {
+    let options = options.unwrap_or_default();
+    let template = match template_id {
+        Some(id) => Some(resolve_template(&get_template(&id).await?, cluster.as_deref()).await?),
+        None => None,
+    };
+
+    let png = tokio::task::spawn_blocking(move || match template {
+        Some(template) => render_png(&apply_template(&svg, &template)?, &options),
+        None => render_png(&svg, &options),
+    })
+        .await
+        .map_err(|e| format!("PNG rendering task failed: {}", e))??;
+
+    Ok(Response::new(png))
+}
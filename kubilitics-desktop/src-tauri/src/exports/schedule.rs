@@ -0,0 +1,301 @@
+// Scheduled automatic exports: recurring topology exports of selected clusters, fetched from the
+// backend's export endpoint and written through the regular export pipeline (index + retention).
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+use super::write_export;
+use crate::backend_ports::BACKEND_PORT;
+use crate::commands::get_app_data_dir;
+
+const SCHEDULER_TICK_SECS: u64 = 60;
+const EXPORT_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Serializes read-modify-write cycles on the schedules file (UI edits vs. scheduler status updates).
+static SCHEDULES_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportCadence {
+    EveryHours { hours: u32 },
+    /// Local wall-clock time.
+    DailyAt { hour: u32, minute: u32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSchedule {
+    /// Empty when creating; assigned by `save_export_schedule`.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub cluster_ids: Vec<String>,
+    /// Backend export format: json | svg | drawio | png
+    pub format: String,
+    pub cadence: ExportCadence,
+    pub enabled: bool,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_run_at: Option<u64>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledExportResult {
+    pub schedule_id: String,
+    pub files: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_schedules_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("export_schedules.json"))
+}
+
+/// Callers must hold SCHEDULES_LOCK.
+async fn load_schedules() -> Result<Vec<ExportSchedule>, String> {
+    let path = get_schedules_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read export schedules".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse export schedules".to_string())
+}
+
+async fn save_schedules(schedules: &[ExportSchedule]) -> Result<(), String> {
+    let path = get_schedules_path().await?;
+
+    let content = serde_json::to_string_pretty(schedules)
+        .map_err(|_| "Failed to serialize export schedules".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write export schedules".to_string())
+}
+
+/// Most recent local `hour:minute` at or before `now`, as a Unix timestamp.
+fn last_daily_occurrence(now: u64, hour: u32, minute: u32) -> Option<u64> {
+    let now = Local.timestamp_opt(now as i64, 0).single()?;
+    let today = now.date_naive().and_hms_opt(hour, minute, 0)?;
+    let today = Local.from_local_datetime(&today).earliest()?;
+    let occurrence = if today <= now {
+        today
+    } else {
+        today.checked_sub_days(chrono::Days::new(1))?
+    };
+    u64::try_from(occurrence.timestamp()).ok()
+}
+
+fn is_due(schedule: &ExportSchedule, now: u64) -> bool {
+    let last = schedule.last_run_at.unwrap_or(schedule.created_at);
+    match schedule.cadence {
+        ExportCadence::EveryHours { hours } => now >= last + u64::from(hours.max(1)) * 3600,
+        ExportCadence::DailyAt { hour, minute } => {
+            last_daily_occurrence(now, hour, minute).is_some_and(|occurrence| occurrence > last)
+        }
+    }
+}
+
+fn file_extension(format: &str) -> &str {
+    match format {
+        "drawio" => "drawio",
+        "svg" => "svg",
+        "png" => "png",
+        _ => "json",
+    }
+}
+
+/// Keep generated filenames portable: cluster ids and schedule names may contain anything.
+fn filename_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect()
+}
+
+async fn export_cluster(client: &reqwest::Client, schedule: &ExportSchedule, cluster_id: &str) -> Result<String, String> {
+    let url = format!(
+        "{}/api/v1/clusters/{}/topology/export?format={}",
+        crate::loopback::base_url(BACKEND_PORT).await, cluster_id, schedule.format
+    );
+    let response = client
+        .post(&url)
+        .timeout(Duration::from_secs(EXPORT_REQUEST_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| format!("Export request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Backend returned {}", response.status()));
+    }
+    let data = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read export response: {}", e))?;
+
+    let filename = format!(
+        "{}-{}-{}.{}",
+        filename_component(&schedule.name),
+        filename_component(cluster_id),
+        Local::now().format("%Y%m%d-%H%M%S"),
+        file_extension(&schedule.format)
+    );
+    let path = write_export(&filename, &data, &schedule.format, Some(cluster_id.to_string())).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Run one schedule for all of its clusters, record the outcome, and notify the user.
+async fn run_schedule(app: &AppHandle, schedule: &ExportSchedule) -> ScheduledExportResult {
+    let mut result = ScheduledExportResult {
+        schedule_id: schedule.id.clone(),
+        files: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    match crate::http_client::shared(app).await {
+        Ok(client) => {
+            for cluster_id in &schedule.cluster_ids {
+                match export_cluster(&client, schedule, cluster_id).await {
+                    Ok(path) => result.files.push(path),
+                    Err(e) => result.errors.push(format!("{}: {}", cluster_id, e)),
+                }
+            }
+        }
+        Err(e) => result.errors.push(e),
+    }
+
+    {
+        let _guard = SCHEDULES_LOCK.lock().await;
+        if let Ok(mut schedules) = load_schedules().await {
+            if let Some(stored) = schedules.iter_mut().find(|s| s.id == schedule.id) {
+                stored.last_run_at = Some(now_secs());
+                stored.last_error = (!result.errors.is_empty()).then(|| result.errors.join("; "));
+            }
+            if let Err(e) = save_schedules(&schedules).await {
+                tracing::error!(error = %e, "Failed to record export schedule run");
+            }
+        }
+    }
+
+    let body = if result.errors.is_empty() {
+        format!("{} export(s) saved", result.files.len())
+    } else {
+        format!("{} saved, {} failed", result.files.len(), result.errors.len())
+    };
+    let _ = app
+        .notification()
+        .builder()
+        .title(format!("Scheduled export \"{}\"", schedule.name))
+        .body(body)
+        .show();
+    let _ = app.emit("export-schedule-completed", &result);
+
+    result
+}
+
+/// Check schedules once a minute and run whichever are due. Runs are sequential so a slow
+/// backend never sees overlapping exports.
+pub fn start_export_scheduler(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(SCHEDULER_TICK_SECS)).await;
+
+            let schedules = {
+                let _guard = SCHEDULES_LOCK.lock().await;
+                match load_schedules().await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Export scheduler skipped");
+                        continue;
+                    }
+                }
+            };
+
+            let now = now_secs();
+            for schedule in schedules.iter().filter(|s| s.enabled && is_due(s, now)) {
+                run_schedule(&app, schedule).await;
+            }
+        }
+    });
+}
+
+#[command]
+pub async fn list_export_schedules() -> Result<Vec<ExportSchedule>, String> {
+    let _guard = SCHEDULES_LOCK.lock().await;
+    load_schedules().await
+}
+
+/// Create (empty id) or update a schedule. Run history is preserved on update.
+#[command]
+pub async fn save_export_schedule(mut schedule: ExportSchedule) -> Result<ExportSchedule, String> {
+    if schedule.cluster_ids.is_empty() {
+        return Err("A scheduled export needs at least one cluster".to_string());
+    }
+    if let ExportCadence::DailyAt { hour, minute } = schedule.cadence {
+        if hour > 23 || minute > 59 {
+            return Err("Invalid time of day".to_string());
+        }
+    }
+
+    let _guard = SCHEDULES_LOCK.lock().await;
+    let mut schedules = load_schedules().await?;
+
+    match schedules.iter_mut().find(|s| !schedule.id.is_empty() && s.id == schedule.id) {
+        Some(existing) => {
+            schedule.created_at = existing.created_at;
+            schedule.last_run_at = existing.last_run_at;
+            schedule.last_error = existing.last_error.clone();
+            *existing = schedule.clone();
+        }
+        None => {
+            schedule.id = format!("{:016x}", rand::random::<u64>());
+            schedule.created_at = now_secs();
+            schedule.last_run_at = None;
+            schedule.last_error = None;
+            schedules.push(schedule.clone());
+        }
+    }
+
+    save_schedules(&schedules).await?;
+    Ok(schedule)
+}
+
+#[command]
+pub async fn delete_export_schedule(id: String) -> Result<(), String> {
+    let _guard = SCHEDULES_LOCK.lock().await;
+    let mut schedules = load_schedules().await?;
+    schedules.retain(|s| s.id != id);
+    save_schedules(&schedules).await
+}
+
+#[command]
+pub async fn run_export_schedule_now(app_handle: AppHandle, id: String) -> Result<ScheduledExportResult, String> {
+    let schedule = {
+        let _guard = SCHEDULES_LOCK.lock().await;
+        load_schedules()
+            .await?
+            .into_iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("Export schedule '{}' not found", id))?
+    };
+
+    Ok(run_schedule(&app_handle, &schedule).await)
+}
@@ -0,0 +1,246 @@
+// Chunked export transfer. `save_topology_export(data: Vec<u8>)` sends the whole payload as a JSON
+// number array through the invoke bridge, which falls over for multi-hundred-MB snapshots. Here the
+// frontend opens an upload, sends raw binary chunks (not JSON) that are appended to a temp file,
+// and the finished file is moved into the exports dir.
+//
+// Protocol: begin_export_upload -> append_export_chunk (raw body, `x-upload-id` header) ... ->
+// finish_export_upload | cancel_export_upload. Progress goes out as "export-upload-progress".
+// Each upload has its own lock, so chunks for different uploads are written concurrently; uploads
+// left idle (a reload mid-transfer never cancels) are evicted when the next one begins.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use tauri::ipc::{InvokeBody, Request};
+use tauri::{command, AppHandle, Emitter};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use super::{export_file_path, get_exports_dir, record_export, upload};
+
+const PARTIAL_DIR: &str = ".partial";
+/// Partial files older than this are leftovers from a crashed or abandoned upload.
+const STALE_PARTIAL_SECS: u64 = 24 * 60 * 60;
+/// Uploads without a chunk for this long are abandoned.
+const IDLE_UPLOAD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+struct PendingUpload {
+    file: tokio::fs::File,
+    temp_path: PathBuf,
+    filename: String,
+    format: String,
+    cluster: Option<String>,
+    received: u64,
+    total: Option<u64>,
+    last_activity: Instant,
+    /// Set once finished, cancelled or evicted, for chunks that were waiting on the lock.
+    closed: bool,
+}
+
+/// Only held to look uploads up; writes happen under each upload's own lock.
+static PENDING_UPLOADS: Mutex<BTreeMap<String, Arc<Mutex<PendingUpload>>>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Serialize)]
+struct UploadProgress<'a> {
+    upload_id: &'a str,
+    received: u64,
+    total: Option<u64>,
+}
+
+fn remove_stale_partials(partial_dir: &std::path::Path) {
+    let Ok(entries) = std::fs::read_dir(partial_dir) else {
+        return;
+    };
+    let cutoff = SystemTime::now() - Duration::from_secs(STALE_PARTIAL_SECS);
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified < cutoff);
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Close and delete uploads that have been idle longer than IDLE_UPLOAD_TIMEOUT.
+async fn evict_idle_uploads() {
+    let mut evicted = Vec::new();
+    PENDING_UPLOADS.lock().await.retain(|_, upload| {
+        // A locked upload is writing a chunk right now, so not idle
+        let idle = upload
+            .try_lock()
+            .is_ok_and(|u| u.last_activity.elapsed() > IDLE_UPLOAD_TIMEOUT);
+        if idle {
+            evicted.push(upload.clone());
+        }
+        !idle
+    });
+    for upload in evicted {
+        let mut upload = upload.lock().await;
+        upload.closed = true;
+        let _ = tokio::fs::remove_file(&upload.temp_path).await;
+    }
+}
+
+/// The upload with `upload_id`, without holding the map lock.
+async fn pending_upload(upload_id: &str) -> Result<Arc<Mutex<PendingUpload>>, String> {
+    PENDING_UPLOADS
+        .lock()
+        .await
+        .get(upload_id)
+        .cloned()
+        .ok_or_else(|| format!("Unknown upload: {}", upload_id))
+}
+
+/// Start a chunked export upload. `total_bytes` is optional; when given it goes out with progress
+/// events, chunks past it are rejected and finishing short of it fails. Returns the upload id to
+/// pass with every chunk.
+#[command]
+pub async fn begin_export_upload(
+    filename: String,
+    format: String,
+    cluster: Option<String>,
+    total_bytes: Option<u64>,
+) -> Result<String, String> {
+    // Validate the name up front rather than after the whole payload has been transferred
+    export_file_path(&filename).await?;
+
+    // Partial files live inside the exports dir so finishing is a same-filesystem rename
+    let partial_dir = get_exports_dir().await?.join(PARTIAL_DIR);
+    std::fs::create_dir_all(&partial_dir)
+        .map_err(|e| format!("Failed to create upload directory: {}", e))?;
+    remove_stale_partials(&partial_dir);
+    evict_idle_uploads().await;
+
+    let upload_id = format!("{:016x}", rand::random::<u64>());
+    let temp_path = partial_dir.join(format!("{}.part", upload_id));
+    let file = tokio::fs::File::create(&temp_path)
+        .await
+        .map_err(|e| format!("Failed to create upload file: {}", e))?;
+
+    PENDING_UPLOADS.lock().await.insert(
+        upload_id.clone(),
+        Arc::new(Mutex::new(PendingUpload {
+            file,
+            temp_path,
+            filename,
+            format,
+            cluster,
+            received: 0,
+            total: total_bytes,
+            last_activity: Instant::now(),
+            closed: false,
+        })),
+    );
+    Ok(upload_id)
+}
+
+/// Append one chunk. The chunk is the raw request body (`invoke(cmd, bytes, { headers })` with a
+/// Uint8Array/ArrayBuffer payload) and the upload id travels in the `x-upload-id` header.
+/// Returns the number of bytes received so far.
+#[command]
+pub async fn append_export_chunk(app_handle: AppHandle, request: Request<'_>) -> Result<u64, String> {
+    let upload_id = request
+        .headers()
+        .get("x-upload-id")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| "Missing x-upload-id header".to_string())?
+        .to_string();
+    let InvokeBody::Raw(chunk) = request.body() else {
+        return Err("Export chunks must be sent as raw binary".to_string());
+    };
+
+    let upload = pending_upload(&upload_id).await?;
+    let mut upload = upload.lock().await;
+    if upload.closed {
+        return Err(format!("Unknown upload: {}", upload_id));
+    }
+    let received = upload.received + chunk.len() as u64;
+    if upload.total.is_some_and(|total| received > total) {
+        return Err(format!(
+            "Chunk exceeds the declared size: {} of {} bytes",
+            received,
+            upload.total.unwrap_or_default()
+        ));
+    }
+    upload
+        .file
+        .write_all(chunk)
+        .await
+        .map_err(|e| format!("Failed to write upload chunk: {}", e))?;
+    upload.received = received;
+    upload.last_activity = Instant::now();
+
+    let _ = app_handle.emit(
+        "export-upload-progress",
+        UploadProgress {
+            upload_id: &upload_id,
+            received: upload.received,
+            total: upload.total,
+        },
+    );
+    Ok(upload.received)
+}
+
+/// Complete an upload: flush the temp file and move it into the exports dir. Returns the final path.
+#[command]
+pub async fn finish_export_upload(upload_id: String) -> Result<String, String> {
+    let upload = PENDING_UPLOADS
+        .lock()
+        .await
+        .remove(&upload_id)
+        .ok_or_else(|| format!("Unknown upload: {}", upload_id))?;
+    // Waits for a chunk still being written
+    let mut upload = upload.lock().await;
+    upload.closed = true;
+
+    let result = async {
+        if let Some(total) = upload.total {
+            if upload.received != total {
+                return Err(format!(
+                    "Upload incomplete: received {} of {} bytes",
+                    upload.received, total
+                ));
+            }
+        }
+        upload
+            .file
+            .sync_all()
+            .await
+            .map_err(|e| format!("Failed to flush upload: {}", e))?;
+
+        let file_path = export_file_path(&upload.filename).await?;
+        tokio::fs::rename(&upload.temp_path, &file_path)
+            .await
+            .map_err(|e| format!("Failed to move export into place: {}", e))?;
+        Ok(file_path)
+    }
+    .await;
+
+    let file_path = match result {
+        Ok(path) => path,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&upload.temp_path).await;
+            return Err(e);
+        }
+    };
+
+    record_export(&file_path, &upload.format, upload.cluster.take()).await?;
+    upload::spawn_auto_upload(file_path.clone());
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// Abort an upload and discard whatever was received.
+#[command]
+pub async fn cancel_export_upload(upload_id: String) -> Result<(), String> {
+    let upload = PENDING_UPLOADS.lock().await.remove(&upload_id);
+    if let Some(upload) = upload {
+        let mut upload = upload.lock().await;
+        upload.closed = true;
+        let _ = tokio::fs::remove_file(&upload.temp_path).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+// SVG parsing shared by the native renderers. The frontend hands over the serialized topology
+// SVG; rendering it here avoids the webview's print/canvas limits on large graphs.
+use std::sync::OnceLock;
+
+use usvg::{fontdb, PostProcessingSteps, TreeParsing, TreePostProc};
+
+/// System fonts are scanned once per process — loading them takes a noticeable fraction of a second.
+fn font_database() -> &'static fontdb::Database {
+    static FONTS: OnceLock<fontdb::Database> = OnceLock::new();
+    FONTS.get_or_init(|| {
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        db
+    })
+}
+
+/// Parse SVG markup into a usvg tree with text converted to paths, so node labels render
+/// identically in every output format.
+pub fn parse_svg(svg: &str) -> Result<usvg::Tree, String> {
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default())
+        .map_err(|e| format!("Failed to parse SVG: {}", e))?;
+    tree.postprocess(PostProcessingSteps::default(), font_database());
+    Ok(tree)
+}
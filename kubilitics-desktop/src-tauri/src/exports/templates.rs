@@ -0,0 +1,328 @@
+// Report templates: a title/subtitle with variable substitution, a legend, a company logo and the
+// timestamp format, applied to PDF and PNG exports so a team's outputs look the same.
+//
+// A template is applied by composing a new SVG around the topology — header and logo above,
+// legend below — which the PDF/PNG renderers then treat like any other SVG.
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use chrono::format::{Item, StrftimeItems};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+use tokio::sync::Mutex;
+use usvg::TreeWriting;
+
+use super::drawio::escape_xml;
+use super::svg::parse_svg;
+use crate::commands::get_app_data_dir;
+
+const DEFAULT_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+const PADDING: f32 = 24.0;
+const TITLE_FONT_SIZE: f32 = 20.0;
+const SUBTITLE_FONT_SIZE: f32 = 12.0;
+const LOGO_MAX_WIDTH: f32 = 160.0;
+const LOGO_HEIGHT: f32 = 40.0;
+const LEGEND_FONT_SIZE: f32 = 11.0;
+const LEGEND_ROW_HEIGHT: f32 = 20.0;
+const LEGEND_SWATCH: f32 = 12.0;
+const MIN_CANVAS_WIDTH: f32 = 480.0;
+
+/// Serializes read-modify-write cycles on the templates file.
+static TEMPLATES_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegendEntry {
+    pub label: String,
+    /// `#rrggbb`
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    /// Empty when creating; assigned by `save_export_template`.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Supports `{cluster}`, `{timestamp}`, `{date}` and `{version}`.
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    #[serde(default)]
+    pub legend: Vec<LegendEntry>,
+    /// PNG, JPEG or SVG file shown in the top-right corner.
+    #[serde(default)]
+    pub logo_path: Option<String>,
+    /// chrono/strftime format used for `{timestamp}`.
+    #[serde(default = "default_timestamp_format")]
+    pub timestamp_format: String,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+fn default_timestamp_format() -> String {
+    DEFAULT_TIMESTAMP_FORMAT.to_string()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_templates_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("export_templates.json"))
+}
+
+/// Callers must hold TEMPLATES_LOCK.
+async fn load_templates() -> Result<Vec<ExportTemplate>, String> {
+    let path = get_templates_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read export templates".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse export templates".to_string())
+}
+
+async fn save_templates(templates: &[ExportTemplate]) -> Result<(), String> {
+    let path = get_templates_path().await?;
+
+    let content = serde_json::to_string_pretty(templates)
+        .map_err(|_| "Failed to serialize export templates".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write export templates".to_string())
+}
+
+/// Look up a template by id.
+pub async fn get_template(id: &str) -> Result<ExportTemplate, String> {
+    let _guard = TEMPLATES_LOCK.lock().await;
+    load_templates()
+        .await?
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Export template not found: {}", id))
+}
+
+/// A template with its variables substituted and logo loaded, ready for `apply_template`.
+#[derive(Debug, Clone)]
+pub struct ResolvedTemplate {
+    title: String,
+    subtitle: Option<String>,
+    legend: Vec<LegendEntry>,
+    logo_data_url: Option<String>,
+}
+
+fn substitute(text: &str, cluster: Option<&str>, timestamp_format: &str) -> String {
+    let now = chrono::Local::now();
+    text.replace("{cluster}", cluster.unwrap_or(""))
+        .replace("{timestamp}", &now.format(timestamp_format).to_string())
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{version}", env!("CARGO_PKG_VERSION"))
+}
+
+async fn load_logo(path: &str) -> Result<String, String> {
+    let mime = match Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("svg") => "image/svg+xml",
+        _ => return Err(format!("Unsupported logo format: {}", path)),
+    };
+    let data = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Failed to read logo {}: {}", path, e))?;
+    Ok(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(data)
+    ))
+}
+
+pub async fn resolve_template(template: &ExportTemplate, cluster: Option<&str>) -> Result<ResolvedTemplate, String> {
+    let logo_data_url = match &template.logo_path {
+        Some(path) if !path.is_empty() => Some(load_logo(path).await?),
+        _ => None,
+    };
+    Ok(ResolvedTemplate {
+        title: substitute(&template.title, cluster, &template.timestamp_format),
+        subtitle: template
+            .subtitle
+            .as_deref()
+            .map(|s| substitute(s, cluster, &template.timestamp_format)),
+        legend: template.legend.clone(),
+        logo_data_url,
+    })
+}
+
+/// Lay legend entries out left to right, wrapping at `width`. Label widths are estimated —
+/// exact text metrics would need a layout pass, and a little slack is harmless here.
+fn legend_layout(legend: &[LegendEntry], width: f32) -> (Vec<(f32, f32)>, f32) {
+    let mut positions = Vec::with_capacity(legend.len());
+    let (mut x, mut y) = (PADDING, 0.0);
+    for entry in legend {
+        let entry_width = LEGEND_SWATCH + 6.0 + entry.label.chars().count() as f32 * LEGEND_FONT_SIZE * 0.6 + 18.0;
+        if x + entry_width > width - PADDING && x > PADDING {
+            x = PADDING;
+            y += LEGEND_ROW_HEIGHT;
+        }
+        positions.push((x, y));
+        x += entry_width;
+    }
+    let height = if legend.is_empty() { 0.0 } else { y + LEGEND_ROW_HEIGHT + PADDING / 2.0 };
+    (positions, height)
+}
+
+/// Wrap the topology SVG with the template's header, logo and legend.
+pub fn apply_template(svg: &str, template: &ResolvedTemplate) -> Result<String, String> {
+    // Re-serialize through usvg: text becomes paths and the output has an explicit size, so the
+    // result can be embedded as an image (nested SVG images are not text-shaped by usvg)
+    let tree = parse_svg(svg)?;
+    let topology_width = tree.size.width();
+    let topology_height = tree.size.height();
+    let flattened = tree.to_string(&usvg::XmlOptions::default());
+
+    let width = topology_width.max(MIN_CANVAS_WIDTH);
+    let mut header_height = PADDING;
+    if !template.title.is_empty() {
+        header_height += TITLE_FONT_SIZE + 6.0;
+    }
+    if template.subtitle.is_some() {
+        header_height += SUBTITLE_FONT_SIZE + 6.0;
+    }
+    if template.logo_data_url.is_some() {
+        header_height = header_height.max(PADDING + LOGO_HEIGHT);
+    }
+    header_height += PADDING / 2.0;
+
+    let (legend_positions, legend_height) = legend_layout(&template.legend, width);
+    let height = header_height + topology_height + legend_height + PADDING / 2.0;
+
+    let mut out = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}"><rect width="{w}" height="{h}" fill="#ffffff"/>"##,
+        w = width,
+        h = height
+    );
+
+    let mut cursor_y = PADDING;
+    if !template.title.is_empty() {
+        cursor_y += TITLE_FONT_SIZE;
+        out.push_str(&format!(
+            r##"<text x="{}" y="{}" font-family="Helvetica, Arial, sans-serif" font-size="{}" font-weight="bold" fill="#0f172a">{}</text>"##,
+            PADDING,
+            cursor_y,
+            TITLE_FONT_SIZE,
+            escape_xml(&template.title)
+        ));
+        cursor_y += 6.0;
+    }
+    if let Some(subtitle) = &template.subtitle {
+        cursor_y += SUBTITLE_FONT_SIZE;
+        out.push_str(&format!(
+            r##"<text x="{}" y="{}" font-family="Helvetica, Arial, sans-serif" font-size="{}" fill="#475569">{}</text>"##,
+            PADDING,
+            cursor_y,
+            SUBTITLE_FONT_SIZE,
+            escape_xml(subtitle)
+        ));
+    }
+    if let Some(logo) = &template.logo_data_url {
+        out.push_str(&format!(
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="xMaxYMid meet" href="{}"/>"#,
+            width - PADDING - LOGO_MAX_WIDTH,
+            PADDING / 2.0,
+            LOGO_MAX_WIDTH,
+            LOGO_HEIGHT,
+            logo
+        ));
+    }
+
+    out.push_str(&format!(
+        r#"<image x="{}" y="{}" width="{}" height="{}" href="data:image/svg+xml;base64,{}"/>"#,
+        (width - topology_width) / 2.0,
+        header_height,
+        topology_width,
+        topology_height,
+        base64::engine::general_purpose::STANDARD.encode(flattened)
+    ));
+
+    let legend_top = header_height + topology_height + PADDING / 2.0;
+    for (entry, (x, y)) in template.legend.iter().zip(legend_positions) {
+        let row_y = legend_top + y;
+        out.push_str(&format!(
+            r#"<rect x="{}" y="{}" width="{s}" height="{s}" rx="2" fill="{}"/>"#,
+            x,
+            row_y,
+            escape_xml(&entry.color),
+            s = LEGEND_SWATCH
+        ));
+        out.push_str(&format!(
+            r##"<text x="{}" y="{}" font-family="Helvetica, Arial, sans-serif" font-size="{}" fill="#334155">{}</text>"##,
+            x + LEGEND_SWATCH + 6.0,
+            row_y + LEGEND_SWATCH - 1.0,
+            LEGEND_FONT_SIZE,
+            escape_xml(&entry.label)
+        ));
+    }
+
+    out.push_str("</svg>");
+    Ok(out)
+}
+
+#[command]
+pub async fn list_export_templates() -> Result<Vec<ExportTemplate>, String> {
+    let _guard = TEMPLATES_LOCK.lock().await;
+    load_templates().await
+}
+
+/// Create (empty `id`) or update a template. Returns the stored template.
+#[command]
+pub async fn save_export_template(mut template: ExportTemplate) -> Result<ExportTemplate, String> {
+    if template.name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    // chrono panics when formatting with an invalid specifier, so reject it here
+    if StrftimeItems::new(&template.timestamp_format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("Invalid timestamp format: {}", template.timestamp_format));
+    }
+
+    let _guard = TEMPLATES_LOCK.lock().await;
+    let mut templates = load_templates().await?;
+    template.updated_at = now_secs();
+
+    match templates.iter_mut().find(|t| !template.id.is_empty() && t.id == template.id) {
+        Some(existing) => {
+            template.created_at = existing.created_at;
+            *existing = template.clone();
+        }
+        None => {
+            template.id = format!("{:016x}", rand::random::<u64>());
+            template.created_at = template.updated_at;
+            templates.push(template.clone());
+        }
+    }
+
+    save_templates(&templates).await?;
+    Ok(template)
+}
+
+#[command]
+pub async fn delete_export_template(id: String) -> Result<(), String> {
+    let _guard = TEMPLATES_LOCK.lock().await;
+    let mut templates = load_templates().await?;
+    templates.retain(|t| t.id != id);
+    save_templates(&templates).await
+}
@@ -0,0 +1,88 @@
+// Subset of the backend's topology graph contract (kubilitics-backend internal/models/topology.go)
+// needed by the Rust-side exporters. Unknown fields are ignored so contract additions never break
+// deserialization.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TopologyGraph {
+    #[serde(default)]
+    pub nodes: Vec<TopologyNode>,
+    #[serde(default)]
+    pub edges: Vec<TopologyEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopologyNode {
+    pub id: String,
+    pub kind: String,
+    #[serde(default)]
+    pub namespace: String,
+    pub name: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub computed: NodeComputed,
+    #[serde(default)]
+    pub position: Option<Position>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeComputed {
+    #[serde(default)]
+    pub health: String,
+    #[serde(default)]
+    pub replicas: Option<Replicas>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Replicas {
+    #[serde(default)]
+    pub desired: i64,
+    #[serde(default)]
+    pub ready: i64,
+    #[serde(default)]
+    pub available: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopologyEdge {
+    pub id: String,
+    pub source: String,
+    pub target: String,
+    #[serde(default)]
+    pub relationship_type: String,
+    #[serde(default)]
+    pub label: String,
+}
+
+const GRID_GAP_X: f64 = 180.0;
+const GRID_GAP_Y: f64 = 60.0;
+
+impl TopologyGraph {
+    pub fn from_value(value: &serde_json::Value) -> Result<Self, String> {
+        serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid topology graph: {}", e))
+    }
+
+    /// Assign grid positions to nodes without one — same layout as the backend's
+    /// `topologyexport.ApplySimpleLayout`, so exports match whichever side produced them.
+    pub fn apply_grid_layout(&mut self) {
+        let cols = ((self.nodes.len() as f64).sqrt().ceil() as usize).max(1);
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            if node.position.is_none() {
+                node.position = Some(Position {
+                    x: (i % cols) as f64 * GRID_GAP_X + 20.0,
+                    y: (i / cols) as f64 * GRID_GAP_Y + 20.0,
+                });
+            }
+        }
+    }
+}
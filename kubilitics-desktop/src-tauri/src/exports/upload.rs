@@ -0,0 +1,365 @@
+// Optional cloud upload of exports (S3, GCS, Azure Blob), e.g. to push nightly topology snapshots
+// to a team bucket. Credentials are never stored here: each provider's builder reads them from the
+// environment / standard credential chain (AWS_* / instance metadata, GOOGLE_APPLICATION_CREDENTIALS,
+// AZURE_STORAGE_*).
+//
+// Files are streamed up as multipart uploads, so a large export is never held in memory, through
+// whichever proxy `proxy::proxy_for` picks for the provider's endpoint.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::path::Path as ObjectPath;
+use object_store::{ClientOptions, ObjectStore, WriteMultipart};
+use serde::{Deserialize, Serialize};
+use tauri::{command, Url};
+use tokio::io::AsyncReadExt;
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+
+/// Upload history kept per destination.
+const MAX_HISTORY_PER_DESTINATION: usize = 50;
+/// Multipart part size; S3 requires at least 5 MiB for every part but the last.
+const PART_SIZE: usize = 8 * 1024 * 1024;
+/// Parts in flight at once.
+const MAX_CONCURRENT_PARTS: usize = 4;
+
+static DESTINATIONS_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudProvider {
+    S3,
+    Gcs,
+    Azure,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportDestination {
+    /// Empty when creating; assigned by `configure_export_destination`.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub provider: CloudProvider,
+    /// Bucket (S3/GCS) or container (Azure).
+    pub bucket: String,
+    /// Key prefix inside the bucket, e.g. "kubilitics/topology".
+    pub prefix: Option<String>,
+    pub region: Option<String>,
+    /// Custom endpoint for S3-compatible stores (MinIO, R2) or Azurite.
+    pub endpoint: Option<String>,
+    /// Upload every new export automatically.
+    pub auto_upload: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadRecord {
+    pub destination_id: String,
+    pub file: String,
+    pub object_key: String,
+    pub uploaded_at: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DestinationStore {
+    destinations: Vec<ExportDestination>,
+    history: Vec<UploadRecord>,
+}
+
+async fn get_destinations_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("export_destinations.json"))
+}
+
+/// Callers must hold DESTINATIONS_LOCK.
+async fn load_store() -> Result<DestinationStore, String> {
+    let path = get_destinations_path().await?;
+
+    let content = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(DestinationStore::default()),
+        Err(_) => return Err("Failed to read export destinations".to_string()),
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse export destinations".to_string())
+}
+
+async fn save_store(store: &DestinationStore) -> Result<(), String> {
+    let path = get_destinations_path().await?;
+
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|_| "Failed to serialize export destinations".to_string())?;
+
+    tokio::fs::write(&path, content)
+        .await
+        .map_err(|_| "Failed to write export destinations".to_string())
+}
+
+/// The endpoint the provider's requests go to, for picking a proxy. Azure's account host isn't
+/// known here; its shared domain still matches NO_PROXY entries like ".core.windows.net".
+fn service_url(destination: &ExportDestination) -> Option<Url> {
+    let url = match (&destination.endpoint, destination.provider) {
+        (Some(endpoint), _) => endpoint.clone(),
+        (None, CloudProvider::S3) => format!(
+            "https://s3.{}.amazonaws.com",
+            destination.region.as_deref().unwrap_or("us-east-1")
+        ),
+        (None, CloudProvider::Gcs) => "https://storage.googleapis.com".to_string(),
+        (None, CloudProvider::Azure) => "https://blob.core.windows.net".to_string(),
+    };
+    Url::parse(&url).ok()
+}
+
+async fn client_options(destination: &ExportDestination) -> ClientOptions {
+    let options = ClientOptions::new();
+    let proxy = match service_url(destination) {
+        Some(url) => crate::proxy::proxy_for(&url).await,
+        None => None,
+    };
+    match proxy {
+        Some(proxy) => options.with_proxy_url(proxy.as_str()),
+        None => options,
+    }
+}
+
+async fn build_object_store(destination: &ExportDestination) -> Result<Arc<dyn ObjectStore>, String> {
+    let options = client_options(destination).await;
+    let store: Arc<dyn ObjectStore> = match destination.provider {
+        CloudProvider::S3 => {
+            let mut builder = AmazonS3Builder::from_env()
+                .with_bucket_name(&destination.bucket)
+                .with_client_options(options);
+            if let Some(region) = &destination.region {
+                builder = builder.with_region(region);
+            }
+            if let Some(endpoint) = &destination.endpoint {
+                builder = builder
+                    .with_endpoint(endpoint)
+                    .with_allow_http(endpoint.starts_with("http://"));
+            }
+            Arc::new(builder.build().map_err(|e| e.to_string())?)
+        }
+        CloudProvider::Gcs => Arc::new(
+            GoogleCloudStorageBuilder::from_env()
+                .with_bucket_name(&destination.bucket)
+                .with_client_options(options)
+                .build()
+                .map_err(|e| e.to_string())?,
+        ),
+        CloudProvider::Azure => {
+            let mut builder = MicrosoftAzureBuilder::from_env()
+                .with_container_name(&destination.bucket)
+                .with_client_options(options);
+            if let Some(endpoint) = &destination.endpoint {
+                builder = builder
+                    .with_endpoint(endpoint.clone())
+                    .with_allow_http(endpoint.starts_with("http://"));
+            }
+            Arc::new(builder.build().map_err(|e| e.to_string())?)
+        }
+    };
+    Ok(store)
+}
+
+fn object_key(destination: &ExportDestination, file: &Path) -> String {
+    let filename = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match destination.prefix.as_deref().map(|p| p.trim_matches('/')) {
+        Some(prefix) if !prefix.is_empty() => format!("{}/{}", prefix, filename),
+        _ => filename,
+    }
+}
+
+async fn upload_file(destination: &ExportDestination, file: &Path) -> UploadRecord {
+    let key = object_key(destination, file);
+
+    let outcome: Result<(), String> = async {
+        crate::airgap::ensure_external_allowed("Export uploads")?;
+        let mut source = tokio::fs::File::open(file)
+            .await
+            .map_err(|e| format!("Failed to read export file: {}", e))?;
+        let store = build_object_store(destination).await?;
+        let upload = store
+            .put_multipart(&ObjectPath::from(key.as_str()))
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut writer = WriteMultipart::new_with_chunk_size(upload, PART_SIZE);
+
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let read = match source.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) => {
+                    let _ = writer.abort().await;
+                    return Err(format!("Failed to read export file: {}", e));
+                }
+            };
+            if let Err(e) = writer.wait_for_capacity(MAX_CONCURRENT_PARTS).await {
+                let _ = writer.abort().await;
+                return Err(e.to_string());
+            }
+            writer.write(&buf[..read]);
+        }
+        writer.finish().await.map_err(|e| e.to_string())?;
+        Ok(())
+    }
+    .await;
+
+    UploadRecord {
+        destination_id: destination.id.clone(),
+        file: file.to_string_lossy().to_string(),
+        object_key: key,
+        uploaded_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        success: outcome.is_ok(),
+        error: outcome.err(),
+    }
+}
+
+async fn append_history(records: Vec<UploadRecord>) -> Result<(), String> {
+    let _guard = DESTINATIONS_LOCK.lock().await;
+    let mut store = load_store().await?;
+    store.history.extend(records);
+
+    // Trim oldest entries per destination
+    let mut kept = Vec::with_capacity(store.history.len());
+    for record in store.history.into_iter().rev() {
+        let count = kept
+            .iter()
+            .filter(|r: &&UploadRecord| r.destination_id == record.destination_id)
+            .count();
+        if count < MAX_HISTORY_PER_DESTINATION {
+            kept.push(record);
+        }
+    }
+    kept.reverse();
+    store.history = kept;
+
+    save_store(&store).await
+}
+
+/// Upload a freshly written export to every destination with `auto_upload` set.
+/// Runs in the background — an unreachable bucket must never fail or delay the export itself.
+pub fn spawn_auto_upload(file: PathBuf) {
+    if crate::airgap::is_air_gapped() {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let destinations = {
+            let _guard = DESTINATIONS_LOCK.lock().await;
+            match load_store().await {
+                Ok(store) => store.destinations,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Export auto-upload skipped");
+                    return;
+                }
+            }
+        };
+
+        let mut records = Vec::new();
+        for destination in destinations.iter().filter(|d| d.auto_upload) {
+            let record = upload_file(destination, &file).await;
+            if let Some(e) = &record.error {
+                tracing::warn!(destination = %destination.name, error = %e, "Export upload failed");
+            }
+            records.push(record);
+        }
+
+        if !records.is_empty() {
+            if let Err(e) = append_history(records).await {
+                tracing::error!(error = %e, "Failed to record export uploads");
+            }
+        }
+    });
+}
+
+/// Create (empty id) or update a cloud destination for exports.
+#[command]
+pub async fn configure_export_destination(mut destination: ExportDestination) -> Result<ExportDestination, String> {
+    if destination.bucket.trim().is_empty() {
+        return Err("Bucket or container name is required".to_string());
+    }
+    // Surface obviously invalid configuration now rather than on the first upload
+    build_object_store(&destination).await?;
+
+    let _guard = DESTINATIONS_LOCK.lock().await;
+    let mut store = load_store().await?;
+
+    match store
+        .destinations
+        .iter_mut()
+        .find(|d| !destination.id.is_empty() && d.id == destination.id)
+    {
+        Some(existing) => *existing = destination.clone(),
+        None => {
+            destination.id = format!("{:016x}", rand::random::<u64>());
+            store.destinations.push(destination.clone());
+        }
+    }
+
+    save_store(&store).await?;
+    Ok(destination)
+}
+
+#[command]
+pub async fn list_export_destinations() -> Result<Vec<ExportDestination>, String> {
+    let _guard = DESTINATIONS_LOCK.lock().await;
+    Ok(load_store().await?.destinations)
+}
+
+#[command]
+pub async fn remove_export_destination(id: String) -> Result<(), String> {
+    let _guard = DESTINATIONS_LOCK.lock().await;
+    let mut store = load_store().await?;
+    store.destinations.retain(|d| d.id != id);
+    store.history.retain(|r| r.destination_id != id);
+    save_store(&store).await
+}
+
+/// Upload history, newest first, optionally for a single destination.
+#[command]
+pub async fn get_export_upload_history(destination_id: Option<String>) -> Result<Vec<UploadRecord>, String> {
+    let _guard = DESTINATIONS_LOCK.lock().await;
+    let store = load_store().await?;
+    Ok(store
+        .history
+        .into_iter()
+        .rev()
+        .filter(|r| destination_id.as_ref().is_none_or(|id| &r.destination_id == id))
+        .collect())
+}
+
+/// Upload an existing export file to one destination on demand.
+#[command]
+pub async fn upload_export(file_path: String, destination_id: String) -> Result<UploadRecord, String> {
+    let destination = {
+        let _guard = DESTINATIONS_LOCK.lock().await;
+        load_store()
+            .await?
+            .destinations
+            .into_iter()
+            .find(|d| d.id == destination_id)
+            .ok_or_else(|| format!("Export destination '{}' not found", destination_id))?
+    };
+
+    let path = PathBuf::from(&file_path);
+    if !path.is_file() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let record = upload_file(&destination, &path).await;
+    append_history(vec![record.clone()]).await?;
+    Ok(record)
+}
@@ -0,0 +1,477 @@
+// GitOps repositories linked to clusters: a Git repo (branch, optional subdirectory) configured in
+// gitops_repos.json and checked out under gitops/<id>/ with gix, so no git binary is needed. The
+// checkout is a read-only mirror — a sync makes a fresh shallow clone and swaps it in. Syncs are
+// off in air-gapped mode, and HTTP(S) remotes are fetched through the configured proxy.
+//
+// The drift report maps every manifest in the repo to its live object by apiVersion/kind,
+// namespace and name, and diffs it through a server-side dry-run apply (see live_diff.rs), so
+// defaulted fields don't count as drift. Directories with a kustomization are rendered with
+// kustomize instead of read file by file. Objects in the cluster that the repo doesn't mention
+// aren't reported; nothing says they belong to it.
+//
+// The checkout is untrusted input: symlinks in it are never followed, and every manifest path is
+// resolved and checked to stay inside the checkout before it is read.
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::command;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+use crate::live_diff::{DiffAction, ObjectDiff};
+
+const MAX_CONCURRENT_DIFFS: usize = 8;
+const FIELD_MANAGER: &str = "kubilitics-gitops";
+const MANIFEST_EXTENSIONS: [&str; 3] = ["yaml", "yml", "json"];
+
+/// Serializes read-modify-write cycles on gitops_repos.json.
+static REPOS_LOCK: Mutex<()> = Mutex::const_new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitOpsRepo {
+    /// Assigned on first save.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// HTTPS or SSH clone URL.
+    pub url: String,
+    /// Remote default branch when omitted.
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Subdirectory holding the manifests; the whole repo when omitted.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Contexts the repo deploys to.
+    #[serde(default)]
+    pub contexts: Vec<String>,
+    #[serde(default)]
+    pub last_synced_at: Option<u64>,
+    #[serde(default)]
+    pub last_commit: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GitOpsSyncResult {
+    pub repo: GitOpsRepo,
+    /// The commit moved since the previous sync.
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftStatus {
+    InSync,
+    Drifted,
+    /// In the repo, not in the cluster.
+    Missing,
+    /// Couldn't be compared (unknown kind, no access, invalid manifest).
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftEntry {
+    /// Manifest file (or kustomization directory) relative to the repo root.
+    pub source: String,
+    pub status: DriftStatus,
+    #[serde(flatten)]
+    pub diff: ObjectDiff,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftReport {
+    pub repo_id: String,
+    pub context: String,
+    pub commit: Option<String>,
+    pub generated_at: u64,
+    pub in_sync: usize,
+    pub drifted: usize,
+    pub missing: usize,
+    pub errors: usize,
+    pub entries: Vec<DriftEntry>,
+    /// Manifests that couldn't be read or rendered, with the reason.
+    pub source_errors: Vec<String>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_repos_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("gitops_repos.json"))
+}
+
+/// Callers must hold REPOS_LOCK.
+async fn load_repos() -> Result<Vec<GitOpsRepo>, String> {
+    let path = get_repos_path().await?;
+
+    if !fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| "Failed to read GitOps repos".to_string())?;
+
+    serde_json::from_str(&content).map_err(|_| "Failed to parse GitOps repos".to_string())
+}
+
+/// Callers must hold REPOS_LOCK.
+async fn save_repos(repos: &[GitOpsRepo]) -> Result<(), String> {
+    let path = get_repos_path().await?;
+
+    let content = serde_json::to_string_pretty(repos)
+        .map_err(|_| "Failed to serialize GitOps repos".to_string())?;
+
+    fs::write(&path, content)
+        .await
+        .map_err(|_| "Failed to write GitOps repos".to_string())
+}
+
+async fn find_repo(id: &str) -> Result<GitOpsRepo, String> {
+    let _guard = REPOS_LOCK.lock().await;
+    load_repos()
+        .await?
+        .into_iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| format!("GitOps repo not found: {}", id))
+}
+
+async fn checkout_dir(id: &str) -> Result<PathBuf, String> {
+    Ok(PathBuf::from(get_app_data_dir().await?)
+        .join("gitops")
+        .join(id))
+}
+
+fn head_commit(dir: &Path) -> Option<String> {
+    let repo = gix::open(dir).ok()?;
+    let id = repo.head_id().ok()?;
+    Some(id.to_string())
+}
+
+/// Shallow-clone `repo` into `dir` (which must not exist) and return the checked-out commit. `proxy`
+/// is used for HTTP(S) remotes.
+fn clone_into(repo: &GitOpsRepo, dir: &Path, proxy: Option<&str>) -> Result<String, String> {
+    let interrupt = AtomicBool::new(false);
+    let mut prepare = gix::prepare_clone(repo.url.as_str(), dir)
+        .map_err(|e| format!("Invalid repository {}: {}", repo.url, e))?
+        .with_shallow(gix::remote::fetch::Shallow::DepthAtRemote(NonZeroU32::MIN));
+    if let Some(proxy) = proxy {
+        prepare = prepare.with_in_memory_config_overrides([format!("http.proxy={}", proxy)]);
+    }
+    if let Some(branch) = repo.branch.as_deref().filter(|b| !b.is_empty()) {
+        prepare = prepare
+            .with_ref_name(Some(branch))
+            .map_err(|e| format!("Invalid branch {}: {}", branch, e))?;
+    }
+    let (mut checkout, _) = prepare
+        .fetch_then_checkout(gix::progress::Discard, &interrupt)
+        .map_err(|e| format!("Failed to fetch {}: {}", repo.url, e))?;
+    let (checked_out, _) = checkout
+        .main_worktree(gix::progress::Discard, &interrupt)
+        .map_err(|e| format!("Failed to check out {}: {}", repo.url, e))?;
+    let commit = checked_out
+        .head_id()
+        .map_err(|e| format!("Repository has no HEAD commit: {}", e))?;
+    Ok(commit.to_string())
+}
+
+#[command]
+pub async fn list_gitops_repos() -> Result<Vec<GitOpsRepo>, String> {
+    let _guard = REPOS_LOCK.lock().await;
+    load_repos().await
+}
+
+/// Create or update a repo link (matched by id). A changed URL or branch takes effect on the next
+/// sync.
+#[command]
+pub async fn configure_gitops_repo(mut repo: GitOpsRepo) -> Result<GitOpsRepo, String> {
+    if repo.name.trim().is_empty() {
+        return Err("Repository name is required".to_string());
+    }
+    if repo.url.trim().is_empty() {
+        return Err("Repository URL is required".to_string());
+    }
+    if repo
+        .path
+        .as_deref()
+        .is_some_and(|p| Path::new(p).is_absolute() || p.split(['/', '\\']).any(|c| c == ".."))
+    {
+        return Err("The manifest path must be relative to the repository root".to_string());
+    }
+    let _guard = REPOS_LOCK.lock().await;
+    let mut repos = load_repos().await?;
+    // Ids name the checkout directory, so only ones assigned here are accepted
+    match repos.iter().find(|r| r.id == repo.id) {
+        Some(existing) => {
+            repo.last_synced_at = existing.last_synced_at;
+            repo.last_commit = existing.last_commit.clone();
+        }
+        None => {
+            repo.id = format!("{:016x}", rand::random::<u64>());
+            repo.last_synced_at = None;
+            repo.last_commit = None;
+        }
+    }
+    repos.retain(|r| r.id != repo.id);
+    repos.push(repo.clone());
+    save_repos(&repos).await?;
+    Ok(repo)
+}
+
+#[command]
+pub async fn remove_gitops_repo(id: String) -> Result<(), String> {
+    {
+        let _guard = REPOS_LOCK.lock().await;
+        let mut repos = load_repos().await?;
+        if !repos.iter().any(|r| r.id == id) {
+            return Err(format!("GitOps repo not found: {}", id));
+        }
+        repos.retain(|r| r.id != id);
+        save_repos(&repos).await?;
+    }
+    let dir = checkout_dir(&id).await?;
+    if fs::try_exists(&dir).await.unwrap_or(false) {
+        fs::remove_dir_all(&dir)
+            .await
+            .map_err(|e| format!("Failed to remove the checkout: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Fetch the latest commit of the configured branch.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn sync_gitops_repo(id: String) -> Result<GitOpsSyncResult, String> {
+    crate::airgap::ensure_external_allowed("GitOps syncs")?;
+    let mut repo = find_repo(&id).await?;
+    let dir = checkout_dir(&id).await?;
+    let staging = dir.with_extension("sync");
+    // SCP-style SSH remotes don't parse as URLs; they never take an HTTP proxy anyway
+    let proxy = match tauri::Url::parse(&repo.url) {
+        Ok(url) => crate::proxy::proxy_for(&url).await.map(|p| p.to_string()),
+        Err(_) => None,
+    };
+
+    let commit = tokio::task::spawn_blocking({
+        let repo = repo.clone();
+        let dir = dir.clone();
+        move || {
+            if staging.exists() {
+                std::fs::remove_dir_all(&staging)
+                    .map_err(|e| format!("Failed to clear {}: {}", staging.display(), e))?;
+            }
+            if let Some(parent) = staging.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let commit = match clone_into(&repo, &staging, proxy.as_deref()) {
+                Ok(commit) => commit,
+                Err(e) => {
+                    let _ = std::fs::remove_dir_all(&staging);
+                    return Err(e);
+                }
+            };
+            if dir.exists() {
+                std::fs::remove_dir_all(&dir)
+                    .map_err(|e| format!("Failed to replace the checkout: {}", e))?;
+            }
+            std::fs::rename(&staging, &dir)
+                .map_err(|e| format!("Failed to replace the checkout: {}", e))?;
+            Ok::<_, String>(commit)
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let changed = repo.last_commit.as_deref() != Some(commit.as_str());
+    repo.last_commit = Some(commit);
+    repo.last_synced_at = Some(now_secs());
+    let _guard = REPOS_LOCK.lock().await;
+    let mut repos = load_repos().await?;
+    if let Some(stored) = repos.iter_mut().find(|r| r.id == id) {
+        stored.last_commit = repo.last_commit.clone();
+        stored.last_synced_at = repo.last_synced_at;
+    }
+    save_repos(&repos).await?;
+    Ok(GitOpsSyncResult { repo, changed })
+}
+
+fn is_manifest(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| MANIFEST_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// `path` resolved, if it stays inside `root` (itself canonical).
+fn inside(root: &Path, path: &Path) -> Option<PathBuf> {
+    path.canonicalize().ok().filter(|p| p.starts_with(root))
+}
+
+/// Manifest sources under `dir`: kustomization directories (not descended into) and plain files.
+/// Symlinks are skipped, not followed, and nothing resolving outside `root` is collected.
+fn collect_sources(
+    root: &Path,
+    dir: &Path,
+    kustomizations: &mut Vec<PathBuf>,
+    files: &mut Vec<PathBuf>,
+) {
+    if crate::kustomize::has_kustomization(dir) {
+        kustomizations.push(dir.to_path_buf());
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        let hidden = path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if hidden || metadata.file_type().is_symlink() {
+            continue;
+        }
+        let Some(path) = inside(root, &path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_sources(root, &path, kustomizations, files);
+        } else if metadata.is_file() && is_manifest(&path) {
+            files.push(path);
+        }
+    }
+}
+
+/// Objects from every manifest source, each with its source label. `root` and `manifests` are
+/// canonical.
+async fn repo_objects(root: &Path, manifests: &Path) -> (Vec<(String, Value)>, Vec<String>) {
+    let (kustomizations, files) = tokio::task::spawn_blocking({
+        let root = root.to_path_buf();
+        let manifests = manifests.to_path_buf();
+        move || {
+            let mut kustomizations = Vec::new();
+            let mut files = Vec::new();
+            collect_sources(&root, &manifests, &mut kustomizations, &mut files);
+            (kustomizations, files)
+        }
+    })
+    .await
+    .unwrap_or_default();
+    let label = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    };
+
+    let mut objects = Vec::new();
+    let mut errors = Vec::new();
+    for dir in kustomizations {
+        let source = label(&dir);
+        let rendered = crate::kustomize::kustomize_build(dir.to_string_lossy().to_string(), None)
+            .await
+            .and_then(|build| crate::apply::parse_documents(&build.yaml));
+        match rendered {
+            Ok(documents) => objects.extend(documents.into_iter().map(|d| (source.clone(), d))),
+            Err(e) => errors.push(format!("{}: {}", source, e)),
+        }
+    }
+    for file in files {
+        let source = label(&file);
+        let parsed = fs::read_to_string(&file)
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|content| crate::apply::parse_documents(&content));
+        match parsed {
+            // Files that aren't Kubernetes objects (Helm values, CI config) are skipped quietly
+            Ok(documents) => objects.extend(
+                documents
+                    .into_iter()
+                    .filter(|d| d.get("apiVersion").is_some() && d.get("kind").is_some())
+                    .map(|d| (source.clone(), d)),
+            ),
+            Err(e) => errors.push(format!("{}: {}", source, e)),
+        }
+    }
+    (objects, errors)
+}
+
+/// Compare the repo's last synced checkout with a context.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn get_drift_report(repo_id: String, context: String) -> Result<DriftReport, String> {
+    let repo = find_repo(&repo_id).await?;
+    let Ok(root) = fs::canonicalize(checkout_dir(&repo_id).await?).await else {
+        return Err("The repository hasn't been synced yet".to_string());
+    };
+    let manifests = match repo.path.as_deref().filter(|p| !p.is_empty()) {
+        Some(path) => root.join(path),
+        None => root.clone(),
+    };
+    // Resolved so a symlinked manifest path can't point outside the checkout
+    let manifests = fs::canonicalize(&manifests)
+        .await
+        .ok()
+        .filter(|resolved| resolved.starts_with(&root));
+    let manifests = match manifests {
+        Some(resolved) if fs::metadata(&resolved).await.is_ok_and(|m| m.is_dir()) => resolved,
+        _ => {
+            return Err(format!(
+                "{} is not a directory in the repository",
+                repo.path.unwrap_or_default()
+            ))
+        }
+    };
+
+    let (objects, source_errors) = repo_objects(&root, &manifests).await;
+    let client = crate::k8s::client_for(&context).await?;
+    let entries: Vec<DriftEntry> = futures::stream::iter(objects.into_iter().enumerate())
+        .map(|(index, (source, document))| {
+            let client = client.clone();
+            async move {
+                let diff =
+                    crate::live_diff::diff_object(&client, index, document, FIELD_MANAGER).await;
+                let status = match diff.action {
+                    DiffAction::Create => DriftStatus::Missing,
+                    DiffAction::Unchanged => DriftStatus::InSync,
+                    DiffAction::Update | DiffAction::Conflict => DriftStatus::Drifted,
+                    DiffAction::Failed => DriftStatus::Error,
+                };
+                DriftEntry {
+                    source,
+                    status,
+                    diff,
+                }
+            }
+        })
+        .buffered(MAX_CONCURRENT_DIFFS)
+        .collect()
+        .await;
+
+    let count = |status: DriftStatus| entries.iter().filter(|e| e.status == status).count();
+    Ok(DriftReport {
+        repo_id,
+        context,
+        commit: head_commit(&root),
+        generated_at: now_secs(),
+        in_sync: count(DriftStatus::InSync),
+        drifted: count(DriftStatus::Drifted),
+        missing: count(DriftStatus::Missing),
+        errors: count(DriftStatus::Error),
+        entries,
+        source_errors,
+    })
+}
@@ -0,0 +1,277 @@
+// Helm releases, read straight from the cluster. Helm 3 keeps every revision of a release in a
+// Secret (type helm.sh/release.v1, labels owner=helm, name, version, status) whose `release` key is
+// base64 of the gzipped release JSON, so listing releases needs no helm binary. The binary is still
+// detected (`get_helm_info`) for the actions that need it.
+//
+// Only the default Secret storage driver is read; releases stored as ConfigMaps or in SQL don't show.
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use base64::{engine::general_purpose, Engine as _};
+use flate2::read::GzDecoder;
+use k8s_openapi::api::core::v1::Secret;
+use kube::api::ListParams;
+use kube::Api;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+
+const RELEASE_SECRET_TYPE: &str = "helm.sh/release.v1";
+const GZIP_MAGIC: [u8; 3] = [0x1f, 0x8b, 0x08];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HelmInfo {
+    pub binary_path: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HelmRelease {
+    pub name: String,
+    pub namespace: String,
+    pub revision: u32,
+    pub status: String,
+    pub chart: String,
+    pub chart_version: String,
+    pub app_version: Option<String>,
+    /// RFC 3339.
+    pub updated: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HelmReleaseDetail {
+    #[serde(flatten)]
+    pub release: HelmRelease,
+    pub notes: Option<String>,
+    pub manifest: String,
+}
+
+#[command]
+pub async fn get_helm_info() -> Result<HelmInfo, String> {
+    let Some(binary) = crate::tools::find_binary("helm") else {
+        return Ok(HelmInfo {
+            binary_path: None,
+            version: None,
+        });
+    };
+    let version = crate::tools::first_output_line(&binary, &["version", "--short"]).await;
+    Ok(HelmInfo {
+        binary_path: Some(binary.to_string_lossy().to_string()),
+        version,
+    })
+}
+
+/// Release JSON from a release Secret's `release` value.
+fn decode_release(secret: &Secret) -> Result<Value, String> {
+    let encoded = secret
+        .data
+        .as_ref()
+        .and_then(|d| d.get("release"))
+        .ok_or("Release secret has no release data")?;
+    let compressed = general_purpose::STANDARD
+        .decode(&encoded.0)
+        .map_err(|e| format!("Invalid release encoding: {}", e))?;
+    let mut json = Vec::new();
+    if compressed.starts_with(&GZIP_MAGIC) {
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut json)
+            .map_err(|e| format!("Invalid release data: {}", e))?;
+    } else {
+        json = compressed;
+    }
+    serde_json::from_slice(&json).map_err(|e| format!("Invalid release JSON: {}", e))
+}
+
+fn summarize(release: &Value) -> HelmRelease {
+    let text = |path: &str| {
+        release
+            .pointer(path)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    HelmRelease {
+        name: text("/name").unwrap_or_default(),
+        namespace: text("/namespace").unwrap_or_default(),
+        revision: release.get("version").and_then(Value::as_u64).unwrap_or(0) as u32,
+        status: text("/info/status").unwrap_or_else(|| "unknown".to_string()),
+        chart: text("/chart/metadata/name").unwrap_or_default(),
+        chart_version: text("/chart/metadata/version").unwrap_or_default(),
+        app_version: text("/chart/metadata/appVersion"),
+        updated: text("/info/last_deployed"),
+        description: text("/info/description"),
+    }
+}
+
+fn label<'a>(secret: &'a Secret, key: &str) -> Option<&'a str> {
+    secret
+        .metadata
+        .labels
+        .as_ref()?
+        .get(key)
+        .map(String::as_str)
+}
+
+fn revision_of(secret: &Secret) -> u32 {
+    label(secret, "version")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Release secrets in a namespace (all namespaces when `None`), optionally for one release.
+async fn release_secrets(
+    context: &str,
+    namespace: Option<&str>,
+    name: Option<&str>,
+) -> Result<Vec<Secret>, String> {
+    let client = crate::k8s::client_for(context).await?;
+    let secrets: Api<Secret> = match namespace {
+        Some(ns) => Api::namespaced(client, ns),
+        None => Api::all(client),
+    };
+    let mut selector = "owner=helm".to_string();
+    if let Some(name) = name {
+        selector.push_str(&format!(",name={}", name));
+    }
+    let params = ListParams::default()
+        .labels(&selector)
+        .fields(&format!("type={}", RELEASE_SECRET_TYPE));
+    let list = secrets
+        .list(&params)
+        .await
+        .map_err(|e| format!("Failed to list Helm releases: {}", e))?;
+    Ok(list.items)
+}
+
+/// Latest revision of every release. Only those secrets are decoded; the labels are enough to
+/// pick them.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn list_helm_releases(
+    context: String,
+    namespace: Option<String>,
+) -> Result<Vec<HelmRelease>, String> {
+    let secrets = release_secrets(&context, namespace.as_deref(), None).await?;
+    let mut latest: BTreeMap<(String, String), &Secret> = BTreeMap::new();
+    for secret in &secrets {
+        let key = (
+            secret.metadata.namespace.clone().unwrap_or_default(),
+            label(secret, "name").unwrap_or_default().to_string(),
+        );
+        match latest.get(&key) {
+            Some(current) if revision_of(current) >= revision_of(secret) => {}
+            _ => {
+                latest.insert(key, secret);
+            }
+        }
+    }
+    latest
+        .values()
+        .map(|secret| decode_release(secret).map(|r| summarize(&r)))
+        .collect()
+}
+
+/// Every revision of a release, newest first.
+#[command]
+pub async fn get_helm_release_history(
+    context: String,
+    namespace: String,
+    name: String,
+) -> Result<Vec<HelmRelease>, String> {
+    let mut secrets = release_secrets(&context, Some(&namespace), Some(&name)).await?;
+    if secrets.is_empty() {
+        return Err(format!("Release '{}' not found in {}", name, namespace));
+    }
+    secrets.sort_by_key(|s| std::cmp::Reverse(revision_of(s)));
+    secrets
+        .iter()
+        .map(|secret| decode_release(secret).map(|r| summarize(&r)))
+        .collect()
+}
+
+async fn release_revision(
+    context: &str,
+    namespace: &str,
+    name: &str,
+    revision: Option<u32>,
+) -> Result<Value, String> {
+    let secrets = release_secrets(context, Some(namespace), Some(name)).await?;
+    let secret = match revision {
+        Some(revision) => secrets.iter().find(|s| revision_of(s) == revision),
+        None => secrets.iter().max_by_key(|s| revision_of(s)),
+    }
+    .ok_or_else(|| match revision {
+        Some(revision) => format!("Revision {} of '{}' not found", revision, name),
+        None => format!("Release '{}' not found in {}", name, namespace),
+    })?;
+    decode_release(secret)
+}
+
+/// Notes and rendered manifest of a revision (latest when `None`).
+#[command]
+pub async fn get_helm_release(
+    context: String,
+    namespace: String,
+    name: String,
+    revision: Option<u32>,
+) -> Result<HelmReleaseDetail, String> {
+    let release = release_revision(&context, &namespace, &name, revision).await?;
+    Ok(HelmReleaseDetail {
+        release: summarize(&release),
+        notes: release
+            .pointer("/info/notes")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        manifest: release
+            .get("manifest")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// Overlay `overrides` on `base` the way Helm merges user values over chart defaults: maps merge
+/// key by key, anything else is replaced, and null deletes the key.
+fn merge_values(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                if value.is_null() {
+                    base.remove(key);
+                } else if let Some(existing) = base.get_mut(key) {
+                    merge_values(existing, value);
+                } else {
+                    base.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
+
+/// Values of a revision as YAML: the user-supplied ones, or with `all` the chart defaults merged
+/// underneath (`helm get values --all`).
+#[command]
+pub async fn get_helm_release_values(
+    context: String,
+    namespace: String,
+    name: String,
+    revision: Option<u32>,
+    all: bool,
+) -> Result<String, String> {
+    let release = release_revision(&context, &namespace, &name, revision).await?;
+    let config = release.get("config").cloned().unwrap_or(Value::Null);
+    let values = if all {
+        let mut merged = release
+            .pointer("/chart/values")
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Default::default()));
+        if !config.is_null() {
+            merge_values(&mut merged, &config);
+        }
+        merged
+    } else {
+        config
+    };
+    serde_yaml::to_string(&values).map_err(|e| format!("Failed to render values: {}", e))
+}
@@ -0,0 +1,80 @@
+// One outbound HTTP client for the shell's requests (sidecar health checks, connectivity and
+// latency probes, exports' backend calls, stream follows, analytics, crash reports and release
+// notes), kept in managed state so they share a connection pool instead of paying a TCP handshake
+// per request. It starts from `proxy::client_builder` and is rebuilt when the resolved proxy
+// changes; in air-gapped mode a separate restricted client is kept and rebuilt when the allowed
+// hosts change (they follow the kubeconfig).
+//
+// Timeouts differ per use, so they are set per request (`RequestBuilder::timeout`) and the client
+// only carries the connect timeout. Clients with settings of their own — a user-configured update
+// proxy, a cluster CA or SOCKS proxy in diagnostics, update downloads — still build one.
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::proxy::ResolvedProxy;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+/// Keeps long-lived responses (stream follows) from being silently dropped by NATs.
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+pub struct HttpClient {
+    /// The built client and the proxy it was built for.
+    cached: Mutex<Option<(ResolvedProxy, reqwest::Client)>>,
+    /// The air-gapped client and the allow-list it was built for.
+    restricted: Mutex<Option<(Arc<HashSet<String>>, reqwest::Client)>>,
+}
+
+fn configure(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    builder
+        .connect_timeout(CONNECT_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(TCP_KEEPALIVE)
+}
+
+impl HttpClient {
+    /// The shared client, built on first use. Clones are handles onto the same pool.
+    pub async fn get(&self) -> Result<reqwest::Client, String> {
+        if crate::airgap::is_air_gapped() {
+            let allowed = crate::airgap::allowed_hosts().await;
+            let mut restricted = self.restricted.lock().await;
+            if let Some((hosts, client)) = restricted.as_ref() {
+                if **hosts == *allowed {
+                    return Ok(client.clone());
+                }
+            }
+            let builder = crate::airgap::restrict_to(reqwest::Client::builder(), allowed.clone());
+            let client = configure(builder)
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            *restricted = Some((allowed, client.clone()));
+            return Ok(client);
+        }
+
+        let resolved = crate::proxy::resolve_proxy().await;
+        let mut cached = self.cached.lock().await;
+        if let Some((proxy, client)) = cached.as_ref() {
+            if *proxy == resolved {
+                return Ok(client.clone());
+            }
+        }
+        let builder = crate::proxy::apply_proxy(reqwest::Client::builder(), &resolved);
+        let client = configure(builder)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        *cached = Some((resolved, client.clone()));
+        Ok(client)
+    }
+}
+
+/// The shared client from `app`'s managed state.
+pub async fn shared(app: &AppHandle) -> Result<reqwest::Client, String> {
+    app.state::<HttpClient>().get().await
+}
@@ -0,0 +1,564 @@
+// Namespace hygiene: objects that are probably left over. ConfigMaps, Secrets and PVCs that no pod
+// or workload template references, Services whose selector matches no pod, finished Jobs that have
+// outlived their TTL (or `completed_job_age_days` without one) and failed pods older than
+// `failed_pod_age_days`. The findings form a cleanup plan; the user picks which to delete.
+//
+// Objects with an owner are left alone (their controller cleans them up), as are the ones the
+// system maintains itself: kube-root-ca.crt, service account and bootstrap tokens, Helm release
+// Secrets, and everything in kube-system, kube-public and kube-node-lease unless asked for.
+// Deletions carry the uid and resourceVersion the analysis saw, so an object that was replaced or
+// changed since is not deleted.
+use std::collections::HashSet;
+
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, StatefulSet};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, PersistentVolumeClaim, Pod, PodSpec, Secret, Service, ServiceAccount,
+};
+use k8s_openapi::api::networking::v1::Ingress;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+use kube::api::{DeleteParams, ListParams, Preconditions};
+use kube::{Api, Client, Resource};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+const DEFAULT_FAILED_POD_AGE_DAYS: u64 = 7;
+const DEFAULT_COMPLETED_JOB_AGE_DAYS: u64 = 7;
+const SYSTEM_CONFIGMAPS: [&str; 1] = ["kube-root-ca.crt"];
+const SYSTEM_SECRET_TYPES: [&str; 3] = [
+    "kubernetes.io/service-account-token",
+    "bootstrap.kubernetes.io/token",
+    "helm.sh/release.v1",
+];
+const SYSTEM_NAMESPACES: [&str; 3] = ["kube-system", "kube-public", "kube-node-lease"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HygieneCategory {
+    UnreferencedConfigMap,
+    UnreferencedSecret,
+    UnusedPersistentVolumeClaim,
+    ServiceWithoutPods,
+    FinishedJob,
+    FailedPod,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HygieneFinding {
+    pub category: HygieneCategory,
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+    pub uid: String,
+    pub resource_version: String,
+    pub reason: String,
+    /// Seconds since creation (finish time for Jobs).
+    pub age_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HygieneReport {
+    pub context: String,
+    pub namespace: Option<String>,
+    pub findings: Vec<HygieneFinding>,
+}
+
+/// A finding picked for deletion; `uid` and `resource_version` come from the finding.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CleanupItem {
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+    pub uid: String,
+    pub resource_version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupResult {
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+    pub deleted: bool,
+    pub error: Option<String>,
+}
+
+/// Namespace-qualified name, the key for every reference set.
+fn key(namespace: &str, name: &str) -> String {
+    format!("{}/{}", namespace, name)
+}
+
+fn age_secs(time: Option<&Time>) -> Option<u64> {
+    let time = time?;
+    let age = chrono::Utc::now()
+        .signed_duration_since(time.0)
+        .num_seconds();
+    u64::try_from(age).ok()
+}
+
+fn is_owned(metadata: &ObjectMeta) -> bool {
+    metadata
+        .owner_references
+        .as_ref()
+        .is_some_and(|owners| !owners.is_empty())
+}
+
+/// Everything a pod spec references by name.
+#[derive(Default)]
+struct References {
+    config_maps: HashSet<String>,
+    secrets: HashSet<String>,
+    claims: HashSet<String>,
+}
+
+impl References {
+    fn add_pod_spec(&mut self, namespace: &str, spec: &PodSpec) {
+        for volume in spec.volumes.iter().flatten() {
+            if let Some(cm) = &volume.config_map {
+                self.config_maps.insert(key(namespace, &cm.name));
+            }
+            if let Some(secret) = volume.secret.as_ref().and_then(|s| s.secret_name.as_ref()) {
+                self.secrets.insert(key(namespace, secret));
+            }
+            if let Some(claim) = &volume.persistent_volume_claim {
+                self.claims.insert(key(namespace, &claim.claim_name));
+            }
+            for source in volume
+                .projected
+                .iter()
+                .flat_map(|p| p.sources.iter().flatten())
+            {
+                if let Some(cm) = &source.config_map {
+                    self.config_maps.insert(key(namespace, &cm.name));
+                }
+                if let Some(secret) = &source.secret {
+                    self.secrets.insert(key(namespace, &secret.name));
+                }
+            }
+        }
+        let containers = spec
+            .containers
+            .iter()
+            .chain(spec.init_containers.iter().flatten());
+        for container in containers {
+            for from in container.env_from.iter().flatten() {
+                if let Some(cm) = &from.config_map_ref {
+                    self.config_maps.insert(key(namespace, &cm.name));
+                }
+                if let Some(secret) = &from.secret_ref {
+                    self.secrets.insert(key(namespace, &secret.name));
+                }
+            }
+            for value_from in container
+                .env
+                .iter()
+                .flatten()
+                .filter_map(|e| e.value_from.as_ref())
+            {
+                if let Some(cm) = &value_from.config_map_key_ref {
+                    self.config_maps.insert(key(namespace, &cm.name));
+                }
+                if let Some(secret) = &value_from.secret_key_ref {
+                    self.secrets.insert(key(namespace, &secret.name));
+                }
+            }
+        }
+        for pull_secret in spec.image_pull_secrets.iter().flatten() {
+            self.secrets.insert(key(namespace, &pull_secret.name));
+        }
+    }
+}
+
+fn api<K>(client: &Client, namespace: Option<&str>) -> Api<K>
+where
+    K: Resource<Scope = k8s_openapi::NamespaceResourceScope>,
+    <K as Resource>::DynamicType: Default,
+{
+    match namespace {
+        Some(namespace) => Api::namespaced(client.clone(), namespace),
+        None => Api::all(client.clone()),
+    }
+}
+
+async fn list<K>(client: &Client, namespace: Option<&str>) -> Result<Vec<K>, String>
+where
+    K: Resource<Scope = k8s_openapi::NamespaceResourceScope>
+        + Clone
+        + serde::de::DeserializeOwned
+        + std::fmt::Debug,
+    <K as Resource>::DynamicType: Default,
+{
+    api::<K>(client, namespace)
+        .list(&ListParams::default())
+        .await
+        .map(|list| list.items)
+        .map_err(|e| format!("Failed to list {}: {}", K::plural(&Default::default()), e))
+}
+
+fn namespace_name(metadata: &ObjectMeta) -> (String, String) {
+    (
+        metadata.namespace.clone().unwrap_or_default(),
+        metadata.name.clone().unwrap_or_default(),
+    )
+}
+
+/// Find likely leftovers in a namespace (all namespaces when `None`). System namespaces are skipped
+/// unless picked as `namespace` or `include_system_namespaces` is set.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn analyze_namespace_hygiene(
+    context: String,
+    namespace: Option<String>,
+    failed_pod_age_days: Option<u64>,
+    completed_job_age_days: Option<u64>,
+    include_system_namespaces: Option<bool>,
+) -> Result<HygieneReport, String> {
+    let client = crate::k8s::client_for(&context).await?;
+    let ns = namespace.as_deref();
+    let failed_pod_age = failed_pod_age_days.unwrap_or(DEFAULT_FAILED_POD_AGE_DAYS) * 86_400;
+    let completed_job_age =
+        completed_job_age_days.unwrap_or(DEFAULT_COMPLETED_JOB_AGE_DAYS) * 86_400;
+    let skip_system = ns.is_none() && !include_system_namespaces.unwrap_or(false);
+
+    let pods: Vec<Pod> = list(&client, ns).await?;
+    let deployments: Vec<Deployment> = list(&client, ns).await?;
+    let statefulsets: Vec<StatefulSet> = list(&client, ns).await?;
+    let daemonsets: Vec<DaemonSet> = list(&client, ns).await?;
+    let jobs: Vec<Job> = list(&client, ns).await?;
+    let cronjobs: Vec<CronJob> = list(&client, ns).await?;
+    let ingresses: Vec<Ingress> = list(&client, ns).await?;
+    let service_accounts: Vec<ServiceAccount> = list(&client, ns).await?;
+
+    let mut refs = References::default();
+    for pod in &pods {
+        let (pod_ns, _) = namespace_name(&pod.metadata);
+        if let Some(spec) = &pod.spec {
+            refs.add_pod_spec(&pod_ns, spec);
+        }
+    }
+    // Templates count too: a deployment scaled to zero still needs its config
+    let templates = deployments
+        .iter()
+        .map(|d| (&d.metadata, d.spec.as_ref().map(|s| &s.template)))
+        .chain(
+            statefulsets
+                .iter()
+                .map(|s| (&s.metadata, s.spec.as_ref().map(|s| &s.template))),
+        )
+        .chain(
+            daemonsets
+                .iter()
+                .map(|d| (&d.metadata, d.spec.as_ref().map(|s| &s.template))),
+        )
+        .chain(
+            jobs.iter()
+                .map(|j| (&j.metadata, j.spec.as_ref().map(|s| &s.template))),
+        )
+        .chain(cronjobs.iter().map(|c| {
+            (
+                &c.metadata,
+                c.spec
+                    .as_ref()
+                    .and_then(|s| s.job_template.spec.as_ref())
+                    .map(|s| &s.template),
+            )
+        }));
+    for (metadata, template) in templates {
+        let (owner_ns, _) = namespace_name(metadata);
+        if let Some(spec) = template.and_then(|t| t.spec.as_ref()) {
+            refs.add_pod_spec(&owner_ns, spec);
+        }
+    }
+    for ingress in &ingresses {
+        let (ingress_ns, _) = namespace_name(&ingress.metadata);
+        let tls = ingress.spec.iter().flat_map(|s| s.tls.iter().flatten());
+        for secret in tls.filter_map(|t| t.secret_name.as_ref()) {
+            refs.secrets.insert(key(&ingress_ns, secret));
+        }
+    }
+    for account in &service_accounts {
+        let (account_ns, _) = namespace_name(&account.metadata);
+        for secret in account
+            .secrets
+            .iter()
+            .flatten()
+            .filter_map(|s| s.name.as_ref())
+        {
+            refs.secrets.insert(key(&account_ns, secret));
+        }
+        for secret in account.image_pull_secrets.iter().flatten() {
+            refs.secrets.insert(key(&account_ns, &secret.name));
+        }
+    }
+    // StatefulSet claims are named <template>-<statefulset>-<ordinal>
+    let claim_prefixes: Vec<(String, String)> = statefulsets
+        .iter()
+        .flat_map(|s| {
+            let (sts_ns, sts_name) = namespace_name(&s.metadata);
+            s.spec
+                .iter()
+                .flat_map(|spec| spec.volume_claim_templates.iter().flatten())
+                .map(move |t| {
+                    let template = t.metadata.name.clone().unwrap_or_default();
+                    (sts_ns.clone(), format!("{}-{}-", template, sts_name))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+    let mut finding = |category, metadata: &ObjectMeta, kind: &str, reason: String, age| {
+        let (namespace, name) = namespace_name(metadata);
+        if skip_system && SYSTEM_NAMESPACES.contains(&namespace.as_str()) {
+            return;
+        }
+        findings.push(HygieneFinding {
+            category,
+            kind: kind.to_string(),
+            namespace,
+            name,
+            uid: metadata.uid.clone().unwrap_or_default(),
+            resource_version: metadata.resource_version.clone().unwrap_or_default(),
+            reason,
+            age_secs: age,
+        });
+    };
+
+    for cm in list::<ConfigMap>(&client, ns).await? {
+        let (cm_ns, name) = namespace_name(&cm.metadata);
+        if is_owned(&cm.metadata)
+            || SYSTEM_CONFIGMAPS.contains(&name.as_str())
+            || refs.config_maps.contains(&key(&cm_ns, &name))
+        {
+            continue;
+        }
+        finding(
+            HygieneCategory::UnreferencedConfigMap,
+            &cm.metadata,
+            "ConfigMap",
+            "Not referenced by any pod, workload template or ingress".to_string(),
+            age_secs(cm.metadata.creation_timestamp.as_ref()),
+        );
+    }
+
+    for secret in list::<Secret>(&client, ns).await? {
+        let (secret_ns, name) = namespace_name(&secret.metadata);
+        let system = secret
+            .type_
+            .as_deref()
+            .is_some_and(|t| SYSTEM_SECRET_TYPES.contains(&t));
+        if is_owned(&secret.metadata) || system || refs.secrets.contains(&key(&secret_ns, &name)) {
+            continue;
+        }
+        finding(
+            HygieneCategory::UnreferencedSecret,
+            &secret.metadata,
+            "Secret",
+            "Not referenced by any pod, workload template, ingress or service account".to_string(),
+            age_secs(secret.metadata.creation_timestamp.as_ref()),
+        );
+    }
+
+    for claim in list::<PersistentVolumeClaim>(&client, ns).await? {
+        let (claim_ns, name) = namespace_name(&claim.metadata);
+        let statefulset_claim = claim_prefixes
+            .iter()
+            .any(|(prefix_ns, prefix)| prefix_ns == &claim_ns && name.starts_with(prefix.as_str()));
+        if is_owned(&claim.metadata)
+            || statefulset_claim
+            || refs.claims.contains(&key(&claim_ns, &name))
+        {
+            continue;
+        }
+        finding(
+            HygieneCategory::UnusedPersistentVolumeClaim,
+            &claim.metadata,
+            "PersistentVolumeClaim",
+            "Not mounted by any pod or workload template".to_string(),
+            age_secs(claim.metadata.creation_timestamp.as_ref()),
+        );
+    }
+
+    for service in list::<Service>(&client, ns).await? {
+        let (service_ns, _) = namespace_name(&service.metadata);
+        // Services without a selector have manually managed endpoints
+        let Some(selector) = service
+            .spec
+            .as_ref()
+            .and_then(|s| s.selector.as_ref())
+            .filter(|s| !s.is_empty())
+        else {
+            continue;
+        };
+        let matched =
+            pods.iter().any(|pod| {
+                pod.metadata.namespace.as_deref() == Some(service_ns.as_str())
+                    && pod.metadata.labels.as_ref().is_some_and(|labels| {
+                        selector.iter().all(|(k, v)| labels.get(k) == Some(v))
+                    })
+            });
+        if is_owned(&service.metadata) || matched {
+            continue;
+        }
+        finding(
+            HygieneCategory::ServiceWithoutPods,
+            &service.metadata,
+            "Service",
+            "Its selector matches no pods".to_string(),
+            age_secs(service.metadata.creation_timestamp.as_ref()),
+        );
+    }
+
+    for job in &jobs {
+        if is_owned(&job.metadata) {
+            continue;
+        }
+        let status = job.status.as_ref();
+        let finished = status
+            .and_then(|s| s.conditions.as_ref())
+            .and_then(|conditions| {
+                conditions
+                    .iter()
+                    .find(|c| (c.type_ == "Complete" || c.type_ == "Failed") && c.status == "True")
+            });
+        let Some(condition) = finished else { continue };
+        let finished_at = status
+            .and_then(|s| s.completion_time.as_ref())
+            .or(condition.last_transition_time.as_ref());
+        let Some(age) = age_secs(finished_at) else {
+            continue;
+        };
+        let ttl = job
+            .spec
+            .as_ref()
+            .and_then(|s| s.ttl_seconds_after_finished)
+            .map(|t| t.max(0) as u64);
+        let reason = match ttl {
+            Some(ttl) if age > ttl => {
+                format!("Finished ({}) past its {}s TTL", condition.type_, ttl)
+            }
+            None if age > completed_job_age => {
+                format!(
+                    "Finished ({}) over {} days ago",
+                    condition.type_,
+                    completed_job_age / 86_400
+                )
+            }
+            _ => continue,
+        };
+        finding(
+            HygieneCategory::FinishedJob,
+            &job.metadata,
+            "Job",
+            reason,
+            Some(age),
+        );
+    }
+
+    for pod in &pods {
+        let failed = pod.status.as_ref().and_then(|s| s.phase.as_deref()) == Some("Failed");
+        let age = age_secs(
+            pod.status
+                .as_ref()
+                .and_then(|s| s.start_time.as_ref())
+                .or(pod.metadata.creation_timestamp.as_ref()),
+        );
+        // Failed pods of a Job are the Job's to clean up
+        let job_pod = pod
+            .metadata
+            .owner_references
+            .iter()
+            .flatten()
+            .any(|o| o.kind == "Job");
+        if !failed || job_pod || age.is_none_or(|a| a < failed_pod_age) {
+            continue;
+        }
+        let reason = pod
+            .status
+            .as_ref()
+            .and_then(|s| s.reason.clone())
+            .map(|r| format!("Failed ({})", r))
+            .unwrap_or_else(|| "Failed".to_string());
+        finding(
+            HygieneCategory::FailedPod,
+            &pod.metadata,
+            "Pod",
+            reason,
+            age,
+        );
+    }
+
+    Ok(HygieneReport {
+        context,
+        namespace,
+        findings,
+    })
+}
+
+async fn delete_item(
+    client: &Client,
+    item: &CleanupItem,
+    params: &DeleteParams,
+) -> Result<(), String> {
+    let ns = Some(item.namespace.as_str());
+    let result = match item.kind.as_str() {
+        "ConfigMap" => api::<ConfigMap>(client, ns)
+            .delete(&item.name, params)
+            .await
+            .map(|_| ()),
+        "Secret" => api::<Secret>(client, ns)
+            .delete(&item.name, params)
+            .await
+            .map(|_| ()),
+        "PersistentVolumeClaim" => api::<PersistentVolumeClaim>(client, ns)
+            .delete(&item.name, params)
+            .await
+            .map(|_| ()),
+        "Service" => api::<Service>(client, ns)
+            .delete(&item.name, params)
+            .await
+            .map(|_| ()),
+        "Job" => api::<Job>(client, ns)
+            .delete(&item.name, params)
+            .await
+            .map(|_| ()),
+        "Pod" => api::<Pod>(client, ns)
+            .delete(&item.name, params)
+            .await
+            .map(|_| ()),
+        other => return Err(format!("Cleanup doesn't delete {}", other)),
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Delete the chosen findings. Jobs take their pods with them. Each deletion is conditional on the
+/// object's uid and resourceVersion being those of the finding; the API server refuses it
+/// otherwise. `dry_run` asks the API server to validate the deletions without carrying them out.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn apply_cleanup_plan(
+    context: String,
+    items: Vec<CleanupItem>,
+    dry_run: bool,
+) -> Result<Vec<CleanupResult>, String> {
+    let client = crate::k8s::client_for(&context).await?;
+    let mut results = Vec::with_capacity(items.len());
+    for item in items {
+        let params = DeleteParams {
+            dry_run,
+            preconditions: Some(Preconditions {
+                uid: Some(item.uid.clone()),
+                resource_version: Some(item.resource_version.clone()),
+            }),
+            ..DeleteParams::background()
+        };
+        let outcome = delete_item(&client, &item, &params).await;
+        results.push(CleanupResult {
+            deleted: outcome.is_ok(),
+            error: outcome.err(),
+            kind: item.kind,
+            namespace: item.namespace,
+            name: item.name,
+        });
+    }
+    Ok(results)
+}
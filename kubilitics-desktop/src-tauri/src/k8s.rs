@@ -0,0 +1,148 @@
+// Direct Kubernetes API access from the shell (kube-rs), for the features that need a live
+// connection the Go backend's REST API can't carry well: port-forwards, exec, log follows, watches.
+//
+// Clients are built from the same kubeconfig the backend uses (custom path if set), with the
+// context's SOCKS proxy — or, without one, the app-wide proxy for the API server — and request
+// policy applied, so the shell and the backend reach a cluster the same way. Request/response clients retry idempotent reads (GET, HEAD) that fail to connect
+// or get 429/502/503/504, per the policy's retries and backoff.
+use std::time::Duration;
+
+use http::{Method, Request, Response, StatusCode};
+use kube::client::{Body, ClientBuilder};
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use kube::{Client, Config};
+use tauri::Url;
+use tower::buffer::BufferLayer;
+use tower::retry::{Policy, RetryLayer};
+
+use crate::cluster_policy::RequestPolicy;
+
+/// Requests queued in front of the connection pool, so the retry layer can clone the service.
+const BUFFER_CAPACITY: usize = 1024;
+
+#[derive(Clone)]
+struct RetryPolicy {
+    remaining: u32,
+    backoff: Duration,
+}
+
+impl<B, E> Policy<Request<Body>, Response<B>, E> for RetryPolicy {
+    type Future = tokio::time::Sleep;
+
+    fn retry(
+        &mut self,
+        _req: &mut Request<Body>,
+        result: &mut Result<Response<B>, E>,
+    ) -> Option<Self::Future> {
+        let retryable = match result {
+            Ok(response) => matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            Err(_) => true,
+        };
+        if !retryable || self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let delay = self.backoff;
+        self.backoff = self.backoff.saturating_mul(2);
+        Some(tokio::time::sleep(delay))
+    }
+
+    /// Only reads are retried; kube sends them without a body.
+    fn clone_request(&mut self, req: &Request<Body>) -> Option<Request<Body>> {
+        if !matches!(*req.method(), Method::GET | Method::HEAD) {
+            return None;
+        }
+        let mut clone = Request::new(Body::empty());
+        *clone.method_mut() = req.method().clone();
+        *clone.uri_mut() = req.uri().clone();
+        *clone.version_mut() = req.version();
+        *clone.headers_mut() = req.headers().clone();
+        Some(clone)
+    }
+}
+
+async fn config_for(context: &str) -> Result<Config, String> {
+    let path = crate::commands::get_kubeconfig_path(None).await?;
+    let kubeconfig = Kubeconfig::read_from(&path)
+        .map_err(|e| format!("Failed to read kubeconfig: {}", e))?;
+    let options = KubeConfigOptions {
+        context: Some(context.to_string()),
+        ..Default::default()
+    };
+    let mut config = Config::from_custom_kubeconfig(kubeconfig, &options)
+        .await
+        .map_err(|e| format!("Failed to load context '{}': {}", context, e))?;
+
+    let policy = crate::cluster_policy::policy_for(context).await;
+    config.connect_timeout = Some(Duration::from_secs(policy.request_timeout_secs));
+    config.read_timeout = Some(Duration::from_secs(policy.request_timeout_secs));
+
+    if let Some(proxy) = crate::socks::context_proxy(context).await {
+        config.proxy_url = Some(
+            kube_proxy_uri(&proxy).ok_or_else(|| format!("Invalid SOCKS proxy for '{}'", context))?,
+        );
+    } else if let Some(proxy) = app_proxy_for(&config).await {
+        match kube_proxy_uri(&proxy) {
+            Some(uri) => config.proxy_url = Some(uri),
+            None => tracing::warn!(
+                context,
+                scheme = proxy.scheme(),
+                "Proxy scheme not supported for cluster connections; connecting directly"
+            ),
+        }
+    }
+    Ok(config)
+}
+
+/// The app-wide proxy (manual, PAC or system) for the context's API server, if it isn't bypassed.
+async fn app_proxy_for(config: &Config) -> Option<Url> {
+    let cluster_url = Url::parse(&config.cluster_url.to_string()).ok()?;
+    crate::proxy::proxy_for(&cluster_url).await
+}
+
+/// `proxy` as kube takes it. kube connects through socks5:// and http:// proxies only; its SOCKS
+/// connector hands host names to the proxy, so socks5h:// is the same thing.
+fn kube_proxy_uri(proxy: &Url) -> Option<http::Uri> {
+    let mut proxy = proxy.clone();
+    if proxy.scheme() == "socks5h" {
+        proxy.set_scheme("socks5").ok()?;
+    }
+    if !matches!(proxy.scheme(), "socks5" | "http") {
+        return None;
+    }
+    proxy.as_str().parse().ok()
+}
+
+fn build(context: &str, config: Config) -> Result<Client, String> {
+    Client::try_from(config).map_err(|e| format!("Failed to create client for '{}': {}", context, e))
+}
+
+/// A client for one kubeconfig context, for request/response calls.
+pub async fn client_for(context: &str) -> Result<Client, String> {
+    let config = config_for(context).await?;
+    let policy: RequestPolicy = crate::cluster_policy::policy_for(context).await;
+    let builder = ClientBuilder::try_from(config)
+        .map_err(|e| format!("Failed to create client for '{}': {}", context, e))?;
+    let retry = RetryPolicy {
+        remaining: policy.retries,
+        backoff: Duration::from_millis(policy.backoff_ms),
+    };
+    Ok(builder
+        .with_layer(&BufferLayer::new(BUFFER_CAPACITY))
+        .with_layer(&RetryLayer::new(retry))
+        .build())
+}
+
+/// A client for long-lived streams (port-forward, exec, follows, watches): no read timeout, since
+/// an idle stream is not a failed one.
+pub async fn streaming_client_for(context: &str) -> Result<Client, String> {
+    let mut config = config_for(context).await?;
+    config.read_timeout = None;
+    build(context, config)
+}
@@ -0,0 +1,125 @@
+// kubectl plugins installed on this machine, so the UI can offer actions like "open with
+// kubectl-neat". A plugin is any executable named kubectl-<name> in the tool search path (see
+// tools.rs) or krew's bin directory; like kubectl, the first one found for a name wins and later
+// ones are reported as shadowed. Versions and descriptions come from krew's receipts; plugins
+// installed by hand have none, and are never run just to ask.
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_yaml::Value;
+use tauri::command;
+
+const PLUGIN_PREFIX: &str = "kubectl-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginSource {
+    Krew,
+    Path,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KubectlPlugin {
+    /// As typed after `kubectl` (kubectl-view_secret → "view-secret"; "ns" for kubectl-ns).
+    pub name: String,
+    pub path: String,
+    pub source: PluginSource,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// Another plugin with the same name comes earlier and is the one kubectl runs.
+    pub shadowed: bool,
+}
+
+/// `$KREW_ROOT`, or ~/.krew.
+fn krew_root() -> Option<PathBuf> {
+    std::env::var_os("KREW_ROOT")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".krew")))
+}
+
+/// Version and short description from krew's receipt for a plugin.
+fn krew_receipt(krew_root: &std::path::Path, name: &str) -> (Option<String>, Option<String>) {
+    let path = krew_root.join("receipts").join(format!("{}.yaml", name));
+    let Some(receipt) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_yaml::from_str::<Value>(&c).ok())
+    else {
+        return (None, None);
+    };
+    let text = |key: &str| {
+        receipt
+            .get("spec")
+            .and_then(|s| s.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    (text("version"), text("shortDescription"))
+}
+
+/// Plugin name from a file name, or `None` when it isn't one.
+fn plugin_name(file_name: &str) -> Option<String> {
+    let stem = if cfg!(target_os = "windows") {
+        file_name
+            .strip_suffix(".exe")
+            .or_else(|| file_name.strip_suffix(".cmd"))?
+    } else {
+        file_name
+    };
+    let name = stem.strip_prefix(PLUGIN_PREFIX)?;
+    (!name.is_empty()).then(|| name.replace('_', "-"))
+}
+
+#[command]
+pub async fn list_kubectl_plugins() -> Result<Vec<KubectlPlugin>, String> {
+    let krew_root = krew_root();
+    let krew_bin = krew_root.as_ref().map(|r| r.join("bin"));
+    let mut dirs = crate::tools::search_dirs();
+    if let Some(bin) = &krew_bin {
+        if !dirs.contains(bin) {
+            dirs.push(bin.clone());
+        }
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut seen = HashSet::new();
+        let mut plugins = Vec::new();
+        for dir in dirs {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut found: Vec<(String, PathBuf)> = entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = plugin_name(&entry.file_name().to_string_lossy())?;
+                    let path = entry.path();
+                    crate::tools::is_executable(&path).then_some((name, path))
+                })
+                .collect();
+            found.sort();
+
+            let from_krew = krew_bin.as_ref() == Some(&dir);
+            for (name, path) in found {
+                let (version, description) = match (&krew_root, from_krew) {
+                    (Some(root), true) => krew_receipt(root, &name),
+                    _ => (None, None),
+                };
+                plugins.push(KubectlPlugin {
+                    shadowed: !seen.insert(name.clone()),
+                    name,
+                    path: path.to_string_lossy().to_string(),
+                    source: if from_krew {
+                        PluginSource::Krew
+                    } else {
+                        PluginSource::Path
+                    },
+                    version,
+                    description,
+                });
+            }
+        }
+        plugins
+    })
+    .await
+    .map_err(|e| format!("Plugin scan failed: {}", e))
+}
@@ -0,0 +1,162 @@
+// Kustomize directories (overlays of a GitOps repo): rendered with the kustomize binary when it's
+// installed, otherwise with the copy built into kubectl (`kubectl kustomize`). The rendered YAML
+// goes through the same pipeline as any manifest — `validate_manifest`, and `apply_manifest` whose
+// dry run is the diff against the cluster — and `apply_kustomization` does build + apply in one.
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::command;
+
+use crate::apply::ApplyResult;
+
+const BUILD_TIMEOUT: Duration = Duration::from_secs(60);
+const KUSTOMIZATION_FILES: [&str; 3] = ["kustomization.yaml", "kustomization.yml", "Kustomization"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KustomizeTool {
+    Kustomize,
+    Kubectl,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KustomizeInfo {
+    /// Tool `kustomize_build` uses; none when neither is installed.
+    pub tool: Option<KustomizeTool>,
+    pub binary_path: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KustomizeObject {
+    pub api_version: String,
+    pub kind: String,
+    pub namespace: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KustomizeBuild {
+    /// The kustomization directory that was built.
+    pub path: String,
+    pub tool: KustomizeTool,
+    pub yaml: String,
+    pub objects: Vec<KustomizeObject>,
+}
+
+fn find_tool() -> Option<(KustomizeTool, PathBuf)> {
+    crate::tools::find_binary("kustomize")
+        .map(|path| (KustomizeTool::Kustomize, path))
+        .or_else(|| crate::tools::find_binary("kubectl").map(|path| (KustomizeTool::Kubectl, path)))
+}
+
+/// `dir` holds a kustomization file.
+pub(crate) fn has_kustomization(dir: &Path) -> bool {
+    KUSTOMIZATION_FILES.iter().any(|f| dir.join(f).is_file())
+}
+
+/// The kustomization directory for `path`, which may also name the kustomization file itself.
+fn kustomization_dir(path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path);
+    let dir = if path.is_file() {
+        path.parent().unwrap_or(Path::new(".")).to_path_buf()
+    } else {
+        path.to_path_buf()
+    };
+    if !has_kustomization(&dir) {
+        return Err(format!("No kustomization file in {}", dir.display()));
+    }
+    Ok(dir)
+}
+
+#[command]
+pub async fn get_kustomize_info() -> Result<KustomizeInfo, String> {
+    let Some((tool, binary)) = find_tool() else {
+        return Ok(KustomizeInfo {
+            tool: None,
+            binary_path: None,
+            version: None,
+        });
+    };
+    let version = match tool {
+        KustomizeTool::Kustomize => crate::tools::first_output_line(&binary, &["version"]).await,
+        KustomizeTool::Kubectl => {
+            crate::tools::first_output_line(&binary, &["version", "--client"]).await
+        }
+    };
+    Ok(KustomizeInfo {
+        tool: Some(tool),
+        binary_path: Some(binary.to_string_lossy().to_string()),
+        version,
+    })
+}
+
+/// Render a kustomization directory. `enable_helm` allows `helmCharts` entries (needs helm on
+/// PATH), which kustomize leaves off by default.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn kustomize_build(
+    path: String,
+    enable_helm: Option<bool>,
+) -> Result<KustomizeBuild, String> {
+    let dir = kustomization_dir(&path)?;
+    let (tool, binary) = find_tool().ok_or("Neither kustomize nor kubectl is installed")?;
+
+    let mut cmd = tokio::process::Command::new(&binary);
+    match tool {
+        KustomizeTool::Kustomize => cmd.arg("build"),
+        KustomizeTool::Kubectl => cmd.arg("kustomize"),
+    };
+    cmd.arg(&dir).kill_on_drop(true);
+    if enable_helm.unwrap_or(false) {
+        cmd.arg("--enable-helm");
+    }
+    let output = tokio::time::timeout(BUILD_TIMEOUT, cmd.output())
+        .await
+        .map_err(|_| "kustomize build timed out".to_string())?
+        .map_err(|e| format!("Failed to run {}: {}", binary.display(), e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("kustomize build failed: {}", stderr.trim()));
+    }
+
+    let yaml = String::from_utf8(output.stdout)
+        .map_err(|_| "kustomize produced output that isn't UTF-8".to_string())?;
+    let objects = crate::apply::parse_documents(&yaml)?
+        .iter()
+        .map(|document| {
+            let text = |pointer: &str| {
+                document
+                    .pointer(pointer)
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            };
+            KustomizeObject {
+                api_version: text("/apiVersion").unwrap_or_default(),
+                kind: text("/kind").unwrap_or_default(),
+                namespace: text("/metadata/namespace"),
+                name: text("/metadata/name").unwrap_or_default(),
+            }
+        })
+        .collect();
+    Ok(KustomizeBuild {
+        path: dir.to_string_lossy().to_string(),
+        tool,
+        yaml,
+        objects,
+    })
+}
+
+/// Build a kustomization and server-side apply the result; with `dry_run` this is a diff.
+#[command]
+pub async fn apply_kustomization(
+    context: String,
+    path: String,
+    enable_helm: Option<bool>,
+    dry_run: bool,
+    field_manager: Option<String>,
+) -> Result<ApplyResult, String> {
+    let build = kustomize_build(path, enable_helm).await?;
+    crate::apply::apply_manifest(context, build.yaml, dry_run, field_manager).await
+}
@@ -0,0 +1,204 @@
+// Background latency prober: round-trip time to the local backend and to the API server of every
+// connected cluster, kept as a rolling history for sparklines.
+//
+// Cluster latency is the TCP connect time to the API server (DNS resolved beforehand, so it isn't
+// counted). That needs no credentials and isn't skewed by TLS or the API server's own load.
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Url};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use crate::backend_ports::BACKEND_PORT;
+
+const PROBE_INTERVAL_SECS: u64 = 30;
+const PROBE_TIMEOUT_SECS: u64 = 5;
+/// One hour at the probe interval.
+const MAX_SAMPLES: usize = 120;
+const BACKEND_TARGET: &str = "backend";
+
+static LATENCY_HISTORY: Mutex<Option<HashMap<String, LatencyHistory>>> = Mutex::const_new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySample {
+    pub at: u64,
+    /// `None` when the probe failed or timed out.
+    pub rtt_ms: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyHistory {
+    /// "backend" or the cluster id.
+    pub target: String,
+    pub name: String,
+    /// backend | cluster
+    pub kind: String,
+    pub samples: VecDeque<LatencySample>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BackendCluster {
+    id: String,
+    name: String,
+    #[serde(default)]
+    context: String,
+    #[serde(default)]
+    server_url: String,
+    #[serde(default)]
+    status: String,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+async fn probe_backend(client: &reqwest::Client) -> Option<f64> {
+    let url = format!("{}/health", crate::loopback::base_url(BACKEND_PORT).await);
+    let start = Instant::now();
+    let response = client
+        .get(&url)
+        .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+        .send()
+        .await
+        .ok()?;
+    response.status().is_success().then(|| elapsed_ms(start))
+}
+
+pub(crate) async fn probe_api_server(context: &str, server_url: &str) -> Option<f64> {
+    let url = Url::parse(server_url).ok()?;
+    let host = url.host_str()?.trim_start_matches('[').trim_end_matches(']').to_string();
+    let port = url.port_or_known_default()?;
+
+    // Through a SOCKS proxy the latency is the time to get the tunnel to the API server up
+    if let Some(proxy) = crate::socks::context_proxy(context).await {
+        let start = Instant::now();
+        return crate::socks::connect(&proxy, &host, port).await.ok().map(|_| elapsed_ms(start));
+    }
+
+    let addr = tokio::net::lookup_host((host, port)).await.ok()?.next()?;
+
+    let start = Instant::now();
+    tokio::time::timeout(Duration::from_secs(PROBE_TIMEOUT_SECS), TcpStream::connect(addr))
+        .await
+        .ok()?
+        .ok()
+        .map(|_| elapsed_ms(start))
+}
+
+async fn connected_clusters(client: &reqwest::Client) -> Vec<BackendCluster> {
+    let url = format!("{}/api/v1/clusters", crate::loopback::base_url(BACKEND_PORT).await);
+    let request = client.get(&url).timeout(Duration::from_secs(PROBE_TIMEOUT_SECS));
+    let Ok(response) = request.send().await else {
+        return Vec::new();
+    };
+    response
+        .json::<Vec<BackendCluster>>()
+        .await
+        .map(|clusters| {
+            clusters
+                .into_iter()
+                // Errored clusters are probed too: an unreachable API server is what the history
+                // (and the VPN check) is there to show
+                .filter(|c| c.status != "disconnected" && !c.server_url.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn record(
+    history: &mut HashMap<String, LatencyHistory>,
+    target: &str,
+    name: &str,
+    kind: &str,
+    sample: LatencySample,
+) {
+    let entry = history
+        .entry(target.to_string())
+        .or_insert_with(|| LatencyHistory {
+            target: target.to_string(),
+            name: name.to_string(),
+            kind: kind.to_string(),
+            samples: VecDeque::with_capacity(MAX_SAMPLES),
+        });
+    entry.name = name.to_string();
+    if entry.samples.len() == MAX_SAMPLES {
+        entry.samples.pop_front();
+    }
+    entry.samples.push_back(sample);
+}
+
+/// One probe round: the backend plus all connected clusters, in parallel. Unreachable clusters
+/// whose context requires a VPN get a `vpn-required` warning.
+async fn probe_round(app: &AppHandle, client: &reqwest::Client) {
+    let clusters = connected_clusters(client).await;
+    let (backend_rtt, cluster_rtts) = tokio::join!(
+        probe_backend(client),
+        futures::future::join_all(clusters.iter().map(|c| probe_api_server(&c.context, &c.server_url)))
+    );
+
+    for (cluster, rtt_ms) in clusters.iter().zip(&cluster_rtts) {
+        match rtt_ms {
+            Some(_) => crate::vpn::report_reachable(&cluster.context).await,
+            None => crate::vpn::report_unreachable(app, &cluster.context).await,
+        }
+    }
+
+    let at = now_secs();
+    let mut guard = LATENCY_HISTORY.lock().await;
+    let history = guard.get_or_insert_with(HashMap::new);
+    record(history, BACKEND_TARGET, "Kubilitics backend", "backend", LatencySample { at, rtt_ms: backend_rtt });
+    for (cluster, rtt_ms) in clusters.iter().zip(cluster_rtts) {
+        record(history, &cluster.id, &cluster.name, "cluster", LatencySample { at, rtt_ms });
+    }
+    // Forget clusters that were disconnected or removed
+    history.retain(|target, _| target == BACKEND_TARGET || clusters.iter().any(|c| &c.id == target));
+}
+
+pub fn start_latency_prober(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(PROBE_INTERVAL_SECS)).await;
+            // Probing every cluster is background traffic — paused in low-bandwidth mode
+            if crate::network::is_low_bandwidth() {
+                continue;
+            }
+
+            let client = match crate::http_client::shared(&app).await {
+                Ok(client) => client,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Latency probe skipped");
+                    continue;
+                }
+            };
+            probe_round(&app, &client).await;
+            let _ = app.emit("latency-updated", ());
+        }
+    });
+}
+
+/// Rolling latency history, oldest sample first. `target` limits it to the backend ("backend")
+/// or one cluster id.
+#[command]
+pub async fn get_latency_history(target: Option<String>) -> Result<Vec<LatencyHistory>, String> {
+    let guard = LATENCY_HISTORY.lock().await;
+    let Some(history) = guard.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let mut result: Vec<LatencyHistory> = history
+        .values()
+        .filter(|h| target.as_ref().is_none_or(|t| &h.target == t))
+        .cloned()
+        .collect();
+    result.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name)));
+    Ok(result)
+}
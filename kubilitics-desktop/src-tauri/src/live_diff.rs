@@ -0,0 +1,320 @@
+// What an apply would change, object by object: the manifest is server-side applied with dryRun=All
+// and the result compared with the live object field by field. Each changed field names the field
+// managers that own it today (from the live object's managedFields), which is who the apply would
+// take the field from — a controller, kubectl, another tool.
+//
+// Objects whose apply would hit a field-manager conflict are diffed with a forced dry run, so the
+// change still shows, and marked as conflicting with the API server's explanation.
+use std::collections::BTreeSet;
+
+use kube::api::{DynamicObject, Patch, PatchParams};
+use kube::Client;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::command;
+
+use crate::apply::{
+    dynamic_api, normalized_value, normalized_yaml, parse_documents, yaml_diff,
+    DEFAULT_FIELD_MANAGER,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffAction {
+    Create,
+    Update,
+    Unchanged,
+    /// Changes fields another manager owns; applying needs force.
+    Conflict,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldChange {
+    /// `.spec.template.spec.containers[name=app].image`; list items with a name are matched by it.
+    pub path: String,
+    pub op: ChangeOp,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    /// Managers owning the field in the live object.
+    pub managers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectDiff {
+    pub index: usize,
+    pub api_version: Option<String>,
+    pub kind: Option<String>,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    pub action: DiffAction,
+    pub changes: Vec<FieldChange>,
+    /// Unified YAML diff, live → applied.
+    pub diff: Option<String>,
+    pub conflict: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Field(String),
+    /// List item matched by its `name`.
+    Named(String),
+    Index(usize),
+}
+
+/// A difference found by `compare`: where, what kind, and the values on either side.
+type Difference = (Vec<Segment>, ChangeOp, Option<Value>, Option<Value>);
+
+fn path_string(path: &[Segment]) -> String {
+    let mut text = String::new();
+    for segment in path {
+        match segment {
+            Segment::Field(field) => {
+                text.push('.');
+                text.push_str(field);
+            }
+            Segment::Named(name) => text.push_str(&format!("[name={}]", name)),
+            Segment::Index(i) => text.push_str(&format!("[{}]", i)),
+        }
+    }
+    text
+}
+
+fn item_name(value: &Value) -> Option<&str> {
+    value.get("name").and_then(Value::as_str)
+}
+
+/// Record every leaf-level difference between `before` and `after`.
+fn compare(
+    path: &mut Vec<Segment>,
+    before: Option<&Value>,
+    after: Option<&Value>,
+    out: &mut Vec<Difference>,
+) {
+    match (before, after) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                path.push(Segment::Field(key.clone()));
+                compare(path, a.get(key), b.get(key), out);
+                path.pop();
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b)))
+            if a.iter().chain(b).all(|item| item_name(item).is_some()) =>
+        {
+            let names: BTreeSet<&str> = a.iter().chain(b).filter_map(item_name).collect();
+            for name in names {
+                let find = |items: &[Value]| {
+                    items
+                        .iter()
+                        .find(|item| item_name(item) == Some(name))
+                        .cloned()
+                };
+                path.push(Segment::Named(name.to_string()));
+                compare(path, find(a).as_ref(), find(b).as_ref(), out);
+                path.pop();
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b))) => {
+            for i in 0..a.len().max(b.len()) {
+                path.push(Segment::Index(i));
+                compare(path, a.get(i), b.get(i), out);
+                path.pop();
+            }
+        }
+        (Some(a), Some(b)) if a == b => {}
+        (None, None) => {}
+        (before, after) => {
+            let op = match (before, after) {
+                (None, _) => ChangeOp::Added,
+                (_, None) => ChangeOp::Removed,
+                _ => ChangeOp::Changed,
+            };
+            out.push((path.clone(), op, before.cloned(), after.cloned()));
+        }
+    }
+}
+
+/// Whether a managedFields `fieldsV1` tree covers `path`: the path exists in it, or it stops at a
+/// leaf above the path (the manager owns that whole value).
+fn owns(fields: &Value, path: &[Segment]) -> bool {
+    let mut node = fields;
+    for segment in path {
+        let Some(children) = node.as_object() else {
+            return false;
+        };
+        if children.is_empty() {
+            return true;
+        }
+        let key = match segment {
+            Segment::Field(field) => format!("f:{}", field),
+            Segment::Named(name) => {
+                let mut item = Map::new();
+                item.insert("name".to_string(), Value::String(name.clone()));
+                format!("k:{}", Value::Object(item))
+            }
+            // Positional items have no key in fieldsV1; the list's owner owns them
+            Segment::Index(_) => return true,
+        };
+        match children.get(&key) {
+            Some(child) => node = child,
+            None => return false,
+        }
+    }
+    true
+}
+
+fn managers_of(live: &DynamicObject, path: &[Segment]) -> Vec<String> {
+    let mut managers: BTreeSet<String> = BTreeSet::new();
+    for entry in live.metadata.managed_fields.iter().flatten() {
+        if entry.subresource.as_deref().is_some_and(|s| !s.is_empty()) {
+            continue;
+        }
+        let Some(fields) = entry.fields_v1.as_ref() else {
+            continue;
+        };
+        if owns(&fields.0, path) {
+            managers.insert(entry.manager.clone().unwrap_or_default());
+        }
+    }
+    managers.into_iter().collect()
+}
+
+async fn diff_document(
+    client: &Client,
+    document: Value,
+    field_manager: &str,
+    result: &mut ObjectDiff,
+) -> Result<(), String> {
+    let object: DynamicObject =
+        serde_json::from_value(document).map_err(|e| format!("Not a Kubernetes object: {}", e))?;
+    let types = object.types.as_ref().ok_or("Missing apiVersion or kind")?;
+    let name = object
+        .metadata
+        .name
+        .as_deref()
+        .ok_or("Missing metadata.name")?;
+    let api = dynamic_api(
+        client,
+        &types.api_version,
+        &types.kind,
+        object.metadata.namespace.as_deref(),
+    )
+    .await?;
+
+    let live = api
+        .get_opt(name)
+        .await
+        .map_err(|e| format!("Failed to read the live object: {}", e))?;
+    let params = PatchParams::apply(field_manager).dry_run();
+    let applied = match api.patch(name, &params, &Patch::Apply(&object)).await {
+        Ok(applied) => applied,
+        Err(kube::Error::Api(response)) if response.code == 409 => {
+            result.conflict = Some(response.message);
+            api.patch(name, &params.clone().force(), &Patch::Apply(&object))
+                .await
+                .map_err(|e| e.to_string())?
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let before = live.as_ref().map(normalized_value);
+    let after = normalized_value(&applied);
+    let mut changes = Vec::new();
+    compare(&mut Vec::new(), before.as_ref(), Some(&after), &mut changes);
+    result.changes = changes
+        .into_iter()
+        .map(|(path, op, before, after)| FieldChange {
+            managers: live
+                .as_ref()
+                .map(|live| managers_of(live, &path))
+                .unwrap_or_default(),
+            path: path_string(&path),
+            op,
+            before,
+            after,
+        })
+        .collect();
+    result.diff = Some(yaml_diff(
+        &live.as_ref().map(normalized_yaml).unwrap_or_default(),
+        &normalized_yaml(&applied),
+        "live",
+        "applied",
+    ));
+    result.action = if result.conflict.is_some() {
+        DiffAction::Conflict
+    } else if live.is_none() {
+        DiffAction::Create
+    } else if result.changes.is_empty() {
+        DiffAction::Unchanged
+    } else {
+        DiffAction::Update
+    };
+    Ok(())
+}
+
+/// Diff one manifest object (position `index`) against the cluster; failures are reported in the
+/// result.
+pub(crate) async fn diff_object(
+    client: &Client,
+    index: usize,
+    document: Value,
+    field_manager: &str,
+) -> ObjectDiff {
+    let field = |path: &str| {
+        document
+            .pointer(path)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let mut result = ObjectDiff {
+        index,
+        api_version: field("/apiVersion"),
+        kind: field("/kind"),
+        namespace: field("/metadata/namespace"),
+        name: field("/metadata/name"),
+        action: DiffAction::Failed,
+        changes: Vec::new(),
+        diff: None,
+        conflict: None,
+        error: None,
+    };
+    if let Err(e) = diff_document(client, document, field_manager, &mut result).await {
+        result.action = DiffAction::Failed;
+        result.error = Some(e);
+    }
+    result
+}
+
+/// Diff every object in `yaml` against the live cluster through a server-side dry-run apply as
+/// `field_manager`. Nothing is changed.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn diff_against_live(
+    context: String,
+    yaml: String,
+    field_manager: Option<String>,
+) -> Result<Vec<ObjectDiff>, String> {
+    let documents = parse_documents(&yaml)?;
+    let field_manager = field_manager
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| DEFAULT_FIELD_MANAGER.to_string());
+    let client = crate::k8s::client_for(&context).await?;
+
+    let mut results = Vec::with_capacity(documents.len());
+    for (index, document) in documents.into_iter().enumerate() {
+        results.push(diff_object(&client, index, document, &field_manager).await);
+    }
+    Ok(results)
+}
@@ -0,0 +1,249 @@
+// Application logging. Everything goes through `tracing`: events are written as JSON lines to a
+// daily-rotated file under app data (a week is kept) and, for `tauri dev`, to stdout. The level can
+// be changed at runtime and sticks across restarts, so a user can turn on debug logging, reproduce
+// a problem and send `get_app_logs` output without restarting into a special mode.
+//
+// This module owns the global subscriber; the span timing layer from `perf` and the breadcrumb
+// layer are installed with it.
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::command;
+use tracing::Level;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+use crate::commands::get_app_data_dir;
+
+const LOGS_DIR: &str = "logs";
+const LOG_FILE_PREFIX: &str = "kubilitics-desktop";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_TAIL: usize = 500;
+const MAX_TAIL: usize = 10_000;
+
+/// Current maximum verbosity, read by the subscriber's level filter.
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+/// Keeps the background writer alive; dropping it would stop file output.
+static WRITER_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum LogLevel {
+    Error = 1,
+    Warn = 2,
+    #[default]
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl LogLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => LogLevel::Error,
+            2 => LogLevel::Warn,
+            4 => LogLevel::Debug,
+            5 => LogLevel::Trace,
+            _ => LogLevel::Info,
+        }
+    }
+
+    fn from_tracing(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warn" | "warning" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingSettings {
+    pub level: LogLevel,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+    /// Structured fields other than the message, plus the enclosing span names.
+    pub fields: Value,
+}
+
+/// `get_app_data_dir` resolved synchronously: logging starts before the async runtime.
+fn app_data_dir_sync() -> Option<PathBuf> {
+    let dir = dirs::data_local_dir()?.join("kubilitics");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+fn load_logging_settings_sync() -> LoggingSettings {
+    app_data_dir_sync()
+        .and_then(|dir| std::fs::read_to_string(dir.join("logging_settings.json")).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+async fn get_logging_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("logging_settings.json"))
+}
+
+async fn save_logging_settings(settings: &LoggingSettings) -> Result<(), String> {
+    let path = get_logging_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize logging settings".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write logging settings".to_string())
+}
+
+fn current_level() -> LogLevel {
+    LogLevel::from_u8(LOG_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Install the global subscriber. First thing in `main` after the panic hook, so setup is logged.
+pub fn init() {
+    use tracing_subscriber::prelude::*;
+
+    LOG_LEVEL.store(load_logging_settings_sync().level as u8, Ordering::Relaxed);
+
+    // Callsite interest is cached; `set_log_level` rebuilds it after changing the level
+    let level_filter = tracing_subscriber::filter::filter_fn(|metadata| {
+        LogLevel::from_tracing(metadata.level()) <= current_level()
+    });
+    let stdout_layer = tracing_subscriber::fmt::layer().with_target(false);
+
+    let file_writer = app_data_dir_sync().and_then(|dir| {
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(MAX_LOG_FILES)
+            .build(dir.join(LOGS_DIR))
+            .ok()
+    });
+    let file_layer = file_writer.map(|appender| {
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let _ = WRITER_GUARD.set(guard);
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(false)
+            .with_span_list(true)
+            .with_writer(writer)
+    });
+
+    let _ = tracing_subscriber::registry()
+        .with(crate::perf::SpanTimingLayer)
+        .with(crate::breadcrumbs::BreadcrumbLayer)
+        .with(stdout_layer.and_then(file_layer).with_filter(level_filter))
+        .try_init();
+}
+
+/// Log files, newest first. Daily rotation names them `<prefix>.<date>.<suffix>`, which sorts by date.
+async fn log_files() -> Result<Vec<PathBuf>, String> {
+    let dir = PathBuf::from(get_app_data_dir().await?).join(LOGS_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(&dir).map_err(|e| format!("Failed to read log directory: {}", e))?;
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(LOG_FILE_PREFIX) && n.ends_with(LOG_FILE_SUFFIX))
+        })
+        .collect();
+    files.sort();
+    files.reverse();
+    Ok(files)
+}
+
+fn parse_line(line: &str) -> Option<LogEntry> {
+    let mut value: Value = serde_json::from_str(line).ok()?;
+    let object = value.as_object_mut()?;
+    let level = object.get("level").and_then(Value::as_str).and_then(LogLevel::parse)?;
+    let mut fields = object.remove("fields").unwrap_or(Value::Null);
+    let message = fields
+        .as_object_mut()
+        .and_then(|f| f.remove("message"))
+        .and_then(|m| m.as_str().map(str::to_string))
+        .unwrap_or_default();
+    if let (Some(spans), Some(f)) = (object.remove("spans"), fields.as_object_mut()) {
+        f.insert("spans".to_string(), spans);
+    }
+    Some(LogEntry {
+        timestamp: object.get("timestamp").and_then(Value::as_str).unwrap_or_default().to_string(),
+        level,
+        target: object.get("target").and_then(Value::as_str).unwrap_or_default().to_string(),
+        message,
+        fields,
+    })
+}
+
+/// The last `tail` entries (default 500) at `level` or more severe, oldest first.
+#[command]
+pub async fn get_app_logs(level: Option<LogLevel>, tail: Option<usize>) -> Result<Vec<LogEntry>, String> {
+    let level = level.unwrap_or(LogLevel::Trace);
+    let tail = tail.unwrap_or(DEFAULT_TAIL).clamp(1, MAX_TAIL);
+
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for path in log_files().await? {
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| format!("Failed to read log file: {}", e))?;
+        let mut file_entries: Vec<LogEntry> = content
+            .lines()
+            .filter_map(parse_line)
+            .filter(|e| e.level <= level)
+            .collect();
+        let needed = tail - entries.len();
+        if file_entries.len() > needed {
+            file_entries.drain(..file_entries.len() - needed);
+        }
+        file_entries.append(&mut entries);
+        entries = file_entries;
+        if entries.len() >= tail {
+            break;
+        }
+    }
+    Ok(entries)
+}
+
+#[command]
+pub async fn get_log_level() -> Result<LogLevel, String> {
+    Ok(current_level())
+}
+
+/// Change the log level. Applies immediately and persists across restarts.
+#[command]
+pub async fn set_log_level(level: LogLevel) -> Result<(), String> {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+    tracing::callsite::rebuild_interest_cache();
+    tracing::info!(?level, "Log level changed");
+    save_logging_settings(&LoggingSettings { level }).await
+}
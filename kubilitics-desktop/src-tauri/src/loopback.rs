@@ -0,0 +1,70 @@
+// Loopback address for the sidecars. `localhost` resolves to ::1 first on some systems and not at
+// all for IPv6 on others, while the backend may be listening on only one family — so instead of
+// trusting the resolver, both 127.0.0.1 and ::1 are tried, happy-eyeballs style: the address that
+// answered last time goes first, the other follows FALLBACK_DELAY later, first connect wins.
+// Once an address has answered it is reused without probing until a caller reports a connection
+// error through `forget`.
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use futures::future::{select_ok, BoxFuture, FutureExt};
+use tokio::sync::Mutex;
+
+const FALLBACK_DELAY: Duration = Duration::from_millis(250);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Per port, the loopback address that accepted the last probe.
+static PREFERRED: Mutex<BTreeMap<u16, IpAddr>> = Mutex::const_new(BTreeMap::new());
+
+const V4: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+const V6: IpAddr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+async fn try_connect(ip: IpAddr, port: u16, delay: Duration) -> Result<IpAddr, ()> {
+    tokio::time::sleep(delay).await;
+    match tokio::time::timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect((ip, port))).await {
+        Ok(Ok(_)) => Ok(ip),
+        _ => Err(()),
+    }
+}
+
+/// The loopback address the service on `port` answers on. When neither answers (e.g. the sidecar
+/// is still starting) the preferred one is returned without being remembered.
+pub async fn resolve(port: u16) -> IpAddr {
+    // IPv4 first by default: the backend has historically listened on 0.0.0.0 only
+    let preferred = PREFERRED.lock().await.get(&port).copied().unwrap_or(V4);
+    let fallback = if preferred == V4 { V6 } else { V4 };
+
+    let attempts: Vec<BoxFuture<'static, Result<IpAddr, ()>>> = vec![
+        try_connect(preferred, port, Duration::ZERO).boxed(),
+        try_connect(fallback, port, FALLBACK_DELAY).boxed(),
+    ];
+    match select_ok(attempts).await {
+        Ok((ip, _)) => {
+            PREFERRED.lock().await.insert(port, ip);
+            ip
+        }
+        Err(()) => preferred,
+    }
+}
+
+/// Drop the remembered address for `port`, so the next lookup probes again. Call it after a
+/// connection to the service failed — it may have restarted on the other family.
+pub async fn forget(port: u16) {
+    PREFERRED.lock().await.remove(&port);
+}
+
+/// The remembered address for `port`, probing only when there is none.
+pub async fn socket_addr(port: u16) -> SocketAddr {
+    let cached = PREFERRED.lock().await.get(&port).copied();
+    let ip = match cached {
+        Some(ip) => ip,
+        None => resolve(port).await,
+    };
+    SocketAddr::new(ip, port)
+}
+
+/// `http://127.0.0.1:<port>` or `http://[::1]:<port>`, whichever answers.
+pub async fn base_url(port: u16) -> String {
+    format!("http://{}", socket_addr(port).await)
+}
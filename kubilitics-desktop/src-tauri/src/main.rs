@@ -3,18 +3,75 @@
 
 use tauri::{Emitter, Manager, RunEvent};
 
+mod access_review;
+mod agent;
+mod airgap;
+mod analytics;
+mod apply;
 mod backend_ports;
+mod breadcrumbs;
+mod cluster_events;
+mod cluster_policy;
 mod commands;
+mod context_terminal;
+mod crash;
+mod crds;
+mod debug;
+mod diagnostics;
+mod dns;
+mod dock;
+mod drafts;
+mod exec;
+mod exports;
+mod gitops;
+mod helm;
+mod http_client;
+mod hygiene;
+mod k8s;
+mod kubectl_plugins;
+mod kustomize;
+mod latency;
+mod live_diff;
+mod logging;
+mod loopback;
+mod manifest_validation;
+mod mdns;
 mod menu;
+mod metrics;
+mod network;
+mod pairing;
+mod perf;
+mod pod_cp;
+mod pod_logs;
+mod portforward;
+mod portforward_profiles;
+mod proxy;
+mod rollout;
 mod sidecar;
+mod snapshot;
+mod socks;
+mod ssh;
+mod storage;
+mod streams;
+mod terminal;
+mod tools;
 mod tray;
+mod updater;
+mod vpn;
+mod watch_cache;
 
 fn main() {
+    crash::install_panic_hook();
+    logging::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .manage(http_client::HttpClient::default())
+        .manage(commands::KubeconfigInfoCache::default())
         .invoke_handler(tauri::generate_handler![
             commands::read_kubeconfig,
             commands::get_kubeconfig_info,
@@ -25,7 +82,7 @@ fn main() {
             commands::save_topology_export,
             commands::open_in_system_editor,
             commands::reveal_in_file_manager,
-            commands::get_recent_exports,
+            exports::get_recent_exports,
             commands::get_app_data_dir,
             commands::select_kubeconfig_file,
             commands::get_selected_contexts,
@@ -39,20 +96,199 @@ fn main() {
             commands::save_encrypted_kubeconfig,
             commands::load_encrypted_kubeconfig,
             commands::check_connectivity,
+            commands::get_connectivity_settings,
+            commands::set_connectivity_settings,
+            network::get_network_status,
+            dns::diagnose_cluster_dns,
+            diagnostics::run_network_diagnostics,
+            network::get_low_bandwidth_state,
+            network::set_low_bandwidth_mode,
+            latency::get_latency_history,
+            vpn::get_vpn_requirements,
+            vpn::set_vpn_requirement,
+            vpn::check_vpn_status,
             commands::get_analytics_consent,
             commands::set_analytics_consent,
             commands::has_analytics_consent_been_asked,
-            commands::check_for_updates,
-            commands::install_update,
+            analytics::track_analytics_event,
+            analytics::get_analytics_queue_status,
+            analytics::flush_analytics_queue,
+            analytics::preview_pending_analytics,
+            analytics::purge_analytics_data,
+            analytics::delete_all_telemetry,
+            crash::list_crash_reports,
+            crash::submit_crash_report,
+            crash::delete_crash_report,
+            crash::get_crash_reporting_consent,
+            crash::set_crash_reporting_consent,
+            perf::export_performance_trace,
+            logging::get_app_logs,
+            logging::get_log_level,
+            logging::set_log_level,
+            breadcrumbs::get_recent_errors,
+            storage::get_stored_data_summary,
+            storage::delete_stored_data,
+            portforward::start_port_forward,
+            portforward::list_port_forwards,
+            portforward::stop_port_forward,
+            exec::open_exec_session,
+            exec::write_exec_stdin,
+            exec::resize_exec,
+            exec::close_exec_session,
+            exec::list_exec_sessions,
+            debug::debug_pod,
+            pod_logs::open_log_stream,
+            pod_logs::update_log_stream_rules,
+            pod_logs::close_log_stream,
+            pod_logs::list_log_streams,
+            pod_cp::pod_cp,
+            watch_cache::start_watch_cache,
+            watch_cache::stop_watch_cache,
+            watch_cache::get_watch_cache_status,
+            watch_cache::list_cached_resources,
+            watch_cache::get_cached_resource,
+            apply::apply_manifest,
+            manifest_validation::validate_manifest,
+            drafts::get_resource_yaml,
+            drafts::list_drafts,
+            drafts::get_draft,
+            drafts::save_draft,
+            drafts::diff_draft,
+            drafts::apply_draft,
+            drafts::discard_draft,
+            cluster_events::start_event_notifications,
+            cluster_events::stop_event_notifications,
+            cluster_events::get_event_alerts,
+            cluster_events::clear_event_alerts,
+            cluster_events::get_event_notification_settings,
+            cluster_events::set_event_notification_settings,
+            metrics::start_metrics_collection,
+            metrics::stop_metrics_collection,
+            metrics::get_metrics_collection_status,
+            metrics::get_metrics,
+            crds::list_crds,
+            crds::list_custom_resources,
+            crds::get_custom_resource,
+            portforward_profiles::list_port_forward_profiles,
+            portforward_profiles::save_port_forward_profile,
+            portforward_profiles::delete_port_forward_profile,
+            portforward_profiles::start_port_forward_profile,
+            portforward_profiles::stop_port_forward_profile,
+            portforward_profiles::set_cluster_connected,
+            helm::get_helm_info,
+            helm::list_helm_releases,
+            helm::get_helm_release_history,
+            helm::get_helm_release,
+            helm::get_helm_release_values,
+            ssh::get_ssh_profiles,
+            ssh::set_ssh_profile,
+            ssh::get_node_ssh_command,
+            ssh::ssh_to_node,
+            context_terminal::open_terminal_for_context,
+            snapshot::capture_cluster_snapshot,
+            snapshot::list_cluster_snapshots,
+            snapshot::delete_cluster_snapshot,
+            snapshot::open_cluster_snapshot,
+            snapshot::close_cluster_snapshot,
+            snapshot::get_open_snapshot,
+            snapshot::list_snapshot_resources,
+            kustomize::get_kustomize_info,
+            kustomize::kustomize_build,
+            kustomize::apply_kustomization,
+            agent::get_agent_status,
+            agent::install_agent,
+            agent::uninstall_agent,
+            access_review::can_i_batch,
+            rollout::watch_rollout,
+            rollout::stop_rollout_watch,
+            hygiene::analyze_namespace_hygiene,
+            hygiene::apply_cleanup_plan,
+            live_diff::diff_against_live,
+            gitops::list_gitops_repos,
+            gitops::configure_gitops_repo,
+            gitops::remove_gitops_repo,
+            gitops::sync_gitops_repo,
+            gitops::get_drift_report,
+            kubectl_plugins::list_kubectl_plugins,
+            updater::check_for_updates,
+            updater::install_update,
+            updater::get_rollback_info,
+            updater::rollback_update,
+            updater::install_update_from_file,
+            updater::set_update_proxy,
+            updater::get_update_signature_details,
+            updater::get_update_channel,
+            updater::set_update_channel,
+            updater::get_update_changelog,
+            updater::get_update_settings,
+            updater::set_update_check_schedule,
             commands::get_desktop_info,
             commands::restart_sidecar,
             commands::is_kcli_sidecar_available,
             sidecar::get_ai_status,
             sidecar::get_backend_status,
+            dock::get_recent_clusters,
+            dock::record_recent_cluster,
+            exports::get_export_retention_policy,
+            exports::set_export_retention_policy,
+            exports::cleanup_exports_now,
+            exports::search_exports,
+            exports::bundle::export_bundle,
+            exports::clipboard::copy_png_to_clipboard,
+            exports::clipboard::copy_svg_as_png_to_clipboard,
+            exports::clipboard::copy_manifest_to_clipboard,
+            exports::diagram::export_topology_diagram,
+            exports::diagram::copy_topology_diagram,
+            exports::diff::diff_exports,
+            exports::drawio::export_topology_drawio,
+            exports::inventory::export_resource_inventory,
+            exports::pdf::export_topology_pdf,
+            exports::png::rasterize_svg_to_png,
+            exports::schedule::list_export_schedules,
+            exports::schedule::save_export_schedule,
+            exports::schedule::delete_export_schedule,
+            exports::schedule::run_export_schedule_now,
+            exports::stream::begin_export_upload,
+            exports::stream::append_export_chunk,
+            exports::stream::finish_export_upload,
+            exports::stream::cancel_export_upload,
+            exports::templates::list_export_templates,
+            exports::templates::save_export_template,
+            exports::templates::delete_export_template,
+            exports::upload::configure_export_destination,
+            exports::upload::list_export_destinations,
+            exports::upload::remove_export_destination,
+            exports::upload::get_export_upload_history,
+            exports::upload::upload_export,
+            pairing::create_pairing_payload,
+            pairing::revoke_pairing_tokens,
+            pairing::list_paired_devices,
+            pairing::revoke_paired_device,
+            mdns::get_mdns_status,
+            mdns::set_mdns_advertising,
+            airgap::get_air_gap_mode,
+            airgap::set_air_gap_mode,
+            proxy::get_proxy_settings,
+            proxy::set_proxy_settings,
+            socks::get_context_proxies,
+            socks::set_context_proxy,
+            socks::test_context_proxy,
+            cluster_policy::get_context_policies,
+            cluster_policy::get_context_policy,
+            cluster_policy::set_context_policy,
+            streams::open_stream,
+            streams::send_stream_message,
+            streams::close_stream,
+            streams::list_streams,
         ])
         .setup(|app| {
             let handle = app.handle().clone();
 
+            // Before anything that might make an outbound call
+            tauri::async_runtime::block_on(airgap::init());
+            // Before the sidecars open their databases
+            tauri::async_runtime::block_on(storage::apply_pending_deletions());
+
             // Native menu (R1.4): File, Edit, View, Help
             if let Ok(menu) = menu::build_app_menu(&handle) {
                 let _ = app.set_menu(menu.clone());
@@ -73,11 +309,25 @@ fn main() {
             }
             // Start Go backend sidecar (and AI backend if available)
             sidecar::start_backend(&handle)?;
+
+            // Keep the exports directory within the configured retention limits
+            exports::start_export_cleanup_task();
+            exports::schedule::start_export_scheduler(&handle);
+            updater::start_update_check_scheduler(&handle);
+            network::start_network_monitor(&handle);
+            network::start_bandwidth_monitor(&handle);
+            latency::start_latency_prober(&handle);
+            mdns::start_mdns_advertising();
+            analytics::start_analytics_uploader(&handle);
             
             // Setup system tray
             if let Err(e) = tray::setup_system_tray(&handle) {
-                eprintln!("Failed to setup system tray: {}", e);
+                tracing::error!("Failed to setup system tray: {}", e);
             }
+
+            // Dock menu mirrors the tray quick actions for users who hide the menu bar
+            #[cfg(target_os = "macos")]
+            dock::setup_dock_menu(&handle);
             
             // Configure window to minimize to tray instead of closing
             if let Some(window) = app.get_webview_window("main") {
@@ -99,6 +349,9 @@ fn main() {
             // ROOT CAUSE E: Stop backend sidecar cleanly on any app exit (Force Quit, cmd+Q,
             // tray Quit). Without this the Go process becomes an orphan after the Tauri shell dies.
             if let RunEvent::Exit = event {
+                // Goodbye packets first, so phones don't keep showing a desktop that's gone
+                tauri::async_runtime::block_on(mdns::stop());
+                tauri::async_runtime::block_on(portforward::stop_all());
                 if let Some(manager) = app_handle.try_state::<std::sync::Arc<sidecar::BackendManager>>() {
                     tauri::async_runtime::block_on(manager.stop());
                 }
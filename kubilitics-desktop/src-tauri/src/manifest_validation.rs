@@ -0,0 +1,433 @@
+// Checking manifests against the cluster's own OpenAPI v3 schema before they're applied, so typos
+// and wrong types show up as field-level messages instead of an API server rejection (or, for
+// unknown fields, silently dropped config). CRDs are covered too, since their schemas are published
+// the same way.
+//
+// /openapi/v3 lists one document per group-version with a content hash in its URL; documents are
+// cached per context by that URL, so a schema is fetched again only after it changed (a CRD
+// update, a cluster upgrade).
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use k8s_openapi::http;
+use kube::Client;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+use tokio::sync::Mutex;
+
+/// Group-version documents by (context, serverRelativeURL).
+static SCHEMA_CACHE: Mutex<Option<HashMap<(String, String), Arc<Value>>>> = Mutex::const_new(None);
+
+/// apiVersion/kind pairs that are deprecated or gone: (apiVersion, kind, removed in, replacement).
+const DEPRECATED_API_VERSIONS: [(&str, &str, &str, &str); 16] = [
+    (
+        "extensions/v1beta1",
+        "Ingress",
+        "1.22",
+        "networking.k8s.io/v1",
+    ),
+    (
+        "networking.k8s.io/v1beta1",
+        "Ingress",
+        "1.22",
+        "networking.k8s.io/v1",
+    ),
+    ("extensions/v1beta1", "Deployment", "1.16", "apps/v1"),
+    ("apps/v1beta1", "Deployment", "1.16", "apps/v1"),
+    ("apps/v1beta2", "Deployment", "1.16", "apps/v1"),
+    ("extensions/v1beta1", "DaemonSet", "1.16", "apps/v1"),
+    ("apps/v1beta2", "StatefulSet", "1.16", "apps/v1"),
+    ("batch/v1beta1", "CronJob", "1.25", "batch/v1"),
+    ("policy/v1beta1", "PodDisruptionBudget", "1.25", "policy/v1"),
+    (
+        "policy/v1beta1",
+        "PodSecurityPolicy",
+        "1.25",
+        "Pod Security Admission",
+    ),
+    (
+        "autoscaling/v2beta1",
+        "HorizontalPodAutoscaler",
+        "1.25",
+        "autoscaling/v2",
+    ),
+    (
+        "autoscaling/v2beta2",
+        "HorizontalPodAutoscaler",
+        "1.26",
+        "autoscaling/v2",
+    ),
+    (
+        "discovery.k8s.io/v1beta1",
+        "EndpointSlice",
+        "1.25",
+        "discovery.k8s.io/v1",
+    ),
+    (
+        "rbac.authorization.k8s.io/v1beta1",
+        "ClusterRole",
+        "1.22",
+        "rbac.authorization.k8s.io/v1",
+    ),
+    (
+        "apiextensions.k8s.io/v1beta1",
+        "CustomResourceDefinition",
+        "1.22",
+        "apiextensions.k8s.io/v1",
+    ),
+    (
+        "storage.k8s.io/v1beta1",
+        "CSIStorageCapacity",
+        "1.27",
+        "storage.k8s.io/v1",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub severity: IssueSeverity,
+    /// JSON path of the field, e.g. `spec.template.spec.containers[0].image`.
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentValidation {
+    pub index: usize,
+    pub api_version: Option<String>,
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub valid: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+async fn fetch_json(client: &Client, url: &str) -> Result<Value, String> {
+    let request = http::Request::get(url)
+        .header("Accept", "application/json")
+        .body(Vec::new())
+        .map_err(|e| e.to_string())?;
+    let text = client
+        .request_text(request)
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid schema from {}: {}", url, e))
+}
+
+/// The OpenAPI document for a group-version, from the cache when its hash hasn't changed.
+async fn group_version_schema(
+    client: &Client,
+    context: &str,
+    discovery: &Value,
+    api_version: &str,
+) -> Result<Option<Arc<Value>>, String> {
+    let path = match api_version.split_once('/') {
+        Some(_) => format!("apis/{}", api_version),
+        None => format!("api/{}", api_version),
+    };
+    let Some(url) = discovery
+        .pointer(&format!("/paths/{}", path.replace('/', "~1")))
+        .and_then(|p| p.get("serverRelativeURL"))
+        .and_then(Value::as_str)
+    else {
+        return Ok(None);
+    };
+
+    let key = (context.to_string(), url.to_string());
+    if let Some(schema) = SCHEMA_CACHE.lock().await.as_ref().and_then(|c| c.get(&key)) {
+        return Ok(Some(schema.clone()));
+    }
+    let schema = Arc::new(fetch_json(client, url).await?);
+    let mut cache = SCHEMA_CACHE.lock().await;
+    let cache = cache.get_or_insert_with(HashMap::new);
+    // Drop this context's older copy of the same group-version
+    let prefix = url.split('?').next().unwrap_or(url);
+    cache.retain(|(c, u), _| c != context || u.split('?').next() != Some(prefix));
+    cache.insert(key, schema.clone());
+    Ok(Some(schema))
+}
+
+/// The schema component for a kind, by its x-kubernetes-group-version-kind.
+fn kind_schema<'a>(document: &'a Value, api_version: &str, kind: &str) -> Option<&'a Value> {
+    let (group, version) = api_version.split_once('/').unwrap_or(("", api_version));
+    document
+        .pointer("/components/schemas")?
+        .as_object()?
+        .values()
+        .find(|schema| {
+            schema
+                .get("x-kubernetes-group-version-kind")
+                .and_then(Value::as_array)
+                .is_some_and(|gvks| {
+                    gvks.iter().any(|gvk| {
+                        gvk.get("group").and_then(Value::as_str) == Some(group)
+                            && gvk.get("version").and_then(Value::as_str) == Some(version)
+                            && gvk.get("kind").and_then(Value::as_str) == Some(kind)
+                    })
+                })
+        })
+}
+
+fn issue(severity: IssueSeverity, path: &str, message: String) -> ValidationIssue {
+    ValidationIssue {
+        severity,
+        path: path.to_string(),
+        message,
+    }
+}
+
+struct Validator<'a> {
+    document: &'a Value,
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'a> Validator<'a> {
+    /// Follow `$ref` and single-element `allOf` (how v3 attaches defaults to a reference).
+    fn resolve(&self, mut schema: &'a Value) -> &'a Value {
+        for _ in 0..32 {
+            if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+                match reference
+                    .strip_prefix('#')
+                    .and_then(|pointer| self.document.pointer(pointer))
+                {
+                    Some(target) => schema = target,
+                    None => return schema,
+                }
+            } else if let Some([only]) = schema
+                .get("allOf")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+            {
+                schema = only;
+            } else {
+                break;
+            }
+        }
+        schema
+    }
+
+    fn error(&mut self, path: &str, message: String) {
+        self.issues.push(issue(IssueSeverity::Error, path, message));
+    }
+
+    fn check(&mut self, value: &Value, schema: &'a Value, path: &str) {
+        let schema = self.resolve(schema);
+        if value.is_null() {
+            return;
+        }
+        if schema
+            .get("x-kubernetes-int-or-string")
+            .and_then(Value::as_bool)
+            == Some(true)
+        {
+            if !(value.is_i64() || value.is_u64() || value.is_string()) {
+                self.error(path, "Expected an integer or a string".to_string());
+            }
+            return;
+        }
+
+        let type_ok = match schema.get("type").and_then(Value::as_str) {
+            Some("string") => value.is_string(),
+            Some("integer") => value.is_i64() || value.is_u64(),
+            Some("number") => value.is_number(),
+            Some("boolean") => value.is_boolean(),
+            Some("array") => value.is_array(),
+            Some("object") => value.is_object(),
+            _ => true,
+        };
+        if !type_ok {
+            let expected = schema
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            self.error(
+                path,
+                format!("Expected {}, got {}", expected, type_name(value)),
+            );
+            return;
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                self.error(path, format!("Must be one of {}", join_values(allowed)));
+            }
+        }
+
+        match value {
+            Value::Object(fields) => self.check_object(fields, schema, path),
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        self.check(item, item_schema, &format!("{}[{}]", path, i));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_object(
+        &mut self,
+        fields: &serde_json::Map<String, Value>,
+        schema: &'a Value,
+        path: &str,
+    ) {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let additional = schema.get("additionalProperties");
+        let preserve_unknown = schema
+            .get("x-kubernetes-preserve-unknown-fields")
+            .and_then(Value::as_bool)
+            == Some(true);
+
+        for (name, field) in fields {
+            let field_path = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", path, name)
+            };
+            if let Some(field_schema) = properties.and_then(|p| p.get(name)) {
+                self.check(field, field_schema, &field_path);
+            } else if let Some(additional) = additional.filter(|a| a.is_object()) {
+                self.check(field, additional, &field_path);
+            } else if properties.is_some()
+                && !preserve_unknown
+                && additional != Some(&Value::Bool(true))
+            {
+                self.error(&field_path, format!("Unknown field \"{}\"", name));
+            }
+        }
+
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(name) {
+                    let field_path = if path.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{}.{}", path, name)
+                    };
+                    self.error(&field_path, "Required field is missing".to_string());
+                }
+            }
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join_values(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| v.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Check every object in `yaml` against the context's schemas: unknown fields, wrong types,
+/// missing required fields and enum values are errors; deprecated apiVersions are warnings (errors
+/// once the cluster no longer serves them).
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn validate_manifest(
+    context: String,
+    yaml: String,
+) -> Result<Vec<DocumentValidation>, String> {
+    let documents = crate::apply::parse_documents(&yaml)?;
+    let client = crate::k8s::client_for(&context).await?;
+    let discovery = fetch_json(&client, "/openapi/v3").await?;
+
+    let mut results = Vec::with_capacity(documents.len());
+    for (index, document) in documents.iter().enumerate() {
+        let text = |path: &str| {
+            document
+                .pointer(path)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let (api_version, kind, name) =
+            (text("/apiVersion"), text("/kind"), text("/metadata/name"));
+        let mut issues = Vec::new();
+
+        match (&api_version, &kind) {
+            (Some(api_version), Some(kind)) => {
+                if let Some((_, _, removed_in, replacement)) = DEPRECATED_API_VERSIONS
+                    .iter()
+                    .find(|(v, k, _, _)| v == api_version && k == kind)
+                {
+                    issues.push(issue(
+                        IssueSeverity::Warning,
+                        "apiVersion",
+                        format!(
+                            "{} {} is deprecated and removed in Kubernetes {}; use {}",
+                            api_version, kind, removed_in, replacement
+                        ),
+                    ));
+                }
+                match group_version_schema(&client, &context, &discovery, api_version).await? {
+                    None => issues.push(issue(
+                        IssueSeverity::Error,
+                        "apiVersion",
+                        format!("{} is not served by this cluster", api_version),
+                    )),
+                    Some(schema_document) => match kind_schema(&schema_document, api_version, kind)
+                    {
+                        None => issues.push(issue(
+                            IssueSeverity::Error,
+                            "kind",
+                            format!("{} has no kind {}", api_version, kind),
+                        )),
+                        Some(schema) => {
+                            let mut validator = Validator {
+                                document: &schema_document,
+                                issues: Vec::new(),
+                            };
+                            validator.check(document, schema, "");
+                            issues.extend(validator.issues);
+                        }
+                    },
+                }
+            }
+            _ => issues.push(issue(
+                IssueSeverity::Error,
+                "",
+                "Missing apiVersion or kind".to_string(),
+            )),
+        }
+        if name.is_none() && document.pointer("/metadata/generateName").is_none() {
+            issues.push(issue(
+                IssueSeverity::Error,
+                "metadata.name",
+                "Required field is missing".to_string(),
+            ));
+        }
+
+        results.push(DocumentValidation {
+            index,
+            api_version,
+            kind,
+            name,
+            valid: issues.iter().all(|i| i.severity != IssueSeverity::Error),
+            issues,
+        });
+    }
+    Ok(results)
+}
@@ -0,0 +1,203 @@
+// Advertise the backend on the LAN as _kubilitics._tcp so the mobile app's discovery finds it
+// without a QR scan. Off by default — it announces the machine to everyone on the network.
+//
+// The TXT record carries what the phone needs before connecting: the backend port, the app
+// version, and a pairing hint (`pairing=open` while a pairing code is outstanding, so the app can
+// jump straight to token entry). Never the token itself.
+use std::path::PathBuf;
+
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use serde::{Deserialize, Serialize};
+use tauri::command;
+use tokio::sync::Mutex;
+
+use crate::backend_ports::BACKEND_PORT;
+use crate::commands::get_app_data_dir;
+
+const SERVICE_TYPE: &str = "_kubilitics._tcp.local.";
+const TXT_VERSION: &str = "1";
+
+static ADVERTISEMENT: Mutex<Option<Advertisement>> = Mutex::const_new(None);
+
+struct Advertisement {
+    daemon: ServiceDaemon,
+    fullname: String,
+    instance_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MdnsSettings {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MdnsStatus {
+    pub enabled: bool,
+    pub advertising: bool,
+    pub service_type: String,
+    pub instance_name: Option<String>,
+}
+
+async fn get_mdns_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("mdns_settings.json"))
+}
+
+async fn load_mdns_settings() -> Result<MdnsSettings, String> {
+    let path = get_mdns_settings_path().await?;
+
+    if !path.exists() {
+        return Ok(MdnsSettings::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read mDNS settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse mDNS settings".to_string())
+}
+
+async fn save_mdns_settings(settings: &MdnsSettings) -> Result<(), String> {
+    let path = get_mdns_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize mDNS settings".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write mDNS settings".to_string())
+}
+
+/// Short machine name for the instance and host labels. GUI apps on macOS don't get $HOSTNAME, so
+/// ask the `hostname` command, which exists on all three platforms.
+fn machine_name() -> String {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .and_then(|name| name.split('.').next().map(str::to_string))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "kubilitics-desktop".to_string())
+}
+
+/// A backend bound to loopback can't be reached from a phone, so there is nothing to advertise.
+async fn ensure_reachable_from_lan() -> Result<(), String> {
+    let bind_address = crate::commands::load_connectivity_settings()
+        .await
+        .ok()
+        .and_then(|s| s.backend_bind_address);
+    match bind_address.and_then(|a| a.parse::<std::net::IpAddr>().ok()) {
+        Some(ip) if ip.is_loopback() => Err(
+            "The backend only listens on loopback; change its bind address to advertise it on the LAN".to_string(),
+        ),
+        _ => Ok(()),
+    }
+}
+
+async fn register(advertisement: &mut Option<Advertisement>) -> Result<(), String> {
+    ensure_reachable_from_lan().await?;
+    unregister(advertisement);
+
+    let machine = tokio::task::spawn_blocking(machine_name)
+        .await
+        .unwrap_or_else(|_| "kubilitics-desktop".to_string());
+    let ip = local_ip_address::local_ip()
+        .map_err(|e| format!("Could not determine this machine's LAN address: {}", e))?;
+    let pairing = if crate::pairing::has_outstanding_tokens().await { "open" } else { "closed" };
+    let port = BACKEND_PORT.to_string();
+    let properties = [
+        ("v", TXT_VERSION),
+        ("port", port.as_str()),
+        ("version", env!("CARGO_PKG_VERSION")),
+        ("pairing", pairing),
+    ];
+
+    let instance_name = format!("Kubilitics on {}", machine);
+    let info = ServiceInfo::new(
+        SERVICE_TYPE,
+        &instance_name,
+        &format!("{}.local.", machine),
+        ip.to_string().as_str(),
+        BACKEND_PORT,
+        &properties[..],
+    )
+    .map_err(|e| format!("Invalid mDNS service: {}", e))?
+    // Follow address changes (Wi-Fi switches) without re-registering
+    .enable_addr_auto();
+    let fullname = info.get_fullname().to_string();
+
+    let daemon = ServiceDaemon::new().map_err(|e| format!("Failed to start mDNS responder: {}", e))?;
+    daemon
+        .register(info)
+        .map_err(|e| format!("Failed to register mDNS service: {}", e))?;
+
+    *advertisement = Some(Advertisement {
+        daemon,
+        fullname,
+        instance_name,
+    });
+    Ok(())
+}
+
+/// Withdraw the service (goodbye packets, so browsers drop it at once) and stop the responder.
+fn unregister(advertisement: &mut Option<Advertisement>) {
+    if let Some(advertisement) = advertisement.take() {
+        let _ = advertisement.daemon.unregister(&advertisement.fullname);
+        let _ = advertisement.daemon.shutdown();
+    }
+}
+
+/// Start advertising at launch if it was switched on.
+pub fn start_mdns_advertising() {
+    tauri::async_runtime::spawn(async {
+        if !load_mdns_settings().await.unwrap_or_default().enabled {
+            return;
+        }
+        if let Err(e) = register(&mut *ADVERTISEMENT.lock().await).await {
+            tracing::warn!(error = %e, "mDNS advertising unavailable");
+        }
+    });
+}
+
+/// Re-announce with the current pairing hint. A no-op when not advertising.
+pub async fn refresh_pairing_hint() {
+    let mut advertisement = ADVERTISEMENT.lock().await;
+    if advertisement.is_some() {
+        if let Err(e) = register(&mut advertisement).await {
+            tracing::warn!(error = %e, "Failed to update mDNS advertisement");
+        }
+    }
+}
+
+/// Stop advertising on exit.
+pub async fn stop() {
+    unregister(&mut *ADVERTISEMENT.lock().await);
+}
+
+#[command]
+pub async fn get_mdns_status() -> Result<MdnsStatus, String> {
+    let enabled = load_mdns_settings().await?.enabled;
+    let advertisement = ADVERTISEMENT.lock().await;
+    Ok(MdnsStatus {
+        enabled,
+        advertising: advertisement.is_some(),
+        service_type: SERVICE_TYPE.to_string(),
+        instance_name: advertisement.as_ref().map(|a| a.instance_name.clone()),
+    })
+}
+
+/// Switch LAN advertising on or off. Takes effect immediately and persists across restarts.
+#[command]
+pub async fn set_mdns_advertising(enabled: bool) -> Result<MdnsStatus, String> {
+    {
+        let mut advertisement = ADVERTISEMENT.lock().await;
+        if enabled {
+            register(&mut advertisement).await?;
+        } else {
+            unregister(&mut advertisement);
+        }
+    }
+    save_mdns_settings(&MdnsSettings { enabled }).await?;
+    get_mdns_status().await
+}
@@ -0,0 +1,300 @@
+// CPU and memory usage from metrics.k8s.io (metrics-server), scraped by the shell on a timer and
+// kept in memory for a short window, so the sparklines on node and pod views read from here instead
+// of asking the backend on every render. Each collection run lists all node and pod metrics once
+// per interval; objects that disappear from a scrape are dropped from the history.
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use kube::api::{ApiResource, DynamicObject, ListParams};
+use kube::{Api, Client};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::command;
+use tokio::sync::{Mutex, RwLock};
+
+const DEFAULT_INTERVAL_SECS: u64 = 15;
+const MIN_INTERVAL_SECS: u64 = 5;
+/// History kept per object.
+const RETENTION: Duration = Duration::from_secs(15 * 60);
+
+struct Collector {
+    history: Arc<RwLock<HashMap<String, VecDeque<MetricSample>>>>,
+    last_error: Arc<RwLock<Option<String>>>,
+    interval_secs: u64,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static COLLECTORS: Mutex<BTreeMap<String, Collector>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MetricSample {
+    /// Unix seconds of the metrics-server sample (the scrape time when it reports none).
+    pub at: u64,
+    pub cpu_millicores: f64,
+    pub memory_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsCollectorStatus {
+    pub context: String,
+    pub interval_secs: u64,
+    pub targets: usize,
+    /// Set while metrics.k8s.io can't be read (e.g. metrics-server not installed).
+    pub last_error: Option<String>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn metrics_resource(kind: &str, plural: &str) -> ApiResource {
+    ApiResource {
+        group: "metrics.k8s.io".to_string(),
+        version: "v1beta1".to_string(),
+        api_version: "metrics.k8s.io/v1beta1".to_string(),
+        kind: kind.to_string(),
+        plural: plural.to_string(),
+    }
+}
+
+/// CPU quantity in millicores: "250m", "1", "1500000n", "12u".
+fn parse_cpu(quantity: &str) -> Option<f64> {
+    let (number, scale) = match quantity.chars().last()? {
+        'n' => (&quantity[..quantity.len() - 1], 1e-6),
+        'u' => (&quantity[..quantity.len() - 1], 1e-3),
+        'm' => (&quantity[..quantity.len() - 1], 1.0),
+        _ => (quantity, 1000.0),
+    };
+    number.parse::<f64>().ok().map(|n| n * scale)
+}
+
+/// Memory quantity in bytes: "123456", "512Ki", "1Gi", "100M".
+fn parse_memory(quantity: &str) -> Option<u64> {
+    const SUFFIXES: [(&str, f64); 10] = [
+        ("Ki", 1024.0),
+        ("Mi", 1048576.0),
+        ("Gi", 1073741824.0),
+        ("Ti", 1099511627776.0),
+        ("k", 1e3),
+        ("K", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+        ("m", 1e-3),
+    ];
+    let (number, scale) = SUFFIXES
+        .iter()
+        .find_map(|(suffix, scale)| quantity.strip_suffix(suffix).map(|n| (n, *scale)))
+        .unwrap_or((quantity, 1.0));
+    number.parse::<f64>().ok().map(|n| (n * scale) as u64)
+}
+
+fn sample_time(object: &DynamicObject, fallback: u64) -> u64 {
+    object
+        .data
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.timestamp() as u64)
+        .unwrap_or(fallback)
+}
+
+fn usage_of(usage: Option<&Value>) -> (f64, u64) {
+    let field = |name: &str| usage.and_then(|u| u.get(name)).and_then(Value::as_str);
+    (
+        field("cpu").and_then(parse_cpu).unwrap_or(0.0),
+        field("memory").and_then(parse_memory).unwrap_or(0),
+    )
+}
+
+/// One scrape: target key → sample. Keys are "node/<name>" and "pod/<namespace>/<name>".
+async fn scrape(client: &Client) -> Result<Vec<(String, MetricSample)>, String> {
+    let now = now_secs();
+    let mut samples = Vec::new();
+
+    let nodes: Api<DynamicObject> =
+        Api::all_with(client.clone(), &metrics_resource("NodeMetrics", "nodes"));
+    let list = nodes
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to read node metrics: {}", e))?;
+    for node in &list.items {
+        let Some(name) = node.metadata.name.as_deref() else {
+            continue;
+        };
+        let (cpu_millicores, memory_bytes) = usage_of(node.data.get("usage"));
+        samples.push((
+            format!("node/{}", name),
+            MetricSample {
+                at: sample_time(node, now),
+                cpu_millicores,
+                memory_bytes,
+            },
+        ));
+    }
+
+    let pods: Api<DynamicObject> =
+        Api::all_with(client.clone(), &metrics_resource("PodMetrics", "pods"));
+    let list = pods
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| format!("Failed to read pod metrics: {}", e))?;
+    for pod in &list.items {
+        let (Some(namespace), Some(name)) = (
+            pod.metadata.namespace.as_deref(),
+            pod.metadata.name.as_deref(),
+        ) else {
+            continue;
+        };
+        let (cpu_millicores, memory_bytes) = pod
+            .data
+            .get("containers")
+            .and_then(Value::as_array)
+            .map(|containers| {
+                containers
+                    .iter()
+                    .map(|c| usage_of(c.get("usage")))
+                    .fold((0.0, 0), |(cpu, mem), (c, m)| (cpu + c, mem + m))
+            })
+            .unwrap_or((0.0, 0));
+        samples.push((
+            format!("pod/{}/{}", namespace, name),
+            MetricSample {
+                at: sample_time(pod, now),
+                cpu_millicores,
+                memory_bytes,
+            },
+        ));
+    }
+    Ok(samples)
+}
+
+async fn collect(
+    client: Client,
+    interval_secs: u64,
+    history: Arc<RwLock<HashMap<String, VecDeque<MetricSample>>>>,
+    last_error: Arc<RwLock<Option<String>>>,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let samples = match scrape(&client).await {
+            Ok(samples) => samples,
+            Err(e) => {
+                *last_error.write().await = Some(e);
+                continue;
+            }
+        };
+        *last_error.write().await = None;
+
+        let cutoff = now_secs().saturating_sub(RETENTION.as_secs());
+        let mut history = history.write().await;
+        let seen: std::collections::HashSet<&String> = samples.iter().map(|(k, _)| k).collect();
+        history.retain(|key, _| seen.contains(key));
+        for (key, sample) in samples {
+            let series = history.entry(key).or_default();
+            // metrics-server refreshes less often than we may poll; skip repeats
+            if series.back().is_some_and(|last| last.at == sample.at) {
+                continue;
+            }
+            series.push_back(sample);
+            while series.front().is_some_and(|s| s.at < cutoff) {
+                series.pop_front();
+            }
+        }
+    }
+}
+
+/// Start collecting a context's metrics every `interval_secs` (default 15). Restarts the collector
+/// when the interval changes; history is kept.
+#[command]
+pub async fn start_metrics_collection(
+    context: String,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    let interval_secs = interval_secs
+        .unwrap_or(DEFAULT_INTERVAL_SECS)
+        .max(MIN_INTERVAL_SECS);
+    let mut collectors = COLLECTORS.lock().await;
+    let (history, last_error) = match collectors.remove(&context) {
+        Some(existing) if existing.interval_secs == interval_secs => {
+            collectors.insert(context, existing);
+            return Ok(());
+        }
+        Some(existing) => {
+            existing.task.abort();
+            (existing.history, existing.last_error)
+        }
+        None => Default::default(),
+    };
+
+    let client = crate::k8s::client_for(&context).await?;
+    let task = tauri::async_runtime::spawn(collect(
+        client,
+        interval_secs,
+        history.clone(),
+        last_error.clone(),
+    ));
+    collectors.insert(
+        context,
+        Collector {
+            history,
+            last_error,
+            interval_secs,
+            task,
+        },
+    );
+    Ok(())
+}
+
+#[command]
+pub async fn stop_metrics_collection(context: String) -> Result<(), String> {
+    if let Some(collector) = COLLECTORS.lock().await.remove(&context) {
+        collector.task.abort();
+    }
+    Ok(())
+}
+
+#[command]
+pub async fn get_metrics_collection_status() -> Result<Vec<MetricsCollectorStatus>, String> {
+    let collectors = COLLECTORS.lock().await;
+    let mut statuses = Vec::with_capacity(collectors.len());
+    for (context, collector) in collectors.iter() {
+        statuses.push(MetricsCollectorStatus {
+            context: context.clone(),
+            interval_secs: collector.interval_secs,
+            targets: collector.history.read().await.len(),
+            last_error: collector.last_error.read().await.clone(),
+        });
+    }
+    Ok(statuses)
+}
+
+/// Samples for `target` ("node/<name>" or "pod/<namespace>/<name>") from the last `range_secs`
+/// (default: everything kept), oldest first.
+#[command]
+pub async fn get_metrics(
+    context: String,
+    target: String,
+    range_secs: Option<u64>,
+) -> Result<Vec<MetricSample>, String> {
+    let history = COLLECTORS
+        .lock()
+        .await
+        .get(&context)
+        .map(|c| c.history.clone())
+        .ok_or_else(|| format!("Metrics are not being collected for '{}'", context))?;
+    let since = range_secs
+        .map(|r| now_secs().saturating_sub(r))
+        .unwrap_or(0);
+    let history = history.read().await;
+    Ok(history
+        .get(&target)
+        .map(|series| series.iter().filter(|s| s.at >= since).copied().collect())
+        .unwrap_or_default())
+}
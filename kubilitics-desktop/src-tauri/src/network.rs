@@ -0,0 +1,391 @@
+// Network status from OS address-change notifications (netlink on Linux, SystemConfiguration on
+// macOS, NotifyIpInterfaceChange on Windows) instead of polling public hosts. "Online" means a
+// routable local address is configured — finding out never sends a request off the machine.
+//
+// Also owns low-bandwidth mode: on a metered connection (or when switched on by hand) background
+// work backs off — sidecar health checks run less often, background refreshes pause — and the
+// frontend is told to reduce its polling.
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::StreamExt;
+use if_watch::IfEvent;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+
+/// Address changes arrive in bursts (one event per address); report once they settle.
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Metered status can change without any address change (e.g. toggled in the OS settings).
+const BANDWIDTH_RECHECK_SECS: u64 = 5 * 60;
+/// How much longer polling intervals get in low-bandwidth mode.
+pub const LOW_BANDWIDTH_INTERVAL_MULTIPLIER: u32 = 3;
+
+static NETWORK_STATUS: Mutex<Option<NetworkStatus>> = Mutex::const_new(None);
+/// Read synchronously by the background loops, so kept outside the settings lock.
+static LOW_BANDWIDTH: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LowBandwidthMode {
+    /// On while the connection is metered.
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BandwidthSettings {
+    pub mode: LowBandwidthMode,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LowBandwidthState {
+    pub enabled: bool,
+    pub mode: LowBandwidthMode,
+    pub metered: bool,
+    /// Factor the frontend should stretch its polling intervals by (1 when disabled).
+    pub poll_interval_multiplier: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkStatus {
+    pub online: bool,
+    /// Routable local addresses.
+    pub addresses: Vec<String>,
+    /// False when the OS watcher isn't running; `online` is then assumed.
+    pub monitored: bool,
+    pub changed_at: u64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Loopback, link-local and unspecified addresses exist without any usable network.
+fn is_routable(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(a) => !a.is_loopback() && !a.is_link_local() && !a.is_unspecified(),
+        IpAddr::V6(a) => {
+            !a.is_loopback() && !a.is_unspecified() && (a.segments()[0] & 0xffc0) != 0xfe80
+        }
+    }
+}
+
+fn apply_event(addresses: &mut BTreeSet<IpAddr>, event: IfEvent) {
+    match event {
+        IfEvent::Up(net) if is_routable(&net.addr()) => {
+            addresses.insert(net.addr());
+        }
+        IfEvent::Up(_) => {}
+        IfEvent::Down(net) => {
+            addresses.remove(&net.addr());
+        }
+    }
+}
+
+/// Record the address set and emit `network-changed` if it differs from the last report.
+async fn publish(app: &AppHandle, addresses: &BTreeSet<IpAddr>) {
+    let addresses: Vec<String> = addresses.iter().map(IpAddr::to_string).collect();
+    let mut current = NETWORK_STATUS.lock().await;
+    if current.as_ref().is_some_and(|s| s.addresses == addresses) {
+        return;
+    }
+
+    let status = NetworkStatus {
+        online: !addresses.is_empty(),
+        addresses,
+        monitored: true,
+        changed_at: now_secs(),
+    };
+    let _ = app.emit("network-changed", &status);
+    *current = Some(status);
+    drop(current);
+
+    // A new network may be a phone hotspot
+    refresh_low_bandwidth(app).await;
+}
+
+/// Watch local address changes for the lifetime of the app.
+pub fn start_network_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut watcher = match if_watch::tokio::IfWatcher::new() {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!(error = %e, "Network change monitoring unavailable");
+                return;
+            }
+        };
+
+        // The watcher only reports changes on some platforms, so seed from the current interfaces
+        let mut addresses: BTreeSet<IpAddr> = local_ip_address::list_afinet_netifas()
+            .map(|ifaces| ifaces.into_iter().map(|(_, ip)| ip).filter(is_routable).collect())
+            .unwrap_or_default();
+        publish(&app, &addresses).await;
+
+        while let Some(event) = watcher.next().await {
+            match event {
+                Ok(event) => apply_event(&mut addresses, event),
+                Err(e) => tracing::warn!(error = %e, "Network change event error"),
+            }
+            while let Ok(Some(event)) = tokio::time::timeout(SETTLE_DELAY, watcher.next()).await {
+                if let Ok(event) = event {
+                    apply_event(&mut addresses, event);
+                }
+            }
+            publish(&app, &addresses).await;
+        }
+        tracing::warn!("Network change monitoring stopped");
+    });
+}
+
+/// Best-effort metered-connection detection (tethering to a phone usually reports as metered).
+/// Unknown counts as not metered, so platforms without a way to ask keep their background work.
+pub fn is_metered_connection() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::Command;
+
+        // NetworkManager's global Metered property: 1 = yes, 3 = guess yes
+        Command::new("busctl")
+            .args([
+                "get-property",
+                "org.freedesktop.NetworkManager",
+                "/org/freedesktop/NetworkManager",
+                "org.freedesktop.NetworkManager",
+                "Metered",
+            ])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| {
+                let out = String::from_utf8_lossy(&o.stdout);
+                matches!(out.trim(), "u 1" | "u 3")
+            })
+            .unwrap_or(false)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        use std::process::Command;
+
+        // Don't flash a console window on every background check
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+        // NetworkCostType: Unrestricted | Fixed | Variable | Unknown
+        const SCRIPT: &str = "[void][Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]; \
+            $p = [Windows.Networking.Connectivity.NetworkInformation]::GetInternetConnectionProfile(); \
+            if ($p) { $p.GetConnectionCost().NetworkCostType }";
+        Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| {
+                let out = String::from_utf8_lossy(&o.stdout);
+                matches!(out.trim(), "Fixed" | "Variable")
+            })
+            .unwrap_or(false)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        path_monitor::is_expensive()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// macOS only reports metered ("expensive": cellular, Personal Hotspot, Low Data Mode) paths through
+/// Network.framework's path monitor — there is no CLI to ask. One monitor runs for the app's
+/// lifetime and keeps the latest answer.
+#[cfg(target_os = "macos")]
+mod path_monitor {
+    use std::ffi::c_void;
+    use std::sync::{Condvar, Mutex, Once};
+    use std::time::Duration;
+
+    use block2::{Block, RcBlock};
+    use dispatch2::DispatchQueue;
+
+    /// How long the first caller waits for the monitor's initial path.
+    const FIRST_PATH_TIMEOUT: Duration = Duration::from_secs(1);
+
+    type NwPathMonitor = *mut c_void;
+    type NwPath = *mut c_void;
+
+    #[link(name = "Network", kind = "framework")]
+    extern "C" {
+        fn nw_path_monitor_create() -> NwPathMonitor;
+        fn nw_path_monitor_set_queue(monitor: NwPathMonitor, queue: &DispatchQueue);
+        fn nw_path_monitor_set_update_handler(monitor: NwPathMonitor, handler: &Block<dyn Fn(NwPath)>);
+        fn nw_path_monitor_start(monitor: NwPathMonitor);
+        fn nw_path_is_expensive(path: NwPath) -> bool;
+    }
+
+    static START: Once = Once::new();
+    /// `None` until the monitor reports its first path.
+    static EXPENSIVE: Mutex<Option<bool>> = Mutex::new(None);
+    static UPDATED: Condvar = Condvar::new();
+
+    fn start() {
+        START.call_once(|| {
+            let queue = DispatchQueue::new("dev.kubilitics.path-monitor", None);
+            let handler = RcBlock::new(|path: NwPath| {
+                // SAFETY: the monitor passes a valid path object for the duration of the call.
+                let expensive = unsafe { nw_path_is_expensive(path) };
+                if let Ok(mut current) = EXPENSIVE.lock() {
+                    *current = Some(expensive);
+                }
+                UPDATED.notify_all();
+            });
+            // SAFETY: the monitor is freshly created and configured before it starts; it copies the
+            // handler block. It is never cancelled, so it is never released.
+            unsafe {
+                let monitor = nw_path_monitor_create();
+                nw_path_monitor_set_queue(monitor, &queue);
+                nw_path_monitor_set_update_handler(monitor, &handler);
+                nw_path_monitor_start(monitor);
+            }
+            // Serves the monitor for the app's lifetime
+            std::mem::forget(queue);
+        });
+    }
+
+    /// Whether the current path is expensive. Blocks the first caller until the monitor's initial
+    /// report (at most FIRST_PATH_TIMEOUT); no report counts as not expensive.
+    pub fn is_expensive() -> bool {
+        start();
+        let Ok(current) = EXPENSIVE.lock() else {
+            return false;
+        };
+        UPDATED
+            .wait_timeout_while(current, FIRST_PATH_TIMEOUT, |current| current.is_none())
+            .ok()
+            .and_then(|(current, _)| *current)
+            .unwrap_or(false)
+    }
+}
+
+async fn get_bandwidth_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("bandwidth_settings.json"))
+}
+
+async fn load_bandwidth_settings() -> Result<BandwidthSettings, String> {
+    let path = get_bandwidth_settings_path().await?;
+
+    if !path.exists() {
+        return Ok(BandwidthSettings::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read bandwidth settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse bandwidth settings".to_string())
+}
+
+async fn save_bandwidth_settings(settings: &BandwidthSettings) -> Result<(), String> {
+    let path = get_bandwidth_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize bandwidth settings".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write bandwidth settings".to_string())
+}
+
+pub fn is_low_bandwidth() -> bool {
+    LOW_BANDWIDTH.load(Ordering::Relaxed)
+}
+
+/// Stretch a background polling interval while in low-bandwidth mode.
+pub fn polling_interval(base: Duration) -> Duration {
+    if is_low_bandwidth() {
+        base * LOW_BANDWIDTH_INTERVAL_MULTIPLIER
+    } else {
+        base
+    }
+}
+
+async fn low_bandwidth_state() -> LowBandwidthState {
+    let mode = load_bandwidth_settings().await.unwrap_or_default().mode;
+    let metered = tokio::task::spawn_blocking(is_metered_connection)
+        .await
+        .unwrap_or(false);
+    let enabled = match mode {
+        LowBandwidthMode::Auto => metered,
+        LowBandwidthMode::On => true,
+        LowBandwidthMode::Off => false,
+    };
+    LowBandwidthState {
+        enabled,
+        mode,
+        metered,
+        poll_interval_multiplier: if enabled { LOW_BANDWIDTH_INTERVAL_MULTIPLIER } else { 1 },
+    }
+}
+
+/// Re-evaluate low-bandwidth mode and emit `low-bandwidth-changed` when it flips.
+async fn refresh_low_bandwidth(app: &AppHandle) -> LowBandwidthState {
+    let state = low_bandwidth_state().await;
+    if LOW_BANDWIDTH.swap(state.enabled, Ordering::Relaxed) != state.enabled {
+        let _ = app.emit("low-bandwidth-changed", &state);
+    }
+    state
+}
+
+/// Periodic metered re-check; address changes trigger one as well.
+pub fn start_bandwidth_monitor(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh_low_bandwidth(&app).await;
+            tokio::time::sleep(Duration::from_secs(BANDWIDTH_RECHECK_SECS)).await;
+        }
+    });
+}
+
+pub async fn current_network_status() -> NetworkStatus {
+    NETWORK_STATUS.lock().await.clone().unwrap_or(NetworkStatus {
+        online: true,
+        addresses: Vec::new(),
+        monitored: false,
+        changed_at: 0,
+    })
+}
+
+#[command]
+pub async fn get_network_status() -> Result<NetworkStatus, String> {
+    Ok(current_network_status().await)
+}
+
+#[command]
+pub async fn get_low_bandwidth_state() -> Result<LowBandwidthState, String> {
+    Ok(low_bandwidth_state().await)
+}
+
+/// Switch low-bandwidth mode: `auto` follows the metered status, `on`/`off` force it.
+#[command]
+pub async fn set_low_bandwidth_mode(app_handle: AppHandle, mode: LowBandwidthMode) -> Result<LowBandwidthState, String> {
+    save_bandwidth_settings(&BandwidthSettings { mode }).await?;
+    Ok(refresh_low_bandwidth(&app_handle).await)
+}
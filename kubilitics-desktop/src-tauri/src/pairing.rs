@@ -0,0 +1,273 @@
+// Desktop half of QR-code pairing: the desktop shows a QR code carrying everything a mobile
+// device needs to connect (backend URL and a short-lived pairing code), so nobody types URLs on a
+// phone keyboard.
+//
+// The code only pairs; it is not a login. The device redeems it once at the backend's
+// `POST /api/v1/pairing/exchange` for a long-lived credential of its own, which it then sends in
+// X-Kubilitics-Device-Token (or `?device_token=` on WebSockets). The desktop writes issued codes
+// (KUBILITICS_PAIRING_TOKENS_PATH); the backend owns the paired-devices file
+// (KUBILITICS_PAIRED_DEVICES_PATH), where credentials are stored hashed, and serves the device list
+// and revocation to loopback callers only. Revoking a device cuts it off at once; the others stay
+// paired.
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{command, AppHandle};
+use tokio::fs;
+use tokio::sync::Mutex;
+
+use crate::backend_ports::BACKEND_PORT;
+use crate::commands::get_app_data_dir;
+
+const PAIRING_PAYLOAD_VERSION: u32 = 1;
+const DEFAULT_TOKEN_TTL_SECS: u64 = 10 * 60;
+const MAX_TOKEN_TTL_SECS: u64 = 60 * 60;
+const QR_MIN_SIZE: u32 = 256;
+const DEVICES_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serializes read-modify-write cycles on the pairing token file.
+static PAIRING_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// What the QR code encodes (as compact JSON).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairingPayload {
+    pub v: u32,
+    pub backend_url: String,
+    pub token: String,
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingCode {
+    pub payload: PairingPayload,
+    /// The exact string encoded in the QR code.
+    pub payload_text: String,
+    pub qr_svg: String,
+}
+
+/// A device paired through the backend's exchange, as listed by the backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairedDevice {
+    pub id: String,
+    pub name: String,
+    pub paired_at: u64,
+}
+
+/// Issued tokens are stored hashed — the plaintext only ever exists in the QR code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IssuedToken {
+    token_sha256: String,
+    expires_at: u64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+async fn get_tokens_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("pairing_tokens.json"))
+}
+
+/// Written only by the backend.
+async fn get_devices_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("paired_devices.json"))
+}
+
+/// Load issued tokens, dropping expired ones. Callers must hold PAIRING_LOCK.
+async fn load_tokens() -> Result<Vec<IssuedToken>, String> {
+    let path = get_tokens_path().await?;
+
+    if !fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| "Failed to read pairing tokens".to_string())?;
+    let tokens: Vec<IssuedToken> = serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse pairing tokens".to_string())?;
+
+    let now = now_secs();
+    Ok(tokens.into_iter().filter(|t| t.expires_at > now).collect())
+}
+
+async fn save_tokens(tokens: &[IssuedToken]) -> Result<(), String> {
+    let path = get_tokens_path().await?;
+
+    let content = serde_json::to_string_pretty(tokens)
+        .map_err(|_| "Failed to serialize pairing tokens".to_string())?;
+
+    // The backend reads this file concurrently: replace it in one rename, never half-written
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content)
+        .await
+        .map_err(|_| "Failed to write pairing tokens".to_string())?;
+    fs::rename(&tmp, &path)
+        .await
+        .map_err(|_| "Failed to write pairing tokens".to_string())
+}
+
+/// Hashes of codes the backend has already exchanged, from its devices file.
+async fn redeemed_codes() -> Vec<String> {
+    #[derive(Deserialize, Default)]
+    struct DevicesFile {
+        #[serde(default)]
+        used_codes: Vec<IssuedToken>,
+    }
+    let Ok(path) = get_devices_path().await else {
+        return Vec::new();
+    };
+    let Ok(content) = fs::read_to_string(&path).await else {
+        return Vec::new();
+    };
+    serde_json::from_str::<DevicesFile>(&content)
+        .unwrap_or_default()
+        .used_codes
+        .into_iter()
+        .map(|t| t.token_sha256)
+        .collect()
+}
+
+/// Environment for the backend sidecar, which redeems codes from the tokens file and keeps the
+/// paired devices.
+pub async fn sidecar_env() -> Vec<(String, String)> {
+    match (get_tokens_path().await, get_devices_path().await) {
+        (Ok(tokens), Ok(devices)) => vec![
+            ("KUBILITICS_PAIRING_TOKENS_PATH".to_string(), tokens.to_string_lossy().to_string()),
+            ("KUBILITICS_PAIRED_DEVICES_PATH".to_string(), devices.to_string_lossy().to_string()),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Whether an unexpired, unredeemed pairing code is outstanding (the mDNS pairing hint).
+pub(crate) async fn has_outstanding_tokens() -> bool {
+    let tokens = {
+        let _guard = PAIRING_LOCK.lock().await;
+        load_tokens().await.unwrap_or_default()
+    };
+    let redeemed = redeemed_codes().await;
+    tokens.iter().any(|t| !redeemed.contains(&t.token_sha256))
+}
+
+/// The backend URL as seen from another device on the LAN — `localhost` is useless to a phone.
+fn lan_backend_url() -> Result<String, String> {
+    let ip = local_ip_address::local_ip()
+        .map_err(|e| format!("Could not determine this machine's LAN address: {}", e))?;
+    Ok(match ip {
+        std::net::IpAddr::V4(v4) => format!("http://{}:{}", v4, BACKEND_PORT),
+        std::net::IpAddr::V6(v6) => format!("http://[{}]:{}", v6, BACKEND_PORT),
+    })
+}
+
+/// Generate a pairing payload with a fresh one-time code, plus its QR code as SVG.
+#[command]
+pub async fn create_pairing_payload(ttl_secs: Option<u64>) -> Result<PairingCode, String> {
+    let ttl = ttl_secs.unwrap_or(DEFAULT_TOKEN_TTL_SECS).clamp(60, MAX_TOKEN_TTL_SECS);
+    let token: String = (0..4).map(|_| format!("{:016x}", rand::random::<u64>())).collect();
+    let expires_at = now_secs() + ttl;
+
+    let payload = PairingPayload {
+        v: PAIRING_PAYLOAD_VERSION,
+        backend_url: lan_backend_url()?,
+        token: token.clone(),
+        expires_at,
+    };
+    let payload_text = serde_json::to_string(&payload)
+        .map_err(|e| format!("Failed to serialize pairing payload: {}", e))?;
+    let qr_svg = QrCode::new(payload_text.as_bytes())
+        .map_err(|e| format!("Failed to generate QR code: {}", e))?
+        .render::<svg::Color>()
+        .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+        .build();
+
+    let guard = PAIRING_LOCK.lock().await;
+    let mut tokens = load_tokens().await?;
+    tokens.push(IssuedToken {
+        token_sha256: hash_token(&token),
+        expires_at,
+    });
+    save_tokens(&tokens).await?;
+    drop(guard);
+    crate::mdns::refresh_pairing_hint().await;
+
+    Ok(PairingCode {
+        payload,
+        payload_text,
+        qr_svg,
+    })
+}
+
+/// Invalidate every outstanding pairing code (e.g. after a QR code was shown on a shared screen).
+/// Devices that already paired are not affected; see `revoke_paired_device`.
+#[command]
+pub async fn revoke_pairing_tokens() -> Result<(), String> {
+    {
+        let _guard = PAIRING_LOCK.lock().await;
+        save_tokens(&[]).await?;
+    }
+    crate::mdns::refresh_pairing_hint().await;
+    Ok(())
+}
+
+async fn devices_url(app_handle: &AppHandle, suffix: &str) -> Result<(reqwest::Client, String), String> {
+    let client = crate::http_client::shared(app_handle).await?;
+    let base = crate::loopback::base_url(BACKEND_PORT).await;
+    Ok((client, format!("{}/api/v1/pairing/devices{}", base, suffix)))
+}
+
+/// The devices paired with this desktop's backend.
+#[command]
+pub async fn list_paired_devices(app_handle: AppHandle) -> Result<Vec<PairedDevice>, String> {
+    let (client, url) = devices_url(&app_handle, "").await?;
+    let response = client
+        .get(&url)
+        .timeout(DEVICES_REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach the backend: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to list paired devices: HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse paired devices: {}", e))
+}
+
+/// Unpair a device. Its credential stops working immediately.
+#[command]
+pub async fn revoke_paired_device(app_handle: AppHandle, id: String) -> Result<(), String> {
+    // Backend-issued ids are hex; anything else would change the request path
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Invalid device id".to_string());
+    }
+    let (client, url) = devices_url(&app_handle, &format!("/{}", id)).await?;
+    let response = client
+        .delete(&url)
+        .timeout(DEVICES_REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach the backend: {}", e))?;
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        reqwest::StatusCode::NOT_FOUND => Err("Device is not paired".to_string()),
+        status => Err(format!("Failed to revoke device: HTTP {}", status)),
+    }
+}
@@ -0,0 +1,263 @@
+// Performance tracing. Sidecar startup, health checks and the heavier commands are instrumented
+// with `tracing` spans; this layer keeps every closed span from the last half hour in memory so a
+// "startup is slow" report can come with a real timeline instead of println timestamps.
+//
+// `export_performance_trace` writes the window as a Chrome trace (chrome://tracing, Perfetto) or as
+// OTLP/JSON for an OpenTelemetry collector or Jaeger import.
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::command;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::commands::get_app_data_dir;
+
+const RETENTION: Duration = Duration::from_secs(30 * 60);
+/// Hard cap in case something opens spans in a tight loop.
+const MAX_RECORDS: usize = 50_000;
+const DEFAULT_EXPORT_MINUTES: u32 = 10;
+const TRACES_DIR: &str = "traces";
+
+static RECORDS: Mutex<VecDeque<SpanRecord>> = Mutex::new(VecDeque::new());
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Small stable per-thread number for the trace viewers' thread lanes.
+    static THREAD_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+fn thread_id() -> u64 {
+    THREAD_ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceFormat {
+    Chrome,
+    Otlp,
+}
+
+#[derive(Debug, Clone)]
+struct SpanRecord {
+    name: &'static str,
+    target: &'static str,
+    span_id: u64,
+    parent_id: Option<u64>,
+    /// Span id of the root of this span's tree; stands in for a trace id.
+    root_id: u64,
+    thread_id: u64,
+    start_unix_us: u64,
+    duration_us: u64,
+    fields: Vec<(&'static str, String)>,
+}
+
+/// Per-span state kept in the registry's extensions until the span closes.
+struct Timing {
+    root_id: u64,
+    thread_id: u64,
+    start_unix_us: u64,
+    started: Instant,
+    fields: Vec<(&'static str, String)>,
+}
+
+#[derive(Default)]
+struct FieldCollector(Vec<(&'static str, String)>);
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let mut s = String::new();
+        let _ = write!(s, "{:?}", value);
+        self.0.push((field.name(), s));
+    }
+}
+
+fn unix_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+/// Records span timings into the in-memory window. Installed by `logging::init`.
+pub struct SpanTimingLayer;
+
+impl<S> Layer<S> for SpanTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let root_id = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<Timing>().map(|t| t.root_id))
+            .unwrap_or(id.into_u64());
+        let mut fields = FieldCollector::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(Timing {
+            root_id,
+            thread_id: thread_id(),
+            start_unix_us: unix_micros(),
+            started: Instant::now(),
+            fields: fields.0,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(timing) = extensions.get_mut::<Timing>() {
+            let mut fields = FieldCollector::default();
+            values.record(&mut fields);
+            timing.fields.extend(fields.0);
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(timing) = span.extensions_mut().remove::<Timing>() else { return };
+        let record = SpanRecord {
+            name: span.name(),
+            target: span.metadata().target(),
+            span_id: id.into_u64(),
+            parent_id: span.parent().map(|p| p.id().into_u64()),
+            root_id: timing.root_id,
+            thread_id: timing.thread_id,
+            start_unix_us: timing.start_unix_us,
+            duration_us: timing.started.elapsed().as_micros() as u64,
+            fields: timing.fields,
+        };
+
+        let Ok(mut records) = RECORDS.lock() else { return };
+        let cutoff = unix_micros().saturating_sub(RETENTION.as_micros() as u64);
+        while records
+            .front()
+            .is_some_and(|r| r.start_unix_us < cutoff || records.len() >= MAX_RECORDS)
+        {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+}
+
+fn records_since(cutoff_us: u64) -> Vec<SpanRecord> {
+    RECORDS
+        .lock()
+        .map(|records| records.iter().filter(|r| r.start_unix_us >= cutoff_us).cloned().collect())
+        .unwrap_or_default()
+}
+
+fn chrome_trace(records: &[SpanRecord]) -> Value {
+    let pid = std::process::id();
+    let events: Vec<Value> = records
+        .iter()
+        .map(|r| {
+            let args: serde_json::Map<String, Value> = r
+                .fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), Value::String(v.clone())))
+                .collect();
+            json!({
+                "name": r.name,
+                "cat": r.target,
+                "ph": "X",
+                "ts": r.start_unix_us,
+                "dur": r.duration_us,
+                "pid": pid,
+                "tid": r.thread_id,
+                "args": args,
+            })
+        })
+        .collect();
+    json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+fn otlp_trace(records: &[SpanRecord]) -> Value {
+    let spans: Vec<Value> = records
+        .iter()
+        .map(|r| {
+            let start_ns = r.start_unix_us * 1_000;
+            let mut attributes: Vec<Value> = r
+                .fields
+                .iter()
+                .map(|(k, v)| json!({ "key": k, "value": { "stringValue": v } }))
+                .collect();
+            attributes.push(json!({ "key": "code.namespace", "value": { "stringValue": r.target } }));
+            attributes.push(json!({ "key": "thread.id", "value": { "intValue": r.thread_id.to_string() } }));
+            json!({
+                // 16-byte trace ids, 8-byte span ids, hex
+                "traceId": format!("{:032x}", r.root_id),
+                "spanId": format!("{:016x}", r.span_id),
+                "parentSpanId": r.parent_id.map(|p| format!("{:016x}", p)).unwrap_or_default(),
+                "name": r.name,
+                "kind": 1,
+                "startTimeUnixNano": start_ns.to_string(),
+                "endTimeUnixNano": (start_ns + r.duration_us * 1_000).to_string(),
+                "attributes": attributes,
+            })
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "kubilitics-desktop" } },
+                    { "key": "service.version", "value": { "stringValue": env!("CARGO_PKG_VERSION") } },
+                    { "key": "os.type", "value": { "stringValue": std::env::consts::OS } },
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "kubilitics-desktop" },
+                "spans": spans,
+            }]
+        }]
+    })
+}
+
+/// Write the spans of the last `minutes` (default 10, at most the 30 retained) to a file under
+/// app data and return its path.
+#[command]
+pub async fn export_performance_trace(minutes: Option<u32>, format: Option<TraceFormat>) -> Result<String, String> {
+    let minutes = minutes.unwrap_or(DEFAULT_EXPORT_MINUTES).max(1) as u64;
+    let format = format.unwrap_or(TraceFormat::Chrome);
+    let cutoff = unix_micros().saturating_sub(minutes * 60 * 1_000_000);
+    let records = records_since(cutoff);
+    if records.is_empty() {
+        return Err(format!("No spans recorded in the last {} minutes", minutes));
+    }
+
+    let (document, suffix) = match format {
+        TraceFormat::Chrome => (chrome_trace(&records), "chrome.json"),
+        TraceFormat::Otlp => (otlp_trace(&records), "otlp.json"),
+    };
+
+    let dir = PathBuf::from(get_app_data_dir().await?).join(TRACES_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create trace directory: {}", e))?;
+    let path = dir.join(format!(
+        "trace-{}.{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        suffix
+    ));
+    let content = serde_json::to_string(&document).map_err(|_| "Failed to serialize trace".to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write trace: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,688 @@
+// Copying files between this machine and a container, the way `kubectl cp` does it: a tar stream
+// over exec, so the container needs a `tar` binary but nothing else. Downloads are spooled to a
+// temporary file and then unpacked; uploads are packed on a blocking thread and streamed into
+// `tar x` chunk by chunk as they are produced. Either way at most MAX_COPY_BYTES are transferred,
+// and progress (bytes actually sent or received) goes out as `pod-cp-progress`.
+//
+// Naming follows kubectl: copying to an existing directory puts the source inside it under its
+// own name; any other destination path is the new name.
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::AttachParams;
+use kube::Api;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+/// Enough for a heap dump of a large JVM.
+const MAX_COPY_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+const CHUNK_BYTES: usize = 64 * 1024;
+/// Packed chunks waiting for the exec stream; bounds an upload's memory to a few chunks.
+const PIPE_CHUNKS: usize = 4;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyDirection {
+    /// From the container to this machine.
+    Download,
+    /// From this machine to the container.
+    Upload,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CopyProgress<'a> {
+    id: &'a str,
+    direction: CopyDirection,
+    bytes: u64,
+    /// Expected tar size for uploads (see `tar_size`); unknown for downloads.
+    total_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CopyResult {
+    pub id: String,
+    pub direction: CopyDirection,
+    /// Bytes of tar stream transferred.
+    pub bytes: u64,
+    pub files: u64,
+    /// Where the copy ended up.
+    pub destination: String,
+}
+
+struct Progress<'a> {
+    app: &'a AppHandle,
+    id: &'a str,
+    direction: CopyDirection,
+    total_bytes: Option<u64>,
+    bytes: u64,
+    last_emit: Instant,
+}
+
+impl Progress<'_> {
+    fn advance(&mut self, n: usize) {
+        self.bytes += n as u64;
+        if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+            self.emit();
+        }
+    }
+
+    fn emit(&mut self) {
+        self.last_emit = Instant::now();
+        let _ = self.app.emit(
+            "pod-cp-progress",
+            CopyProgress {
+                id: self.id,
+                direction: self.direction,
+                bytes: self.bytes,
+                total_bytes: self.total_bytes,
+            },
+        );
+    }
+}
+
+/// Split a container path into (directory, name); `/` as directory for top-level paths.
+fn split_remote(path: &str) -> Result<(String, String), String> {
+    let trimmed = path.trim_end_matches('/');
+    let (dir, name) = trimmed.rsplit_once('/').unwrap_or(("", trimmed));
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(format!("Invalid container path: {}", path));
+    }
+    let dir = if dir.is_empty() {
+        if path.starts_with('/') {
+            "/"
+        } else {
+            "."
+        }
+    } else {
+        dir
+    };
+    Ok((dir.to_string(), name.to_string()))
+}
+
+/// A tar entry path below `root`, refusing absolute paths and `..` (a hostile container could
+/// otherwise write anywhere).
+fn safe_join(root: &Path, relative: &Path) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+fn attach_params(container: &Option<String>, stdin: bool) -> AttachParams {
+    let mut params = AttachParams::default()
+        .stdin(stdin)
+        .stdout(true)
+        .stderr(true);
+    if let Some(container) = container {
+        params = params.container(container.clone());
+    }
+    params
+}
+
+async fn read_stderr(stderr: Option<impl tokio::io::AsyncRead + Unpin>) -> String {
+    let mut text = String::new();
+    if let Some(mut stderr) = stderr {
+        let _ = stderr.read_to_string(&mut text).await;
+    }
+    text.trim().to_string()
+}
+
+async fn download(
+    pods: &Api<Pod>,
+    pod: &str,
+    container: &Option<String>,
+    src: &str,
+    dst: &Path,
+    progress: &mut Progress<'_>,
+) -> Result<(u64, PathBuf), String> {
+    let (dir, name) = split_remote(src)?;
+    let (root, spool_path) = tokio::task::spawn_blocking({
+        let dst = dst.to_path_buf();
+        let name = name.clone();
+        move || {
+            let root = if dst.is_dir() {
+                dst.join(&name)
+            } else {
+                dst.clone()
+            };
+            (root, spool_path_for(&dst))
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut attached = pods
+        .exec(
+            pod,
+            ["tar", "cf", "-", "-C", &dir, &name],
+            &attach_params(container, false),
+        )
+        .await
+        .map_err(|e| format!("Failed to exec tar in {}: {}", pod, e))?;
+    let mut stdout = attached.stdout().ok_or("Exec stream has no output")?;
+    let stderr = attached.stderr();
+
+    let spooled = spool(&mut stdout, &spool_path, src, progress).await;
+    if let Err(e) = spooled {
+        let _ = tokio::fs::remove_file(&spool_path).await;
+        return Err(e);
+    }
+    progress.emit();
+    let errors = read_stderr(stderr).await;
+    if progress.bytes == 0 {
+        let _ = tokio::fs::remove_file(&spool_path).await;
+        return Err(if errors.is_empty() {
+            format!("{} not found in the container", src)
+        } else {
+            errors
+        });
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let result = unpack(&spool_path, &name, &root).map(|files| (files, root));
+        let _ = std::fs::remove_file(&spool_path);
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Write the tar stream to `path`, enforcing the size limit.
+async fn spool(
+    stdout: &mut (impl tokio::io::AsyncRead + Unpin),
+    path: &Path,
+    src: &str,
+    progress: &mut Progress<'_>,
+) -> Result<(), String> {
+    let mut file = tokio::fs::File::create(path)
+        .await
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut buffer = vec![0u8; CHUNK_BYTES];
+    loop {
+        let n = stdout
+            .read(&mut buffer)
+            .await
+            .map_err(|e| format!("Copy interrupted: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        if progress.bytes + n as u64 > MAX_COPY_BYTES {
+            return Err(format!(
+                "{} is larger than the {} GiB copy limit",
+                src,
+                MAX_COPY_BYTES >> 30
+            ));
+        }
+        file.write_all(&buffer[..n])
+            .await
+            .map_err(|e| format!("Failed to write: {}", e))?;
+        progress.advance(n);
+    }
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write: {}", e))
+}
+
+/// Temporary file next to the destination, so unpacking doesn't cross filesystems.
+fn spool_path_for(dst: &Path) -> PathBuf {
+    let dir = if dst.is_dir() {
+        dst.to_path_buf()
+    } else {
+        dst.parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(std::env::temp_dir)
+    };
+    dir.join(format!(".kubilitics-cp-{:016x}.tar", rand::random::<u64>()))
+}
+
+/// Unpack a tar whose entries start with `name` into `root` (entry `name/x` → `root/x`).
+fn unpack(archive_path: &Path, name: &str, root: &Path) -> Result<u64, String> {
+    let file = std::fs::File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut archive = tar::Archive::new(file);
+    let mut files = 0;
+    for entry in archive
+        .entries()
+        .map_err(|e| format!("Invalid tar stream: {}", e))?
+    {
+        let mut entry = entry.map_err(|e| format!("Invalid tar stream: {}", e))?;
+        let entry_path = entry.path().map_err(|e| e.to_string())?.into_owned();
+        let relative = entry_path.strip_prefix(name).unwrap_or(&entry_path);
+        let target = safe_join(root, relative)
+            .ok_or_else(|| format!("Refusing unsafe path in archive: {}", entry_path.display()))?;
+        // Links could point outside the destination; kubectl skips them too
+        let kind = entry.header().entry_type();
+        if kind.is_symlink() || kind.is_hard_link() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        entry
+            .unpack(&target)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        if kind.is_file() {
+            files += 1;
+        }
+    }
+    Ok(files)
+}
+
+/// `Write` end of the upload pipe: hands CHUNK_BYTES-sized chunks from the packing thread to the
+/// task writing the exec stream. Fails with BrokenPipe once that task has stopped receiving.
+struct ChunkWriter {
+    chunk: Vec<u8>,
+    tx: mpsc::Sender<Vec<u8>>,
+}
+
+impl ChunkWriter {
+    fn new(tx: mpsc::Sender<Vec<u8>>) -> Self {
+        Self {
+            chunk: Vec::with_capacity(CHUNK_BYTES),
+            tx,
+        }
+    }
+
+    fn send(&mut self) -> std::io::Result<()> {
+        let chunk = std::mem::replace(&mut self.chunk, Vec::with_capacity(CHUNK_BYTES));
+        self.tx
+            .blocking_send(chunk)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "upload stopped"))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(CHUNK_BYTES - self.chunk.len());
+        self.chunk.extend_from_slice(&buf[..n]);
+        if self.chunk.len() == CHUNK_BYTES {
+            self.send()?;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if !self.chunk.is_empty() {
+            self.send()?;
+        }
+        Ok(())
+    }
+}
+
+/// Write a tar of `src`, with its top entry renamed to `name`, into `out`; returns the file count.
+fn pack(src: &Path, name: &str, out: ChunkWriter) -> Result<u64, String> {
+    let mut builder = tar::Builder::new(out);
+    builder.follow_symlinks(false);
+    let files = if src.is_dir() {
+        builder
+            .append_dir_all(name, src)
+            .map_err(|e| format!("Failed to read {}: {}", src.display(), e))?;
+        walk_count(src)
+    } else {
+        builder
+            .append_path_with_name(src, name)
+            .map_err(|e| format!("Failed to read {}: {}", src.display(), e))?;
+        1
+    };
+    let mut out = builder.into_inner().map_err(|e| e.to_string())?;
+    out.flush().map_err(|e| e.to_string())?;
+    Ok(files)
+}
+
+/// Size of the tar stream `pack` writes for `path`: a 512-byte header per entry, contents padded to
+/// 512 bytes, and the two-block trailer. Names too long for a plain header add extension headers
+/// that aren't counted, so progress can end slightly above this.
+fn tar_size(path: &Path) -> u64 {
+    fn entry_size(path: &Path) -> u64 {
+        match std::fs::symlink_metadata(path) {
+            Ok(m) if m.is_dir() => {
+                512 + std::fs::read_dir(path)
+                    .map(|entries| entries.flatten().map(|e| entry_size(&e.path())).sum())
+                    .unwrap_or(0)
+            }
+            Ok(m) if m.is_file() => 512 + m.len().div_ceil(512) * 512,
+            Ok(_) => 512,
+            Err(_) => 0,
+        }
+    }
+    entry_size(path) + 1024
+}
+
+fn walk_count(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| {
+                    let path = e.path();
+                    if path.is_dir() {
+                        walk_count(&path)
+                    } else {
+                        1
+                    }
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn local_size(path: &Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(m) if m.is_dir() => std::fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| local_size(&e.path())).sum())
+            .unwrap_or(0),
+        Ok(m) => m.len(),
+        Err(_) => 0,
+    }
+}
+
+async fn upload(
+    pods: &Api<Pod>,
+    pod: &str,
+    container: &Option<String>,
+    src: &Path,
+    dst: &str,
+    progress: &mut Progress<'_>,
+) -> Result<(u64, PathBuf), String> {
+    // Walks the whole tree for large directories, so off the async runtime
+    let src_owned = src.to_path_buf();
+    let total_bytes = tokio::task::spawn_blocking(move || {
+        if !src_owned.exists() {
+            return Err(format!("{} does not exist", src_owned.display()));
+        }
+        if local_size(&src_owned) > MAX_COPY_BYTES {
+            return Err(format!(
+                "{} is larger than the {} GiB copy limit",
+                src_owned.display(),
+                MAX_COPY_BYTES >> 30
+            ));
+        }
+        Ok(tar_size(&src_owned))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    let source_name = src
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid source path")?;
+    // "dir/" means into dir; otherwise the last component is the new name
+    let (dir, name) = if dst.ends_with('/') {
+        (dst.trim_end_matches('/').to_string(), source_name)
+    } else {
+        split_remote(dst)?
+    };
+    let dir = if dir.is_empty() { "/".to_string() } else { dir };
+
+    progress.total_bytes = Some(total_bytes);
+
+    let mut attached = pods
+        .exec(
+            pod,
+            ["tar", "xmf", "-", "-C", &dir],
+            &attach_params(container, true),
+        )
+        .await
+        .map_err(|e| format!("Failed to exec tar in {}: {}", pod, e))?;
+    let mut stdin = attached.stdin().ok_or("Exec stream has no input")?;
+    let stderr = attached.stderr();
+
+    let (tx, mut rx) = mpsc::channel(PIPE_CHUNKS);
+    let packer = tokio::task::spawn_blocking({
+        let src = src.to_path_buf();
+        let name = name.clone();
+        move || pack(&src, &name, ChunkWriter::new(tx))
+    });
+    let mut sent = Ok(());
+    while let Some(chunk) = rx.recv().await {
+        if let Err(e) = stdin.write_all(&chunk).await {
+            sent = Err(format!("Copy interrupted: {}", e));
+            break;
+        }
+        progress.advance(chunk.len());
+    }
+    // Closing the receiver stops a packer that is still running after a failed write
+    drop(rx);
+    let packed = packer.await.map_err(|e| e.to_string())?;
+    sent?;
+    let files = packed?;
+    stdin.flush().await.map_err(|e| e.to_string())?;
+    drop(stdin);
+    progress.emit();
+
+    let status = match attached.take_status() {
+        Some(status) => status.await,
+        None => None,
+    };
+    if let Some(status) = status.filter(|s| s.status.as_deref() == Some("Failure")) {
+        let errors = read_stderr(stderr).await;
+        return Err(if errors.is_empty() {
+            status
+                .message
+                .unwrap_or_else(|| "tar failed in the container".to_string())
+        } else {
+            errors
+        });
+    }
+    let destination = if dir == "/" {
+        format!("/{}", name)
+    } else {
+        format!("{}/{}", dir, name)
+    };
+    Ok((files, PathBuf::from(destination)))
+}
+
+/// Copy `src` to `dst`: a container path to a local one for `download`, the reverse for `upload`.
+#[command]
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, err)]
+pub async fn pod_cp(
+    app_handle: AppHandle,
+    context: String,
+    namespace: String,
+    pod: String,
+    container: Option<String>,
+    src: String,
+    dst: String,
+    direction: CopyDirection,
+) -> Result<CopyResult, String> {
+    let client = crate::k8s::streaming_client_for(&context).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+    let id = format!("{:016x}", rand::random::<u64>());
+    let mut progress = Progress {
+        app: &app_handle,
+        id: &id,
+        direction,
+        total_bytes: None,
+        bytes: 0,
+        last_emit: Instant::now(),
+    };
+
+    let (files, destination) = match direction {
+        CopyDirection::Download => {
+            download(
+                &pods,
+                &pod,
+                &container,
+                &src,
+                Path::new(&dst),
+                &mut progress,
+            )
+            .await?
+        }
+        CopyDirection::Upload => {
+            upload(
+                &pods,
+                &pod,
+                &container,
+                Path::new(&src),
+                &dst,
+                &mut progress,
+            )
+            .await?
+        }
+    };
+    Ok(CopyResult {
+        bytes: progress.bytes,
+        id,
+        direction,
+        files,
+        destination: destination.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("kubilitics-cp-test-{:016x}", rand::random::<u64>()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A tar header for `path` written as raw bytes, so paths `tar::Builder` refuses (`..`,
+    /// absolute) can be put in an archive the way a hostile container could.
+    fn raw_header(path: &str, kind: tar::EntryType, size: u64) -> tar::Header {
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..path.len()].copy_from_slice(path.as_bytes());
+        header.set_entry_type(kind);
+        header.set_mode(0o644);
+        header.set_size(size);
+        header
+    }
+
+    fn write_archive(dir: &Path, entries: &[(&str, tar::EntryType, Option<&str>, &[u8])]) -> PathBuf {
+        let path = dir.join("archive.tar");
+        let mut builder = tar::Builder::new(std::fs::File::create(&path).unwrap());
+        for (name, kind, link, data) in entries {
+            let mut header = raw_header(name, *kind, data.len() as u64);
+            if let Some(link) = link {
+                header.set_link_name(link).unwrap();
+            }
+            header.set_cksum();
+            builder.append(&header, *data).unwrap();
+        }
+        builder.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn split_remote_separates_directory_and_name() {
+        let cases = [
+            ("/var/log/app.log", Some(("/var/log", "app.log"))),
+            ("/var/log/", Some(("/var", "log"))),
+            ("/hosts", Some(("/", "hosts"))),
+            ("hosts", Some((".", "hosts"))),
+            ("data/dump.hprof", Some(("data", "dump.hprof"))),
+            ("/", None),
+            ("", None),
+            ("/tmp/..", None),
+            ("/tmp/.", None),
+            ("..", None),
+        ];
+        for (path, expected) in cases {
+            let expected = expected.map(|(dir, name)| (dir.to_string(), name.to_string()));
+            assert_eq!(split_remote(path).ok(), expected, "path: {:?}", path);
+        }
+    }
+
+    #[test]
+    fn safe_join_stays_below_root() {
+        let root = Path::new("/dest");
+        let cases = [
+            ("a/b.txt", Some("/dest/a/b.txt")),
+            ("./a/./b.txt", Some("/dest/a/b.txt")),
+            ("", Some("/dest")),
+            ("../escape", None),
+            ("a/../../escape", None),
+            ("a/..", None),
+            ("/etc/passwd", None),
+        ];
+        for (relative, expected) in cases {
+            assert_eq!(
+                safe_join(root, Path::new(relative)),
+                expected.map(PathBuf::from),
+                "relative: {:?}",
+                relative
+            );
+        }
+    }
+
+    #[test]
+    fn unpack_renames_the_top_entry_into_root() {
+        let dir = temp_dir();
+        let archive = write_archive(
+            &dir,
+            &[
+                ("logs/", tar::EntryType::Directory, None, b""),
+                ("logs/app.log", tar::EntryType::Regular, None, b"hello"),
+                ("logs/nested/", tar::EntryType::Directory, None, b""),
+                ("logs/nested/b.log", tar::EntryType::Regular, None, b"world"),
+            ],
+        );
+        let root = dir.join("copied");
+
+        assert_eq!(unpack(&archive, "logs", &root), Ok(2));
+        assert_eq!(std::fs::read(root.join("app.log")).unwrap(), b"hello");
+        assert_eq!(std::fs::read(root.join("nested/b.log")).unwrap(), b"world");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unpack_writes_a_top_level_file_to_root() {
+        let dir = temp_dir();
+        let archive = write_archive(&dir, &[("dump.hprof", tar::EntryType::Regular, None, b"heap")]);
+        let root = dir.join("renamed.hprof");
+
+        assert_eq!(unpack(&archive, "dump.hprof", &root), Ok(1));
+        assert_eq!(std::fs::read(&root).unwrap(), b"heap");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unpack_refuses_paths_outside_root() {
+        for hostile in ["logs/../../escape.txt", "../escape.txt", "/tmp/escape.txt"] {
+            let dir = temp_dir();
+            let archive = write_archive(&dir, &[(hostile, tar::EntryType::Regular, None, b"x")]);
+            let root = dir.join("copied");
+
+            let result = unpack(&archive, "logs", &root);
+            assert!(result.is_err_and(|e| e.contains("unsafe path")), "entry: {}", hostile);
+            assert!(!dir.join("escape.txt").exists());
+            let _ = std::fs::remove_dir_all(&dir);
+        }
+    }
+
+    #[test]
+    fn unpack_skips_links() {
+        let dir = temp_dir();
+        let archive = write_archive(
+            &dir,
+            &[
+                ("logs/", tar::EntryType::Directory, None, b""),
+                ("logs/passwd", tar::EntryType::Symlink, Some("/etc/passwd"), b""),
+                ("logs/up", tar::EntryType::Symlink, Some("../.."), b""),
+                ("logs/hard", tar::EntryType::Link, Some("/etc/passwd"), b""),
+                ("logs/app.log", tar::EntryType::Regular, None, b"hello"),
+            ],
+        );
+        let root = dir.join("copied");
+
+        assert_eq!(unpack(&archive, "logs", &root), Ok(1));
+        for link in ["passwd", "up", "hard"] {
+            assert!(std::fs::symlink_metadata(root.join(link)).is_err(), "{} was created", link);
+        }
+        assert!(root.join("app.log").is_file());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
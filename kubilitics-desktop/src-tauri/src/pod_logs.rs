@@ -0,0 +1,452 @@
+// Container log follows run by the shell. Any number can be open at once; each applies its own
+// filter and highlight rules before anything reaches the WebView, and lines go out in batches
+// (`log-lines`, at most every FLUSH_INTERVAL) so a chatty container can't flood the event bridge.
+// When the UI can't keep up, the oldest undelivered lines are dropped and the count reported.
+//
+// The API server ends a follow when the container restarts, the kubelet rotates the file, or the
+// connection idles out. Unless the pod is gone or finished, the stream reconnects from the last
+// timestamp seen and skips lines it already delivered. State changes go out as `log-stream-status`.
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+use futures::{AsyncBufReadExt, StreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::LogParams;
+use kube::Api;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_BATCH_LINES: usize = 500;
+/// Undelivered lines kept per stream before the oldest are dropped.
+const MAX_PENDING_LINES: usize = 5_000;
+const INITIAL_BACKOFF_MS: u64 = 1_000;
+const MAX_BACKOFF_MS: u64 = 30_000;
+const DEFAULT_TAIL_LINES: i64 = 500;
+
+struct ManagedLogStream {
+    status: LogStreamStatus,
+    rules: Arc<RwLock<CompiledRules>>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static LOG_STREAMS: Mutex<BTreeMap<String, ManagedLogStream>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogFilterRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Drop matching lines instead of keeping only matching ones.
+    #[serde(default)]
+    pub exclude: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogHighlightRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Opaque to the shell; the UI maps it to a color ("error", "warning", a hex value, …).
+    pub style: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LogStreamOptions {
+    pub container: Option<String>,
+    /// Lines of history before following (default 500).
+    pub tail_lines: Option<i64>,
+    pub since_seconds: Option<i64>,
+    /// Logs of the previous container instance (no follow).
+    pub previous: bool,
+    /// Keep the API server's timestamp prefix on each line.
+    pub timestamps: bool,
+    pub filters: Vec<LogFilterRule>,
+    pub highlights: Vec<LogHighlightRule>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogStreamState {
+    Streaming,
+    Reconnecting,
+    /// The pod is gone or finished, or previous logs were read to the end.
+    Ended,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogStreamStatus {
+    pub id: String,
+    pub context: String,
+    pub namespace: String,
+    pub pod: String,
+    pub container: Option<String>,
+    pub state: LogStreamState,
+    pub lines: u64,
+    pub filtered_out: u64,
+    pub dropped: u64,
+    pub reconnects: u32,
+    pub started_at: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HighlightSpan {
+    pub start: usize,
+    pub end: usize,
+    pub style: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub timestamp: Option<String>,
+    pub text: String,
+    pub highlights: Vec<HighlightSpan>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LogBatch<'a> {
+    id: &'a str,
+    lines: Vec<LogLine>,
+    /// Lines dropped since the previous batch because the UI fell behind.
+    dropped: u64,
+}
+
+struct CompiledRules {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    highlights: Vec<(Regex, String)>,
+}
+
+fn compile(pattern: &str, regex: bool, case_sensitive: bool) -> Result<Regex, String> {
+    let source = if regex { pattern.to_string() } else { regex::escape(pattern) };
+    RegexBuilder::new(&source)
+        .case_insensitive(!case_sensitive)
+        .size_limit(1 << 20)
+        .build()
+        .map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))
+}
+
+impl CompiledRules {
+    fn new(filters: &[LogFilterRule], highlights: &[LogHighlightRule]) -> Result<Self, String> {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        for rule in filters {
+            let re = compile(&rule.pattern, rule.regex, rule.case_sensitive)?;
+            if rule.exclude {
+                exclude.push(re);
+            } else {
+                include.push(re);
+            }
+        }
+        let highlights = highlights
+            .iter()
+            .map(|rule| Ok((compile(&rule.pattern, rule.regex, rule.case_sensitive)?, rule.style.clone())))
+            .collect::<Result<_, String>>()?;
+        Ok(Self {
+            include,
+            exclude,
+            highlights,
+        })
+    }
+
+    /// Kept when it matches any include rule (or there are none) and no exclude rule.
+    fn keeps(&self, text: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(text)))
+            && !self.exclude.iter().any(|re| re.is_match(text))
+    }
+
+    fn highlight(&self, text: &str) -> Vec<HighlightSpan> {
+        self.highlights
+            .iter()
+            .flat_map(|(re, style)| {
+                re.find_iter(text).map(move |m| HighlightSpan {
+                    start: m.start(),
+                    end: m.end(),
+                    style: style.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn update_status(app: &AppHandle, id: &str, update: impl FnOnce(&mut LogStreamStatus)) {
+    let mut streams = LOG_STREAMS.lock().await;
+    if let Some(stream) = streams.get_mut(id) {
+        update(&mut stream.status);
+        let _ = app.emit("log-stream-status", &stream.status);
+    }
+}
+
+/// Lines waiting to be emitted, with the drop count since the last flush.
+#[derive(Default)]
+struct Pending {
+    lines: VecDeque<LogLine>,
+    dropped: u64,
+}
+
+impl Pending {
+    fn push(&mut self, line: LogLine) {
+        if self.lines.len() == MAX_PENDING_LINES {
+            self.lines.pop_front();
+            self.dropped += 1;
+        }
+        self.lines.push_back(line);
+    }
+
+    fn flush(&mut self, app: &AppHandle, id: &str) -> u64 {
+        if self.lines.is_empty() && self.dropped == 0 {
+            return 0;
+        }
+        let dropped = std::mem::take(&mut self.dropped);
+        while !self.lines.is_empty() {
+            let count = self.lines.len().min(MAX_BATCH_LINES);
+            let lines: Vec<LogLine> = self.lines.drain(..count).collect();
+            let _ = app.emit("log-lines", LogBatch { id, lines, dropped: 0 });
+        }
+        if dropped > 0 {
+            let _ = app.emit("log-lines", LogBatch { id, lines: Vec::new(), dropped });
+        }
+        dropped
+    }
+}
+
+/// Whether a finished follow should be reopened: not when the pod is gone or has completed.
+async fn should_reconnect(pods: &Api<Pod>, pod: &str) -> bool {
+    match pods.get_opt(pod).await {
+        Ok(Some(pod)) => !matches!(
+            pod.status.and_then(|s| s.phase).as_deref(),
+            Some("Succeeded") | Some("Failed")
+        ),
+        Ok(None) => false,
+        // API unreachable: keep trying
+        Err(_) => true,
+    }
+}
+
+struct StreamContext {
+    app: AppHandle,
+    id: String,
+    pods: Api<Pod>,
+    pod: String,
+    options: LogStreamOptions,
+    rules: Arc<RwLock<CompiledRules>>,
+}
+
+/// One follow. Returns when the API server ends the stream.
+async fn follow_once(
+    ctx: &StreamContext,
+    since: Option<DateTime<Utc>>,
+    last_seen: &mut Option<DateTime<Utc>>,
+    pending: &mut Pending,
+) -> Result<(), String> {
+    let params = LogParams {
+        container: ctx.options.container.clone(),
+        follow: !ctx.options.previous,
+        previous: ctx.options.previous,
+        // Always requested: needed to resume without duplicates
+        timestamps: true,
+        tail_lines: if since.is_some() { None } else { Some(ctx.options.tail_lines.unwrap_or(DEFAULT_TAIL_LINES)) },
+        since_seconds: if since.is_some() { None } else { ctx.options.since_seconds },
+        since_time: since,
+        ..Default::default()
+    };
+    let reader = ctx
+        .pods
+        .log_stream(&ctx.pod, &params)
+        .await
+        .map_err(|e| format!("Failed to open log stream: {}", e))?;
+    update_status(&ctx.app, &ctx.id, |s| {
+        s.state = LogStreamState::Streaming;
+        s.error = None;
+    })
+    .await;
+
+    let mut lines = reader.lines();
+    let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+    let (mut kept, mut filtered) = (0u64, 0u64);
+    loop {
+        tokio::select! {
+            line = lines.next() => {
+                let Some(line) = line else { break };
+                let line = line.map_err(|e| format!("Log stream failed: {}", e))?;
+                let (timestamp, text) = match line.split_once(' ') {
+                    Some((ts, rest)) => match DateTime::parse_from_rfc3339(ts) {
+                        Ok(parsed) => (Some((ts.to_string(), parsed.with_timezone(&Utc))), rest.to_string()),
+                        Err(_) => (None, line),
+                    },
+                    None => (None, line),
+                };
+                if let Some((_, parsed)) = &timestamp {
+                    // Already delivered before the reconnect
+                    if since.is_some() && last_seen.is_some_and(|seen| *parsed <= seen) {
+                        continue;
+                    }
+                    *last_seen = Some(*parsed);
+                }
+                let rules = ctx.rules.read().map_err(|_| "Log rules unavailable".to_string())?;
+                if !rules.keeps(&text) {
+                    filtered += 1;
+                    continue;
+                }
+                kept += 1;
+                pending.push(LogLine {
+                    highlights: rules.highlight(&text),
+                    timestamp: timestamp.filter(|_| ctx.options.timestamps).map(|(raw, _)| raw),
+                    text,
+                });
+            }
+            _ = flush.tick() => {
+                let dropped = pending.flush(&ctx.app, &ctx.id);
+                if kept > 0 || filtered > 0 || dropped > 0 {
+                    update_status(&ctx.app, &ctx.id, |s| {
+                        s.lines += kept;
+                        s.filtered_out += filtered;
+                        s.dropped += dropped;
+                    })
+                    .await;
+                    (kept, filtered) = (0, 0);
+                }
+            }
+        }
+    }
+    let dropped = pending.flush(&ctx.app, &ctx.id);
+    update_status(&ctx.app, &ctx.id, |s| {
+        s.lines += kept;
+        s.filtered_out += filtered;
+        s.dropped += dropped;
+    })
+    .await;
+    Ok(())
+}
+
+async fn run_stream(ctx: StreamContext) {
+    let mut last_seen: Option<DateTime<Utc>> = None;
+    let mut pending = Pending::default();
+    let mut backoff = INITIAL_BACKOFF_MS;
+    let mut first = true;
+
+    loop {
+        let since = if first { None } else { last_seen };
+        first = false;
+        let result = follow_once(&ctx, since, &mut last_seen, &mut pending).await;
+        if result.is_ok() {
+            backoff = INITIAL_BACKOFF_MS;
+        }
+
+        if ctx.options.previous || !should_reconnect(&ctx.pods, &ctx.pod).await {
+            update_status(&ctx.app, &ctx.id, |s| {
+                s.state = LogStreamState::Ended;
+                s.error = result.err();
+            })
+            .await;
+            return;
+        }
+
+        update_status(&ctx.app, &ctx.id, |s| {
+            s.state = LogStreamState::Reconnecting;
+            s.reconnects += 1;
+            s.error = result.err();
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(backoff)).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF_MS);
+    }
+}
+
+/// Follow a container's logs. Lines arrive as `log-lines` batches for the returned id.
+#[command]
+pub async fn open_log_stream(
+    app_handle: AppHandle,
+    context: String,
+    namespace: String,
+    pod: String,
+    options: Option<LogStreamOptions>,
+) -> Result<LogStreamStatus, String> {
+    let options = options.unwrap_or_default();
+    let rules = Arc::new(RwLock::new(CompiledRules::new(&options.filters, &options.highlights)?));
+    let client = crate::k8s::streaming_client_for(&context).await?;
+    let pods: Api<Pod> = Api::namespaced(client, &namespace);
+
+    let id = format!("{:016x}", rand::random::<u64>());
+    let status = LogStreamStatus {
+        id: id.clone(),
+        context,
+        namespace,
+        pod: pod.clone(),
+        container: options.container.clone(),
+        state: LogStreamState::Streaming,
+        lines: 0,
+        filtered_out: 0,
+        dropped: 0,
+        reconnects: 0,
+        started_at: now_secs(),
+        error: None,
+    };
+    let ctx = StreamContext {
+        app: app_handle,
+        id: id.clone(),
+        pods,
+        pod,
+        options,
+        rules: rules.clone(),
+    };
+
+    let mut streams = LOG_STREAMS.lock().await;
+    let task = tauri::async_runtime::spawn(run_stream(ctx));
+    streams.insert(id, ManagedLogStream {
+        status: status.clone(),
+        rules,
+        task,
+    });
+    Ok(status)
+}
+
+/// Replace a stream's filter and highlight rules. Applies to lines from now on.
+#[command]
+pub async fn update_log_stream_rules(
+    id: String,
+    filters: Vec<LogFilterRule>,
+    highlights: Vec<LogHighlightRule>,
+) -> Result<(), String> {
+    let compiled = CompiledRules::new(&filters, &highlights)?;
+    let streams = LOG_STREAMS.lock().await;
+    let stream = streams.get(&id).ok_or_else(|| format!("Log stream not found: {}", id))?;
+    *stream.rules.write().map_err(|_| "Log rules unavailable".to_string())? = compiled;
+    Ok(())
+}
+
+#[command]
+pub async fn close_log_stream(id: String) -> Result<(), String> {
+    if let Some(stream) = LOG_STREAMS.lock().await.remove(&id) {
+        stream.task.abort();
+    }
+    Ok(())
+}
+
+/// Every open stream, including ended ones until they are closed.
+#[command]
+pub async fn list_log_streams() -> Result<Vec<LogStreamStatus>, String> {
+    Ok(LOG_STREAMS
+        .lock()
+        .await
+        .values()
+        .map(|s| s.status.clone())
+        .collect())
+}
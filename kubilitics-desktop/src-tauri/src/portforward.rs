@@ -0,0 +1,434 @@
+// Port-forwards run by the shell over the Kubernetes API, so kubectl doesn't need to be installed
+// and a forward outlives the page that opened it.
+//
+// A target is `pod/<name>:<port>`, `svc/<name>:<port>` or `deploy/<name>:<port>`; the port may be a
+// number or a port name. Services and deployments are resolved to a running pod (and a service port
+// to its target port). Each local connection opens its own stream to the pod; when that fails —
+// typically because the pod was replaced by a rollout — the target is resolved again and the
+// connection retried with backoff, so a forward to a service keeps working across restarts.
+//
+// Local ports are checked against the other forwards and against the OS before anything is started.
+// State changes go out as `port-forward-status`.
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{Pod, Service};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::ListParams;
+use kube::{Api, Client};
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+const MAX_CONNECT_ATTEMPTS: u32 = 4;
+const INITIAL_BACKOFF_MS: u64 = 500;
+
+struct ManagedForward {
+    status: PortForwardStatus,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static FORWARDS: Mutex<BTreeMap<String, ManagedForward>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortForwardState {
+    Active,
+    /// The pod went away; resolving the target again.
+    Reconnecting,
+    /// The last connection couldn't reach any pod. The next one tries again.
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortForwardStatus {
+    pub id: String,
+    pub context: String,
+    pub namespace: String,
+    pub target: String,
+    pub local_port: u16,
+    /// Pod and container port currently forwarded to.
+    pub pod: Option<String>,
+    pub remote_port: Option<u16>,
+    pub state: PortForwardState,
+    pub active_connections: u32,
+    pub total_connections: u64,
+    pub started_at: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetKind {
+    Pod,
+    Service,
+    Deployment,
+}
+
+#[derive(Debug, Clone)]
+enum PortRef {
+    Number(u16),
+    Name(String),
+}
+
+#[derive(Debug, Clone)]
+struct Target {
+    kind: TargetKind,
+    name: String,
+    port: PortRef,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn parse_target(target: &str) -> Result<Target, String> {
+    let invalid = || format!("Invalid target '{}': expected pod/<name>:<port>, svc/<name>:<port> or deploy/<name>:<port>", target);
+    let (kind, rest) = target.split_once('/').ok_or_else(invalid)?;
+    let (name, port) = rest.rsplit_once(':').ok_or_else(invalid)?;
+    let kind = match kind {
+        "pod" | "pods" | "po" => TargetKind::Pod,
+        "svc" | "service" | "services" => TargetKind::Service,
+        "deploy" | "deployment" | "deployments" => TargetKind::Deployment,
+        _ => return Err(invalid()),
+    };
+    if name.is_empty() || port.is_empty() {
+        return Err(invalid());
+    }
+    let port = match port.parse::<u16>() {
+        Ok(0) => return Err(invalid()),
+        Ok(n) => PortRef::Number(n),
+        Err(_) => PortRef::Name(port.to_string()),
+    };
+    Ok(Target {
+        kind,
+        name: name.to_string(),
+        port,
+    })
+}
+
+fn named_container_port(pod: &Pod, name: &str) -> Option<u16> {
+    pod.spec
+        .as_ref()?
+        .containers
+        .iter()
+        .flat_map(|c| c.ports.iter().flatten())
+        .find(|p| p.name.as_deref() == Some(name))
+        .and_then(|p| u16::try_from(p.container_port).ok())
+}
+
+fn pod_port(pod: &Pod, port: &PortRef) -> Result<u16, String> {
+    match port {
+        PortRef::Number(n) => Ok(*n),
+        PortRef::Name(name) => named_container_port(pod, name)
+            .ok_or_else(|| format!("Pod has no container port named '{}'", name)),
+    }
+}
+
+fn is_running(pod: &Pod) -> bool {
+    pod.metadata.deletion_timestamp.is_none()
+        && pod.status.as_ref().and_then(|s| s.phase.as_deref()) == Some("Running")
+}
+
+async fn running_pod_for_selector(pods: &Api<Pod>, selector: &BTreeMap<String, String>) -> Result<Pod, String> {
+    if selector.is_empty() {
+        return Err("Target has no pod selector".to_string());
+    }
+    let labels = selector
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",");
+    pods.list(&ListParams::default().labels(&labels))
+        .await
+        .map_err(|e| format!("Failed to list pods: {}", e))?
+        .items
+        .into_iter()
+        .find(is_running)
+        .ok_or_else(|| "No running pod found for target".to_string())
+}
+
+/// The pod and container port a target currently points at.
+async fn resolve(client: &Client, namespace: &str, target: &Target) -> Result<(String, u16), String> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    match target.kind {
+        TargetKind::Pod => {
+            let pod = pods
+                .get(&target.name)
+                .await
+                .map_err(|e| format!("Failed to get pod {}: {}", target.name, e))?;
+            if !is_running(&pod) {
+                return Err(format!("Pod {} is not running", target.name));
+            }
+            Ok((target.name.clone(), pod_port(&pod, &target.port)?))
+        }
+        TargetKind::Deployment => {
+            let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+            let deployment = deployments
+                .get(&target.name)
+                .await
+                .map_err(|e| format!("Failed to get deployment {}: {}", target.name, e))?;
+            let selector = deployment
+                .spec
+                .and_then(|s| s.selector.match_labels)
+                .unwrap_or_default();
+            let pod = running_pod_for_selector(&pods, &selector).await?;
+            let port = pod_port(&pod, &target.port)?;
+            Ok((pod.metadata.name.unwrap_or_default(), port))
+        }
+        TargetKind::Service => {
+            let services: Api<Service> = Api::namespaced(client.clone(), namespace);
+            let service = services
+                .get(&target.name)
+                .await
+                .map_err(|e| format!("Failed to get service {}: {}", target.name, e))?;
+            let spec = service.spec.unwrap_or_default();
+            let service_port = spec
+                .ports
+                .unwrap_or_default()
+                .into_iter()
+                .find(|p| match &target.port {
+                    PortRef::Number(n) => p.port == i32::from(*n),
+                    PortRef::Name(name) => p.name.as_deref() == Some(name.as_str()),
+                })
+                .ok_or_else(|| format!("Service {} has no port {}", target.name, port_label(&target.port)))?;
+            let pod = running_pod_for_selector(&pods, &spec.selector.unwrap_or_default()).await?;
+            let port = match service_port.target_port {
+                Some(IntOrString::Int(n)) => u16::try_from(n).map_err(|_| format!("Invalid target port {}", n))?,
+                Some(IntOrString::String(name)) => named_container_port(&pod, &name)
+                    .ok_or_else(|| format!("Pod has no container port named '{}'", name))?,
+                None => u16::try_from(service_port.port).map_err(|_| "Invalid service port".to_string())?,
+            };
+            Ok((pod.metadata.name.unwrap_or_default(), port))
+        }
+    }
+}
+
+fn port_label(port: &PortRef) -> String {
+    match port {
+        PortRef::Number(n) => n.to_string(),
+        PortRef::Name(name) => name.clone(),
+    }
+}
+
+async fn update_status(app: &AppHandle, id: &str, update: impl FnOnce(&mut PortForwardStatus)) {
+    let mut forwards = FORWARDS.lock().await;
+    if let Some(forward) = forwards.get_mut(id) {
+        update(&mut forward.status);
+        let _ = app.emit("port-forward-status", &forward.status);
+    }
+}
+
+/// Open a stream to the pod and pipe the connection through it. Errors only before the stream is
+/// established; once data flows, a broken connection just ends.
+async fn forward_connection(pods: &Api<Pod>, pod: &str, port: u16, connection: &mut TcpStream) -> Result<(), String> {
+    let mut forwarder = pods
+        .portforward(pod, &[port])
+        .await
+        .map_err(|e| format!("Failed to open port-forward to {}: {}", pod, e))?;
+    let mut upstream = forwarder
+        .take_stream(port)
+        .ok_or_else(|| "Port-forward stream unavailable".to_string())?;
+    let _ = tokio::io::copy_bidirectional(connection, &mut upstream).await;
+    drop(upstream);
+    let _ = forwarder.join().await;
+    Ok(())
+}
+
+struct ForwardContext {
+    app: AppHandle,
+    id: String,
+    client: Client,
+    namespace: String,
+    target: Target,
+    /// Pod and port in use, cleared when they stop working.
+    current: Mutex<Option<(String, u16)>>,
+}
+
+async fn handle_connection(ctx: Arc<ForwardContext>, mut connection: TcpStream) {
+    let pods: Api<Pod> = Api::namespaced(ctx.client.clone(), &ctx.namespace);
+    update_status(&ctx.app, &ctx.id, |s| {
+        s.active_connections += 1;
+        s.total_connections += 1;
+    })
+    .await;
+
+    let mut backoff = Duration::from_millis(INITIAL_BACKOFF_MS);
+    for attempt in 1..=MAX_CONNECT_ATTEMPTS {
+        let current = ctx.current.lock().await.clone();
+        let resolved = match current {
+            Some(resolved) => Ok(resolved),
+            None => resolve(&ctx.client, &ctx.namespace, &ctx.target).await,
+        };
+        let result = match resolved {
+            Ok((pod, port)) => {
+                *ctx.current.lock().await = Some((pod.clone(), port));
+                forward_connection(&pods, &pod, port, &mut connection)
+                    .await
+                    .map(|()| (pod, port))
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok((pod, port)) => {
+                update_status(&ctx.app, &ctx.id, |s| {
+                    s.state = PortForwardState::Active;
+                    s.pod = Some(pod);
+                    s.remote_port = Some(port);
+                    s.error = None;
+                })
+                .await;
+                break;
+            }
+            Err(e) => {
+                *ctx.current.lock().await = None;
+                let state = if attempt < MAX_CONNECT_ATTEMPTS {
+                    PortForwardState::Reconnecting
+                } else {
+                    PortForwardState::Error
+                };
+                update_status(&ctx.app, &ctx.id, |s| {
+                    s.state = state;
+                    s.error = Some(e);
+                })
+                .await;
+                if state == PortForwardState::Error {
+                    break;
+                }
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+    }
+
+    update_status(&ctx.app, &ctx.id, |s| s.active_connections = s.active_connections.saturating_sub(1)).await;
+}
+
+/// Connections live in a JoinSet owned by this task, so aborting the forward cuts them too.
+async fn accept_loop(ctx: Arc<ForwardContext>, listener: TcpListener) {
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((connection, _)) => {
+                    let _ = connection.set_nodelay(true);
+                    connections.spawn(handle_connection(ctx.clone(), connection));
+                }
+                Err(e) => {
+                    tracing::warn!(id = %ctx.id, "Port-forward accept failed: {}", e);
+                    tokio::time::sleep(Duration::from_millis(INITIAL_BACKOFF_MS)).await;
+                }
+            },
+            // Reap finished connections
+            Some(_) = connections.join_next() => {}
+        }
+    }
+}
+
+/// Forward `local_port` (any free port when omitted) on 127.0.0.1 to `target` in `namespace`.
+#[command]
+pub async fn start_port_forward(
+    app_handle: AppHandle,
+    context: String,
+    namespace: String,
+    target: String,
+    local_port: Option<u16>,
+) -> Result<PortForwardStatus, String> {
+    let parsed = parse_target(&target)?;
+    let local_port = local_port.unwrap_or(0);
+
+    if local_port != 0 {
+        if let Some(existing) = FORWARDS.lock().await.values().find(|f| f.status.local_port == local_port) {
+            return Err(format!(
+                "Local port {} is already forwarded to {} ({})",
+                local_port, existing.status.target, existing.status.context
+            ));
+        }
+    }
+
+    // Resolve first, so a typo fails here instead of on the first connection
+    let client = crate::k8s::streaming_client_for(&context).await?;
+    let (pod, remote_port) = resolve(&client, &namespace, &parsed).await?;
+
+    let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, local_port)))
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AddrInUse => format!("Local port {} is already in use by another application", local_port),
+            std::io::ErrorKind::PermissionDenied => format!("Not allowed to listen on port {}", local_port),
+            _ => format!("Failed to listen on port {}: {}", local_port, e),
+        })?;
+    let local_port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to listen: {}", e))?
+        .port();
+
+    let id = format!("{:016x}", rand::random::<u64>());
+    let status = PortForwardStatus {
+        id: id.clone(),
+        context,
+        namespace: namespace.clone(),
+        target,
+        local_port,
+        pod: Some(pod.clone()),
+        remote_port: Some(remote_port),
+        state: PortForwardState::Active,
+        active_connections: 0,
+        total_connections: 0,
+        started_at: now_secs(),
+        error: None,
+    };
+    let ctx = Arc::new(ForwardContext {
+        app: app_handle.clone(),
+        id: id.clone(),
+        client,
+        namespace,
+        target: parsed,
+        current: Mutex::new(Some((pod, remote_port))),
+    });
+
+    // Hold the lock across spawn + insert so the task can't update a status that isn't there yet
+    let mut forwards = FORWARDS.lock().await;
+    let task = tauri::async_runtime::spawn(accept_loop(ctx, listener));
+    forwards.insert(id, ManagedForward {
+        status: status.clone(),
+        task,
+    });
+    let _ = app_handle.emit("port-forward-status", &status);
+    Ok(status)
+}
+
+#[command]
+pub async fn list_port_forwards() -> Result<Vec<PortForwardStatus>, String> {
+    Ok(FORWARDS
+        .lock()
+        .await
+        .values()
+        .map(|f| f.status.clone())
+        .collect())
+}
+
+/// Stop a forward and free its local port. Open connections are cut.
+#[command]
+pub async fn stop_port_forward(id: String) -> Result<(), String> {
+    let forward = FORWARDS
+        .lock()
+        .await
+        .remove(&id)
+        .ok_or_else(|| format!("Port-forward not found: {}", id))?;
+    forward.task.abort();
+    Ok(())
+}
+
+/// Stop every forward on exit.
+pub async fn stop_all() {
+    for (_, forward) in std::mem::take(&mut *FORWARDS.lock().await) {
+        forward.task.abort();
+    }
+}
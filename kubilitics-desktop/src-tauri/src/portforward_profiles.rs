@@ -0,0 +1,260 @@
+// Named sets of port-forwards ("argo + grafana + db") saved per context in
+// port_forward_profiles.json. A profile starts and stops as a unit; profiles marked `autostart`
+// start when the frontend reports their context connected (`set_cluster_connected`) and stop when
+// it disconnects. Running profiles are listed in the tray, where clicking one stops it.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle};
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+use crate::portforward::{self, PortForwardStatus};
+
+/// Running profiles: profile id → ids of the forwards it started.
+static RUNNING: Mutex<BTreeMap<String, Vec<String>>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileForward {
+    pub namespace: String,
+    /// As for `start_port_forward`: `pod|svc|deploy/<name>:<port>`.
+    pub target: String,
+    /// Any free port when omitted.
+    pub local_port: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortForwardProfile {
+    /// Assigned on first save.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub context: String,
+    #[serde(default)]
+    pub autostart: bool,
+    pub forwards: Vec<ProfileForward>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileForwardResult {
+    pub target: String,
+    pub status: Option<PortForwardStatus>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortForwardProfileStatus {
+    #[serde(flatten)]
+    pub profile: PortForwardProfile,
+    pub running: bool,
+    /// Forwards of the profile that are still up.
+    pub active: Vec<PortForwardStatus>,
+}
+
+async fn get_profiles_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("port_forward_profiles.json"))
+}
+
+async fn load_profiles() -> Result<Vec<PortForwardProfile>, String> {
+    let path = get_profiles_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read port-forward profiles".to_string())?;
+
+    serde_json::from_str(&content).map_err(|_| "Failed to parse port-forward profiles".to_string())
+}
+
+async fn save_profiles(profiles: &[PortForwardProfile]) -> Result<(), String> {
+    let path = get_profiles_path().await?;
+
+    let content = serde_json::to_string_pretty(profiles)
+        .map_err(|_| "Failed to serialize port-forward profiles".to_string())?;
+
+    std::fs::write(&path, content).map_err(|_| "Failed to write port-forward profiles".to_string())
+}
+
+/// Show the running profiles in the tray.
+async fn refresh_tray(app: &AppHandle) {
+    let profiles = load_profiles().await.unwrap_or_default();
+    let forwards = portforward::list_port_forwards().await.unwrap_or_default();
+    let entries = RUNNING
+        .lock()
+        .await
+        .iter()
+        .filter_map(|(id, forward_ids)| {
+            let profile = profiles.iter().find(|p| &p.id == id)?;
+            let active = forwards
+                .iter()
+                .filter(|f| forward_ids.contains(&f.id))
+                .count();
+            Some(crate::tray::TrayForwardProfile {
+                id: id.clone(),
+                label: format!(
+                    "Stop {} ({}/{} active)",
+                    profile.name,
+                    active,
+                    profile.forwards.len()
+                ),
+            })
+        })
+        .collect();
+    crate::tray::set_forward_profiles(app, entries);
+}
+
+#[command]
+pub async fn list_port_forward_profiles() -> Result<Vec<PortForwardProfileStatus>, String> {
+    let profiles = load_profiles().await?;
+    let forwards = portforward::list_port_forwards().await?;
+    let running = RUNNING.lock().await;
+    Ok(profiles
+        .into_iter()
+        .map(|profile| {
+            let forward_ids = running.get(&profile.id);
+            PortForwardProfileStatus {
+                running: forward_ids.is_some(),
+                active: forwards
+                    .iter()
+                    .filter(|f| forward_ids.is_some_and(|ids| ids.contains(&f.id)))
+                    .cloned()
+                    .collect(),
+                profile,
+            }
+        })
+        .collect())
+}
+
+/// Create or update a profile (matched by id). Changes apply the next time it starts.
+#[command]
+pub async fn save_port_forward_profile(
+    mut profile: PortForwardProfile,
+) -> Result<PortForwardProfile, String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    if profile.forwards.is_empty() {
+        return Err("A profile needs at least one port-forward".to_string());
+    }
+    let mut ports: Vec<u16> = profile
+        .forwards
+        .iter()
+        .filter_map(|f| f.local_port)
+        .collect();
+    ports.sort_unstable();
+    if ports.windows(2).any(|w| w[0] == w[1]) {
+        return Err("Two forwards in the profile use the same local port".to_string());
+    }
+    if profile.id.is_empty() {
+        profile.id = format!("{:016x}", rand::random::<u64>());
+    }
+
+    let mut profiles = load_profiles().await?;
+    profiles.retain(|p| p.id != profile.id);
+    profiles.push(profile.clone());
+    save_profiles(&profiles).await?;
+    Ok(profile)
+}
+
+#[command]
+pub async fn delete_port_forward_profile(app_handle: AppHandle, id: String) -> Result<(), String> {
+    stop_port_forward_profile(app_handle, id.clone()).await?;
+    let mut profiles = load_profiles().await?;
+    profiles.retain(|p| p.id != id);
+    save_profiles(&profiles).await
+}
+
+async fn start_profile(app: &AppHandle, profile: &PortForwardProfile) -> Vec<ProfileForwardResult> {
+    let mut results = Vec::with_capacity(profile.forwards.len());
+    let mut started = Vec::new();
+    for forward in &profile.forwards {
+        let result = portforward::start_port_forward(
+            app.clone(),
+            profile.context.clone(),
+            forward.namespace.clone(),
+            forward.target.clone(),
+            forward.local_port,
+        )
+        .await;
+        results.push(match result {
+            Ok(status) => {
+                started.push(status.id.clone());
+                ProfileForwardResult {
+                    target: forward.target.clone(),
+                    status: Some(status),
+                    error: None,
+                }
+            }
+            Err(e) => ProfileForwardResult {
+                target: forward.target.clone(),
+                status: None,
+                error: Some(e),
+            },
+        });
+    }
+    RUNNING.lock().await.insert(profile.id.clone(), started);
+    refresh_tray(app).await;
+    results
+}
+
+/// Start every forward in a profile. Forwards that fail (port taken, pod missing) are reported and
+/// the rest still start.
+#[command]
+pub async fn start_port_forward_profile(
+    app_handle: AppHandle,
+    id: String,
+) -> Result<Vec<ProfileForwardResult>, String> {
+    if RUNNING.lock().await.contains_key(&id) {
+        return Err("The profile is already running".to_string());
+    }
+    let profile = load_profiles()
+        .await?
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Port-forward profile not found: {}", id))?;
+    Ok(start_profile(&app_handle, &profile).await)
+}
+
+#[command]
+pub async fn stop_port_forward_profile(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let Some(forward_ids) = RUNNING.lock().await.remove(&id) else {
+        return Ok(());
+    };
+    for forward_id in forward_ids {
+        // Forwards stopped individually are already gone
+        let _ = portforward::stop_port_forward(forward_id).await;
+    }
+    refresh_tray(&app_handle).await;
+    Ok(())
+}
+
+/// Called by the frontend when a context connects or disconnects: starts its autostart profiles,
+/// or stops all of its running profiles.
+#[command]
+pub async fn set_cluster_connected(
+    app_handle: AppHandle,
+    context: String,
+    connected: bool,
+) -> Result<(), String> {
+    let profiles: Vec<PortForwardProfile> = load_profiles()
+        .await?
+        .into_iter()
+        .filter(|p| p.context == context)
+        .collect();
+    for profile in profiles {
+        let running = RUNNING.lock().await.contains_key(&profile.id);
+        if connected && profile.autostart && !running {
+            let results = start_profile(&app_handle, &profile).await;
+            for failed in results.iter().filter_map(|r| r.error.as_ref()) {
+                tracing::warn!(profile = %profile.name, error = %failed, "Autostart port-forward failed");
+            }
+        } else if !connected && running {
+            stop_port_forward_profile(app_handle.clone(), profile.id).await?;
+        }
+    }
+    Ok(())
+}
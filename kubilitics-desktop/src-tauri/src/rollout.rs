@@ -0,0 +1,309 @@
+// Following a rollout the way `kubectl rollout status` does, for Deployments, StatefulSets and
+// DaemonSets. The object is polled and every change in progress goes out as a `rollout-progress`
+// event, ending with a complete, failed (progress deadline exceeded) or timed-out one.
+//
+// The progress rules are kubectl's: the controller must have observed the latest generation, then
+// every replica must be updated and available (ready, for StatefulSets) and old ones gone.
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, StatefulSet};
+use kube::{Api, Client};
+use serde::Serialize;
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 600;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+static WATCHES: Mutex<BTreeMap<String, tauri::async_runtime::JoinHandle<()>>> =
+    Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutPhase {
+    Progressing,
+    Complete,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RolloutProgress {
+    pub phase: RolloutPhase,
+    pub replicas: i32,
+    pub updated_replicas: i32,
+    pub ready_replicas: i32,
+    pub available_replicas: i32,
+    /// What the rollout is waiting for, or why it failed.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RolloutEvent {
+    pub id: String,
+    pub context: String,
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub progress: RolloutProgress,
+}
+
+/// Rollout state of a Deployment, as `kubectl rollout status` judges it.
+pub(crate) fn deployment_progress(deployment: &Deployment) -> RolloutProgress {
+    let replicas = deployment
+        .spec
+        .as_ref()
+        .and_then(|s| s.replicas)
+        .unwrap_or(1);
+    let status = deployment.status.clone().unwrap_or_default();
+    let mut progress = RolloutProgress {
+        phase: RolloutPhase::Progressing,
+        replicas,
+        updated_replicas: status.updated_replicas.unwrap_or(0),
+        ready_replicas: status.ready_replicas.unwrap_or(0),
+        available_replicas: status.available_replicas.unwrap_or(0),
+        message: None,
+    };
+
+    if status.observed_generation.unwrap_or(0) < deployment.metadata.generation.unwrap_or(0) {
+        progress.message = Some("Waiting for the deployment spec update to be observed".into());
+        return progress;
+    }
+    let deadline_exceeded = status.conditions.as_ref().and_then(|conditions| {
+        conditions.iter().find(|c| {
+            c.type_ == "Progressing" && c.reason.as_deref() == Some("ProgressDeadlineExceeded")
+        })
+    });
+    if let Some(condition) = deadline_exceeded {
+        progress.phase = RolloutPhase::Failed;
+        progress.message = condition
+            .message
+            .clone()
+            .or_else(|| Some("Progress deadline exceeded".to_string()));
+        return progress;
+    }
+
+    let total = status.replicas.unwrap_or(0);
+    progress.message = if progress.updated_replicas < replicas {
+        Some(format!(
+            "{} of {} new replicas have been updated",
+            progress.updated_replicas, replicas
+        ))
+    } else if total > progress.updated_replicas {
+        Some(format!(
+            "{} old replicas are pending termination",
+            total - progress.updated_replicas
+        ))
+    } else if progress.available_replicas < progress.updated_replicas {
+        Some(format!(
+            "{} of {} updated replicas are available",
+            progress.available_replicas, progress.updated_replicas
+        ))
+    } else {
+        progress.phase = RolloutPhase::Complete;
+        None
+    };
+    progress
+}
+
+fn statefulset_progress(statefulset: &StatefulSet) -> RolloutProgress {
+    let spec = statefulset.spec.as_ref();
+    let replicas = spec.and_then(|s| s.replicas).unwrap_or(1);
+    let status = statefulset.status.clone().unwrap_or_default();
+    let mut progress = RolloutProgress {
+        phase: RolloutPhase::Progressing,
+        replicas,
+        updated_replicas: status.updated_replicas.unwrap_or(0),
+        ready_replicas: status.ready_replicas.unwrap_or(0),
+        available_replicas: status.available_replicas.unwrap_or(0),
+        message: None,
+    };
+
+    let strategy = spec.and_then(|s| s.update_strategy.as_ref());
+    if strategy.and_then(|s| s.type_.as_deref()) == Some("OnDelete") {
+        progress.phase = RolloutPhase::Complete;
+        progress.message = Some("OnDelete update strategy: pods update when deleted".to_string());
+        return progress;
+    }
+    if status.observed_generation.unwrap_or(0) < statefulset.metadata.generation.unwrap_or(0) {
+        progress.message = Some("Waiting for the statefulset spec update to be observed".into());
+        return progress;
+    }
+    if progress.ready_replicas < replicas {
+        progress.message = Some(format!(
+            "{} of {} pods are ready",
+            progress.ready_replicas, replicas
+        ));
+        return progress;
+    }
+    let partition = strategy
+        .and_then(|s| s.rolling_update.as_ref())
+        .and_then(|r| r.partition)
+        .unwrap_or(0);
+    if partition > 0 {
+        let expected = (replicas - partition).max(0);
+        progress.message = if progress.updated_replicas < expected {
+            Some(format!(
+                "{} of {} pods updated for the partitioned roll out",
+                progress.updated_replicas, expected
+            ))
+        } else {
+            progress.phase = RolloutPhase::Complete;
+            None
+        };
+        return progress;
+    }
+    progress.message = if status.update_revision != status.current_revision {
+        Some(format!(
+            "{} of {} pods are at the new revision",
+            progress.updated_replicas, replicas
+        ))
+    } else {
+        progress.phase = RolloutPhase::Complete;
+        None
+    };
+    progress
+}
+
+fn daemonset_progress(daemonset: &DaemonSet) -> RolloutProgress {
+    let status = daemonset.status.clone().unwrap_or_default();
+    let desired = status.desired_number_scheduled;
+    let mut progress = RolloutProgress {
+        phase: RolloutPhase::Progressing,
+        replicas: desired,
+        updated_replicas: status.updated_number_scheduled.unwrap_or(0),
+        ready_replicas: status.number_ready,
+        available_replicas: status.number_available.unwrap_or(0),
+        message: None,
+    };
+
+    let strategy = daemonset
+        .spec
+        .as_ref()
+        .and_then(|s| s.update_strategy.as_ref())
+        .and_then(|s| s.type_.as_deref());
+    if strategy == Some("OnDelete") {
+        progress.phase = RolloutPhase::Complete;
+        progress.message = Some("OnDelete update strategy: pods update when deleted".to_string());
+        return progress;
+    }
+    progress.message =
+        if status.observed_generation.unwrap_or(0) < daemonset.metadata.generation.unwrap_or(0) {
+            Some("Waiting for the daemonset spec update to be observed".into())
+        } else if progress.updated_replicas < desired {
+            Some(format!(
+                "{} of {} updated pods are scheduled",
+                progress.updated_replicas, desired
+            ))
+        } else if progress.available_replicas < desired {
+            Some(format!(
+                "{} of {} updated pods are available",
+                progress.available_replicas, desired
+            ))
+        } else {
+            progress.phase = RolloutPhase::Complete;
+            None
+        };
+    progress
+}
+
+async fn fetch_progress(
+    client: &Client,
+    kind: &str,
+    namespace: &str,
+    name: &str,
+) -> Result<RolloutProgress, String> {
+    let not_found = || format!("{} {}/{} not found", kind, namespace, name);
+    match kind {
+        "Deployment" => Api::<Deployment>::namespaced(client.clone(), namespace)
+            .get_opt(name)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|d| deployment_progress(&d))
+            .ok_or_else(not_found),
+        "StatefulSet" => Api::<StatefulSet>::namespaced(client.clone(), namespace)
+            .get_opt(name)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|s| statefulset_progress(&s))
+            .ok_or_else(not_found),
+        "DaemonSet" => Api::<DaemonSet>::namespaced(client.clone(), namespace)
+            .get_opt(name)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|d| daemonset_progress(&d))
+            .ok_or_else(not_found),
+        _ => Err(format!("Rollouts can't be followed for {}", kind)),
+    }
+}
+
+/// Poll until the rollout settles or `timeout` passes, emitting each change.
+async fn follow(app: AppHandle, client: Client, mut event: RolloutEvent, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    let mut last: Option<RolloutProgress> = None;
+    loop {
+        match fetch_progress(&client, &event.kind, &event.namespace, &event.name).await {
+            Ok(progress) => event.progress = progress,
+            // A transient API error shouldn't end the watch; a deleted object does
+            Err(e) if e.ends_with("not found") => {
+                event.progress.phase = RolloutPhase::Failed;
+                event.progress.message = Some(e);
+            }
+            Err(e) => tracing::warn!(error = %e, "Rollout status check failed"),
+        }
+        if event.progress.phase == RolloutPhase::Progressing && Instant::now() >= deadline {
+            event.progress.phase = RolloutPhase::Failed;
+            event.progress.message =
+                Some("Timed out waiting for the rollout to finish".to_string());
+        }
+        if last.as_ref() != Some(&event.progress) {
+            let _ = app.emit("rollout-progress", &event);
+            last = Some(event.progress.clone());
+        }
+        if event.progress.phase != RolloutPhase::Progressing {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    WATCHES.lock().await.remove(&event.id);
+}
+
+/// Follow a Deployment, StatefulSet or DaemonSet rollout, emitting `rollout-progress` until it
+/// completes, fails or `timeout_secs` (default 600) passes. Returns the current state, whose id
+/// identifies the watch's events.
+#[command]
+pub async fn watch_rollout(
+    app_handle: AppHandle,
+    context: String,
+    kind: String,
+    namespace: String,
+    name: String,
+    timeout_secs: Option<u64>,
+) -> Result<RolloutEvent, String> {
+    let client = crate::k8s::client_for(&context).await?;
+    let progress = fetch_progress(&client, &kind, &namespace, &name).await?;
+    let event = RolloutEvent {
+        id: format!("{:016x}", rand::random::<u64>()),
+        context,
+        kind,
+        namespace,
+        name,
+        progress,
+    };
+    let timeout = Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+
+    // Held across the spawn so the task's own removal can't run before the insert
+    let mut watches = WATCHES.lock().await;
+    let task = tauri::async_runtime::spawn(follow(app_handle, client, event.clone(), timeout));
+    watches.insert(event.id.clone(), task);
+    Ok(event)
+}
+
+#[command]
+pub async fn stop_rollout_watch(id: String) -> Result<(), String> {
+    if let Some(task) = WATCHES.lock().await.remove(&id) {
+        task.abort();
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::CommandChild;
 use tauri_plugin_shell::ShellExt;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 use tokio::time::sleep;
 
 use serde::{Deserialize, Serialize};
@@ -21,41 +24,121 @@ pub struct AISidecarStatus {
     pub port: u16,
 }
 
+/// What the process lifecycle needs from a child: `CommandChild` in the app, a fake in tests.
+trait ChildProcess {
+    fn kill(self) -> Result<(), String>;
+}
+
+impl ChildProcess for CommandChild {
+    fn kill(self) -> Result<(), String> {
+        CommandChild::kill(self).map_err(|e| e.to_string())
+    }
+}
+
+/// One sidecar's process handle and running flag. The mutex is held from killing the old process to
+/// storing the new one, and `stopping` is checked under it, so a restart from the UI, the health
+/// monitor and stop() can't interleave and orphan a process.
+struct ProcessSlot<C> {
+    name: &'static str,
+    child: Mutex<Option<C>>,
+    running: AtomicBool,
+    /// Set by begin_stop(); a restart that was waiting on the lock then backs off instead of spawning.
+    stopping: AtomicBool,
+}
+
+impl<C: ChildProcess> ProcessSlot<C> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            child: Mutex::new(None),
+            running: AtomicBool::new(false),
+            stopping: AtomicBool::new(false),
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    /// Kill the current process, if any, and store the one `spawn` starts.
+    async fn replace<E>(&self, spawn: impl FnOnce() -> Result<C, E>) -> Result<(), Box<dyn std::error::Error>>
+    where
+        E: Into<Box<dyn std::error::Error>>,
+    {
+        let mut child = self.child.lock().await;
+        if self.stopping.load(Ordering::SeqCst) {
+            return Err(format!("{} is shutting down", self.name).into());
+        }
+        // The old process (unhealthy, or the one being restarted) would otherwise keep the port
+        // and run untracked.
+        if let Some(previous) = child.take() {
+            let _ = previous.kill();
+        }
+        *child = Some(spawn().map_err(|e| -> Box<dyn std::error::Error> { e.into() })?);
+        self.set_running(true);
+        Ok(())
+    }
+
+    /// Refuse any further spawn. The current process keeps running until `kill`.
+    fn begin_stop(&self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        self.set_running(false);
+    }
+
+    /// Kill the current process; returns whether there was one.
+    async fn kill(&self) -> bool {
+        let mut child = self.child.lock().await;
+        self.set_running(false);
+        match child.take() {
+            Some(process) => {
+                let _ = process.kill();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Flags and counters are atomics so status reads never wait on a lock.
 pub struct BackendManager {
     app_handle: AppHandle,
-    restart_count: Arc<Mutex<u32>>,
-    is_running: Arc<Mutex<bool>>,
+    restart_count: AtomicU32,
     /// True once the backend has emitted "ready" — lets get_backend_status answer immediately.
-    is_ready: Arc<Mutex<bool>>,
+    is_ready: AtomicBool,
     /// TASK-SIDECAR-001: Store process handle so we can kill on exit, not just send HTTP shutdown.
-    backend_process: Arc<Mutex<Option<tauri_plugin_shell::process::CommandChild>>>,
-    ai_process: Arc<Mutex<Option<tauri_plugin_shell::process::CommandChild>>>,
-    ai_restart_count: Arc<Mutex<u32>>,
-    ai_is_running: Arc<Mutex<bool>>,
-    ai_available: Arc<Mutex<bool>>,
+    backend: ProcessSlot<CommandChild>,
+    ai: ProcessSlot<CommandChild>,
+    ai_restart_count: AtomicU32,
+    ai_available: AtomicBool,
+    /// The AI health monitor runs once per manager, however often the AI backend is (re)started.
+    ai_monitor_started: AtomicBool,
 }
 
 impl BackendManager {
     pub fn new(app_handle: AppHandle) -> Self {
         Self {
             app_handle,
-            restart_count: Arc::new(Mutex::new(0)),
-            is_running: Arc::new(Mutex::new(false)),
-            is_ready: Arc::new(Mutex::new(false)),
-            backend_process: Arc::new(Mutex::new(None)),
-            ai_process: Arc::new(Mutex::new(None)),
-            ai_restart_count: Arc::new(Mutex::new(0)),
-            ai_is_running: Arc::new(Mutex::new(false)),
-            ai_available: Arc::new(Mutex::new(false)),
+            restart_count: AtomicU32::new(0),
+            is_ready: AtomicBool::new(false),
+            backend: ProcessSlot::new("Backend"),
+            ai: ProcessSlot::new("AI backend"),
+            ai_restart_count: AtomicU32::new(0),
+            ai_available: AtomicBool::new(false),
+            ai_monitor_started: AtomicBool::new(false),
         }
     }
 
     pub fn is_ready(&self) -> bool {
-        *self.is_ready.lock().unwrap()
+        self.is_ready.load(Ordering::SeqCst)
     }
 
     /// Start backend and health monitor. Takes Arc<Self> so the health monitor can restart
     /// the same instance (P1-2) instead of creating a new BackendManager.
+    #[tracing::instrument(skip_all)]
     pub async fn start(self: Arc<Self>) -> Result<(), Box<dyn std::error::Error>> {
         // Emit startup event so the frontend can show a loading state.
         let _ = self.app_handle.emit("backend-status", serde_json::json!({
@@ -69,10 +152,10 @@ impl BackendManager {
         // before we emit "ready" (the JS setup() runs after the first render tick).
         // Increased delay to 1500ms to ensure listener is registered even on slower systems.
         if self.is_port_in_use(BACKEND_PORT).await {
-            println!("Port {} already in use — assuming backend is already running", BACKEND_PORT);
-            *self.is_running.lock().unwrap() = true;
+            tracing::info!(port = BACKEND_PORT, "Port already in use — assuming backend is already running");
+            self.backend.set_running(true);
             sleep(Duration::from_millis(1500)).await;
-            *self.is_ready.lock().unwrap() = true;
+            self.is_ready.store(true, Ordering::SeqCst);
             let _ = self.app_handle.emit("backend-status", serde_json::json!({
                 "status": "ready",
                 "message": "Backend engine ready"
@@ -85,7 +168,7 @@ impl BackendManager {
 
         match self.start_backend_process().await {
             Ok(()) => {
-                *self.is_ready.lock().unwrap() = true;
+                self.is_ready.store(true, Ordering::SeqCst);
                 let _ = self.app_handle.emit("backend-status", serde_json::json!({
                     "status": "ready",
                     "message": "Backend engine ready"
@@ -95,7 +178,7 @@ impl BackendManager {
             Err(e) => {
                 // FIX TASK-013: Use {:#} (alternate format) for better error messages.
                 // Plain {} on boxed errors often produces empty string or unhelpful Rust internals.
-                eprintln!("Backend failed to start: {:#}", e);
+                tracing::error!("Backend failed to start: {:#}", e);
                 let _ = self.app_handle.emit("backend-status", serde_json::json!({
                     "status": "error",
                     "message": format!("Backend engine failed to start: {:#}", e)
@@ -113,12 +196,14 @@ impl BackendManager {
 
     /// P0-E / P1-1: Restart the backend process (e.g. from "Restart Engine" in UI).
     /// Emits backend-status: starting, then on success backend-status: ready and backend-circuit-reset.
+    #[tracing::instrument(skip_all)]
     pub async fn restart(&self) -> Result<(), Box<dyn std::error::Error>> {
         let _ = self.app_handle.emit("backend-status", serde_json::json!({
             "status": "starting",
             "message": "Restarting backend engine…"
         }));
         self.start_backend_process().await?;
+        crate::breadcrumbs::record(crate::breadcrumbs::BreadcrumbKind::Restart, "restart", "Backend restarted on request");
         let _ = self.app_handle.emit("backend-status", serde_json::json!({
             "status": "ready",
             "message": "Backend engine ready"
@@ -127,6 +212,7 @@ impl BackendManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
     async fn start_backend_process(&self) -> Result<(), Box<dyn std::error::Error>> {
         let sidecar_command = self.app_handle.shell().sidecar("kubilitics-backend")?;
 
@@ -177,13 +263,37 @@ impl BackendManager {
         if !kubeconfig_path.is_empty() {
             cmd = cmd.env("KUBECONFIG", &kubeconfig_path);
         }
-
-        let (_rx, child) = cmd.spawn()?;
+        // Cluster API servers and external calls go through the configured proxy
+        for (name, value) in crate::proxy::sidecar_env().await {
+            cmd = cmd.env(name, value);
+        }
+        // Unset keeps the backend's default of every interface, both address families
+        if let Some(bind_address) = crate::commands::load_connectivity_settings()
+            .await
+            .ok()
+            .and_then(|s| s.backend_bind_address)
+        {
+            cmd = cmd.env("KUBILITICS_BIND_ADDRESS", bind_address);
+        }
+        // Per-context SOCKS proxies (proxy-url for the matching cluster clients)
+        for (name, value) in crate::socks::sidecar_env().await {
+            cmd = cmd.env(name, value);
+        }
+        // Per-context request timeout / retry budgets
+        for (name, value) in crate::cluster_policy::sidecar_env().await {
+            cmd = cmd.env(name, value);
+        }
+        for (name, value) in crate::airgap::sidecar_env() {
+            cmd = cmd.env(name, value);
+        }
+        // LAN clients must be devices paired with this desktop
+        for (name, value) in crate::pairing::sidecar_env().await {
+            cmd = cmd.env(name, value);
+        }
 
         // TASK-SIDECAR-001: Store the process handle so stop() can kill it on force-quit.
-        *self.backend_process.lock().unwrap() = Some(child);
-        *self.is_running.lock().unwrap() = true;
-        println!("Kubilitics backend started on http://localhost:{}", BACKEND_PORT);
+        self.backend.replace(|| cmd.spawn().map(|(_rx, child)| child)).await?;
+        tracing::info!("Kubilitics backend started on http://localhost:{}", BACKEND_PORT);
         
         // Wait for backend to be ready
         self.wait_for_ready().await?;
@@ -191,8 +301,9 @@ impl BackendManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(attempts))]
     async fn wait_for_ready(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let url = format!("http://localhost:{}/health", BACKEND_PORT);
+        let client = crate::http_client::shared(&self.app_handle).await?;
 
         // Performance optimization: Allow up to 60 seconds (120 attempts × 500ms) for the backend to start.
         // Go binary cold-start on first launch can take 10-15 seconds on a slow machine.
@@ -200,11 +311,16 @@ impl BackendManager {
         // Emit progress events less frequently (every 2 seconds instead of 3) to reduce overhead.
         // Backend starts in background - UI is not blocked (handled by non-blocking overlay).
         for attempt in 1..=120 {
-            if let Ok(response) = reqwest::get(&url).await {
-                if response.status().is_success() {
-                    println!("Backend is ready after {} attempts", attempt);
+            // Resolved per attempt: until the backend listens there is no family to settle on
+            let url = format!("{}/health", crate::loopback::base_url(BACKEND_PORT).await);
+            match client.get(&url).timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS)).send().await {
+                Ok(response) if response.status().is_success() => {
+                    tracing::Span::current().record("attempts", attempt);
+                    tracing::info!(attempts = attempt, "Backend is ready");
                     return Ok(());
                 }
+                Err(e) if e.is_connect() => crate::loopback::forget(BACKEND_PORT).await,
+                _ => {}
             }
             // Emit progress every 2 seconds (every 4 attempts) - less frequent to reduce overhead
             // UI is not blocked, so frequent updates aren't needed
@@ -223,9 +339,18 @@ impl BackendManager {
 
     /// P1-11: Only treat port as "in use by our backend" if the health response is from kubilitics-backend.
     /// Another HTTP server on 819 would otherwise be treated as ready and we'd skip spawning.
+    #[tracing::instrument(skip(self))]
     async fn is_port_in_use(&self, port: u16) -> bool {
-        let url = format!("http://localhost:{}/health", port);
-        let Ok(response) = reqwest::get(&url).await else {
+        let url = format!("{}/health", crate::loopback::base_url(port).await);
+        let Ok(client) = crate::http_client::shared(&self.app_handle).await else {
+            return false;
+        };
+        let Ok(response) = client
+            .get(&url)
+            .timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS))
+            .send()
+            .await
+        else {
             return false;
         };
         if !response.status().is_success() {
@@ -246,31 +371,27 @@ impl BackendManager {
     fn start_health_monitor(this: Arc<Self>) {
         tokio::spawn(async move {
             loop {
-                sleep(Duration::from_secs(HEALTH_CHECK_INTERVAL_SECS)).await;
-
-                let running = {
-                    let guard = this.is_running.lock().unwrap();
-                    *guard
-                };
+                sleep(crate::network::polling_interval(Duration::from_secs(HEALTH_CHECK_INTERVAL_SECS))).await;
 
-                if !running {
+                if !this.backend.is_running() {
                     continue;
                 }
 
-                if !Self::check_health(BACKEND_PORT).await {
-                    println!("Backend health check failed. Attempting restart...");
+                if !this.check_health(BACKEND_PORT).await {
+                    tracing::warn!("Backend health check failed. Attempting restart...");
 
-                    let count = {
-                        let mut guard = this.restart_count.lock().unwrap();
-                        *guard += 1;
-                        *guard
-                    };
+                    let count = this.restart_count.fetch_add(1, Ordering::SeqCst) + 1;
 
                     if count <= MAX_RESTART_ATTEMPTS {
                         if let Err(e) = this.start_backend_process().await {
-                            eprintln!("Failed to restart backend: {}", e);
+                            tracing::error!(error = %e, "Failed to restart backend");
                         } else {
-                            println!("Backend restarted successfully (attempt {})", count);
+                            tracing::info!(attempt = count, "Backend restarted successfully");
+                            crate::breadcrumbs::record(
+                                crate::breadcrumbs::BreadcrumbKind::Restart,
+                                "health_monitor",
+                                format!("Backend restarted after failed health check (attempt {})", count),
+                            );
                             let _ = this.app_handle.emit("backend-status", serde_json::json!({
                                 "status": "ready",
                                 "message": "Backend engine ready"
@@ -278,57 +399,73 @@ impl BackendManager {
                             let _ = this.app_handle.emit("backend-circuit-reset", ());
                         }
                     } else {
-                        eprintln!("Max restart attempts reached. Backend will not restart.");
-                        let mut guard = this.is_running.lock().unwrap();
-                        *guard = false;
+                        tracing::error!("Max restart attempts reached. Backend will not restart.");
+                        this.backend.set_running(false);
                     }
                 }
             }
         });
     }
 
-    async fn check_health(port: u16) -> bool {
-        let url = format!("http://localhost:{}/health", port);
-        
-        match tokio::time::timeout(
-            Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS),
-            reqwest::get(&url)
-        ).await {
-            Ok(Ok(response)) => response.status().is_success(),
-            _ => false,
+    #[tracing::instrument(skip(self))]
+    async fn check_health(&self, port: u16) -> bool {
+        let url = format!("{}/health", crate::loopback::base_url(port).await);
+        let Ok(client) = crate::http_client::shared(&self.app_handle).await else {
+            return false;
+        };
+
+        match client
+            .get(&url)
+            .timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS))
+            .send()
+            .await
+        {
+            Ok(response) => response.status().is_success(),
+            Err(e) => {
+                if e.is_connect() {
+                    crate::loopback::forget(port).await;
+                }
+                false
+            }
         }
     }
 
     pub async fn stop(&self) {
-        *self.is_running.lock().unwrap() = false;
+        self.backend.begin_stop();
+        self.ai.begin_stop();
 
         // Stop AI backend first
         self.stop_ai_backend().await;
 
         // Try graceful HTTP shutdown; fall through to SIGKILL on failure or force-quit.
-        let url = format!("http://localhost:{}/api/v1/shutdown", BACKEND_PORT);
-        let client = reqwest::Client::new();
-        let _ = client.post(&url).send().await;
+        let url = format!("{}/api/v1/shutdown", crate::loopback::base_url(BACKEND_PORT).await);
+        let client = crate::http_client::shared(&self.app_handle).await.unwrap_or_default();
+        let _ = client.post(&url).timeout(Duration::from_secs(2)).send().await;
 
         // Wait briefly for graceful exit, then kill the process handle if still alive.
         sleep(Duration::from_millis(1500)).await;
-        if let Ok(mut guard) = self.backend_process.lock() {
-            if let Some(child) = guard.take() {
-                let _ = child.kill();
-                println!("Backend process killed on exit");
-            }
+        if self.backend.kill().await {
+            tracing::info!("Backend process killed on exit");
         }
 
-        println!("Backend stopped");
+        tracing::info!("Backend stopped");
     }
 
     // AI Backend Management
 
+    #[tracing::instrument(skip_all)]
     async fn start_ai_backend(self: &Arc<Self>) {
+        // Its LLM providers are external, so in air-gapped mode the AI backend does not run at all
+        if crate::airgap::is_air_gapped() {
+            tracing::info!("Air-gapped mode: AI backend not started");
+            self.ai_available.store(false, Ordering::SeqCst);
+            return;
+        }
+
         // Check if AI binary exists
         if !self.check_ai_binary_exists().await {
-            println!("AI backend binary not found, AI features will be unavailable");
-            *self.ai_available.lock().unwrap() = false;
+            tracing::warn!("AI backend binary not found, AI features will be unavailable");
+            self.ai_available.store(false, Ordering::SeqCst);
             return;
         }
 
@@ -336,23 +473,20 @@ impl BackendManager {
         // (e.g. in dev mode from dev-desktop.sh, or a previous session).
         // If the port is in use AND responds to /health, adopt it instead of refusing to start.
         if self.is_port_in_use(AI_BACKEND_PORT).await {
-            let health_url = format!("http://localhost:{}/health", AI_BACKEND_PORT);
-            let client = reqwest::Client::builder()
-                .timeout(Duration::from_secs(3))
-                .build()
-                .unwrap_or_default();
-            match client.get(&health_url).send().await {
+            let health_url = format!("{}/health", crate::loopback::base_url(AI_BACKEND_PORT).await);
+            let client = crate::http_client::shared(&self.app_handle).await.unwrap_or_default();
+            match client.get(&health_url).timeout(Duration::from_secs(3)).send().await {
                 Ok(resp) if resp.status().is_success() => {
-                    println!("AI port {} already in use — healthy AI instance adopted", AI_BACKEND_PORT);
-                    *self.ai_available.lock().unwrap() = true;
-                    *self.ai_is_running.lock().unwrap() = true;
+                    tracing::info!("AI port {} already in use — healthy AI instance adopted", AI_BACKEND_PORT);
+                    self.ai_available.store(true, Ordering::SeqCst);
+                    self.ai.set_running(true);
                     // Start health monitor so we track the adopted process.
                     Self::start_ai_health_monitor(self.clone());
                     return;
                 }
                 _ => {
-                    println!("AI backend port {} is in use by an unresponsive process — AI unavailable", AI_BACKEND_PORT);
-                    *self.ai_available.lock().unwrap() = false;
+                    tracing::warn!("AI backend port {} is in use by an unresponsive process — AI unavailable", AI_BACKEND_PORT);
+                    self.ai_available.store(false, Ordering::SeqCst);
                     return;
                 }
             }
@@ -360,14 +494,13 @@ impl BackendManager {
 
         match self.start_ai_backend_process().await {
             Ok(_) => {
-                *self.ai_available.lock().unwrap() = true;
-                *self.ai_is_running.lock().unwrap() = true;
+                self.ai_available.store(true, Ordering::SeqCst);
                 // TASK-SIDECAR-003: Pass Arc<Self> so health monitor uses same instance.
                 Self::start_ai_health_monitor(self.clone());
             }
             Err(e) => {
-                eprintln!("Failed to start AI backend: {}", e);
-                *self.ai_available.lock().unwrap() = false;
+                tracing::error!("Failed to start AI backend: {}", e);
+                self.ai_available.store(false, Ordering::SeqCst);
             }
         }
     }
@@ -414,7 +547,11 @@ impl BackendManager {
         false
     }
 
+    #[tracing::instrument(skip_all)]
     async fn start_ai_backend_process(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Also covers restarts from the health monitor
+        crate::airgap::ensure_external_allowed("AI features")?;
+
         let app_data_dir = dirs::data_local_dir()
             .ok_or("Could not find data directory")?
             .join("kubilitics");
@@ -434,21 +571,21 @@ impl BackendManager {
             BACKEND_PORT
         );
 
-        let (_rx, child) = sidecar_command
+        let cmd = sidecar_command
+            .envs(crate::proxy::sidecar_env().await)
             .env("KUBILITICS_PORT", AI_BACKEND_PORT.to_string())
             .env("KUBILITICS_BACKEND_ADDRESS", "localhost:50051")
-            .env("KUBILITICS_BACKEND_HTTP_BASE_URL", format!("http://localhost:{}", BACKEND_PORT))
+            .env("KUBILITICS_BACKEND_HTTP_BASE_URL", crate::loopback::base_url(BACKEND_PORT).await)
             .env("KUBILITICS_MCP_ENABLED", "true")
             .env("KUBILITICS_SAFETY_ENABLED", "true")
             .env("KUBILITICS_ANALYTICS_ENABLED", "true")
             .env("KUBILITICS_DATABASE_PATH", ai_data_dir.join("kubilitics-ai.db").to_string_lossy().to_string())
             .env("KUBILITICS_DATABASE_SQLITE_PATH", ai_data_dir.join("kubilitics-ai.db").to_string_lossy().to_string())
             .env("KUBILITICS_DATABASE_TYPE", "sqlite")
-            .env("KUBILITICS_ALLOWED_ORIGINS", tauri_allowed_origins)
-            .spawn()?;
+            .env("KUBILITICS_ALLOWED_ORIGINS", tauri_allowed_origins);
 
-        *self.ai_process.lock().unwrap() = Some(child);
-        println!("AI backend started on http://localhost:{}", AI_BACKEND_PORT);
+        self.ai.replace(|| cmd.spawn().map(|(_rx, child)| child)).await?;
+        tracing::info!("AI backend started on http://localhost:{}", AI_BACKEND_PORT);
         
         // Wait for AI backend to be ready
         self.wait_for_ai_ready().await?;
@@ -456,16 +593,20 @@ impl BackendManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
     async fn wait_for_ai_ready(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let url = format!("http://localhost:{}/health", AI_BACKEND_PORT);
+        let client = crate::http_client::shared(&self.app_handle).await?;
 
         // Allow up to 30 seconds (60 attempts × 500ms) for the AI backend to start.
         for attempt in 1..=60 {
-            if let Ok(response) = reqwest::get(&url).await {
-                if response.status().is_success() {
-                    println!("AI backend is ready after {} attempts", attempt);
+            let url = format!("{}/health", crate::loopback::base_url(AI_BACKEND_PORT).await);
+            match client.get(&url).timeout(Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS)).send().await {
+                Ok(response) if response.status().is_success() => {
+                    tracing::info!(attempts = attempt, "AI backend is ready");
                     return Ok(());
                 }
+                Err(e) if e.is_connect() => crate::loopback::forget(AI_BACKEND_PORT).await,
+                _ => {}
             }
             sleep(Duration::from_millis(500)).await;
         }
@@ -474,67 +615,75 @@ impl BackendManager {
     }
 
     /// TASK-SIDECAR-003: Takes Arc<Self> so the restart uses the same manager instance
-    /// (same ai_process handle, ai_restart_count, etc.) instead of a fresh BackendManager.
+    /// (same `ai` process slot, ai_restart_count, etc.) instead of a fresh BackendManager.
     fn start_ai_health_monitor(this: Arc<Self>) {
+        if this.ai_monitor_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
         tokio::spawn(async move {
             loop {
-                sleep(Duration::from_secs(AI_HEALTH_CHECK_INTERVAL_SECS)).await;
+                sleep(crate::network::polling_interval(Duration::from_secs(AI_HEALTH_CHECK_INTERVAL_SECS))).await;
 
-                let running = *this.ai_is_running.lock().unwrap();
-                if !running {
+                if !this.ai.is_running() {
                     continue;
                 }
 
-                if !Self::check_health(AI_BACKEND_PORT).await {
-                    println!("AI backend health check failed. Attempting restart...");
+                if !this.check_health(AI_BACKEND_PORT).await {
+                    tracing::warn!("AI backend health check failed. Attempting restart...");
 
-                    let count = {
-                        let mut guard = this.ai_restart_count.lock().unwrap();
-                        *guard += 1;
-                        *guard
-                    };
+                    let count = this.ai_restart_count.fetch_add(1, Ordering::SeqCst) + 1;
 
                     if count <= AI_MAX_RESTART_ATTEMPTS {
                         sleep(Duration::from_secs(AI_RESTART_DELAY_SECS)).await;
                         if let Err(e) = this.start_ai_backend_process().await {
-                            eprintln!("Failed to restart AI backend: {}", e);
+                            tracing::error!(error = %e, "Failed to restart AI backend");
                         } else {
-                            println!("AI backend restarted successfully (attempt {})", count);
-                            *this.ai_is_running.lock().unwrap() = true;
+                            tracing::info!(attempt = count, "AI backend restarted successfully");
+                            crate::breadcrumbs::record(
+                                crate::breadcrumbs::BreadcrumbKind::Restart,
+                                "ai_health_monitor",
+                                format!("AI backend restarted after failed health check (attempt {})", count),
+                            );
                         }
                     } else {
-                        eprintln!("Max AI restart attempts reached. AI backend will not restart.");
-                        *this.ai_is_running.lock().unwrap() = false;
-                        *this.ai_available.lock().unwrap() = false;
+                        tracing::error!("Max AI restart attempts reached. AI backend will not restart.");
+                        this.ai.set_running(false);
+                        this.ai_available.store(false, Ordering::SeqCst);
                     }
                 }
             }
         });
     }
 
-    #[allow(dead_code)]
+    /// Follow a change of air-gapped mode: stop the AI backend when it turns on, start it again when
+    /// it turns off.
+    pub async fn apply_air_gap_mode(self: &Arc<Self>) {
+        if crate::airgap::is_air_gapped() {
+            self.stop_ai_backend().await;
+            self.ai_available.store(false, Ordering::SeqCst);
+        } else if !self.ai.is_running() {
+            self.ai_restart_count.store(0, Ordering::SeqCst);
+            self.start_ai_backend().await;
+        }
+    }
+
     async fn stop_ai_backend(&self) {
-        *self.ai_is_running.lock().unwrap() = false;
-        
         // Kill the AI process if it exists
-        if let Ok(mut process_guard) = self.ai_process.lock() {
-            if let Some(child) = process_guard.take() {
-                let _ = child.kill();
-                println!("AI backend stopped");
-            }
+        if self.ai.kill().await {
+            tracing::info!("AI backend stopped");
         }
         
         // Send graceful shutdown signal to AI backend
-        let url = format!("http://localhost:{}/api/v1/shutdown", AI_BACKEND_PORT);
-        let client = reqwest::Client::new();
-        let _ = client.post(&url).send().await;
+        let url = format!("{}/api/v1/shutdown", crate::loopback::base_url(AI_BACKEND_PORT).await);
+        let client = crate::http_client::shared(&self.app_handle).await.unwrap_or_default();
+        let _ = client.post(&url).timeout(Duration::from_secs(2)).send().await;
         
         sleep(Duration::from_secs(1)).await;
     }
 
     pub fn get_ai_status(&self) -> AISidecarStatus {
-        let available = *self.ai_available.lock().unwrap();
-        let running = *self.ai_is_running.lock().unwrap();
+        let available = self.ai_available.load(Ordering::SeqCst);
+        let running = self.ai.is_running();
         
         AISidecarStatus {
             available,
@@ -544,6 +693,7 @@ impl BackendManager {
     }
 
     /// P1-10: Resolve kcli binary deterministically by target triple so universal builds pick the correct arch.
+    #[tracing::instrument(skip_all)]
     async fn resolve_kcli_binary_path(&self) -> Result<String, Box<dyn std::error::Error>> {
         let kcli_sidecar_exists = self.app_handle.shell().sidecar("kcli").is_ok();
 
@@ -624,7 +774,7 @@ pub fn start_backend(app_handle: &AppHandle) -> Result<Arc<BackendManager>, Box<
     let manager_clone = manager.clone();
     tauri::async_runtime::spawn(async move {
         if let Err(e) = manager_clone.start().await {
-            eprintln!("Failed to start backend: {}", e);
+            tracing::error!("Failed to start backend: {}", e);
         }
     });
     
@@ -656,3 +806,88 @@ pub fn get_ai_status(app_handle: AppHandle) -> Result<AISidecarStatus, String> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Counts itself in `alive` until killed.
+    struct FakeChild {
+        alive: Arc<AtomicUsize>,
+    }
+
+    impl ChildProcess for FakeChild {
+        fn kill(self) -> Result<(), String> {
+            self.alive.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn spawn_fake(alive: &Arc<AtomicUsize>) -> Result<FakeChild, String> {
+        alive.fetch_add(1, Ordering::SeqCst);
+        Ok(FakeChild { alive: alive.clone() })
+    }
+
+    /// Restarts racing stop() — the UI's "Restart Engine" and the health monitor during app exit —
+    /// must not leave a process running after stop(), nor a slot that claims one is.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_restart_and_stop_leave_no_orphan() {
+        for _ in 0..100 {
+            let slot = Arc::new(ProcessSlot::<FakeChild>::new("Backend"));
+            let alive = Arc::new(AtomicUsize::new(0));
+            slot.replace(|| spawn_fake(&alive)).await.unwrap();
+
+            let restarts: Vec<_> = (0..8)
+                .map(|_| {
+                    let slot = slot.clone();
+                    let alive = alive.clone();
+                    tokio::spawn(async move { slot.replace(|| spawn_fake(&alive)).await.is_ok() })
+                })
+                .collect();
+            let stop = tokio::spawn({
+                let slot = slot.clone();
+                async move {
+                    // Same order as BackendManager::stop: refuse spawns, wait for the graceful
+                    // shutdown, then kill
+                    slot.begin_stop();
+                    tokio::task::yield_now().await;
+                    slot.kill().await;
+                }
+            });
+            for restart in restarts {
+                restart.await.unwrap();
+            }
+            stop.await.unwrap();
+
+            assert_eq!(alive.load(Ordering::SeqCst), 0, "a process outlived stop()");
+            assert!(!slot.is_running());
+            assert!(slot.child.lock().await.is_none());
+            assert!(slot.replace(|| spawn_fake(&alive)).await.is_err());
+            assert_eq!(alive.load(Ordering::SeqCst), 0);
+        }
+    }
+
+    /// Each restart kills the process it replaces, so at most one is ever alive.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_restarts_keep_one_process() {
+        let slot = Arc::new(ProcessSlot::<FakeChild>::new("Backend"));
+        let alive = Arc::new(AtomicUsize::new(0));
+        let restarts: Vec<_> = (0..32)
+            .map(|_| {
+                let slot = slot.clone();
+                let alive = alive.clone();
+                tokio::spawn(async move { slot.replace(|| spawn_fake(&alive)).await.unwrap() })
+            })
+            .collect();
+        for restart in restarts {
+            restart.await.unwrap();
+        }
+
+        assert_eq!(alive.load(Ordering::SeqCst), 1);
+        assert!(slot.is_running());
+        assert!(slot.kill().await);
+        assert_eq!(alive.load(Ordering::SeqCst), 0);
+        assert!(!slot.is_running());
+    }
+}
@@ -0,0 +1,486 @@
+// Cluster snapshots for offline analysis: the objects of selected kinds, captured once and saved as
+// snapshots/<id>.tar.gz. The archive holds index.json (context, time, scope, counts per kind) and
+// resources/<kind>.json (a JSON array per kind), so it can be read by hand or shared as a file.
+//
+// Secrets keep their metadata and key names but lose their values unless the scope asks for them,
+// including the last-applied annotation that repeats them. managedFields are dropped everywhere.
+//
+// An archive opened with `open_cluster_snapshot` is held in memory and served by
+// `list_snapshot_resources`, which is what the frontend's offline cluster mode reads from.
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::autoscaling::v2::HorizontalPodAutoscaler;
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, Event as CoreEvent, Namespace, Node, PersistentVolume, PersistentVolumeClaim, Pod,
+    Secret, Service, ServiceAccount,
+};
+use k8s_openapi::api::networking::v1::{Ingress, NetworkPolicy};
+use k8s_openapi::api::rbac::v1::{ClusterRole, ClusterRoleBinding, Role, RoleBinding};
+use k8s_openapi::api::storage::v1::StorageClass;
+use kube::api::{ApiResource, DynamicObject, ListParams};
+use kube::{Api, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::command;
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+
+const PAGE_SIZE: u32 = 500;
+const LAST_APPLIED_ANNOTATION: &str = "kubectl.kubernetes.io/last-applied-configuration";
+
+/// Captured when the scope names no kinds.
+const DEFAULT_KINDS: [&str; 24] = [
+    "namespaces",
+    "nodes",
+    "pods",
+    "deployments",
+    "replicasets",
+    "statefulsets",
+    "daemonsets",
+    "jobs",
+    "cronjobs",
+    "services",
+    "ingresses",
+    "networkpolicies",
+    "configmaps",
+    "secrets",
+    "serviceaccounts",
+    "persistentvolumeclaims",
+    "persistentvolumes",
+    "storageclasses",
+    "horizontalpodautoscalers",
+    "roles",
+    "rolebindings",
+    "clusterroles",
+    "clusterrolebindings",
+    "events",
+];
+
+static OPEN_SNAPSHOT: Mutex<Option<OpenSnapshot>> = Mutex::const_new(None);
+
+struct OpenSnapshot {
+    path: PathBuf,
+    index: SnapshotIndex,
+    resources: BTreeMap<String, Vec<Value>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotScope {
+    /// Plural lowercase kinds; the default set when empty.
+    #[serde(default)]
+    pub kinds: Vec<String>,
+    /// Namespaces to capture namespaced kinds from; all when empty.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Keep Secret values. Off by default.
+    #[serde(default)]
+    pub include_secret_data: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotKind {
+    pub kind: String,
+    pub count: usize,
+    /// Listing failed (usually RBAC); the kind is missing from the archive.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotIndex {
+    pub id: String,
+    pub context: String,
+    pub server_version: Option<String>,
+    pub captured_at: u64,
+    pub scope: SnapshotScope,
+    pub kinds: Vec<SnapshotKind>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotSummary {
+    pub path: String,
+    pub bytes: u64,
+    pub index: SnapshotIndex,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// API resource and scope for a kind a snapshot can capture.
+fn resource_for(kind: &str) -> Option<(ApiResource, bool)> {
+    let resource = match kind {
+        "pods" => (ApiResource::erase::<Pod>(&()), true),
+        "deployments" => (ApiResource::erase::<Deployment>(&()), true),
+        "replicasets" => (ApiResource::erase::<ReplicaSet>(&()), true),
+        "statefulsets" => (ApiResource::erase::<StatefulSet>(&()), true),
+        "daemonsets" => (ApiResource::erase::<DaemonSet>(&()), true),
+        "jobs" => (ApiResource::erase::<Job>(&()), true),
+        "cronjobs" => (ApiResource::erase::<CronJob>(&()), true),
+        "services" => (ApiResource::erase::<Service>(&()), true),
+        "ingresses" => (ApiResource::erase::<Ingress>(&()), true),
+        "networkpolicies" => (ApiResource::erase::<NetworkPolicy>(&()), true),
+        "configmaps" => (ApiResource::erase::<ConfigMap>(&()), true),
+        "secrets" => (ApiResource::erase::<Secret>(&()), true),
+        "serviceaccounts" => (ApiResource::erase::<ServiceAccount>(&()), true),
+        "persistentvolumeclaims" => (ApiResource::erase::<PersistentVolumeClaim>(&()), true),
+        "horizontalpodautoscalers" => (ApiResource::erase::<HorizontalPodAutoscaler>(&()), true),
+        "roles" => (ApiResource::erase::<Role>(&()), true),
+        "rolebindings" => (ApiResource::erase::<RoleBinding>(&()), true),
+        "events" => (ApiResource::erase::<CoreEvent>(&()), true),
+        "namespaces" => (ApiResource::erase::<Namespace>(&()), false),
+        "nodes" => (ApiResource::erase::<Node>(&()), false),
+        "persistentvolumes" => (ApiResource::erase::<PersistentVolume>(&()), false),
+        "storageclasses" => (ApiResource::erase::<StorageClass>(&()), false),
+        "clusterroles" => (ApiResource::erase::<ClusterRole>(&()), false),
+        "clusterrolebindings" => (ApiResource::erase::<ClusterRoleBinding>(&()), false),
+        _ => return None,
+    };
+    Some(resource)
+}
+
+async fn get_snapshots_dir() -> Result<PathBuf, String> {
+    let dir = PathBuf::from(get_app_data_dir().await?).join("snapshots");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create snapshots directory: {}", e))?;
+    Ok(dir)
+}
+
+/// Object as stored: apiVersion/kind filled in, managedFields dropped, Secret values removed
+/// unless `keep_secret_data`.
+fn sanitize(
+    mut object: DynamicObject,
+    resource: &ApiResource,
+    keep_secret_data: bool,
+) -> Option<Value> {
+    object.metadata.managed_fields = None;
+    let is_secret = resource.kind == "Secret" && resource.group.is_empty();
+    if is_secret && !keep_secret_data {
+        if let Some(annotations) = object.metadata.annotations.as_mut() {
+            annotations.remove(LAST_APPLIED_ANNOTATION);
+        }
+    }
+    let mut value = serde_json::to_value(&object).ok()?;
+    let map = value.as_object_mut()?;
+    map.insert(
+        "apiVersion".to_string(),
+        Value::String(resource.api_version.clone()),
+    );
+    map.insert("kind".to_string(), Value::String(resource.kind.clone()));
+    if is_secret && !keep_secret_data {
+        for field in ["data", "stringData"] {
+            if let Some(Value::Object(entries)) = map.get_mut(field) {
+                for entry in entries.values_mut() {
+                    *entry = Value::String(String::new());
+                }
+            }
+        }
+    }
+    Some(value)
+}
+
+async fn list_all(api: Api<DynamicObject>) -> Result<Vec<DynamicObject>, kube::Error> {
+    let mut items = Vec::new();
+    let mut params = ListParams::default().limit(PAGE_SIZE);
+    loop {
+        let page = api.list(&params).await?;
+        items.extend(page.items);
+        match page.metadata.continue_.filter(|t| !t.is_empty()) {
+            Some(token) => params = params.continue_token(&token),
+            None => return Ok(items),
+        }
+    }
+}
+
+async fn capture_kind(
+    client: &Client,
+    kind: &str,
+    scope: &SnapshotScope,
+) -> Result<Vec<Value>, String> {
+    let (resource, namespaced) =
+        resource_for(kind).ok_or_else(|| format!("Unsupported kind: {}", kind))?;
+    let apis: Vec<Api<DynamicObject>> = if namespaced && !scope.namespaces.is_empty() {
+        scope
+            .namespaces
+            .iter()
+            .map(|ns| Api::namespaced_with(client.clone(), ns, &resource))
+            .collect()
+    } else {
+        vec![Api::all_with(client.clone(), &resource)]
+    };
+
+    let mut values = Vec::new();
+    for api in apis {
+        let objects = list_all(api).await.map_err(|e| e.to_string())?;
+        values.extend(
+            objects
+                .into_iter()
+                .filter_map(|o| sanitize(o, &resource, scope.include_secret_data)),
+        );
+    }
+    Ok(values)
+}
+
+fn append_json(
+    archive: &mut tar::Builder<GzEncoder<std::fs::File>>,
+    name: &str,
+    value: &impl Serialize,
+    mtime: u64,
+) -> Result<(), String> {
+    let data = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    archive
+        .append_data(&mut header, name, data.as_slice())
+        .map_err(|e| format!("Failed to write {} to the snapshot: {}", name, e))
+}
+
+fn write_archive(
+    path: &Path,
+    index: &SnapshotIndex,
+    resources: &BTreeMap<String, Vec<Value>>,
+) -> Result<(), String> {
+    let file = std::fs::File::create(path)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    append_json(&mut archive, "index.json", index, index.captured_at)?;
+    for (kind, items) in resources {
+        append_json(
+            &mut archive,
+            &format!("resources/{}.json", kind),
+            items,
+            index.captured_at,
+        )?;
+    }
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to write the snapshot: {}", e))?;
+    Ok(())
+}
+
+/// Index and resources of an archive. Only `index.json` is read when `index_only`.
+fn read_archive(
+    path: &Path,
+    index_only: bool,
+) -> Result<(SnapshotIndex, BTreeMap<String, Vec<Value>>), String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut index = None;
+    let mut resources = BTreeMap::new();
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Not a snapshot archive: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Corrupt snapshot archive: {}", e))?;
+        let name = entry
+            .path()
+            .map_err(|e| format!("Corrupt snapshot archive: {}", e))?
+            .to_string_lossy()
+            .to_string();
+        let mut content = Vec::new();
+        if name == "index.json" {
+            entry
+                .read_to_end(&mut content)
+                .map_err(|e| format!("Corrupt snapshot archive: {}", e))?;
+            index = Some(
+                serde_json::from_slice::<SnapshotIndex>(&content)
+                    .map_err(|e| format!("Invalid snapshot index: {}", e))?,
+            );
+            if index_only {
+                break;
+            }
+        } else if let Some(kind) = name
+            .strip_prefix("resources/")
+            .and_then(|n| n.strip_suffix(".json"))
+        {
+            if index_only {
+                continue;
+            }
+            entry
+                .read_to_end(&mut content)
+                .map_err(|e| format!("Corrupt snapshot archive: {}", e))?;
+            let items: Vec<Value> = serde_json::from_slice(&content)
+                .map_err(|e| format!("Invalid snapshot data for {}: {}", kind, e))?;
+            resources.insert(kind.to_string(), items);
+        }
+    }
+    let index = index.ok_or("Not a snapshot archive: index.json is missing")?;
+    Ok((index, resources))
+}
+
+/// Capture the objects of `scope` from a context into a new snapshot archive. Kinds that can't be
+/// listed are recorded in the index rather than failing the capture.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn capture_cluster_snapshot(
+    context: String,
+    scope: Option<SnapshotScope>,
+) -> Result<SnapshotSummary, String> {
+    let mut scope = scope.unwrap_or_default();
+    if scope.kinds.is_empty() {
+        scope.kinds = DEFAULT_KINDS.iter().map(|k| k.to_string()).collect();
+    }
+    scope.kinds.sort();
+    scope.kinds.dedup();
+    if let Some(unknown) = scope.kinds.iter().find(|k| resource_for(k).is_none()) {
+        return Err(format!("Unsupported kind: {}", unknown));
+    }
+
+    let client = crate::k8s::client_for(&context).await?;
+    let server_version = client.apiserver_version().await.ok().map(|v| v.git_version);
+
+    let mut kinds = Vec::with_capacity(scope.kinds.len());
+    let mut resources = BTreeMap::new();
+    for kind in &scope.kinds {
+        match capture_kind(&client, kind, &scope).await {
+            Ok(items) => {
+                kinds.push(SnapshotKind {
+                    kind: kind.clone(),
+                    count: items.len(),
+                    error: None,
+                });
+                resources.insert(kind.clone(), items);
+            }
+            Err(e) => kinds.push(SnapshotKind {
+                kind: kind.clone(),
+                count: 0,
+                error: Some(e),
+            }),
+        }
+    }
+
+    let index = SnapshotIndex {
+        id: format!("{:016x}", rand::random::<u64>()),
+        context,
+        server_version,
+        captured_at: now_secs(),
+        scope,
+        kinds,
+    };
+    let path = get_snapshots_dir()
+        .await?
+        .join(format!("{}.tar.gz", index.id));
+    let index = tokio::task::spawn_blocking({
+        let path = path.clone();
+        move || write_archive(&path, &index, &resources).map(|_| index)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    Ok(SnapshotSummary {
+        path: path.to_string_lossy().to_string(),
+        bytes,
+        index,
+    })
+}
+
+/// Snapshots in the app's snapshot directory, newest first.
+#[command]
+pub async fn list_cluster_snapshots() -> Result<Vec<SnapshotSummary>, String> {
+    let dir = get_snapshots_dir().await?;
+    tokio::task::spawn_blocking(move || {
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to read snapshots directory: {}", e))?;
+        let mut snapshots: Vec<SnapshotSummary> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.to_string_lossy().ends_with(".tar.gz"))
+            .filter_map(|path| {
+                let (index, _) = read_archive(&path, true).ok()?;
+                Some(SnapshotSummary {
+                    bytes: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+                    path: path.to_string_lossy().to_string(),
+                    index,
+                })
+            })
+            .collect();
+        snapshots.sort_by(|a, b| b.index.captured_at.cmp(&a.index.captured_at));
+        Ok(snapshots)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[command]
+pub async fn delete_cluster_snapshot(path: String) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    if path.parent() != Some(get_snapshots_dir().await?.as_path()) {
+        return Err("Not a snapshot in the app's snapshot directory".to_string());
+    }
+    let mut open = OPEN_SNAPSHOT.lock().await;
+    if open.as_ref().is_some_and(|s| s.path == path) {
+        *open = None;
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to delete snapshot: {}", e))
+}
+
+/// Load a snapshot archive (one from the list, or a file shared by someone else) for offline
+/// browsing. Replaces any snapshot already open.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn open_cluster_snapshot(path: String) -> Result<SnapshotIndex, String> {
+    let path = PathBuf::from(path);
+    let (index, resources) = tokio::task::spawn_blocking({
+        let path = path.clone();
+        move || read_archive(&path, false)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    *OPEN_SNAPSHOT.lock().await = Some(OpenSnapshot {
+        path,
+        index: index.clone(),
+        resources,
+    });
+    Ok(index)
+}
+
+#[command]
+pub async fn close_cluster_snapshot() -> Result<(), String> {
+    *OPEN_SNAPSHOT.lock().await = None;
+    Ok(())
+}
+
+/// Index of the open snapshot, if any.
+#[command]
+pub async fn get_open_snapshot() -> Result<Option<SnapshotIndex>, String> {
+    Ok(OPEN_SNAPSHOT.lock().await.as_ref().map(|s| s.index.clone()))
+}
+
+/// Objects of a kind in the open snapshot, optionally limited to one namespace.
+#[command]
+pub async fn list_snapshot_resources(
+    kind: String,
+    namespace: Option<String>,
+) -> Result<Vec<Value>, String> {
+    let open = OPEN_SNAPSHOT.lock().await;
+    let snapshot = open.as_ref().ok_or("No snapshot is open")?;
+    let items = snapshot
+        .resources
+        .get(&kind)
+        .ok_or_else(|| format!("The snapshot has no {}", kind))?;
+    Ok(items
+        .iter()
+        .filter(|item| {
+            namespace.as_deref().is_none_or(|ns| {
+                item.pointer("/metadata/namespace").and_then(|v| v.as_str()) == Some(ns)
+            })
+        })
+        .cloned()
+        .collect())
+}
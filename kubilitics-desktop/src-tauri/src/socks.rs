@@ -0,0 +1,268 @@
+// Per-context SOCKS5 proxies, typically an `ssh -D` dynamic forward through a jump host. The
+// shell's own probes of a context's API server tunnel through it, and the backend gets the whole
+// map in KUBILITICS_CONTEXT_PROXIES, which its cluster client factory applies as the context's
+// proxy (read at startup, so changes take effect on the next backend restart).
+//
+// socks5:// resolves the API server name locally; socks5h:// hands the name to the proxy, which is
+// what a jump host in front of internal DNS needs.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, Url};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::commands::get_app_data_dir;
+
+const HANDSHAKE_TIMEOUT_SECS: u64 = 5;
+const SOCKS_VERSION: u8 = 0x05;
+const AUTH_NONE: u8 = 0x00;
+const AUTH_USERNAME_PASSWORD: u8 = 0x02;
+const CMD_CONNECT: u8 = 0x01;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextProxy {
+    pub context: String,
+    /// socks5://[user:pass@]host:port or socks5h://…
+    pub proxy_url: String,
+}
+
+async fn get_context_proxies_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("context_proxies.json"))
+}
+
+pub async fn load_context_proxies() -> Result<Vec<ContextProxy>, String> {
+    let path = get_context_proxies_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read context proxies".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse context proxies".to_string())
+}
+
+async fn save_context_proxies(proxies: &[ContextProxy]) -> Result<(), String> {
+    let path = get_context_proxies_path().await?;
+
+    let content = serde_json::to_string_pretty(proxies)
+        .map_err(|_| "Failed to serialize context proxies".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write context proxies".to_string())
+}
+
+/// The SOCKS proxy configured for a context, if any.
+pub async fn context_proxy(context: &str) -> Option<Url> {
+    load_context_proxies()
+        .await
+        .ok()?
+        .into_iter()
+        .find(|p| p.context == context)
+        .and_then(|p| Url::parse(&p.proxy_url).ok())
+}
+
+fn validate_proxy_url(proxy_url: &str) -> Result<(), String> {
+    let url = Url::parse(proxy_url).map_err(|e| format!("Invalid SOCKS proxy: {}", e))?;
+    if !matches!(url.scheme(), "socks5" | "socks5h") {
+        return Err("SOCKS proxy must use socks5:// or socks5h://".to_string());
+    }
+    if url.host_str().is_none() || url.port().is_none() {
+        return Err("SOCKS proxy needs a host and port".to_string());
+    }
+    Ok(())
+}
+
+/// Environment for the backend sidecar: context name → proxy URL as a JSON object. Unset when no
+/// context has a proxy.
+pub async fn sidecar_env() -> Vec<(String, String)> {
+    let proxies = load_context_proxies().await.unwrap_or_default();
+    if proxies.is_empty() {
+        return Vec::new();
+    }
+    let map: BTreeMap<String, String> = proxies
+        .into_iter()
+        .map(|p| (p.context, p.proxy_url))
+        .collect();
+    match serde_json::to_string(&map) {
+        Ok(json) => vec![("KUBILITICS_CONTEXT_PROXIES".to_string(), json)],
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn read_reply_address(stream: &mut TcpStream, atyp: u8) -> std::io::Result<()> {
+    let len = match atyp {
+        ATYP_IPV4 => 4,
+        ATYP_IPV6 => 16,
+        ATYP_DOMAIN => stream.read_u8().await? as usize,
+        _ => return Err(std::io::Error::other("SOCKS proxy sent an unknown address type")),
+    };
+    // Bound address and port; not needed for a client-side CONNECT
+    let mut skip = vec![0u8; len + 2];
+    stream.read_exact(&mut skip).await?;
+    Ok(())
+}
+
+async fn handshake(stream: &mut TcpStream, proxy: &Url, host: &str, port: u16) -> std::io::Result<()> {
+    let username = percent_decode(proxy.username());
+    let password = proxy.password().map(percent_decode).unwrap_or_default();
+    let use_auth = !username.is_empty();
+
+    let methods: &[u8] = if use_auth { &[AUTH_NONE, AUTH_USERNAME_PASSWORD] } else { &[AUTH_NONE] };
+    let mut greeting = vec![SOCKS_VERSION, methods.len() as u8];
+    greeting.extend_from_slice(methods);
+    stream.write_all(&greeting).await?;
+
+    let mut choice = [0u8; 2];
+    stream.read_exact(&mut choice).await?;
+    match choice[1] {
+        AUTH_NONE => {}
+        AUTH_USERNAME_PASSWORD if use_auth => {
+            // RFC 1929 sub-negotiation
+            let mut auth = vec![0x01, username.len() as u8];
+            auth.extend_from_slice(username.as_bytes());
+            auth.push(password.len() as u8);
+            auth.extend_from_slice(password.as_bytes());
+            stream.write_all(&auth).await?;
+            let mut status = [0u8; 2];
+            stream.read_exact(&mut status).await?;
+            if status[1] != 0 {
+                return Err(std::io::Error::other("SOCKS proxy rejected the credentials"));
+            }
+        }
+        _ => return Err(std::io::Error::other("SOCKS proxy offered no usable authentication method")),
+    }
+
+    let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0x00];
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => {
+            request.push(ATYP_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(std::net::IpAddr::V6(ip)) => {
+            request.push(ATYP_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            request.push(ATYP_DOMAIN);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[1] != 0 {
+        return Err(std::io::Error::other(format!("SOCKS proxy refused the connection (code {})", reply[1])));
+    }
+    read_reply_address(stream, reply[3]).await
+}
+
+/// Userinfo in a URL is percent-encoded; SOCKS wants the raw bytes.
+pub(crate) fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Open a TCP connection to `host:port` through a SOCKS5 proxy. With socks5:// the name is resolved
+/// here first; with socks5h:// the proxy resolves it.
+pub async fn connect(proxy: &Url, host: &str, port: u16) -> Result<TcpStream, String> {
+    let proxy_host = proxy
+        .host_str()
+        .ok_or_else(|| "SOCKS proxy has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let proxy_port = proxy.port().ok_or_else(|| "SOCKS proxy has no port".to_string())?;
+
+    let target_host = if proxy.scheme() == "socks5" && host.parse::<std::net::IpAddr>().is_err() {
+        tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+            .next()
+            .map(|addr| addr.ip().to_string())
+            .ok_or_else(|| format!("Failed to resolve {}", host))?
+    } else {
+        host.to_string()
+    };
+
+    tokio::time::timeout(Duration::from_secs(HANDSHAKE_TIMEOUT_SECS), async {
+        let mut stream = TcpStream::connect((proxy_host.as_str(), proxy_port))
+            .await
+            .map_err(|e| format!("Failed to reach SOCKS proxy: {}", e))?;
+        handshake(&mut stream, proxy, &target_host, port)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok::<_, String>(stream)
+    })
+    .await
+    .map_err(|_| "SOCKS proxy timed out".to_string())?
+}
+
+#[command]
+pub async fn get_context_proxies() -> Result<Vec<ContextProxy>, String> {
+    load_context_proxies().await
+}
+
+/// Set (or with `proxy_url: None`, clear) a context's SOCKS5 proxy. The backend picks it up on its
+/// next start (`restart_sidecar`); the shell's probes use it immediately.
+#[command]
+pub async fn set_context_proxy(context: String, proxy_url: Option<String>) -> Result<(), String> {
+    let proxy_url = proxy_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    if let Some(url) = &proxy_url {
+        validate_proxy_url(url)?;
+    }
+
+    let mut proxies = load_context_proxies().await?;
+    proxies.retain(|p| p.context != context);
+    if let Some(proxy_url) = proxy_url {
+        proxies.push(ContextProxy { context, proxy_url });
+    }
+    save_context_proxies(&proxies).await
+}
+
+/// Check that a context's SOCKS proxy can reach its API server; returns the tunnel setup time in ms.
+#[command]
+pub async fn test_context_proxy(context: String) -> Result<f64, String> {
+    let proxy = context_proxy(&context)
+        .await
+        .ok_or_else(|| format!("Context '{}' has no SOCKS proxy", context))?;
+    let server_url = crate::commands::get_context_server_url(&context).await?;
+    let url = Url::parse(&server_url).map_err(|e| format!("Invalid server URL: {}", e))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| "Server URL has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let start = std::time::Instant::now();
+    connect(&proxy, &host, port).await?;
+    Ok(start.elapsed().as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,230 @@
+// SSH into cluster nodes. The node's address comes from its status (external IP by default, since
+// that's what a laptop can usually reach); user, key, port and an optional jump host come from the
+// cluster's SSH profile (ssh_profiles.json), and anything unset is left to ~/.ssh/config. The
+// session opens in the user's terminal so host key prompts and agent forwarding behave as usual.
+use std::path::PathBuf;
+
+use k8s_openapi::api::core::v1::Node;
+use kube::Api;
+use serde::{Deserialize, Serialize};
+use tauri::command;
+use tokio::fs;
+
+use crate::commands::get_app_data_dir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeAddressType {
+    #[default]
+    External,
+    Internal,
+    Hostname,
+}
+
+impl NodeAddressType {
+    fn as_k8s(self) -> &'static str {
+        match self {
+            Self::External => "ExternalIP",
+            Self::Internal => "InternalIP",
+            Self::Hostname => "Hostname",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SshProfile {
+    pub user: Option<String>,
+    /// Private key; `~/` is expanded.
+    pub identity_file: Option<String>,
+    pub port: Option<u16>,
+    /// Preferred address; the others are tried in order when the node doesn't have it.
+    pub address_type: NodeAddressType,
+    /// `[user@]host[:port]`, passed to `ssh -J`.
+    pub jump_host: Option<String>,
+    pub extra_args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextSshProfile {
+    pub context: String,
+    #[serde(flatten)]
+    pub profile: SshProfile,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SshTarget {
+    pub node: String,
+    pub address: String,
+    pub address_type: String,
+    /// Program and arguments, ready to run.
+    pub command: Vec<String>,
+}
+
+async fn get_ssh_profiles_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("ssh_profiles.json"))
+}
+
+async fn load_ssh_profiles() -> Result<Vec<ContextSshProfile>, String> {
+    let path = get_ssh_profiles_path().await?;
+
+    if !fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| "Failed to read SSH profiles".to_string())?;
+
+    serde_json::from_str(&content).map_err(|_| "Failed to parse SSH profiles".to_string())
+}
+
+async fn save_ssh_profiles(profiles: &[ContextSshProfile]) -> Result<(), String> {
+    let path = get_ssh_profiles_path().await?;
+
+    let content = serde_json::to_string_pretty(profiles)
+        .map_err(|_| "Failed to serialize SSH profiles".to_string())?;
+
+    fs::write(&path, content)
+        .await
+        .map_err(|_| "Failed to write SSH profiles".to_string())
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn validate(profile: &SshProfile) -> Result<(), String> {
+    if let Some(file) = &profile.identity_file {
+        if !expand_home(file).is_file() {
+            return Err(format!("Identity file not found: {}", file));
+        }
+    }
+    if profile.port == Some(0) {
+        return Err("SSH port must be between 1 and 65535".to_string());
+    }
+    if profile
+        .user
+        .as_deref()
+        .is_some_and(|u| u.contains(['@', ' ']))
+    {
+        return Err("SSH user must not contain '@' or spaces".to_string());
+    }
+    Ok(())
+}
+
+#[command]
+pub async fn get_ssh_profiles() -> Result<Vec<ContextSshProfile>, String> {
+    load_ssh_profiles().await
+}
+
+/// Set (or with `profile: None`, remove) a context's SSH profile.
+#[command]
+pub async fn set_ssh_profile(context: String, profile: Option<SshProfile>) -> Result<(), String> {
+    if let Some(profile) = &profile {
+        validate(profile)?;
+    }
+
+    let mut profiles = load_ssh_profiles().await?;
+    profiles.retain(|p| p.context != context);
+    if let Some(profile) = profile {
+        profiles.push(ContextSshProfile { context, profile });
+    }
+    save_ssh_profiles(&profiles).await
+}
+
+/// Resolve a node's address and build the ssh command for it.
+async fn ssh_target(context: &str, node_name: &str) -> Result<SshTarget, String> {
+    let profile = load_ssh_profiles()
+        .await?
+        .into_iter()
+        .find(|p| p.context == context)
+        .map(|p| p.profile)
+        .unwrap_or_default();
+
+    let client = crate::k8s::client_for(context).await?;
+    let node = Api::<Node>::all(client)
+        .get(node_name)
+        .await
+        .map_err(|e| format!("Failed to get node {}: {}", node_name, e))?;
+    let addresses = node.status.and_then(|s| s.addresses).unwrap_or_default();
+
+    let mut preference = vec![profile.address_type];
+    preference.extend(
+        [
+            NodeAddressType::External,
+            NodeAddressType::Internal,
+            NodeAddressType::Hostname,
+        ]
+        .into_iter()
+        .filter(|t| *t != profile.address_type),
+    );
+    let (address_type, address) = preference
+        .iter()
+        .find_map(|t| {
+            addresses
+                .iter()
+                .find(|a| a.type_ == t.as_k8s() && !a.address.is_empty())
+                .map(|a| (a.type_.clone(), a.address.clone()))
+        })
+        .ok_or_else(|| format!("Node {} reports no address", node_name))?;
+    // The address comes from the cluster; anything but a host name or IP literal could be read as
+    // an ssh option ("-oProxyCommand=…") or by a shell
+    if address.starts_with('-')
+        || !address
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ".-:%[]".contains(c))
+    {
+        return Err(format!("Node {} reports an invalid address: {}", node_name, address));
+    }
+
+    let ssh = crate::tools::find_binary("ssh")
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "ssh".to_string());
+    let mut command = vec![ssh];
+    if let Some(port) = profile.port {
+        command.extend(["-p".to_string(), port.to_string()]);
+    }
+    if let Some(file) = &profile.identity_file {
+        command.extend([
+            "-i".to_string(),
+            expand_home(file).to_string_lossy().to_string(),
+        ]);
+    }
+    if let Some(jump) = profile.jump_host.filter(|j| !j.trim().is_empty()) {
+        command.extend(["-J".to_string(), jump]);
+    }
+    command.extend(profile.extra_args);
+    // Nothing after this is parsed as an option
+    command.push("--".to_string());
+    command.push(match profile.user.filter(|u| !u.is_empty()) {
+        Some(user) => format!("{}@{}", user, address),
+        None => address.clone(),
+    });
+
+    Ok(SshTarget {
+        node: node_name.to_string(),
+        address,
+        address_type,
+        command,
+    })
+}
+
+/// The ssh command for a node without running it (for "copy command").
+#[command]
+pub async fn get_node_ssh_command(context: String, node: String) -> Result<SshTarget, String> {
+    ssh_target(&context, &node).await
+}
+
+/// Open an SSH session to a node in the user's terminal.
+#[command]
+#[tracing::instrument(skip_all, err)]
+pub async fn ssh_to_node(context: String, node: String) -> Result<SshTarget, String> {
+    let target = ssh_target(&context, &node).await?;
+    crate::terminal::open_in_terminal(&target.command)?;
+    Ok(target)
+}
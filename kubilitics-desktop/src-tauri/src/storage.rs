@@ -0,0 +1,248 @@
+// What the app keeps on disk, for the Privacy & Storage settings page. Everything lives in the app
+// data directory; each top-level entry is sorted into a category the user can inspect and delete.
+//
+// Databases belong to the running sidecars and can't be removed from under them, so deleting that
+// category is deferred: it is recorded in pending_deletions.json and carried out at the next launch,
+// before the sidecars start.
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::command;
+
+use crate::commands::get_app_data_dir;
+
+const PENDING_DELETIONS_FILE: &str = "pending_deletions.json";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageCategory {
+    /// Preferences: proxy, connectivity, updates, export settings and the like.
+    Settings,
+    /// Selected contexts, the kubeconfig path, the encrypted kubeconfig and its key, and the context
+    /// overlays for external terminals.
+    Kubeconfig,
+    /// Exported files, resource drafts and cluster snapshots.
+    Exports,
+    Logs,
+    /// Analytics queue and consent, crash reports and consent.
+    Telemetry,
+    /// Backend and AI databases (cluster registry, history, AI conversations).
+    Databases,
+    /// Downloaded updates and rollback copies.
+    Updates,
+    Other,
+}
+
+const CATEGORIES: [StorageCategory; 8] = [
+    StorageCategory::Settings,
+    StorageCategory::Kubeconfig,
+    StorageCategory::Exports,
+    StorageCategory::Logs,
+    StorageCategory::Telemetry,
+    StorageCategory::Databases,
+    StorageCategory::Updates,
+    StorageCategory::Other,
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredItem {
+    pub path: String,
+    pub bytes: u64,
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageCategorySummary {
+    pub category: StorageCategory,
+    pub bytes: u64,
+    pub items: Vec<StoredItem>,
+    /// Deletion of this category is waiting for the next launch.
+    pub deletion_pending: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredDataSummary {
+    pub data_dir: String,
+    pub total_bytes: u64,
+    pub categories: Vec<StorageCategorySummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageDeletionResult {
+    pub category: StorageCategory,
+    pub freed_bytes: u64,
+    /// Items that couldn't be removed (e.g. a log file held open on Windows), with the reason.
+    pub failed: Vec<String>,
+    /// True when deletion was scheduled for the next launch instead.
+    pub deferred: bool,
+}
+
+fn categorize(name: &str) -> StorageCategory {
+    match name {
+        "kubeconfig_security.json" | "encryption.key" | "terminal_kubeconfigs" => StorageCategory::Kubeconfig,
+        "exports" | "exports_index.json" | "drafts" | "snapshots" => StorageCategory::Exports,
+        "logs" | "traces" => StorageCategory::Logs,
+        "analytics_settings.json" | "analytics_queue.json" | "crash_reports" | "crash_reporting_settings.json" => {
+            StorageCategory::Telemetry
+        }
+        "ai" => StorageCategory::Databases,
+        "updates" => StorageCategory::Updates,
+        // SQLite keeps -wal / -shm files next to the database
+        n if n.starts_with("kubilitics.db") => StorageCategory::Databases,
+        PENDING_DELETIONS_FILE => StorageCategory::Other,
+        n if n.ends_with(".json") => StorageCategory::Settings,
+        _ => StorageCategory::Other,
+    }
+}
+
+fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else { return 0 };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| size_of(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+fn items_in(data_dir: &Path, category: StorageCategory) -> Vec<(PathBuf, StoredItem)> {
+    let Ok(entries) = std::fs::read_dir(data_dir) else { return Vec::new() };
+    let mut items: Vec<(PathBuf, StoredItem)> = entries
+        .flatten()
+        .filter(|e| categorize(&e.file_name().to_string_lossy()) == category)
+        .map(|e| {
+            let path = e.path();
+            let item = StoredItem {
+                path: path.to_string_lossy().to_string(),
+                bytes: size_of(&path),
+                is_dir: path.is_dir(),
+            };
+            (path, item)
+        })
+        .collect();
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items
+}
+
+fn remove(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+fn delete_category(data_dir: &Path, category: StorageCategory) -> (u64, Vec<String>) {
+    let mut freed = 0;
+    let mut failed = Vec::new();
+    for (path, item) in items_in(data_dir, category) {
+        match remove(&path) {
+            Ok(()) => freed += item.bytes,
+            Err(e) => failed.push(format!("{}: {}", item.path, e)),
+        }
+    }
+    (freed, failed)
+}
+
+async fn load_pending_deletions(data_dir: &Path) -> Vec<StorageCategory> {
+    std::fs::read_to_string(data_dir.join(PENDING_DELETIONS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+async fn save_pending_deletions(data_dir: &Path, pending: &[StorageCategory]) -> Result<(), String> {
+    let path = data_dir.join(PENDING_DELETIONS_FILE);
+    if pending.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|_| "Failed to write pending deletions".to_string())?;
+        }
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(pending)
+        .map_err(|_| "Failed to serialize pending deletions".to_string())?;
+    std::fs::write(&path, content).map_err(|_| "Failed to write pending deletions".to_string())
+}
+
+/// Carry out deletions scheduled by the previous session. Runs in setup, before the sidecars start.
+pub async fn apply_pending_deletions() {
+    let Ok(data_dir) = get_app_data_dir().await.map(PathBuf::from) else { return };
+    let pending = load_pending_deletions(&data_dir).await;
+    for category in &pending {
+        let (freed, failed) = delete_category(&data_dir, *category);
+        tracing::info!(?category, freed_bytes = freed, "Deleted stored data scheduled at last run");
+        for failure in failed {
+            tracing::warn!(?category, "Failed to delete stored data: {}", failure);
+        }
+    }
+    if !pending.is_empty() {
+        let _ = save_pending_deletions(&data_dir, &[]).await;
+    }
+}
+
+/// Everything under the app data directory, by category, with sizes and paths.
+#[command]
+pub async fn get_stored_data_summary() -> Result<StoredDataSummary, String> {
+    let data_dir = PathBuf::from(get_app_data_dir().await?);
+    let pending = load_pending_deletions(&data_dir).await;
+
+    let dir = data_dir.clone();
+    let categories = tokio::task::spawn_blocking(move || {
+        CATEGORIES
+            .iter()
+            .map(|&category| {
+                let items: Vec<StoredItem> = items_in(&dir, category).into_iter().map(|(_, item)| item).collect();
+                StorageCategorySummary {
+                    category,
+                    bytes: items.iter().map(|i| i.bytes).sum(),
+                    items,
+                    deletion_pending: pending.contains(&category),
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Failed to scan stored data: {}", e))?;
+
+    Ok(StoredDataSummary {
+        data_dir: data_dir.to_string_lossy().to_string(),
+        total_bytes: categories.iter().map(|c| c.bytes).sum(),
+        categories,
+    })
+}
+
+/// Delete one category. Databases are deleted at the next launch; everything else immediately.
+/// Deleting Kubeconfig or Settings returns the app to its first-launch state for those screens.
+#[command]
+pub async fn delete_stored_data(category: StorageCategory) -> Result<StorageDeletionResult, String> {
+    let data_dir = PathBuf::from(get_app_data_dir().await?);
+
+    if category == StorageCategory::Databases {
+        let mut pending = load_pending_deletions(&data_dir).await;
+        if !pending.contains(&category) {
+            pending.push(category);
+        }
+        save_pending_deletions(&data_dir, &pending).await?;
+        return Ok(StorageDeletionResult {
+            category,
+            freed_bytes: 0,
+            failed: Vec::new(),
+            deferred: true,
+        });
+    }
+
+    // The analytics queue is cached in memory and would be written back on the next event
+    if category == StorageCategory::Telemetry {
+        crate::analytics::purge_analytics_data().await?;
+    }
+
+    let (freed_bytes, failed) = tokio::task::spawn_blocking(move || delete_category(&data_dir, category))
+        .await
+        .map_err(|e| format!("Failed to delete stored data: {}", e))?;
+    Ok(StorageDeletionResult {
+        category,
+        freed_bytes,
+        failed,
+        deferred: false,
+    })
+}
@@ -0,0 +1,390 @@
+// Long-lived connections (resource watch WebSockets, log follows) owned by the shell instead of the
+// WebView, so they survive page reloads, get a real keepalive, and come back after a backend
+// restart or a network blip.
+//
+// WebSockets are pinged every KEEPALIVE_INTERVAL and dropped when nothing (pong or data) arrives
+// within STALE_AFTER; HTTP follows rely on TCP keepalive. A dropped stream reconnects with
+// exponential backoff. When the caller names a resume parameter and where the token sits in each
+// JSON message (e.g. `resourceVersion` and `/object/metadata/resourceVersion`), the reconnect
+// carries the last token so the server continues where the stream left off.
+//
+// wss:// runs over rustls with the bundled webpki root certificates.
+//
+// Data goes out as `stream-message` ({ id, data }), state changes as `stream-status`.
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Url};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::backend_ports::BACKEND_PORT;
+
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const STALE_AFTER: Duration = Duration::from_secs(45);
+const INITIAL_BACKOFF_MS: u64 = 500;
+const MAX_BACKOFF_MS: u64 = 30_000;
+const DEFAULT_MAX_RECONNECTS: u32 = 20;
+
+struct ManagedStream {
+    status: StreamStatus,
+    task: tauri::async_runtime::JoinHandle<()>,
+    outgoing: mpsc::UnboundedSender<String>,
+}
+
+static STREAMS: Mutex<BTreeMap<String, ManagedStream>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamKind {
+    WebSocket,
+    /// Chunked HTTP response read until the server ends it, e.g. `logs?follow=true`.
+    HttpFollow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamState {
+    Connecting,
+    Open,
+    Reconnecting,
+    /// Ended by the server (HTTP follow) or by `close_stream`.
+    Closed,
+    /// Gave up after the reconnect limit.
+    Failed,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StreamOptions {
+    pub kind: StreamKind,
+    /// Absolute URL, or a path on the local backend ("/ws/resources?…").
+    pub url: String,
+    /// Query parameter that carries the resume token on reconnect.
+    #[serde(default)]
+    pub resume_param: Option<String>,
+    /// JSON pointer to the resume token inside each message.
+    #[serde(default)]
+    pub resume_pointer: Option<String>,
+    #[serde(default)]
+    pub max_reconnects: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamStatus {
+    pub id: String,
+    pub kind: StreamKind,
+    /// URL without the query string (which may hold a token).
+    pub target: String,
+    pub state: StreamState,
+    pub reconnects: u32,
+    pub messages: u64,
+    pub bytes: u64,
+    pub opened_at: u64,
+    pub last_message_at: Option<u64>,
+    pub resume_token: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StreamMessage<'a> {
+    id: &'a str,
+    data: &'a str,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn resolve_url(options: &StreamOptions) -> Result<Url, String> {
+    let url = if options.url.starts_with('/') {
+        let scheme = match options.kind {
+            StreamKind::WebSocket => "ws",
+            StreamKind::HttpFollow => "http",
+        };
+        format!("{}://{}{}", scheme, crate::loopback::socket_addr(BACKEND_PORT).await, options.url)
+    } else {
+        options.url.clone()
+    };
+    let url = Url::parse(&url).map_err(|e| format!("Invalid stream URL: {}", e))?;
+    let schemes: &[&str] = match options.kind {
+        StreamKind::WebSocket => &["ws", "wss"],
+        StreamKind::HttpFollow => &["http", "https"],
+    };
+    if !schemes.contains(&url.scheme()) {
+        return Err(format!("Stream URL must use one of: {}", schemes.join(", ")));
+    }
+    crate::airgap::ensure_host_allowed(&url).await?;
+    Ok(url)
+}
+
+fn with_resume_token(url: &Url, param: Option<&str>, token: Option<&str>) -> Url {
+    let (Some(param), Some(token)) = (param, token) else {
+        return url.clone();
+    };
+    let mut url = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| name != param)
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs).append_pair(param, token);
+    url
+}
+
+async fn update_status(app: &AppHandle, id: &str, update: impl FnOnce(&mut StreamStatus)) {
+    let mut streams = STREAMS.lock().await;
+    if let Some(stream) = streams.get_mut(id) {
+        update(&mut stream.status);
+        let _ = app.emit("stream-status", &stream.status);
+    }
+}
+
+async fn set_state(app: &AppHandle, id: &str, state: StreamState, error: Option<String>) {
+    update_status(app, id, |status| {
+        status.state = state;
+        status.error = error;
+    })
+    .await;
+}
+
+/// Forward one message and note its resume token. Counters are updated without an event.
+async fn deliver(app: &AppHandle, id: &str, data: &str, resume_pointer: Option<&str>) {
+    let _ = app.emit("stream-message", StreamMessage { id, data });
+
+    let token = resume_pointer.and_then(|pointer| {
+        let value: serde_json::Value = serde_json::from_str(data).ok()?;
+        match value.pointer(pointer)? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    });
+    if let Some(stream) = STREAMS.lock().await.get_mut(id) {
+        stream.status.messages += 1;
+        stream.status.bytes += data.len() as u64;
+        stream.status.last_message_at = Some(now_secs());
+        if token.is_some() {
+            stream.status.resume_token = token;
+        }
+    }
+}
+
+/// One WebSocket session. `Ok` only when the caller side (outgoing channel) went away.
+async fn run_websocket(
+    app: &AppHandle,
+    id: &str,
+    url: &Url,
+    options: &StreamOptions,
+    outgoing: &mut mpsc::UnboundedReceiver<String>,
+) -> Result<(), String> {
+    // Checked again per session: air-gapped mode may have been switched on since the stream opened
+    crate::airgap::ensure_host_allowed(url).await?;
+    let (ws, _) = tokio::time::timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS), async {
+        let stream = crate::proxy::connect(url).await?;
+        tokio_tungstenite::client_async_tls(url.as_str(), stream)
+            .await
+            .map_err(|e| format!("Failed to connect: {}", e))
+    })
+    .await
+    .map_err(|_| "Connection timed out".to_string())??;
+    set_state(app, id, StreamState::Open, None).await;
+
+    let (mut sink, mut source) = ws.split();
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    keepalive.tick().await;
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            message = source.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    last_seen = Instant::now();
+                    deliver(app, id, &text, options.resume_pointer.as_deref()).await;
+                }
+                Some(Ok(Message::Binary(data))) => {
+                    last_seen = Instant::now();
+                    deliver(app, id, &String::from_utf8_lossy(&data), options.resume_pointer.as_deref()).await;
+                }
+                // tungstenite answers pings itself
+                Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => last_seen = Instant::now(),
+                Some(Ok(Message::Close(_))) | None => return Err("Server closed the connection".to_string()),
+                Some(Err(e)) => return Err(format!("Connection lost: {}", e)),
+            },
+            _ = keepalive.tick() => {
+                if last_seen.elapsed() > STALE_AFTER {
+                    return Err("No response to keepalive pings".to_string());
+                }
+                sink.send(Message::Ping(Vec::new()))
+                    .await
+                    .map_err(|e| format!("Connection lost: {}", e))?;
+            }
+            text = outgoing.recv() => match text {
+                Some(text) => sink.send(Message::Text(text))
+                    .await
+                    .map_err(|e| format!("Connection lost: {}", e))?,
+                None => {
+                    let _ = sink.send(Message::Close(None)).await;
+                    return Ok(());
+                }
+            },
+        }
+    }
+}
+
+/// One HTTP follow session, delivered line by line. `Ok` when the server ends the response.
+async fn run_http_follow(app: &AppHandle, id: &str, url: &Url, options: &StreamOptions) -> Result<(), String> {
+    // No request timeout: the follow stays open for as long as the server keeps sending
+    let client = crate::http_client::shared(app).await?;
+    let mut response = client
+        .get(url.as_str())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Server returned {}", response.status()));
+    }
+    set_state(app, id, StreamState::Open, None).await;
+
+    // Chunks can split lines (and UTF-8 sequences); only complete lines are delivered
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        match response.chunk().await {
+            Ok(Some(chunk)) => {
+                pending.extend_from_slice(&chunk);
+                if let Some(end) = pending.iter().rposition(|b| *b == b'\n') {
+                    let lines: Vec<u8> = pending.drain(..=end).collect();
+                    for line in String::from_utf8_lossy(&lines).lines() {
+                        deliver(app, id, line, options.resume_pointer.as_deref()).await;
+                    }
+                }
+            }
+            Ok(None) => {
+                if !pending.is_empty() {
+                    deliver(app, id, &String::from_utf8_lossy(&pending), options.resume_pointer.as_deref()).await;
+                }
+                return Ok(());
+            }
+            Err(e) => return Err(format!("Connection lost: {}", e)),
+        }
+    }
+}
+
+async fn run_stream(app: AppHandle, id: String, url: Url, options: StreamOptions, mut outgoing: mpsc::UnboundedReceiver<String>) {
+    let max_reconnects = options.max_reconnects.unwrap_or(DEFAULT_MAX_RECONNECTS);
+    let mut failures: u32 = 0;
+
+    loop {
+        let resume_token = STREAMS
+            .lock()
+            .await
+            .get(&id)
+            .and_then(|s| s.status.resume_token.clone());
+        let session_url = with_resume_token(&url, options.resume_param.as_deref(), resume_token.as_deref());
+        let messages_before = STREAMS.lock().await.get(&id).map(|s| s.status.messages).unwrap_or(0);
+
+        let result = match options.kind {
+            StreamKind::WebSocket => run_websocket(&app, &id, &session_url, &options, &mut outgoing).await,
+            StreamKind::HttpFollow => run_http_follow(&app, &id, &session_url, &options).await,
+        };
+        let error = match result {
+            Ok(()) => {
+                set_state(&app, &id, StreamState::Closed, None).await;
+                break;
+            }
+            Err(e) => e,
+        };
+
+        // A session that delivered data was healthy; start the backoff over
+        let messages_after = STREAMS.lock().await.get(&id).map(|s| s.status.messages).unwrap_or(0);
+        failures = if messages_after > messages_before { 1 } else { failures + 1 };
+        if failures > max_reconnects {
+            set_state(&app, &id, StreamState::Failed, Some(error)).await;
+            break;
+        }
+
+        update_status(&app, &id, |status| {
+            status.state = StreamState::Reconnecting;
+            status.reconnects += 1;
+            status.error = Some(error);
+        })
+        .await;
+        let backoff = (INITIAL_BACKOFF_MS << (failures - 1).min(10)).min(MAX_BACKOFF_MS);
+        tokio::time::sleep(crate::network::polling_interval(Duration::from_millis(backoff))).await;
+    }
+}
+
+/// Open a managed stream and return its id. Messages arrive as `stream-message` events.
+#[command]
+pub async fn open_stream(app_handle: AppHandle, options: StreamOptions) -> Result<String, String> {
+    let url = resolve_url(&options).await?;
+    let id = format!("{:016x}", rand::random::<u64>());
+    let mut target = url.clone();
+    target.set_query(None);
+
+    let status = StreamStatus {
+        id: id.clone(),
+        kind: options.kind,
+        target: target.to_string(),
+        state: StreamState::Connecting,
+        reconnects: 0,
+        messages: 0,
+        bytes: 0,
+        opened_at: now_secs(),
+        last_message_at: None,
+        resume_token: None,
+        error: None,
+    };
+
+    // Hold the lock across the spawn so the task can't look itself up before it's registered
+    let mut streams = STREAMS.lock().await;
+    let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+    let task = tauri::async_runtime::spawn(run_stream(app_handle.clone(), id.clone(), url, options, outgoing_rx));
+    let _ = app_handle.emit("stream-status", &status);
+    streams.insert(id.clone(), ManagedStream { status, task, outgoing });
+    Ok(id)
+}
+
+/// Send a text message on a WebSocket stream.
+#[command]
+pub async fn send_stream_message(id: String, message: String) -> Result<(), String> {
+    let streams = STREAMS.lock().await;
+    let stream = streams.get(&id).ok_or_else(|| format!("Stream '{}' not found", id))?;
+    if stream.status.kind != StreamKind::WebSocket {
+        return Err("Only WebSocket streams accept messages".to_string());
+    }
+    if stream.status.state != StreamState::Open {
+        return Err("Stream is not connected".to_string());
+    }
+    stream
+        .outgoing
+        .send(message)
+        .map_err(|_| "Stream is closed".to_string())
+}
+
+#[command]
+pub async fn close_stream(app_handle: AppHandle, id: String) -> Result<(), String> {
+    let Some(mut stream) = STREAMS.lock().await.remove(&id) else {
+        return Ok(());
+    };
+    stream.task.abort();
+    stream.status.state = StreamState::Closed;
+    let _ = app_handle.emit("stream-status", &stream.status);
+    Ok(())
+}
+
+/// Status of every managed stream. Closed and failed streams stay listed until `close_stream`.
+#[command]
+pub async fn list_streams() -> Result<Vec<StreamStatus>, String> {
+    Ok(STREAMS
+        .lock()
+        .await
+        .values()
+        .map(|s| s.status.clone())
+        .collect())
+}
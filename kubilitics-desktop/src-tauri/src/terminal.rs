@@ -0,0 +1,142 @@
+// Running a command in a new window of the user's terminal, for things that belong in a real
+// terminal rather than the embedded one (SSH sessions with agent forwarding and host key prompts, a
+// shell set up for a context).
+//
+// macOS uses Terminal.app through AppleScript; Windows prefers Windows Terminal and falls back to a
+// console window; Linux tries x-terminal-emulator (Debian alternatives) and then the common
+// terminals by name.
+use std::process::Command;
+
+#[cfg(target_os = "linux")]
+const LINUX_TERMINALS: [(&str, &str); 6] = [
+    ("x-terminal-emulator", "-e"),
+    ("gnome-terminal", "--"),
+    ("konsole", "-e"),
+    ("xfce4-terminal", "-x"),
+    ("kitty", "--"),
+    ("xterm", "-e"),
+];
+
+/// `arg` quoted for a POSIX shell.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// Open a terminal window running `command` (program and arguments).
+pub fn open_in_terminal(command: &[String]) -> Result<(), String> {
+    if command.is_empty() {
+        return Err("No command to run".to_string());
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let line = command
+            .iter()
+            .map(|a| shell_quote(a))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let script = format!(
+            "tell application \"Terminal\"\nactivate\ndo script \"{}\"\nend tell",
+            line.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        Command::new("osascript")
+            .args(["-e", &script])
+            .spawn()
+            .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(wt) = crate::tools::find_binary("wt") {
+            Command::new(wt)
+                .args(command)
+                .spawn()
+                .map_err(|e| format!("Failed to open Windows Terminal: {}", e))?;
+        } else {
+            use std::os::windows::process::CommandExt;
+
+            // Started directly in a console of its own rather than through `cmd /C start`, which
+            // would reinterpret &, | and ^ in the arguments
+            const CREATE_NEW_CONSOLE: u32 = 0x0000_0010;
+            Command::new(&command[0])
+                .args(&command[1..])
+                .creation_flags(CREATE_NEW_CONSOLE)
+                .spawn()
+                .map_err(|e| format!("Failed to open a console window: {}", e))?;
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let (terminal, flag) = LINUX_TERMINALS
+            .iter()
+            .find_map(|(name, flag)| crate::tools::find_binary(name).map(|path| (path, *flag)))
+            .ok_or("No terminal emulator found")?;
+        Command::new(terminal)
+            .arg(flag)
+            .args(command)
+            .spawn()
+            .map_err(|e| format!("Failed to open a terminal: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Open a terminal window with the user's shell and `env` set in it.
+///
+/// On macOS the variables are exported by the script Terminal.app types into the new window; on
+/// Windows the console window is started with them, so it lands in Windows Terminal when that's
+/// the default terminal application.
+pub fn open_shell_in_terminal(env: &[(String, String)]) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        let line = env
+            .iter()
+            .map(|(key, value)| format!("export {}={}", key, shell_quote(value)))
+            .chain(std::iter::once("clear".to_string()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let script = format!(
+            "tell application \"Terminal\"\nactivate\ndo script \"{}\"\nend tell",
+            line.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        Command::new("osascript")
+            .args(["-e", &script])
+            .spawn()
+            .map_err(|e| format!("Failed to open Terminal: {}", e))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("cmd")
+            .args(["/C", "start", "", "cmd", "/K"])
+            .envs(env.iter().map(|(k, v)| (k, v)))
+            .spawn()
+            .map_err(|e| format!("Failed to open a console window: {}", e))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        let (terminal, flag) = LINUX_TERMINALS
+            .iter()
+            .find_map(|(name, flag)| crate::tools::find_binary(name).map(|path| (path, *flag)))
+            .ok_or("No terminal emulator found")?;
+        Command::new(terminal)
+            .arg(flag)
+            .arg(shell)
+            .envs(env.iter().map(|(k, v)| (k, v)))
+            .spawn()
+            .map_err(|e| format!("Failed to open a terminal: {}", e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// Locating command-line tools the shell can delegate to (helm, kubectl, kustomize). An app launched
+// from Finder or a desktop launcher doesn't get the login shell's PATH, so besides PATH the usual
+// package-manager install directories are searched too.
+use std::path::{Path, PathBuf};
+
+#[cfg(target_os = "macos")]
+const EXTRA_DIRS: [&str; 3] = ["/opt/homebrew/bin", "/usr/local/bin", "/opt/local/bin"];
+#[cfg(target_os = "linux")]
+const EXTRA_DIRS: [&str; 3] = [
+    "/usr/local/bin",
+    "/snap/bin",
+    "/home/linuxbrew/.linuxbrew/bin",
+];
+#[cfg(target_os = "windows")]
+const EXTRA_DIRS: [&str; 0] = [];
+
+fn executable_name(name: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    }
+}
+
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// Every directory searched, PATH first, then the install directories and ~/bin, ~/.local/bin.
+pub fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    dirs.extend(EXTRA_DIRS.iter().map(PathBuf::from));
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join("bin"));
+        dirs.push(home.join(".local").join("bin"));
+    }
+    let mut seen = std::collections::HashSet::new();
+    dirs.retain(|d| seen.insert(d.clone()));
+    dirs
+}
+
+/// Full path of a tool, if installed.
+pub fn find_binary(name: &str) -> Option<PathBuf> {
+    let file = executable_name(name);
+    search_dirs()
+        .into_iter()
+        .map(|dir| dir.join(&file))
+        .find(|path| is_executable(path))
+}
+
+/// First line of `<binary> <args>` output, for version strings.
+pub async fn first_output_line(binary: &Path, args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new(binary)
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+}
@@ -1,17 +1,90 @@
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri::menu::{Menu, MenuBuilder, SubmenuBuilder};
 use tauri::tray::{TrayIconBuilder, TrayIconEvent};
 
+const TRAY_ID: &str = "main";
+const ALERT_ID_PREFIX: &str = "alert:";
+const FORWARD_PROFILE_ID_PREFIX: &str = "forward_profile:";
+/// Alerts listed in the tray menu; the rest are in the app.
+const MAX_TRAY_ALERTS: usize = 8;
+
+/// An entry in the tray's Alerts submenu.
+pub struct TrayAlert {
+    pub id: String,
+    pub label: String,
+}
+
+/// A running port-forward profile; its menu item stops it.
+pub struct TrayForwardProfile {
+    pub id: String,
+    pub label: String,
+}
+
+/// What the dynamic parts of the tray menu show.
+#[derive(Default)]
+struct TrayState {
+    alerts: Vec<TrayAlert>,
+    forward_profiles: Vec<TrayForwardProfile>,
+}
+
+static TRAY_STATE: std::sync::Mutex<TrayState> = std::sync::Mutex::new(TrayState {
+    alerts: Vec::new(),
+    forward_profiles: Vec::new(),
+});
+
+fn build_menu(app: &AppHandle, state: &TrayState) -> tauri::Result<Menu<Wry>> {
+    let mut menu = MenuBuilder::new(app)
+        .text("open", "Open Kubilitics")
+        .text("status", "Show Cluster Status");
+
+    if !state.alerts.is_empty() {
+        let mut submenu = SubmenuBuilder::new(app, format!("Alerts ({})", state.alerts.len()));
+        for alert in state.alerts.iter().take(MAX_TRAY_ALERTS) {
+            submenu = submenu.text(format!("{}{}", ALERT_ID_PREFIX, alert.id), &alert.label);
+        }
+        let submenu = submenu.separator().text("clear_alerts", "Clear Alerts").build()?;
+        menu = menu.item(&submenu);
+    }
+
+    if !state.forward_profiles.is_empty() {
+        let mut submenu = SubmenuBuilder::new(app, format!("Port Forwards ({})", state.forward_profiles.len()));
+        for profile in &state.forward_profiles {
+            submenu = submenu.text(format!("{}{}", FORWARD_PROFILE_ID_PREFIX, profile.id), &profile.label);
+        }
+        menu = menu.item(&submenu.build()?);
+    }
+
+    menu.separator().text("quit", "Quit").build()
+}
+
+fn rebuild(app: &AppHandle, update: impl FnOnce(&mut TrayState)) {
+    let Ok(mut state) = TRAY_STATE.lock() else { return };
+    update(&mut state);
+    let Some(tray) = app.tray_by_id(TRAY_ID) else { return };
+    match build_menu(app, &state) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => tracing::warn!("Failed to update tray menu: {}", e),
+    }
+}
+
+/// Replace the Alerts submenu (newest first); no submenu when `alerts` is empty.
+pub fn set_alerts(app: &AppHandle, alerts: Vec<TrayAlert>) {
+    rebuild(app, |state| state.alerts = alerts);
+}
+
+/// Replace the Port Forwards submenu; no submenu when no profile is running.
+pub fn set_forward_profiles(app: &AppHandle, profiles: Vec<TrayForwardProfile>) {
+    rebuild(app, |state| state.forward_profiles = profiles);
+}
+
 pub fn setup_system_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Create tray icon menu
-    let menu = tauri::menu::MenuBuilder::new(app)
-        .text("open", "Open Kubilitics")
-        .text("status", "Show Cluster Status")
-        .separator()
-        .text("quit", "Quit")
-        .build()?;
+    let menu = build_menu(app, &TrayState::default())?;
 
     // Create tray icon with menu event handling
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .menu(&menu)
         .icon(app.default_window_icon().unwrap().clone())
         .tooltip("Kubilitics - The Kubernetes OS")
@@ -44,10 +117,30 @@ pub fn setup_system_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Erro
                     // Emit event to show cluster status
                     let _ = tray.app_handle().emit("tray-show-status", ());
                 }
+                "clear_alerts" => {
+                    let app = tray.app_handle().clone();
+                    tauri::async_runtime::spawn(async move {
+                        let _ = crate::cluster_events::clear_event_alerts(app).await;
+                    });
+                }
                 "quit" => {
                     tray.app_handle().exit(0);
                 }
-                _ => {}
+                id => {
+                    if let Some(alert_id) = id.strip_prefix(ALERT_ID_PREFIX) {
+                        if let Some(window) = tray.app_handle().get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
+                        let _ = tray.app_handle().emit("tray-open-alert", alert_id);
+                    } else if let Some(profile_id) = id.strip_prefix(FORWARD_PROFILE_ID_PREFIX) {
+                        let app = tray.app_handle().clone();
+                        let profile_id = profile_id.to_string();
+                        tauri::async_runtime::spawn(async move {
+                            let _ = crate::portforward_profiles::stop_port_forward_profile(app, profile_id).await;
+                        });
+                    }
+                }
             }
         })
         .build(app)?;
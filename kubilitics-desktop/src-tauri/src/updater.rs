@@ -0,0 +1,1000 @@
+// Application updates through tauri-plugin-updater: check the release endpoint, download with
+// progress events, install, then offer to relaunch so the new version takes effect.
+//
+// The download itself is done here rather than by the plugin: bundles carry three sidecar binaries
+// and are large, so interrupted downloads resume from a partial file (HTTP Range) instead of
+// starting over. The signature is verified against the configured public key before installing.
+//
+// Every bundle installed is kept with its signature. When the next update replaces that version,
+// the bundle moves to updates/rollback so `rollback_update` can reinstall it if the new release
+// breaks something. The app data directory is user-writable and some installs run privileged
+// (deb/rpm through pkexec), so the rollback bundle is verified against the updater key again
+// before it is installed.
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Url};
+use tokio::io::AsyncWriteExt;
+use tauri_plugin_updater::{Update, Updater, UpdaterExt};
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+use crate::proxy::ProxyMode;
+
+mod install;
+
+use install::InstallOutcome;
+
+// Stable uses the endpoint from tauri.conf.json; the other channels have their own manifests.
+const BETA_ENDPOINT: &str = "https://releases.kubilitics.dev/update/beta/{{target}}/{{arch}}/{{current_version}}";
+const NIGHTLY_ENDPOINT: &str = "https://releases.kubilitics.dev/update/nightly/{{target}}/{{arch}}/{{current_version}}";
+/// Fallback source for release notes when the updater manifest has none.
+const GITHUB_RELEASES_API: &str = "https://api.github.com/repos/kubilitics/kubilitics/releases/tags";
+const CHANGELOG_REQUEST_TIMEOUT_SECS: u64 = 15;
+const DEFAULT_CHECK_INTERVAL_HOURS: u32 = 24;
+/// Let the sidecars and the UI settle before the first background check.
+const STARTUP_CHECK_DELAY_SECS: u64 = 60;
+const CHECK_SCHEDULER_TICK_SECS: u64 = 15 * 60;
+const ROLLBACK_BUNDLE: &str = "bundle";
+const ROLLBACK_SIGNATURE: &str = "bundle.sig";
+const ROLLBACK_METADATA: &str = "rollback.json";
+
+/// The update found by the last check, kept so installing doesn't query the endpoint again.
+static PENDING_UPDATE: Mutex<Option<Update>> = Mutex::const_new(None);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl UpdateChannel {
+    fn endpoint(self) -> Option<&'static str> {
+        match self {
+            UpdateChannel::Stable => None,
+            UpdateChannel::Beta => Some(BETA_ENDPOINT),
+            UpdateChannel::Nightly => Some(NIGHTLY_ENDPOINT),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateSettings {
+    pub channel: UpdateChannel,
+    /// Background check frequency; 0 disables automatic checks.
+    pub check_interval_hours: u32,
+    /// Skip background checks while on a metered connection (manual checks always run).
+    pub skip_on_metered: bool,
+    /// HTTP(S) proxy for update checks, downloads and release notes. Unset falls back to the
+    /// app-wide proxy settings.
+    pub proxy: Option<String>,
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self {
+            channel: UpdateChannel::Stable,
+            check_interval_hours: DEFAULT_CHECK_INTERVAL_HOURS,
+            skip_on_metered: true,
+            proxy: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangelogSection {
+    /// Heading text without the leading `#`s; empty for bullets before the first heading.
+    pub title: String,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateChangelog {
+    pub version: String,
+    pub date: Option<String>,
+    /// The notes as published, for rendering as-is.
+    pub markdown: String,
+    /// Bullet items grouped by heading ("Features", "Fixes", ...), for a compact "what's new" view.
+    pub sections: Vec<ChangelogSection>,
+    /// manifest | github
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl From<&Update> for UpdateInfo {
+    fn from(update: &Update) -> Self {
+        Self {
+            version: update.version.clone(),
+            current_version: update.current_version.clone(),
+            date: update.date.map(|d| d.to_string()),
+            notes: update.body.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackInfo {
+    pub version: String,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SignatureDetails {
+    pub verified: bool,
+    /// Why verification failed, when it did.
+    pub error: Option<String>,
+    /// Key ID the bundle was signed with.
+    pub key_id: String,
+    /// Key ID of the updater public key shipped with the app; differs from `key_id` when the
+    /// bundle was signed by another key.
+    pub trusted_key_id: String,
+    /// The signature's untrusted comment (signer-chosen, not covered by the signature).
+    pub signed_by: String,
+    /// Signing time from the trusted comment, Unix seconds.
+    pub signed_at: Option<u64>,
+    /// Bundle file name from the trusted comment.
+    pub file: Option<String>,
+    pub trusted_comment: String,
+    pub verified_at: u64,
+}
+
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: Option<u64>,
+    percent: Option<f64>,
+    bytes_per_second: u64,
+    /// Bytes that were already on disk from an interrupted download.
+    resumed_from: u64,
+}
+
+async fn get_update_settings_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("update_settings.json"))
+}
+
+pub async fn load_update_settings() -> Result<UpdateSettings, String> {
+    let path = get_update_settings_path().await?;
+
+    if !path.exists() {
+        return Ok(UpdateSettings::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read update settings".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse update settings".to_string())
+}
+
+async fn save_update_settings(settings: &UpdateSettings) -> Result<(), String> {
+    let path = get_update_settings_path().await?;
+
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|_| "Failed to serialize update settings".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write update settings".to_string())
+}
+
+fn parse_proxy_url(proxy: &str) -> Result<Url, String> {
+    let url = Url::parse(proxy).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Proxy URL must use http or https".to_string());
+    }
+    Ok(url)
+}
+
+/// Updater for the configured release channel and proxy. The proxy is carried over to the
+/// `Update` it returns, which `download_client` honors.
+async fn build_updater(app_handle: &AppHandle) -> Result<Updater, String> {
+    let settings = load_update_settings().await?;
+    let mut builder = app_handle.updater_builder();
+    if let Some(endpoint) = settings.channel.endpoint() {
+        let url = Url::parse(endpoint).map_err(|e| format!("Invalid update endpoint: {}", e))?;
+        builder = builder
+            .endpoints(vec![url])
+            .map_err(|e| format!("Invalid update endpoint: {}", e))?;
+    }
+    match &settings.proxy {
+        Some(proxy) => builder = builder.proxy(parse_proxy_url(proxy)?),
+        None => {
+            let resolved = crate::proxy::resolve_proxy().await;
+            match resolved.mode {
+                ProxyMode::System => {}
+                ProxyMode::Direct => builder = builder.no_proxy(),
+                ProxyMode::Manual | ProxyMode::Pac => {
+                    if let Some(proxy) = resolved.https_proxy() {
+                        let url = Url::parse(proxy).map_err(|e| format!("Invalid proxy URL: {}", e))?;
+                        builder = builder.proxy(url);
+                    }
+                }
+            }
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Updater unavailable: {}", e))
+}
+
+async fn find_update(app_handle: &AppHandle) -> Result<Option<Update>, String> {
+    crate::airgap::ensure_external_allowed("Update checks")?;
+    let updater = build_updater(app_handle).await?;
+    updater
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+/// Ask whether to relaunch now. Installing replaces the bundle on disk, but the running process
+/// stays on the old version until it restarts. (On Windows the installer exits the app itself.)
+/// `version` is unknown for bundles installed from a file.
+fn prompt_restart(app_handle: &AppHandle, version: Option<&str>) {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+
+    let installed = match version {
+        Some(version) => format!("Kubilitics {} has been installed.", version),
+        None => "The update has been installed.".to_string(),
+    };
+    let handle = app_handle.clone();
+    app_handle
+        .dialog()
+        .message(format!("{} Restart now to finish updating?", installed))
+        .title("Update Installed")
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Restart Now".to_string(),
+            "Later".to_string(),
+        ))
+        .show(move |restart| {
+            if restart {
+                // RunEvent::Exit stops the sidecars before the relaunch
+                handle.restart();
+            } else {
+                let _ = handle.emit("update-restart-pending", ());
+            }
+        });
+}
+
+async fn get_updates_dir() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("updates"))
+}
+
+fn filename_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Partial download location — keyed by version and target so a resume never mixes artifacts.
+async fn partial_download_path(update: &Update) -> Result<PathBuf, String> {
+    let name = filename_component(&format!("{}-{}.partial", update.version, update.target));
+    Ok(get_updates_dir().await?.join(name))
+}
+
+/// Public key from the updater plugin config in tauri.conf.json.
+fn updater_pubkey(app_handle: &AppHandle) -> Result<String, String> {
+    app_handle
+        .config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|u| u.get("pubkey"))
+        .and_then(|k| k.as_str())
+        .map(String::from)
+        .ok_or_else(|| "No updater public key configured".to_string())
+}
+
+/// Both the updater key and update signatures are base64-wrapped minisign text.
+fn decode_minisign(b64: &str) -> Result<String, String> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(b64.trim())
+        .map_err(|e| format!("Invalid base64: {}", e))?;
+    String::from_utf8(bytes).map_err(|e| format!("Invalid UTF-8: {}", e))
+}
+
+/// Verify a minisign signature the same way the updater plugin does.
+fn verify_signature(data: &[u8], signature: &str, pubkey: &str) -> Result<(), String> {
+    let public_key = minisign_verify::PublicKey::decode(&decode_minisign(pubkey)?)
+        .map_err(|e| format!("Invalid updater public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(&decode_minisign(signature)?)
+        .map_err(|e| format!("Invalid update signature: {}", e))?;
+    public_key
+        .verify(data, &signature, true)
+        .map_err(|e| format!("Update signature verification failed: {}", e))
+}
+
+/// Key ID as `minisign` prints it: bytes 2..10 of the base64 line after the comment (key and
+/// signature share the layout), little-endian.
+fn minisign_key_id(text: &str) -> Option<String> {
+    let raw = base64::engine::general_purpose::STANDARD
+        .decode(text.lines().nth(1)?.trim())
+        .ok()?;
+    let key_id: [u8; 8] = raw.get(2..10)?.try_into().ok()?;
+    Some(format!("{:016X}", u64::from_le_bytes(key_id)))
+}
+
+/// Verify `data` and describe the signature. Unlike `verify_signature`, a failed verification is
+/// reported in the result rather than as an error, so the UI can show what didn't match.
+fn inspect_signature(data: &[u8], signature: &str, pubkey: &str) -> Result<SignatureDetails, String> {
+    let signature_text = decode_minisign(signature)?;
+    let pubkey_text = decode_minisign(pubkey)?;
+    let decoded = minisign_verify::Signature::decode(&signature_text)
+        .map_err(|e| format!("Invalid update signature: {}", e))?;
+
+    // Tauri signs with "timestamp:<unix secs>\tfile:<bundle name>"
+    let trusted_comment = decoded.trusted_comment().to_string();
+    let field = |name: &str| {
+        trusted_comment
+            .split('\t')
+            .find_map(|f| f.strip_prefix(name)?.strip_prefix(':'))
+            .map(str::to_string)
+    };
+    let error = verify_signature(data, signature, pubkey).err();
+
+    Ok(SignatureDetails {
+        verified: error.is_none(),
+        error,
+        key_id: minisign_key_id(&signature_text).unwrap_or_default(),
+        trusted_key_id: minisign_key_id(&pubkey_text).unwrap_or_default(),
+        signed_by: decoded
+            .untrusted_comment()
+            .trim_start_matches("untrusted comment:")
+            .trim()
+            .to_string(),
+        signed_at: field("timestamp").and_then(|t| t.parse().ok()),
+        file: field("file"),
+        trusted_comment,
+        verified_at: now_secs(),
+    })
+}
+
+fn download_client(update: &Update) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(timeout) = update.timeout {
+        builder = builder.timeout(timeout);
+    }
+    if update.no_proxy {
+        builder = builder.no_proxy();
+    } else if let Some(proxy) = &update.proxy {
+        let proxy = reqwest::Proxy::all(proxy.as_str()).map_err(|e| format!("Invalid proxy: {}", e))?;
+        builder = builder.proxy(proxy);
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Download the update bundle to its partial file, resuming a previous partial download when the
+/// server supports range requests. A file that is already complete is not fetched again.
+async fn download_update_file(app_handle: &AppHandle, update: &Update) -> Result<PathBuf, String> {
+    let path = partial_download_path(update).await?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create updates directory: {}", e))?;
+    }
+    let mut offset = tokio::fs::metadata(&path).await.map(|m| m.len()).unwrap_or(0);
+
+    let mut request = download_client(update)?
+        .get(update.download_url.clone())
+        .headers(update.headers.clone())
+        .header(reqwest::header::ACCEPT, "application/octet-stream");
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| format!("Update download failed: {}", e))?;
+
+    let status = response.status();
+    let complete = status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && offset > 0;
+    if !complete {
+        if !status.is_success() {
+            return Err(format!("Update download failed with status: {}", status));
+        }
+        // 200 instead of 206 means the server ignored the range — start over
+        let resuming = status == reqwest::StatusCode::PARTIAL_CONTENT;
+        if !resuming {
+            offset = 0;
+        }
+        let total = response.content_length().map(|len| len + offset);
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resuming)
+            .truncate(!resuming)
+            .open(&path)
+            .await
+            .map_err(|e| format!("Failed to open update download file: {}", e))?;
+
+        let resumed_from = offset;
+        let mut downloaded = offset;
+        let mut last_emit = Instant::now();
+        let mut bytes_since_emit: u64 = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Update download interrupted: {}", e))?
+        {
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| format!("Failed to write update download: {}", e))?;
+            downloaded += chunk.len() as u64;
+            bytes_since_emit += chunk.len() as u64;
+
+            let elapsed = last_emit.elapsed();
+            if elapsed >= PROGRESS_EVENT_INTERVAL || Some(downloaded) == total {
+                let _ = app_handle.emit(
+                    "update-download-progress",
+                    DownloadProgress {
+                        downloaded,
+                        total,
+                        percent: total.map(|t| downloaded as f64 * 100.0 / t.max(1) as f64),
+                        bytes_per_second: (bytes_since_emit as f64 / elapsed.as_secs_f64().max(0.001)) as u64,
+                        resumed_from,
+                    },
+                );
+                last_emit = Instant::now();
+                bytes_since_emit = 0;
+            }
+        }
+        file.flush()
+            .await
+            .map_err(|e| format!("Failed to write update download: {}", e))?;
+    }
+    let _ = app_handle.emit("update-download-finished", ());
+    Ok(path)
+}
+
+/// Download the update bundle and return the verified bytes.
+async fn download_update(app_handle: &AppHandle, update: &Update) -> Result<Vec<u8>, String> {
+    let path = download_update_file(app_handle, update).await?;
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("Failed to read update download: {}", e))?;
+    if let Err(e) = verify_signature(&bytes, &update.signature, &updater_pubkey(app_handle)?) {
+        // A corrupt partial file would fail every resume — discard it
+        let _ = tokio::fs::remove_file(&path).await;
+        return Err(e);
+    }
+    Ok(bytes)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+async fn get_rollback_dir() -> Result<PathBuf, String> {
+    Ok(get_updates_dir().await?.join("rollback"))
+}
+
+/// The bundle the running version was installed from, named after its version. Only one is kept.
+async fn get_installed_bundle_dir() -> Result<PathBuf, String> {
+    Ok(get_updates_dir().await?.join("installed"))
+}
+
+async fn load_rollback_info() -> Result<Option<RollbackInfo>, String> {
+    let dir = get_rollback_dir().await?;
+    let path = dir.join(ROLLBACK_METADATA);
+
+    for file in [ROLLBACK_METADATA, ROLLBACK_BUNDLE, ROLLBACK_SIGNATURE] {
+        if !tokio::fs::try_exists(dir.join(file)).await.unwrap_or(false) {
+            return Ok(None);
+        }
+    }
+
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|_| "Failed to read rollback info".to_string())?;
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|_| "Failed to parse rollback info".to_string())
+}
+
+/// Keep the bundle the running version was installed from, with its signature, as the rollback
+/// target. When there is none (the first update of an install) the previous rollback is kept.
+async fn prepare_rollback(current_version: &str) -> Result<(), String> {
+    let rollback_dir = get_rollback_dir().await?;
+    let installed = get_installed_bundle_dir().await?;
+    let name = filename_component(current_version);
+    let bundle = installed.join(&name);
+    let signature = installed.join(format!("{}.sig", name));
+    if !tokio::fs::try_exists(&bundle).await.unwrap_or(false)
+        || !tokio::fs::try_exists(&signature).await.unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    let staging = get_updates_dir().await?.join("rollback.tmp");
+    let _ = tokio::fs::remove_dir_all(&staging).await;
+    tokio::fs::create_dir_all(&staging)
+        .await
+        .map_err(|e| format!("Failed to create rollback directory: {}", e))?;
+    tokio::fs::rename(&bundle, staging.join(ROLLBACK_BUNDLE))
+        .await
+        .map_err(|e| format!("Failed to keep installer for rollback: {}", e))?;
+    tokio::fs::rename(&signature, staging.join(ROLLBACK_SIGNATURE))
+        .await
+        .map_err(|e| format!("Failed to keep installer signature for rollback: {}", e))?;
+
+    let info = RollbackInfo {
+        version: current_version.to_string(),
+        created_at: now_secs(),
+    };
+    let content = serde_json::to_string_pretty(&info)
+        .map_err(|_| "Failed to serialize rollback info".to_string())?;
+    tokio::fs::write(staging.join(ROLLBACK_METADATA), content)
+        .await
+        .map_err(|_| "Failed to write rollback info".to_string())?;
+
+    let _ = tokio::fs::remove_dir_all(&rollback_dir).await;
+    tokio::fs::rename(&staging, &rollback_dir)
+        .await
+        .map_err(|e| format!("Failed to store rollback: {}", e))
+}
+
+/// Remember the bundle a version is being installed from, and its signature, so it can become the
+/// rollback target once that version is itself replaced. Done before installing: the Windows
+/// installers need the app to quit as soon as they start.
+async fn keep_installed_bundle(version: &str, bytes: &[u8], signature: &str) -> Result<(), String> {
+    let dir = get_installed_bundle_dir().await?;
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("Failed to create installed bundle directory: {}", e))?;
+    let name = filename_component(version);
+    tokio::fs::write(dir.join(&name), bytes)
+        .await
+        .map_err(|e| format!("Failed to keep installed bundle: {}", e))?;
+    tokio::fs::write(dir.join(format!("{}.sig", name)), signature)
+        .await
+        .map_err(|e| format!("Failed to keep installed bundle signature: {}", e))
+}
+
+/// Forget a kept bundle whose install failed; it doesn't belong to the running version.
+async fn discard_installed_bundle() {
+    if let Ok(dir) = get_installed_bundle_dir().await {
+        let _ = tokio::fs::remove_dir_all(dir).await;
+    }
+}
+
+/// Background check: notify about a new version, never download or install on its own.
+/// Returns the version found, if any.
+async fn background_check(app_handle: &AppHandle, notified_version: Option<&str>) -> Option<String> {
+    use tauri_plugin_notification::NotificationExt;
+
+    let update = match find_update(app_handle).await {
+        Ok(Some(update)) => update,
+        Ok(None) => return None,
+        Err(e) => {
+            tracing::warn!(error = %e, "Background update check failed");
+            return None;
+        }
+    };
+    let info = UpdateInfo::from(&update);
+    *PENDING_UPDATE.lock().await = Some(update);
+
+    // One notification per version, not one per check
+    if notified_version != Some(info.version.as_str()) {
+        let _ = app_handle
+            .notification()
+            .builder()
+            .title("Kubilitics update available")
+            .body(format!(
+                "Version {} is ready to install (you have {}).",
+                info.version, info.current_version
+            ))
+            .show();
+    }
+    let _ = app_handle.emit("update-available", &info);
+    Some(info.version)
+}
+
+/// Check for updates shortly after startup and then every `check_interval_hours`.
+pub fn start_update_check_scheduler(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(STARTUP_CHECK_DELAY_SECS)).await;
+
+        let mut last_check: Option<Instant> = None;
+        let mut notified_version: Option<String> = None;
+        loop {
+            let settings = load_update_settings().await.unwrap_or_default();
+            let interval = Duration::from_secs(u64::from(settings.check_interval_hours) * 60 * 60);
+            let due = settings.check_interval_hours > 0
+                && !crate::airgap::is_air_gapped()
+                && last_check.is_none_or(|checked| checked.elapsed() >= interval);
+
+            if due {
+                // Low-bandwidth mode pauses background checks whatever skip_on_metered says
+                let metered = crate::network::is_low_bandwidth()
+                    || (settings.skip_on_metered
+                        && tokio::task::spawn_blocking(crate::network::is_metered_connection)
+                            .await
+                            .unwrap_or(false));
+                // On a metered connection the check stays due and is retried next tick
+                if !metered {
+                    last_check = Some(Instant::now());
+                    if let Some(version) = background_check(&app, notified_version.as_deref()).await {
+                        notified_version = Some(version);
+                    }
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(CHECK_SCHEDULER_TICK_SECS)).await;
+        }
+    });
+}
+
+/// Returns the available update, or `None` when already on the latest version.
+#[command]
+pub async fn check_for_updates(app_handle: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    let update = find_update(&app_handle).await?;
+    let info = update.as_ref().map(UpdateInfo::from);
+    *PENDING_UPDATE.lock().await = update;
+    Ok(info)
+}
+
+/// Download and install the pending update, emitting `update-download-progress` while downloading,
+/// then prompt for a restart. An interrupted download resumes where it stopped on the next call.
+#[command]
+pub async fn install_update(app_handle: AppHandle) -> Result<(), String> {
+    crate::airgap::ensure_external_allowed("Update downloads")?;
+    let pending = PENDING_UPDATE.lock().await.take();
+    let update = match pending {
+        Some(update) => update,
+        None => find_update(&app_handle)
+            .await?
+            .ok_or_else(|| "No update available".to_string())?,
+    };
+
+    let bytes = download_update(&app_handle, &update).await?;
+    // A missing rollback shouldn't block the update itself
+    if let Err(e) = prepare_rollback(&update.current_version).await {
+        tracing::warn!(error = %e, "Failed to keep the current version for rollback");
+    }
+    if let Err(e) = keep_installed_bundle(&update.version, &bytes, &update.signature).await {
+        tracing::warn!(error = %e, "Failed to keep the installed update bundle");
+    }
+    // Installing unpacks and swaps the bundle (or runs the installer) synchronously
+    let installer = update.clone();
+    let installed = tokio::task::spawn_blocking(move || installer.install(bytes))
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+    if let Err(e) = installed {
+        discard_installed_bundle().await;
+        return Err(format!("Failed to install update: {}", e));
+    }
+    if let Ok(path) = partial_download_path(&update).await {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+
+    let _ = app_handle.emit("update-installed", &update.version);
+    prompt_restart(&app_handle, Some(&update.version));
+    Ok(())
+}
+
+/// The version in a release bundle's file name, e.g. "1.4.0" in "Kubilitics_1.4.0_amd64.AppImage".
+fn bundle_file_version(path: &str) -> Option<String> {
+    let name = std::path::Path::new(path).file_name()?.to_str()?;
+    name.split(['_', '-']).find_map(|part| {
+        let version = part.trim_start_matches('v');
+        let numbers: Vec<&str> = version.split('.').take(3).collect();
+        let is_version = numbers.len() == 3
+            && numbers.iter().all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        is_version.then(|| numbers.join("."))
+    })
+}
+
+/// Install a manually downloaded bundle (air-gapped machines, blocked release endpoint). The
+/// signature published next to it (`<bundle>.sig` unless `signature_path` says otherwise) must
+/// verify against the updater public key, exactly as for online updates. Like an online update, the
+/// bundle is kept as the next rollback target; `version` names it, defaulting to the version in the
+/// file name, and without either it isn't kept.
+#[command]
+pub async fn install_update_from_file(
+    app_handle: AppHandle,
+    path: String,
+    signature_path: Option<String>,
+    version: Option<String>,
+) -> Result<(), String> {
+    let signature_path = signature_path.unwrap_or_else(|| format!("{}.sig", path));
+    let signature = tokio::fs::read_to_string(&signature_path)
+        .await
+        .map_err(|e| format!("Failed to read signature file {}: {}", signature_path, e))?;
+    let bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|e| format!("Failed to read update bundle: {}", e))?;
+    verify_signature(&bytes, signature.trim(), &updater_pubkey(&app_handle)?)?;
+
+    let current_version = app_handle.package_info().version.to_string();
+    if let Err(e) = prepare_rollback(&current_version).await {
+        tracing::warn!(error = %e, "Failed to keep the current version for rollback");
+    }
+    match version.or_else(|| bundle_file_version(&path)) {
+        Some(version) => {
+            if let Err(e) = keep_installed_bundle(&version, &bytes, signature.trim()).await {
+                tracing::warn!(error = %e, "Failed to keep the installed update bundle");
+            }
+        }
+        None => tracing::warn!(path = %path, "Update bundle version unknown; it won't be kept for rollback"),
+    }
+    let outcome = tokio::task::spawn_blocking(move || install::install_bundle(&bytes))
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            discard_installed_bundle().await;
+            return Err(e);
+        }
+    };
+    // Whatever was pending is superseded by what was just installed
+    *PENDING_UPDATE.lock().await = None;
+
+    let _ = app_handle.emit("update-installed", ());
+    match outcome {
+        InstallOutcome::RestartRequired => prompt_restart(&app_handle, None),
+        InstallOutcome::ExitRequired => app_handle.exit(0),
+    }
+    Ok(())
+}
+
+/// Signature details for an update before installing it: the pending update (downloaded to its
+/// partial file, which `install_update` then reuses) or, with `path`, a manually downloaded bundle
+/// and its `.sig`.
+#[command]
+pub async fn get_update_signature_details(
+    app_handle: AppHandle,
+    path: Option<String>,
+    signature_path: Option<String>,
+) -> Result<SignatureDetails, String> {
+    let pubkey = updater_pubkey(&app_handle)?;
+
+    let (data, signature) = match path {
+        Some(path) => {
+            let signature_path = signature_path.unwrap_or_else(|| format!("{}.sig", path));
+            let signature = tokio::fs::read_to_string(&signature_path)
+                .await
+                .map_err(|e| format!("Failed to read signature file {}: {}", signature_path, e))?;
+            let data = tokio::fs::read(&path)
+                .await
+                .map_err(|e| format!("Failed to read update bundle: {}", e))?;
+            (data, signature)
+        }
+        None => {
+            let pending = PENDING_UPDATE.lock().await.clone();
+            let update = match pending {
+                Some(update) => update,
+                None => {
+                    let update = find_update(&app_handle)
+                        .await?
+                        .ok_or_else(|| "No update available".to_string())?;
+                    *PENDING_UPDATE.lock().await = Some(update.clone());
+                    update
+                }
+            };
+            let file = download_update_file(&app_handle, &update).await?;
+            let data = tokio::fs::read(&file)
+                .await
+                .map_err(|e| format!("Failed to read update download: {}", e))?;
+            (data, update.signature.clone())
+        }
+    };
+
+    tokio::task::spawn_blocking(move || inspect_signature(&data, &signature, &pubkey))
+        .await
+        .map_err(|e| format!("Signature check failed: {}", e))?
+}
+
+/// The version `rollback_update` would reinstall, if any.
+#[command]
+pub async fn get_rollback_info() -> Result<Option<RollbackInfo>, String> {
+    load_rollback_info().await
+}
+
+/// Reinstall the version that was replaced by the last update. The rollback copy is consumed; the
+/// newer release stays available through the regular update check.
+#[command]
+pub async fn rollback_update(app_handle: AppHandle) -> Result<RollbackInfo, String> {
+    let info = load_rollback_info()
+        .await?
+        .ok_or_else(|| "No previous version available to roll back to".to_string())?;
+    let dir = get_rollback_dir().await?;
+    let bytes = tokio::fs::read(dir.join(ROLLBACK_BUNDLE))
+        .await
+        .map_err(|e| format!("Failed to read rollback bundle: {}", e))?;
+    let signature = tokio::fs::read_to_string(dir.join(ROLLBACK_SIGNATURE))
+        .await
+        .map_err(|e| format!("Failed to read rollback bundle signature: {}", e))?;
+    // The bundle sat in a user-writable directory since it was first verified
+    verify_signature(&bytes, signature.trim(), &updater_pubkey(&app_handle)?)
+        .map_err(|e| format!("Refusing to roll back: {}", e))?;
+
+    let outcome = tokio::task::spawn_blocking(move || install::install_bundle(&bytes))
+        .await
+        .map_err(|e| format!("Rollback failed: {}", e))??;
+
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    // The kept bundle belongs to the version being rolled back from
+    if let Ok(installed) = get_installed_bundle_dir().await {
+        let _ = tokio::fs::remove_dir_all(installed).await;
+    }
+    *PENDING_UPDATE.lock().await = None;
+
+    let _ = app_handle.emit("update-rolled-back", &info);
+    match outcome {
+        InstallOutcome::RestartRequired => prompt_restart(&app_handle, Some(&info.version)),
+        // RunEvent::Exit stops the sidecars before the installer replaces the binaries
+        InstallOutcome::ExitRequired => app_handle.exit(0),
+    }
+    Ok(info)
+}
+
+#[command]
+pub async fn get_update_channel() -> Result<UpdateChannel, String> {
+    Ok(load_update_settings().await?.channel)
+}
+
+/// Switch release channel. Takes effect on the next check; an update found on the previous
+/// channel is discarded. Moving to an older channel never downgrades — the app stays on its
+/// version until that channel catches up.
+#[command]
+pub async fn set_update_channel(channel: UpdateChannel) -> Result<(), String> {
+    let mut settings = load_update_settings().await?;
+    settings.channel = channel;
+    save_update_settings(&settings).await?;
+    *PENDING_UPDATE.lock().await = None;
+    Ok(())
+}
+
+fn parse_changelog_sections(markdown: &str) -> Vec<ChangelogSection> {
+    let mut sections: Vec<ChangelogSection> = Vec::new();
+    for line in markdown.lines().map(str::trim) {
+        if let Some(heading) = line.strip_prefix('#') {
+            sections.push(ChangelogSection {
+                title: heading.trim_start_matches('#').trim().to_string(),
+                items: Vec::new(),
+            });
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            if sections.is_empty() {
+                sections.push(ChangelogSection {
+                    title: String::new(),
+                    items: Vec::new(),
+                });
+            }
+            if let Some(section) = sections.last_mut() {
+                section.items.push(item.trim().to_string());
+            }
+        }
+    }
+    sections.retain(|s| !s.items.is_empty());
+    sections
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    body: Option<String>,
+    published_at: Option<String>,
+}
+
+async fn fetch_github_release_notes(app: &AppHandle, version: &str) -> Result<(String, Option<String>), String> {
+    crate::airgap::ensure_external_allowed("Release note downloads")?;
+    let client = match load_update_settings().await?.proxy {
+        Some(proxy) => {
+            let proxy = reqwest::Proxy::all(parse_proxy_url(&proxy)?.as_str())
+                .map_err(|e| format!("Invalid proxy: {}", e))?;
+            reqwest::Client::builder()
+                .proxy(proxy)
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+        }
+        None => crate::http_client::shared(app).await?,
+    };
+
+    let version = version.trim_start_matches('v');
+    // Release tags are usually "v1.2.3", but accept bare versions too
+    for tag in [format!("v{}", version), version.to_string()] {
+        let response = client
+            .get(format!("{}/{}", GITHUB_RELEASES_API, tag))
+            .timeout(Duration::from_secs(CHANGELOG_REQUEST_TIMEOUT_SECS))
+            .header(reqwest::header::USER_AGENT, concat!("kubilitics-desktop/", env!("CARGO_PKG_VERSION")))
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch release notes: {}", e))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            continue;
+        }
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch release notes: {}", response.status()));
+        }
+        let release: GithubRelease = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse release notes: {}", e))?;
+        return Ok((release.body.unwrap_or_default(), release.published_at));
+    }
+    Err(format!("No release notes found for version {}", version))
+}
+
+/// Release notes for `version` — from the updater manifest when it is the pending update and the
+/// manifest carries notes, otherwise from the GitHub release.
+#[command]
+pub async fn get_update_changelog(app_handle: AppHandle, version: String) -> Result<UpdateChangelog, String> {
+    let from_manifest = PENDING_UPDATE
+        .lock()
+        .await
+        .as_ref()
+        .filter(|u| u.version.trim_start_matches('v') == version.trim_start_matches('v'))
+        .and_then(|u| {
+            let notes = u.body.clone().filter(|b| !b.trim().is_empty())?;
+            Some((notes, u.date.map(|d| d.to_string())))
+        });
+
+    let (markdown, date, source) = match from_manifest {
+        Some((notes, date)) => (notes, date, "manifest"),
+        None => {
+            let (notes, date) = fetch_github_release_notes(&app_handle, &version).await?;
+            (notes, date, "github")
+        }
+    };
+
+    Ok(UpdateChangelog {
+        version,
+        date,
+        sections: parse_changelog_sections(&markdown),
+        markdown,
+        source: source.to_string(),
+    })
+}
+
+#[command]
+pub async fn get_update_settings() -> Result<UpdateSettings, String> {
+    load_update_settings().await
+}
+
+/// Save an http(s) proxy that overrides the app's proxy settings for update checks, downloads and
+/// release notes. `None` (or a blank string) removes the override, so they follow the app-wide
+/// settings again. Takes effect from the next check; an update already found keeps its proxy.
+#[command]
+pub async fn set_update_proxy(proxy: Option<String>) -> Result<(), String> {
+    let proxy = proxy.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(proxy) = &proxy {
+        parse_proxy_url(proxy)?;
+    }
+    let mut settings = load_update_settings().await?;
+    settings.proxy = proxy;
+    save_update_settings(&settings).await
+}
+
+/// Configure background update checks. `interval_hours` = 0 turns them off ("never").
+#[command]
+pub async fn set_update_check_schedule(interval_hours: u32, skip_on_metered: bool) -> Result<(), String> {
+    let mut settings = load_update_settings().await?;
+    settings.check_interval_hours = interval_hours;
+    settings.skip_on_metered = skip_on_metered;
+    save_update_settings(&settings).await
+}
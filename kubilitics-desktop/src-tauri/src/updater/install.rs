@@ -0,0 +1,210 @@
+// Installing an update bundle we already have on disk (the kept installer of a previous version or
+// a manually downloaded release), mirroring tauri-plugin-updater's per-platform install steps.
+// The plugin can only install an `Update` returned by a live check against the release endpoint.
+// Callers verify the bundle's signature first.
+use std::path::Path;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::path::PathBuf;
+
+/// What the caller has to do after a successful install.
+pub enum InstallOutcome {
+    /// The bundle on disk was replaced; the installed version runs after a relaunch.
+    RestartRequired,
+    /// An installer was launched and needs the app to quit before it can replace it (Windows).
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    ExitRequired,
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x1f, 0x8b])
+}
+
+#[cfg(target_os = "macos")]
+fn current_app_bundle() -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to locate the running app: {}", e))?;
+    exe.ancestors()
+        .find(|p| p.extension().is_some_and(|ext| ext == "app"))
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Kubilitics is not running from an app bundle".to_string())
+}
+
+/// The AppImage file the app was launched from. Set by the AppImage runtime; absent for deb/rpm
+/// installs, which are managed by the system package manager instead.
+#[cfg(target_os = "linux")]
+fn current_appimage() -> Option<PathBuf> {
+    std::env::var_os("APPIMAGE").map(PathBuf::from)
+}
+
+/// Install a .app.tar.gz: unpack next to the running bundle (same volume, so the swap is a rename)
+/// and exchange the two, restoring the old bundle if the second rename fails.
+#[cfg(target_os = "macos")]
+pub fn install_bundle(bytes: &[u8]) -> Result<InstallOutcome, String> {
+    if !is_gzip(bytes) {
+        return Err("Unsupported update bundle: expected a .app.tar.gz archive".to_string());
+    }
+    let app = current_app_bundle()?;
+    let parent = app
+        .parent()
+        .ok_or_else(|| "Invalid app bundle path".to_string())?;
+    let staging = parent.join(format!(".kubilitics-update-{:016x}", rand::random::<u64>()));
+
+    let result = swap_app_bundle(bytes, &app, &staging);
+    let _ = std::fs::remove_dir_all(&staging);
+    result.map(|()| InstallOutcome::RestartRequired)
+}
+
+#[cfg(target_os = "macos")]
+fn swap_app_bundle(bytes: &[u8], app: &Path, staging: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(staging)
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+    tar::Archive::new(flate2::read::GzDecoder::new(bytes))
+        .unpack(staging)
+        .map_err(|e| format!("Failed to extract update bundle: {}", e))?;
+
+    let new_app = std::fs::read_dir(staging)
+        .map_err(|e| format!("Failed to read extracted bundle: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .find(|p| p.extension().is_some_and(|ext| ext == "app"))
+        .ok_or_else(|| "Update bundle contains no .app".to_string())?;
+
+    let backup = staging.join("previous");
+    std::fs::rename(app, &backup)
+        .map_err(|e| format!("Failed to move the current app aside: {}", e))?;
+    if let Err(e) = std::fs::rename(&new_app, app) {
+        let _ = std::fs::rename(&backup, app);
+        return Err(format!("Failed to install app bundle: {}", e));
+    }
+    Ok(())
+}
+
+/// Install an AppImage (raw or .AppImage.tar.gz) over the running one, or a deb/rpm package
+/// through the package manager with a graphical privilege prompt.
+#[cfg(target_os = "linux")]
+pub fn install_bundle(bytes: &[u8]) -> Result<InstallOutcome, String> {
+    if bytes.starts_with(b"!<arch>\n") {
+        return install_package(bytes, "deb", "dpkg", &["-i"]);
+    }
+    if bytes.starts_with(&[0xed, 0xab, 0xee, 0xdb]) {
+        // --oldpackage: rolling back is a downgrade, which `rpm -U` refuses by default
+        return install_package(bytes, "rpm", "rpm", &["-U", "--oldpackage"]);
+    }
+
+    let target = current_appimage()
+        .ok_or_else(|| "Kubilitics was not launched from an AppImage".to_string())?;
+    let appimage = if is_gzip(bytes) {
+        extract_appimage(bytes)?
+    } else {
+        bytes.to_vec()
+    };
+    if !appimage.starts_with(b"\x7fELF") {
+        return Err("Unsupported update bundle: expected an AppImage".to_string());
+    }
+
+    replace_file(&target, &appimage)?;
+    Ok(InstallOutcome::RestartRequired)
+}
+
+#[cfg(target_os = "linux")]
+fn extract_appimage(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(bytes));
+    let entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read update bundle: {}", e))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Failed to read update bundle: {}", e))?;
+        let is_appimage = entry
+            .path()
+            .is_ok_and(|p| p.extension().is_some_and(|ext| ext == "AppImage"));
+        if is_appimage {
+            let mut data = Vec::new();
+            entry
+                .read_to_end(&mut data)
+                .map_err(|e| format!("Failed to extract AppImage: {}", e))?;
+            return Ok(data);
+        }
+    }
+    Err("Update bundle contains no AppImage".to_string())
+}
+
+/// Write next to the target and rename over it, so a failed write never leaves a broken AppImage.
+#[cfg(target_os = "linux")]
+fn replace_file(target: &Path, data: &[u8]) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let name = target
+        .file_name()
+        .ok_or_else(|| "Invalid AppImage path".to_string())?;
+    let tmp = target.with_file_name(format!(".{}.update", name.to_string_lossy()));
+    std::fs::write(&tmp, data).map_err(|e| format!("Failed to write AppImage: {}", e))?;
+    std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o755))
+        .and_then(|()| std::fs::rename(&tmp, target))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            format!("Failed to replace AppImage: {}", e)
+        })
+}
+
+#[cfg(target_os = "linux")]
+fn install_package(bytes: &[u8], extension: &str, tool: &str, args: &[&str]) -> Result<InstallOutcome, String> {
+    let path = std::env::temp_dir().join(format!(
+        "kubilitics-{:016x}.{}",
+        rand::random::<u64>(),
+        extension
+    ));
+    std::fs::write(&path, bytes).map_err(|e| format!("Failed to write package: {}", e))?;
+    let status = std::process::Command::new("pkexec")
+        .arg(tool)
+        .args(args)
+        .arg(&path)
+        .status();
+    let _ = std::fs::remove_file(&path);
+
+    match status {
+        Ok(status) if status.success() => Ok(InstallOutcome::RestartRequired),
+        Ok(status) => Err(format!("{} exited with {}", tool, status)),
+        Err(e) => Err(format!("Failed to run pkexec: {}", e)),
+    }
+}
+
+/// Launch the NSIS or MSI installer in passive mode. It can't overwrite a running executable, so
+/// the caller must quit right after; both installers relaunch the app when done.
+#[cfg(target_os = "windows")]
+pub fn install_bundle(bytes: &[u8]) -> Result<InstallOutcome, String> {
+    use std::process::Command;
+
+    // MSI files are OLE compound documents
+    let msi = bytes.starts_with(&[0xd0, 0xcf, 0x11, 0xe0, 0xa1, 0xb1, 0x1a, 0xe1]);
+    if !msi && !bytes.starts_with(b"MZ") {
+        return Err("Unsupported update bundle: expected an .exe or .msi installer".to_string());
+    }
+    let path = std::env::temp_dir().join(format!(
+        "kubilitics-installer-{:016x}.{}",
+        rand::random::<u64>(),
+        if msi { "msi" } else { "exe" }
+    ));
+    std::fs::write(&path, bytes).map_err(|e| format!("Failed to write installer: {}", e))?;
+
+    let mut command = if msi {
+        let mut c = Command::new("msiexec");
+        c.arg("/i")
+            .arg(&path)
+            .args(["/passive", "/promptrestart", "AUTOLAUNCHAPP=True"]);
+        c
+    } else {
+        let mut c = Command::new(&path);
+        c.args(["/P", "/R", "/UPDATE"]);
+        c
+    };
+    command
+        .spawn()
+        .map_err(|e| format!("Failed to launch installer: {}", e))?;
+    Ok(InstallOutcome::ExitRequired)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn install_bundle(_bytes: &[u8]) -> Result<InstallOutcome, String> {
+    Err("Installing update bundles is not supported on this platform".to_string())
+}
@@ -0,0 +1,230 @@
+// Per-context "requires VPN" tags and a best-effort check whether a VPN is up, so a cluster that's
+// unreachable because the VPN is down gets "connect to your VPN" instead of a generic error.
+//
+// A VPN counts as up when a tunnel interface (utun/tun/tap/ppp/wg, or a known VPN client adapter on
+// Windows) has a routable address, or — more reliably — when the context's detection host, a host
+// only reachable through the VPN, accepts a TCP connection.
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+use crate::commands::get_app_data_dir;
+
+const DETECTION_TIMEOUT_SECS: u64 = 3;
+const DEFAULT_DETECTION_PORT: u16 = 443;
+/// Interface name prefixes used by tunnel drivers (macOS utun, Linux tun/tap/ppp, WireGuard).
+const TUNNEL_PREFIXES: [&str; 7] = ["utun", "tun", "tap", "ppp", "wg", "ipsec", "gpd"];
+/// Substrings of Windows adapter names installed by common VPN clients.
+const VPN_ADAPTER_NAMES: [&str; 8] = [
+    "vpn",
+    "tap-windows",
+    "wireguard",
+    "anyconnect",
+    "globalprotect",
+    "pangp",
+    "fortinet",
+    "juniper",
+];
+
+/// Contexts already warned about, so the prober doesn't re-warn every round.
+static WARNED_CONTEXTS: Mutex<Option<HashSet<String>>> = Mutex::const_new(None);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VpnRequirement {
+    pub context: String,
+    /// `host` or `host:port` reachable only over the VPN (port defaults to 443).
+    #[serde(default)]
+    pub detection_host: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VpnStatus {
+    pub context: String,
+    pub required: bool,
+    /// Tunnel interfaces with a routable address.
+    pub interfaces: Vec<String>,
+    /// `None` when the context has no detection host.
+    pub detection_host_reachable: Option<bool>,
+    pub likely_connected: bool,
+    /// What to tell the user when the context requires a VPN that looks down.
+    pub message: Option<String>,
+}
+
+async fn get_vpn_requirements_path() -> Result<PathBuf, String> {
+    let app_data_dir = get_app_data_dir().await?;
+    Ok(PathBuf::from(app_data_dir).join("vpn_requirements.json"))
+}
+
+async fn load_vpn_requirements() -> Result<Vec<VpnRequirement>, String> {
+    let path = get_vpn_requirements_path().await?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| "Failed to read VPN requirements".to_string())?;
+
+    serde_json::from_str(&content)
+        .map_err(|_| "Failed to parse VPN requirements".to_string())
+}
+
+async fn save_vpn_requirements(requirements: &[VpnRequirement]) -> Result<(), String> {
+    let path = get_vpn_requirements_path().await?;
+
+    let content = serde_json::to_string_pretty(requirements)
+        .map_err(|_| "Failed to serialize VPN requirements".to_string())?;
+
+    std::fs::write(&path, content)
+        .map_err(|_| "Failed to write VPN requirements".to_string())
+}
+
+fn is_routable(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(a) => !a.is_loopback() && !a.is_link_local(),
+        // macOS keeps several utun interfaces with only link-local IPv6 addresses for system services
+        IpAddr::V6(a) => !a.is_loopback() && (a.segments()[0] & 0xffc0) != 0xfe80,
+    }
+}
+
+fn is_tunnel_interface(name: &str) -> bool {
+    let name = name.to_lowercase();
+    TUNNEL_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        || VPN_ADAPTER_NAMES.iter().any(|vpn| name.contains(vpn))
+}
+
+fn tunnel_interfaces() -> Vec<String> {
+    let mut names: Vec<String> = local_ip_address::list_afinet_netifas()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(name, ip)| is_tunnel_interface(name) && is_routable(ip))
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+async fn detection_host_reachable(detection_host: &str) -> bool {
+    let target = if detection_host.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        detection_host.to_string()
+    } else {
+        format!("{}:{}", detection_host, DEFAULT_DETECTION_PORT)
+    };
+    matches!(
+        tokio::time::timeout(
+            Duration::from_secs(DETECTION_TIMEOUT_SECS),
+            tokio::net::TcpStream::connect(target),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+async fn vpn_status(context: &str, requirement: Option<&VpnRequirement>) -> VpnStatus {
+    let interfaces = tokio::task::spawn_blocking(tunnel_interfaces)
+        .await
+        .unwrap_or_default();
+    let detection_host = requirement.and_then(|r| r.detection_host.as_deref());
+    let detection_host_reachable = match detection_host {
+        Some(host) => Some(detection_host_reachable(host).await),
+        None => None,
+    };
+    // The detection host is authoritative; interfaces are only a heuristic
+    let likely_connected = detection_host_reachable.unwrap_or(!interfaces.is_empty());
+
+    let message = (requirement.is_some() && !likely_connected).then(|| match detection_host {
+        Some(host) => format!(
+            "Context \"{}\" requires a VPN, and {} is not reachable. Connect to your VPN, then retry.",
+            context, host
+        ),
+        None => format!(
+            "Context \"{}\" requires a VPN, but no VPN connection was detected. Connect to your VPN, then retry.",
+            context
+        ),
+    });
+
+    VpnStatus {
+        context: context.to_string(),
+        required: requirement.is_some(),
+        interfaces,
+        detection_host_reachable,
+        likely_connected,
+        message,
+    }
+}
+
+/// Called when a cluster can't be reached: if its context requires a VPN that looks down, emit
+/// `vpn-required` with the message (once, until the cluster is reachable again).
+pub async fn report_unreachable(app: &AppHandle, context: &str) {
+    let Ok(requirements) = load_vpn_requirements().await else {
+        return;
+    };
+    let Some(requirement) = requirements.iter().find(|r| r.context == context) else {
+        return;
+    };
+    if WARNED_CONTEXTS
+        .lock()
+        .await
+        .as_ref()
+        .is_some_and(|warned| warned.contains(context))
+    {
+        return;
+    }
+
+    let status = vpn_status(context, Some(requirement)).await;
+    if status.message.is_some() {
+        WARNED_CONTEXTS
+            .lock()
+            .await
+            .get_or_insert_with(HashSet::new)
+            .insert(context.to_string());
+        let _ = app.emit("vpn-required", &status);
+    }
+}
+
+/// Clear the warning state once a context's cluster is reachable again.
+pub async fn report_reachable(context: &str) {
+    if let Some(warned) = WARNED_CONTEXTS.lock().await.as_mut() {
+        warned.remove(context);
+    }
+}
+
+#[command]
+pub async fn get_vpn_requirements() -> Result<Vec<VpnRequirement>, String> {
+    load_vpn_requirements().await
+}
+
+/// Tag (`required`) or untag a context as needing a VPN.
+#[command]
+pub async fn set_vpn_requirement(
+    context: String,
+    required: bool,
+    detection_host: Option<String>,
+) -> Result<(), String> {
+    let mut requirements = load_vpn_requirements().await?;
+    requirements.retain(|r| r.context != context);
+    if required {
+        requirements.push(VpnRequirement {
+            context,
+            detection_host: detection_host
+                .map(|h| h.trim().to_string())
+                .filter(|h| !h.is_empty()),
+        });
+    }
+    save_vpn_requirements(&requirements).await
+}
+
+/// VPN state for a context, with an actionable message when it requires a VPN that looks down.
+/// The frontend calls this when a connection to the context fails.
+#[command]
+pub async fn check_vpn_status(context: String) -> Result<VpnStatus, String> {
+    let requirements = load_vpn_requirements().await?;
+    let requirement = requirements.iter().find(|r| r.context == context);
+    Ok(vpn_status(&context, requirement).await)
+}
@@ -0,0 +1,474 @@
+// In-memory resource cache fed by watches, for the views that otherwise poll the backend every few
+// seconds (pod lists, deployment status, nodes). One watcher per kind per context keeps a full
+// copy; the frontend reads lists and single objects from it and gets changes pushed as
+// `watch-deltas` ({ context, deltas }), batched every DELTA_FLUSH_INTERVAL.
+//
+// Watchers relist and back off on their own after errors or expired resource versions; a relist
+// replaces the kind's contents and is announced as a `resynced` delta instead of one delta per
+// object. Secrets are deliberately not cached.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures::StreamExt;
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::batch::v1::{CronJob, Job};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, Event as CoreEvent, Namespace, Node, PersistentVolumeClaim, Pod, Service,
+};
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::api::{ApiResource, DynamicObject};
+use kube::runtime::watcher::{self, Event};
+use kube::runtime::WatchStreamExt;
+use kube::{Api, Client};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{command, AppHandle, Emitter};
+use tokio::sync::{mpsc, Mutex};
+
+const DELTA_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+const DEFAULT_KINDS: [&str; 7] = [
+    "pods",
+    "services",
+    "deployments",
+    "replicasets",
+    "statefulsets",
+    "daemonsets",
+    "nodes",
+];
+
+struct WatchedContext {
+    cache: Arc<RwLock<ContextCache>>,
+    tasks: Vec<tauri::async_runtime::JoinHandle<()>>,
+    started_at: u64,
+}
+
+static CACHES: Mutex<BTreeMap<String, WatchedContext>> = Mutex::const_new(BTreeMap::new());
+
+#[derive(Default)]
+struct KindCache {
+    /// Keyed by "namespace/name" ("name" for cluster-scoped kinds).
+    objects: HashMap<String, Value>,
+    synced: bool,
+    last_sync_at: Option<u64>,
+    error: Option<String>,
+}
+
+#[derive(Default)]
+struct ContextCache {
+    kinds: BTreeMap<String, KindCache>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeltaType {
+    Upserted,
+    Deleted,
+    /// The kind was relisted; re-read it with `list_cached_resources`.
+    Resynced,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchDelta {
+    pub kind: String,
+    #[serde(rename = "type")]
+    pub delta_type: DeltaType,
+    pub namespace: Option<String>,
+    pub name: Option<String>,
+    /// The object after the change (before deletion for `deleted`); absent for `resynced`.
+    pub object: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct WatchDeltaBatch<'a> {
+    context: &'a str,
+    deltas: Vec<WatchDelta>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KindCacheStatus {
+    pub kind: String,
+    pub objects: usize,
+    pub synced: bool,
+    pub last_sync_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchCacheStatus {
+    pub context: String,
+    pub started_at: u64,
+    pub kinds: Vec<KindCacheStatus>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// API resource and scope for a cacheable kind (plural, lowercase).
+fn resource_for(kind: &str) -> Option<(ApiResource, bool)> {
+    let resource = match kind {
+        "pods" => (ApiResource::erase::<Pod>(&()), true),
+        "services" => (ApiResource::erase::<Service>(&()), true),
+        "configmaps" => (ApiResource::erase::<ConfigMap>(&()), true),
+        "persistentvolumeclaims" => (ApiResource::erase::<PersistentVolumeClaim>(&()), true),
+        "events" => (ApiResource::erase::<CoreEvent>(&()), true),
+        "deployments" => (ApiResource::erase::<Deployment>(&()), true),
+        "replicasets" => (ApiResource::erase::<ReplicaSet>(&()), true),
+        "statefulsets" => (ApiResource::erase::<StatefulSet>(&()), true),
+        "daemonsets" => (ApiResource::erase::<DaemonSet>(&()), true),
+        "jobs" => (ApiResource::erase::<Job>(&()), true),
+        "cronjobs" => (ApiResource::erase::<CronJob>(&()), true),
+        "ingresses" => (ApiResource::erase::<Ingress>(&()), true),
+        "nodes" => (ApiResource::erase::<Node>(&()), false),
+        "namespaces" => (ApiResource::erase::<Namespace>(&()), false),
+        _ => return None,
+    };
+    Some(resource)
+}
+
+fn cache_key(namespace: Option<&str>, name: &str) -> String {
+    match namespace {
+        Some(ns) => format!("{}/{}", ns, name),
+        None => name.to_string(),
+    }
+}
+
+/// JSON for the cache: apiVersion/kind filled in (list items may lack them), managedFields dropped
+/// since nothing in the UI reads them and they are often half the object.
+fn to_cached(mut object: DynamicObject, resource: &ApiResource) -> Option<(String, Value)> {
+    object.metadata.managed_fields = None;
+    let key = cache_key(
+        object.metadata.namespace.as_deref(),
+        object.metadata.name.as_deref()?,
+    );
+    let mut value = serde_json::to_value(&object).ok()?;
+    if let Some(map) = value.as_object_mut() {
+        map.insert(
+            "apiVersion".to_string(),
+            Value::String(resource.api_version.clone()),
+        );
+        map.insert("kind".to_string(), Value::String(resource.kind.clone()));
+    }
+    Some((key, value))
+}
+
+fn delta(kind: &str, delta_type: DeltaType, object: &Value) -> WatchDelta {
+    let metadata = object.get("metadata");
+    let field = |name: &str| {
+        metadata
+            .and_then(|m| m.get(name))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    WatchDelta {
+        kind: kind.to_string(),
+        delta_type,
+        namespace: field("namespace"),
+        name: field("name"),
+        object: Some(object.clone()),
+    }
+}
+
+async fn watch_kind(
+    client: Client,
+    kind: String,
+    resource: ApiResource,
+    cache: Arc<RwLock<ContextCache>>,
+    deltas: mpsc::UnboundedSender<WatchDelta>,
+) {
+    let api: Api<DynamicObject> = Api::all_with(client, &resource);
+    let mut stream = watcher::watcher(api, watcher::Config::default())
+        .default_backoff()
+        .boxed();
+    let mut relist: HashMap<String, Value> = HashMap::new();
+
+    while let Some(event) = stream.next().await {
+        let Ok(mut guard) = cache.write() else { return };
+        let entry = guard.kinds.entry(kind.clone()).or_default();
+        match event {
+            Ok(Event::Init) => relist.clear(),
+            Ok(Event::InitApply(object)) => {
+                if let Some((key, value)) = to_cached(object, &resource) {
+                    relist.insert(key, value);
+                }
+            }
+            Ok(Event::InitDone) => {
+                entry.objects = std::mem::take(&mut relist);
+                entry.synced = true;
+                entry.last_sync_at = Some(now_secs());
+                entry.error = None;
+                let _ = deltas.send(WatchDelta {
+                    kind: kind.clone(),
+                    delta_type: DeltaType::Resynced,
+                    namespace: None,
+                    name: None,
+                    object: None,
+                });
+            }
+            Ok(Event::Apply(object)) => {
+                if let Some((key, value)) = to_cached(object, &resource) {
+                    let _ = deltas.send(delta(&kind, DeltaType::Upserted, &value));
+                    entry.objects.insert(key, value);
+                }
+                entry.error = None;
+            }
+            Ok(Event::Delete(object)) => {
+                if let Some((key, value)) = to_cached(object, &resource) {
+                    entry.objects.remove(&key);
+                    let _ = deltas.send(delta(&kind, DeltaType::Deleted, &value));
+                }
+            }
+            // The watcher backs off and retries by itself
+            Err(e) => entry.error = Some(e.to_string()),
+        }
+    }
+}
+
+async fn flush_deltas(
+    app: AppHandle,
+    context: String,
+    mut deltas: mpsc::UnboundedReceiver<WatchDelta>,
+) {
+    let mut interval = tokio::time::interval(DELTA_FLUSH_INTERVAL);
+    let mut batch = Vec::new();
+    loop {
+        tokio::select! {
+            delta = deltas.recv() => match delta {
+                Some(delta) => batch.push(delta),
+                None => return,
+            },
+            _ = interval.tick() => {
+                if !batch.is_empty() {
+                    let _ = app.emit("watch-deltas", WatchDeltaBatch {
+                        context: &context,
+                        deltas: std::mem::take(&mut batch),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn matches_labels(object: &Value, selector: &[(String, String)]) -> bool {
+    let labels = object.pointer("/metadata/labels");
+    selector
+        .iter()
+        .all(|(k, v)| labels.and_then(|l| l.get(k)).and_then(Value::as_str) == Some(v.as_str()))
+}
+
+/// Equality-based selector "a=b,c=d" (also "a==b").
+fn parse_selector(selector: &str) -> Result<Vec<(String, String)>, String> {
+    selector
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            let (k, v) = pair
+                .split_once("==")
+                .or_else(|| pair.split_once('='))
+                .ok_or_else(|| {
+                    format!(
+                        "Unsupported label selector '{}': only key=value terms are supported",
+                        pair
+                    )
+                })?;
+            Ok((k.trim().to_string(), v.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Start watching `kinds` (default: pods, services, workloads and nodes) in a context. Calling it
+/// again for a watched context adds the missing kinds.
+#[command]
+pub async fn start_watch_cache(
+    app_handle: AppHandle,
+    context: String,
+    kinds: Option<Vec<String>>,
+) -> Result<WatchCacheStatus, String> {
+    let kinds = kinds.unwrap_or_else(|| DEFAULT_KINDS.iter().map(|k| k.to_string()).collect());
+    let resources = kinds
+        .iter()
+        .map(|kind| {
+            resource_for(kind)
+                .map(|(resource, _)| (kind.clone(), resource))
+                .ok_or_else(|| format!("Kind '{}' can't be cached", kind))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let client = crate::k8s::streaming_client_for(&context).await?;
+
+    {
+        let mut caches = CACHES.lock().await;
+        let watched = caches
+            .entry(context.clone())
+            .or_insert_with(|| WatchedContext {
+                cache: Arc::new(RwLock::new(ContextCache::default())),
+                tasks: Vec::new(),
+                started_at: now_secs(),
+            });
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut added = false;
+        for (kind, resource) in resources {
+            let mut cache = watched
+                .cache
+                .write()
+                .map_err(|_| "Watch cache unavailable".to_string())?;
+            if cache.kinds.contains_key(&kind) {
+                continue;
+            }
+            cache.kinds.insert(kind.clone(), KindCache::default());
+            drop(cache);
+            watched.tasks.push(tauri::async_runtime::spawn(watch_kind(
+                client.clone(),
+                kind,
+                resource,
+                watched.cache.clone(),
+                sender.clone(),
+            )));
+            added = true;
+        }
+        // Each start call gets its own flusher; it ends when its watchers do
+        if added {
+            watched.tasks.push(tauri::async_runtime::spawn(flush_deltas(
+                app_handle,
+                context.clone(),
+                receiver,
+            )));
+        }
+    }
+
+    watch_cache_status(&context).await
+}
+
+/// Stop all watchers for a context and drop its cache.
+#[command]
+pub async fn stop_watch_cache(context: String) -> Result<(), String> {
+    if let Some(watched) = CACHES.lock().await.remove(&context) {
+        for task in watched.tasks {
+            task.abort();
+        }
+    }
+    Ok(())
+}
+
+async fn watch_cache_status(context: &str) -> Result<WatchCacheStatus, String> {
+    let caches = CACHES.lock().await;
+    let watched = caches
+        .get(context)
+        .ok_or_else(|| format!("Context '{}' is not being watched", context))?;
+    let cache = watched
+        .cache
+        .read()
+        .map_err(|_| "Watch cache unavailable".to_string())?;
+    Ok(WatchCacheStatus {
+        context: context.to_string(),
+        started_at: watched.started_at,
+        kinds: cache
+            .kinds
+            .iter()
+            .map(|(kind, c)| KindCacheStatus {
+                kind: kind.clone(),
+                objects: c.objects.len(),
+                synced: c.synced,
+                last_sync_at: c.last_sync_at,
+                error: c.error.clone(),
+            })
+            .collect(),
+    })
+}
+
+#[command]
+pub async fn get_watch_cache_status() -> Result<Vec<WatchCacheStatus>, String> {
+    let contexts: Vec<String> = CACHES.lock().await.keys().cloned().collect();
+    let mut statuses = Vec::new();
+    for context in contexts {
+        if let Ok(status) = watch_cache_status(&context).await {
+            statuses.push(status);
+        }
+    }
+    Ok(statuses)
+}
+
+/// Cached objects of a kind, optionally narrowed to a namespace and an equality label selector.
+/// Errors while the kind hasn't finished its first list, so the caller can fall back to the backend.
+#[command]
+pub async fn list_cached_resources(
+    context: String,
+    kind: String,
+    namespace: Option<String>,
+    label_selector: Option<String>,
+) -> Result<Vec<Value>, String> {
+    let (_, namespaced) =
+        resource_for(&kind).ok_or_else(|| format!("Kind '{}' can't be cached", kind))?;
+    let selector = parse_selector(label_selector.as_deref().unwrap_or(""))?;
+    let caches = CACHES.lock().await;
+    let watched = caches
+        .get(&context)
+        .ok_or_else(|| format!("Context '{}' is not being watched", context))?;
+    let cache = watched
+        .cache
+        .read()
+        .map_err(|_| "Watch cache unavailable".to_string())?;
+    let kind_cache = cache
+        .kinds
+        .get(&kind)
+        .filter(|k| k.synced)
+        .ok_or_else(|| format!("'{}' is not cached yet in '{}'", kind, context))?;
+
+    let prefix = namespace
+        .filter(|_| namespaced)
+        .map(|ns| format!("{}/", ns));
+    let mut objects: Vec<(&String, &Value)> = kind_cache
+        .objects
+        .iter()
+        .filter(|(key, _)| {
+            prefix
+                .as_ref()
+                .map_or(true, |p| key.starts_with(p.as_str()))
+        })
+        .filter(|(_, object)| matches_labels(object, &selector))
+        .collect();
+    objects.sort_by(|a, b| a.0.cmp(b.0));
+    Ok(objects
+        .into_iter()
+        .map(|(_, object)| object.clone())
+        .collect())
+}
+
+/// One cached object; `None` when the cache has synced and the object doesn't exist.
+#[command]
+pub async fn get_cached_resource(
+    context: String,
+    kind: String,
+    namespace: Option<String>,
+    name: String,
+) -> Result<Option<Value>, String> {
+    let (_, namespaced) =
+        resource_for(&kind).ok_or_else(|| format!("Kind '{}' can't be cached", kind))?;
+    let key = cache_key(
+        if namespaced {
+            namespace.as_deref()
+        } else {
+            None
+        },
+        &name,
+    );
+    let caches = CACHES.lock().await;
+    let watched = caches
+        .get(&context)
+        .ok_or_else(|| format!("Context '{}' is not being watched", context))?;
+    let cache = watched
+        .cache
+        .read()
+        .map_err(|_| "Watch cache unavailable".to_string())?;
+    let kind_cache = cache
+        .kinds
+        .get(&kind)
+        .filter(|k| k.synced)
+        .ok_or_else(|| format!("'{}' is not cached yet in '{}'", kind, context))?;
+    Ok(kind_cache.objects.get(&key).cloned())
+}
@@ -93,6 +93,7 @@ export const invoke = async (cmd: string, _args?: unknown): Promise<unknown> =>
                 backend_uptime_seconds: null,
                 kubeconfig_path: '~/.kube/config',
                 app_data_dir: '/tmp/kubilitics',
+                update_channel: 'stable',
             };
 
         case 'check_for_updates':
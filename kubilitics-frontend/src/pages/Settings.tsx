@@ -42,6 +42,7 @@ interface DesktopInfo {
   backend_uptime_seconds: number | null;
   kubeconfig_path: string;
   app_data_dir: string;
+  update_channel: 'stable' | 'beta' | 'nightly';
 }
 
 interface AISidecarStatus {